@@ -0,0 +1,75 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Solver timings: `cargo bench`.
+//!
+//! Solves a fixed set of deals, then times the per-node work the search
+//! repeats: copying a position and checking it against the visited set,
+//! for `Board` and for the `PackedBoard` the solver stores.
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use szsol_rs::board::{Board, Rules};
+use szsol_rs::packed::PackedBoard;
+use szsol_rs::solver;
+
+/// Deals that the solver finishes without hitting its node limit.
+const SEEDS: [u64; 8] = [1, 2, 4, 5, 6, 8, 10, 42];
+const ROUNDS: usize = 200_000;
+
+fn time<T>(label: &str, f: impl FnOnce() -> T) -> Duration {
+    let start = Instant::now();
+    black_box(f());
+    let elapsed = start.elapsed();
+    println!("{:<32} {:>10.1} ms", label, elapsed.as_secs_f64() * 1000.0);
+    elapsed
+}
+
+fn main() {
+    time("solve 8 deals", || {
+        SEEDS.iter().filter_map(|&seed| solver::solve_uncached(&Board::deal(seed, Rules::STANDARD), |_| true)).count()
+    });
+
+    let boards: Vec<Board> = SEEDS.iter().map(|&seed| Board::deal(seed, Rules::STANDARD)).collect();
+    let packed: Vec<PackedBoard> = boards.iter().map(PackedBoard::from).collect();
+    let board_time = time("Board: clone + visited insert", || {
+        let mut visited = HashSet::new();
+        for i in 0..ROUNDS {
+            visited.insert(boards[i % boards.len()].clone());
+        }
+        visited.len()
+    });
+    let packed_time = time("PackedBoard: copy + visited insert", || {
+        let mut visited = HashSet::new();
+        for i in 0..ROUNDS {
+            visited.insert(packed[i % packed.len()]);
+        }
+        visited.len()
+    });
+    println!("speedup {:.1}x", board_time.as_secs_f64() / packed_time.as_secs_f64());
+    println!(
+        "size: Board {} bytes + column storage, PackedBoard {} bytes",
+        std::mem::size_of::<Board>(),
+        std::mem::size_of::<PackedBoard>()
+    );
+}
@@ -0,0 +1,136 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+
+/* C API of the szsol engine, generated from src/ffi.rs by cbindgen
+ * (see cbindgen.toml); do not edit by hand.  Link against the library
+ * built by `cargo build --lib --release`. */
+
+#ifndef SZSOL_H
+#define SZSOL_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct SzsolBoard SzsolBoard;
+
+#define SZSOL_COL_TO_COL 0
+
+#define SZSOL_COL_TO_FREE 1
+
+#define SZSOL_FREE_TO_COL 2
+
+#define SZSOL_COL_TO_FOUNDATION 3
+
+#define SZSOL_FREE_TO_FOUNDATION 4
+
+#define SZSOL_MERGE_DRAGONS 5
+
+/*
+ One move.  `src` and `dst` are 0-based column or free-cell indexes;
+ `depth` counts cards below the top for a column-to-column stack move
+ (0 = the top card only).  For a dragon merge `src` is the suit:
+ 0 red, 1 green, 2 black.
+ */
+typedef struct {
+  uint8_t kind;
+  uint8_t src;
+  uint8_t dst;
+  uint8_t depth;
+} SzsolMove;
+
+/*
+ A summary of a board for callers that do not want to parse JSON.
+ */
+typedef struct {
+  uint64_t seed;
+  /*
+   Highest card on each foundation (red, green, black, yellow); 0 when
+   empty or when the deal has no such suit.
+   */
+  uint8_t foundations[4];
+  bool flower_placed;
+  /*
+   Cards still in the columns and free cells.
+   */
+  uint32_t cards_left;
+  bool won;
+  /*
+   Not won, and no move is left.
+   */
+  bool stuck;
+} SzsolState;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Deal the board for `seed` on a standard table, with the opening
+ auto-moves made.  Free it with [`szsol_board_free`].
+ */
+SzsolBoard *szsol_board_new(uint64_t seed);
+
+/*
+ # Safety
+ `board` must come from [`szsol_board_new`] and not be used afterwards.
+ Null is ignored.
+ */
+void szsol_board_free(SzsolBoard *board);
+
+/*
+ Write up to `capacity` of the board's moves to `out` and return how many
+ there are in all, so a caller can size its buffer with a first call
+ passing `capacity` 0.  Moves that can never help (such as shuffling a
+ card between empty columns) are left out.
+
+ # Safety
+ `board` must be a live handle; `out` must have room for `capacity` moves
+ (it may be null when `capacity` is 0).
+ */
+size_t szsol_board_moves(const SzsolBoard *board, SzsolMove *out, size_t capacity);
+
+/*
+ Make `m` followed by the safe auto-moves.  Returns 0 on success and -1
+ when the move is not legal (the board is then unchanged).
+
+ # Safety
+ `board` must be a live handle.
+ */
+int32_t szsol_board_apply(SzsolBoard *board, SzsolMove m);
+
+/*
+ # Safety
+ `board` must be a live handle.  A null handle gives an all-zero state.
+ */
+SzsolState szsol_board_state(const SzsolBoard *board);
+
+/*
+ The board as a JSON object: the summary fields plus `"board"` with every
+ column, free cell and foundation.  Free the string with
+ [`szsol_string_free`].
+
+ # Safety
+ `board` must be a live handle.  Returns null for a null handle.
+ */
+char *szsol_board_json(const SzsolBoard *board);
+
+/*
+ # Safety
+ `text` must come from [`szsol_board_json`] and not be used afterwards.
+ Null is ignored.
+ */
+void szsol_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SZSOL_H */
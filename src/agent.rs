@@ -0,0 +1,106 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Computer players for `--bot`.
+//!
+//! An [`Agent`] is asked for a move on each turn and the game plays it
+//! exactly as if it had been typed.  [`GreedyAgent`] is the built-in
+//! baseline; the game can also run agents in other programs (the
+//! `external-agents` feature).
+use std::collections::HashSet;
+
+use crate::board::{Board, FreeCellState};
+use crate::solver::SolverMove;
+
+/// A move as agents see it: the solver's move type.
+pub type Move = SolverMove;
+
+pub trait Agent {
+    /// Shown next to each move the agent plays.
+    fn name(&self) -> &str;
+
+    /// The next move on `board`, or `None` to give up.  Safe auto-moves have
+    /// already been made.
+    fn choose_move(&mut self, board: &Board) -> Option<Move>;
+
+    /// Forget anything kept from the previous deal.
+    fn new_game(&mut self) {}
+}
+
+/// The built-in agent named `name`.
+pub fn builtin(name: &str) -> Option<Box<dyn Agent>> {
+    match name {
+        "greedy" => Some(Box::new(GreedyAgent::default())),
+        _ => None,
+    }
+}
+
+/// Names accepted by [`builtin`].
+pub const BUILTIN_NAMES: &[&str] = &["greedy"];
+
+/// Plays the move whose resulting board scores best, never returning to a
+/// position it has already been in.  No lookahead: it wins the easier deals
+/// and is a yardstick for smarter agents.
+#[derive(Default)]
+pub struct GreedyAgent {
+    seen: HashSet<Board>,
+}
+
+impl Agent for GreedyAgent {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        self.seen.insert(board.clone());
+        let (m, next) = board
+            .valid_moves()
+            .into_iter()
+            .filter_map(|m| {
+                let mut next = board.clone();
+                next.apply_move(m);
+                (!self.seen.contains(&next)).then_some((m, next))
+            })
+            .max_by_key(|(_, next)| score(next))?;
+        self.seen.insert(next);
+        Some(m)
+    }
+
+    fn new_game(&mut self) {
+        self.seen.clear();
+    }
+}
+
+/// Cards home count most, then merged dragons and free space; cards out of
+/// sequence in a column count against.
+fn score(board: &Board) -> i32 {
+    let home: i32 = board.foundations.iter().map(|&v| v as i32).sum::<i32>() + board.flower_placed as i32;
+    let locked = board.free_cells.iter().filter(|cell| matches!(cell, FreeCellState::DragonLocked(_))).count() as i32;
+    let empty_cells = board.free_cells.iter().filter(|cell| cell.is_empty()).count() as i32;
+    let empty_columns = board.columns.iter().filter(|column| column.is_empty()).count() as i32;
+    let breaks: i32 = board
+        .columns
+        .iter()
+        .map(|column| column.windows(2).filter(|pair| !board.rules.can_stack(pair[1], pair[0])).count() as i32)
+        .sum();
+    100 * home + 40 * locked + 10 * empty_columns + 5 * empty_cells - 3 * breaks
+}
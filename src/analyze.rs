@@ -0,0 +1,348 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! `analyze`: run the solver over a range of seeds.
+//!
+//! Prints the solvable share, the average solution length and a histogram
+//! of how hard the deals were, and writes one CSV row per seed.  Difficulty
+//! is judged by how many positions the solver had to explore.
+//!
+//! `report difficulty` rates the seeds a player has played the same way,
+//! on a worker thread, and sets them against how often they were won.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::board::{Board, Rules};
+use crate::history::{Effort, History};
+use crate::i18n::tr;
+use crate::solver::{self, NODE_LIMIT};
+
+/// Difficulty bands by positions explored; a deal falls in the first band
+/// whose limit it stays under.  Unsolved deals get a band of their own.
+const BANDS: [(&str, usize); 4] = [
+    ("trivial", 1_000),
+    ("easy", 10_000),
+    ("medium", 100_000),
+    ("hard", NODE_LIMIT + 1),
+];
+
+/// How many of the latest played seeds `report difficulty` rates; hard
+/// deals keep the solver busy for a while.
+const REPORT_SEEDS: usize = 40;
+
+/// One seed's result.
+pub struct Analysis {
+    seed: u64,
+    rules: Rules,
+    /// Solution length, if the solver found one.
+    solution: Option<usize>,
+    nodes: usize,
+}
+
+impl Analysis {
+    fn difficulty(&self) -> &'static str {
+        difficulty(self.solution.is_some(), self.nodes)
+    }
+
+    /// What `History` keeps of it.
+    pub fn effort(&self) -> Effort {
+        Effort { seed: self.seed, rules: self.rules, nodes: self.nodes as u64, solved: self.solution.is_some() }
+    }
+}
+
+/// The band of a deal that took `nodes` positions, or "unsolved".
+fn difficulty(solved: bool, nodes: usize) -> &'static str {
+    if solved { band(nodes) } else { "unsolved" }
+}
+
+/// The difficulty band of a solved deal that took `nodes` positions.
+pub fn band(nodes: usize) -> &'static str {
+    BANDS.iter().find(|(_, limit)| nodes < *limit).map_or("hard", |(name, _)| name)
+}
+
+/// Parse `a..b` (end excluded) or `a..=b`.
+pub fn parse_range(text: &str) -> Option<RangeInclusive<u64>> {
+    if let Some((start, end)) = text.split_once("..=") {
+        return Some(start.parse().ok()?..=end.parse().ok()?).filter(|range| !range.is_empty());
+    }
+    let (start, end) = text.split_once("..")?;
+    let (start, end): (u64, u64) = (start.parse().ok()?, end.parse().ok()?);
+    Some(start..=end.checked_sub(1)?).filter(|range| !range.is_empty())
+}
+
+/// Solve every seed in `seeds` under `rules` on `threads` workers, report
+/// and write the CSV to `out`.  Returns the process exit code.
+pub fn run(seeds: RangeInclusive<u64>, rules: Rules, threads: usize, out: &str) -> i32 {
+    let file = match File::create(out) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("{}", tr!("Could not write {}: {}", out, e));
+            return 1;
+        }
+    };
+    let total = seeds.end().saturating_sub(*seeds.start()).saturating_add(1);
+    let done = AtomicU64::new(0);
+    let results = Mutex::new(Vec::new());
+    let deal = |i: u64| Board::deal(seeds.start() + i, rules);
+    rate_all(total, deal, threads, &AtomicBool::new(false), |result| {
+        results.lock().unwrap_or_else(|e| e.into_inner()).push(result);
+        let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+        if finished.is_multiple_of(100) || finished == total {
+            eprint!("\r{}", tr!("Analyzed {} of {} seeds.", finished, total));
+        }
+    });
+    eprintln!();
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|result| result.seed);
+
+    let mut writer = BufWriter::new(file);
+    let mut csv = writeln!(writer, "seed,solvable,solution_length,nodes_explored,difficulty");
+    for result in &results {
+        if csv.is_err() {
+            break;
+        }
+        let length = result.solution.map_or_else(String::new, |len| len.to_string());
+        csv = writeln!(
+            writer,
+            "{},{},{},{},{}",
+            result.seed,
+            result.solution.is_some(),
+            length,
+            result.nodes,
+            result.difficulty()
+        );
+    }
+    if let Err(e) = csv.and_then(|()| writer.flush()) {
+        eprintln!("{}", tr!("Could not write {}: {}", out, e));
+        return 1;
+    }
+    report(&results);
+    println!("{}", tr!("Wrote {} rows to {}.", results.len(), out));
+    0
+}
+
+/// Rate `count` deals on `threads` workers, `deal(i)` being the i-th, and
+/// hand each result to `rated` as it comes.  Setting `cancel` stops the
+/// workers, dropping the deals they were on.
+fn rate_all(
+    count: u64,
+    deal: impl Fn(u64) -> Board + Sync,
+    threads: usize,
+    cancel: &AtomicBool,
+    rated: impl Fn(Analysis) + Sync,
+) {
+    let next = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= count {
+                    break;
+                }
+                match rate(&deal(i), cancel) {
+                    Some(result) => rated(result),
+                    None => break,
+                }
+            });
+        }
+    });
+}
+
+/// Solve the deal starting at `board`; `None` if `cancel` was set first.
+fn rate(board: &Board, cancel: &AtomicBool) -> Option<Analysis> {
+    let mut nodes = 0;
+    let solution = solver::solve_uncached(board, |progress| {
+        nodes = progress.nodes_explored().max(nodes);
+        !cancel.load(Ordering::Relaxed)
+    });
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+    Some(Analysis { seed: board.seed, rules: board.rules, solution: solution.map(|path| path.len()), nodes })
+}
+
+/// The solver runs behind `report difficulty`, on a worker thread so the
+/// game goes on meanwhile.  Dropping it stops the search, the way a
+/// cancelled hint stops the solver.
+pub struct RatingJob {
+    results: Receiver<Analysis>,
+    cancel: Arc<AtomicBool>,
+    pending: usize,
+}
+
+impl RatingJob {
+    /// Rate the deals starting at `boards`, one at a time: a hard deal can
+    /// take the solver close to a gigabyte.
+    pub fn spawn(boards: Vec<Board>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = Arc::clone(&cancel);
+        let pending = boards.len();
+        thread::spawn(move || {
+            let deal = |i: u64| boards[i as usize].clone();
+            rate_all(boards.len() as u64, deal, 1, &worker_cancel, |result| {
+                let _ = tx.send(result);
+            });
+        });
+        Self { results: rx, cancel, pending }
+    }
+
+    /// The deals rated since the last call.
+    pub fn poll(&mut self) -> Vec<Analysis> {
+        let rated: Vec<Analysis> = self.results.try_iter().collect();
+        self.pending -= rated.len();
+        rated
+    }
+
+    /// Deals still to rate.
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+}
+
+impl Drop for RatingJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn report(results: &[Analysis]) {
+    let lengths: Vec<usize> = results.iter().filter_map(|result| result.solution).collect();
+    let count = results.len().max(1);
+    println!(
+        "{}",
+        tr!("Solvable: {} of {} ({}%).", lengths.len(), results.len(), format!("{:.1}", 100.0 * lengths.len() as f64 / count as f64))
+    );
+    if !lengths.is_empty() {
+        let average = lengths.iter().sum::<usize>() as f64 / lengths.len() as f64;
+        let (shortest, longest) = (lengths.iter().min().unwrap_or(&0), lengths.iter().max().unwrap_or(&0));
+        println!(
+            "{}",
+            tr!("Solution length: {} on average, {} to {}.", format!("{:.1}", average), shortest, longest)
+        );
+    }
+    println!("{}", tr!("Difficulty:"));
+    let names = BANDS.iter().map(|(name, _)| *name).chain(["unsolved"]);
+    for name in names {
+        let n = results.iter().filter(|result| result.difficulty() == name).count();
+        let bar = "#".repeat((40 * n).div_ceil(count));
+        println!("  {:<9} {:>7}  {}", name, n, bar);
+    }
+}
+
+/// The latest seeds played to the end, with their first position and
+/// (games, wins), newest seed first.  The same seed under other rules is
+/// another deal.
+fn played_seeds(history: &History) -> Vec<(Board, usize, usize)> {
+    let mut seeds: Vec<(Board, usize, usize)> = Vec::new();
+    for record in history.records.iter().rev().filter(|r| r.end_time.is_some() && r.is_dealt()) {
+        let full = seeds.len() == REPORT_SEEDS;
+        match seeds.iter_mut().find(|(board, ..)| board.seed == record.seed && board.rules == record.rules) {
+            Some((_, games, wins)) => {
+                *games += 1;
+                *wins += usize::from(record.won);
+            }
+            None if !full => {
+                let board = record.initial_board.clone().unwrap_or_else(|| Board::deal(record.seed, record.rules));
+                seeds.push((board, 1, usize::from(record.won)));
+            }
+            None => {}
+        }
+    }
+    seeds
+}
+
+/// The first positions of the seeds `report difficulty` covers that the
+/// solver has not rated yet.
+pub fn unrated_seeds(history: &History) -> Vec<Board> {
+    played_seeds(history)
+        .into_iter()
+        .map(|(board, ..)| board)
+        .filter(|board| history.effort(board.seed, board.rules).is_none())
+        .collect()
+}
+
+/// `report difficulty`: the latest seeds played to the end, by difficulty
+/// band against the share of games on each that were won, with each
+/// band's win rate and the hardest band mostly won.  Seeds the solver has
+/// not rated yet (see [`unrated_seeds`]) are left out.
+pub fn difficulty_report(history: &History) -> Vec<String> {
+    let seeds: Vec<(Effort, usize, usize)> = played_seeds(history)
+        .into_iter()
+        .filter_map(|(board, games, wins)| Some((history.effort(board.seed, board.rules)?, games, wins)))
+        .collect();
+    if seeds.is_empty() {
+        return vec![tr!("No finished games to rate yet.")];
+    }
+
+    // Per band: seeds in each win-rate column, games and wins.
+    const COLUMNS: [&str; 4] = ["0%", "<50%", "<100%", "100%"];
+    let names: Vec<&str> = BANDS.iter().map(|(name, _)| *name).chain(["unsolved"]).collect();
+    let mut rows = vec![([0usize; 4], 0usize, 0usize); names.len()];
+    for (effort, games, wins) in &seeds {
+        let rated = difficulty(effort.solved, effort.nodes as usize);
+        let band = names.iter().position(|&n| n == rated).unwrap_or(names.len() - 1);
+        let column = match wins * 100 / games {
+            0 => 0,
+            1..50 => 1,
+            50..100 => 2,
+            _ => 3,
+        };
+        let row = &mut rows[band];
+        row.0[column] += 1;
+        row.1 += games;
+        row.2 += wins;
+    }
+
+    let mut lines = vec![
+        tr!("Your last {} seeds by solver difficulty and how often you won them:", seeds.len()),
+        format!("  {:<9} {:>5} {:>5} {:>5} {:>5}  {:>5} {:>8}", "", COLUMNS[0], COLUMNS[1], COLUMNS[2], COLUMNS[3], tr!("games"), tr!("win rate")),
+    ];
+    let mut ceiling = None;
+    for (name, (cells, games, wins)) in names.iter().zip(&rows) {
+        if *games == 0 {
+            lines.push(format!("  {:<9} {:>5} {:>5} {:>5} {:>5}  {:>5}", name, "·", "·", "·", "·", 0));
+            continue;
+        }
+        let rate = wins * 100 / games;
+        if rate >= 50 && *name != "unsolved" {
+            ceiling = Some(*name);
+        }
+        let cell = |n: usize| if n == 0 { "·".to_string() } else { n.to_string() };
+        // Darker for a higher win rate, so the skill ceiling shows at a glance.
+        let heat = ["░", "▒", "▓", "█"][(rate / 25).min(3)].repeat(5);
+        lines.push(format!(
+            "  {:<9} {:>5} {:>5} {:>5} {:>5}  {:>5} {:>7}%  {}",
+            name, cell(cells[0]), cell(cells[1]), cell(cells[2]), cell(cells[3]), games, rate, heat
+        ));
+    }
+    lines.push(match ceiling {
+        Some(band) => tr!("Skill ceiling: you win most deals up to '{}'.", band),
+        None => tr!("Skill ceiling: no difficulty won half the time yet."),
+    });
+    lines
+}
@@ -0,0 +1,313 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! `serve --http`: the rules engine as a JSON API for web frontends.
+//!
+//! - `POST /games` deals a board (body optional: `{"seed": 42}` or
+//!   `{"seed": "some phrase"}`).
+//! - `POST /games/:id/moves` runs commands exactly as typed at the prompt
+//!   (`{"command": "15"}` or `{"commands": ["15", "u"]}`).
+//! - `GET /games/:id` returns the game's state.
+//!
+//! Replies are JSON objects; failures carry an `"error"` member.  Requests
+//! are served one at a time, and games live in memory only.
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::config::UserConfig;
+use crate::game::Game;
+use crate::i18n::tr;
+use crate::json::Json;
+use crate::renderer::PlainRenderer;
+
+/// Port used by `serve --http` when `--port` is not given.
+pub const DEFAULT_PORT: u16 = 8080;
+/// Games kept at once; dealing another drops the oldest.
+const MAX_GAMES: usize = 256;
+/// Largest request body accepted.
+const MAX_BODY: usize = 64 * 1024;
+/// A client that stalls this long is dropped so others are not held up.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A game and the text its renderer has written since it was last read.
+struct Session {
+    game: Game<PlainRenderer>,
+    transcript: Transcript,
+}
+
+/// Collects a session's plain-renderer output.
+#[derive(Clone, Default)]
+struct Transcript(Arc<Mutex<Vec<u8>>>);
+
+impl Transcript {
+    /// The lines written since the last call.
+    fn take(&self) -> Vec<String> {
+        let bytes = std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()));
+        String::from_utf8_lossy(&bytes).lines().map(str::to_string).collect()
+    }
+}
+
+impl Write for Transcript {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: Json,
+}
+
+impl Response {
+    fn ok(status: u16, body: Json) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response { status, body: Json::Object(vec![("error".to_string(), Json::from(message.into()))]) }
+    }
+}
+
+/// The API's games and the configuration each new one is dealt with.
+struct Api {
+    games: BTreeMap<u64, Session>,
+    next_id: u64,
+    user_config: UserConfig,
+}
+
+/// Answer requests on `port` until the process is stopped.  Returns the
+/// exit code for a listener that could not be opened.
+pub fn serve(port: u16, user_config: UserConfig) -> i32 {
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("{}", tr!("Could not listen on port {}: {}", port, e));
+            return 1;
+        }
+    };
+    eprintln!("{}", tr!("Serving the szsol API on http://localhost:{}/games", port));
+    let mut api = Api { games: BTreeMap::new(), next_id: 1, user_config };
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+        let response = match read_request(&stream) {
+            Ok(request) => api.route(&request),
+            Err(e) => Response::error(400, e),
+        };
+        let _ = write_response(stream, &response);
+    }
+    0
+}
+
+impl Api {
+    fn route(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("OPTIONS", _) => Response::ok(204, Json::Null),
+            ("POST", ["games"]) => self.deal(&request.body),
+            ("GET", ["games", id]) => match self.game_id(id) {
+                Ok(id) => Response::ok(200, state(id, self.games.get_mut(&id).expect("checked by game_id"))),
+                Err(response) => response,
+            },
+            ("POST", ["games", id, "moves"]) => self.play(id, &request.body),
+            (_, ["games"] | ["games", _] | ["games", _, "moves"]) => Response::error(405, "method not allowed"),
+            _ => Response::error(404, "no such endpoint"),
+        }
+    }
+
+    /// `POST /games`
+    fn deal(&mut self, body: &str) -> Response {
+        let body = match parse_body(body) {
+            Ok(body) => body,
+            Err(response) => return response,
+        };
+        let (seed, phrase) = match body.get("seed") {
+            None | Some(Json::Null) => (None, None),
+            Some(Json::Int(n)) => match u64::try_from(*n) {
+                Ok(seed) => (Some(seed), None),
+                Err(_) => return Response::error(400, "seed is out of range"),
+            },
+            Some(Json::Str(text)) => {
+                let (seed, phrase) = crate::command::parse_seed_or_phrase(text);
+                (Some(seed), phrase)
+            }
+            Some(_) => return Response::error(400, "seed must be a number or a string"),
+        };
+        if self.games.len() >= MAX_GAMES {
+            self.games.pop_first();
+        }
+        let transcript = Transcript::default();
+        let renderer = PlainRenderer::new()
+            .with_index_base(self.user_config.index_base())
+            .with_output(Box::new(transcript.clone()));
+        let mut game = Game::init(seed, phrase, renderer, self.user_config.clone());
+        game.start();
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut session = Session { game, transcript };
+        let response = Response::ok(201, state(id, &mut session));
+        self.games.insert(id, session);
+        response
+    }
+
+    /// `POST /games/:id/moves`
+    fn play(&mut self, id: &str, body: &str) -> Response {
+        let commands = match parse_body(body).map(|body| commands(&body)) {
+            Ok(Some(commands)) => commands,
+            Ok(None) => return Response::error(400, "expected {\"command\": \"...\"} or {\"commands\": [...]}"),
+            Err(response) => return response,
+        };
+        let id = match self.game_id(id) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let session = self.games.get_mut(&id).expect("checked by game_id");
+        let quit = commands.iter().any(|command| session.game.execute(command));
+        let mut body = state(id, session);
+        if quit {
+            if let Json::Object(members) = &mut body {
+                members.push(("closed".to_string(), Json::from(true)));
+            }
+            self.games.remove(&id);
+        }
+        Response::ok(200, body)
+    }
+
+    /// The id of a live game named in a path.
+    fn game_id(&self, id: &str) -> Result<u64, Response> {
+        id.parse()
+            .ok()
+            .filter(|id| self.games.contains_key(id))
+            .ok_or_else(|| Response::error(404, "no such game"))
+    }
+}
+
+/// The command lines of a moves request.
+fn commands(body: &Json) -> Option<Vec<String>> {
+    if let Some(command) = body.get("command") {
+        return Some(vec![command.as_str()?.to_string()]);
+    }
+    body.get("commands")?
+        .as_array()?
+        .iter()
+        .map(|command| command.as_str().map(str::to_string))
+        .collect()
+}
+
+/// A request body as JSON; an empty body counts as `{}`.
+fn parse_body(body: &str) -> Result<Json, Response> {
+    if body.trim().is_empty() {
+        return Ok(Json::Object(Vec::new()));
+    }
+    match Json::parse(body) {
+        Ok(json @ Json::Object(_)) => Ok(json),
+        Ok(_) => Err(Response::error(400, "the body must be a JSON object")),
+        Err(e) => Err(Response::error(400, format!("invalid JSON: {}", e))),
+    }
+}
+
+/// What `GET /games/:id` returns, plus any output not yet collected.
+fn state(id: u64, session: &mut Session) -> Json {
+    session.game.poll_background();
+    let board = session.game.board();
+    Json::Object(vec![
+        ("id".to_string(), Json::from(id)),
+        ("seed".to_string(), Json::from(board.seed)),
+        ("moves".to_string(), Json::from(session.game.moves())),
+        ("par".to_string(), Json::from(session.game.par().map(|par| par as u32))),
+        ("won".to_string(), Json::from(board.is_won())),
+        ("code".to_string(), Json::from(crate::share::encode(board))),
+        ("board".to_string(), crate::json_serde::to_json(board).unwrap_or(Json::Null)),
+        ("output".to_string(), Json::Array(session.transcript.take().into_iter().map(Json::from).collect())),
+    ])
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err("malformed request line".to_string());
+    };
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    let method = method.to_string();
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|e| e.to_string())? == 0 {
+            return Err("connection closed in the headers".to_string());
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') && name.eq_ignore_ascii_case("content-length") {
+            length = value.trim().parse().map_err(|_| "bad Content-Length".to_string())?;
+        }
+    }
+    if length > MAX_BODY {
+        return Err("request body too large".to_string());
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    let body = String::from_utf8(body).map_err(|_| "the body is not UTF-8".to_string())?;
+    Ok(Request { method, path, body })
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    };
+    let body = if response.status == 204 { String::new() } else { response.body.pretty() };
+    // Any origin may call the API, so a frontend can be served from anywhere.
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
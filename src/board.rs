@@ -20,19 +20,28 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use std::ops::RangeInclusive;
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
 use serde::{Serialize, Deserialize};
 
 use crate::card::{Card, Suit, full_deck};
 use crate::event::GameEvent;
-
-/// Number of tableau columns.
-pub const NUM_COLUMNS: usize = 8;
-/// Number of free-cell slots.
-pub const NUM_FREE_CELLS: usize = 3;
-/// Number of foundation slots (one per suit).
-pub const NUM_FOUNDATIONS: usize = 3;
+use crate::rules_file;
+
+/// Most tableau columns a table can have (see [`Rules`]).
+pub const MAX_COLUMNS: usize = 10;
+/// Most free-cell slots a table can have.
+pub const MAX_FREE_CELLS: usize = 6;
+/// Most suits a deck can have, and so most foundation slots.
+pub const MAX_SUITS: usize = 4;
+/// Highest rank a deck can go up to.
+pub const MAX_RANK: u8 = 13;
+/// Most dragons a suit can have.
+pub const MAX_DRAGONS: usize = 4;
+/// Cards in the largest deck.
+pub const MAX_DECK: usize = MAX_SUITS * (MAX_RANK as usize + MAX_DRAGONS) + 1;
 
 /// A free-cell slot can be:
 /// - Empty
@@ -58,6 +67,278 @@ impl FreeCellState {
     }
 }
 
+/// Which cards `auto_move_with` sends to the foundation on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoMovePolicy {
+    Off,
+    /// The flower and cards no other card could still need (the original game's rule).
+    Safe,
+    /// Any card the foundation accepts.
+    Eager,
+}
+
+/// A built-in game, each defined by a rules file shipped in `rules/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// SHENZHEN I/O's: dragons, the flower, and runs of alternating suits.
+    Shenzhen,
+    /// Classic FreeCell: no dragons or flower, runs of alternating colors,
+    /// and stack moves limited by the free space.
+    FreeCell,
+}
+
+impl Variant {
+    pub fn parse(value: &str) -> Option<Variant> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "shenzhen" => Some(Variant::Shenzhen),
+            "freecell" => Some(Variant::FreeCell),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Shenzhen => "shenzhen",
+            Variant::FreeCell => "freecell",
+        }
+    }
+
+    /// The game's rules, read from its built-in rules file.
+    pub fn rules(self) -> Rules {
+        let text = match self {
+            Variant::Shenzhen => rules_file::SHENZHEN,
+            Variant::FreeCell => rules_file::FREECELL,
+        };
+        rules_file::parse_toml(text).expect("built-in rules files are valid")
+    }
+}
+
+/// Which cards may be placed on a column card one rank higher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Stacking {
+    /// Any other suit (SHENZHEN I/O).
+    #[default]
+    AlternateSuit,
+    /// The other color: red (`R`, `Y`) on black (`G`, `B`) and back (FreeCell).
+    AlternateColor,
+}
+
+impl Stacking {
+    pub fn parse(value: &str) -> Option<Stacking> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "alternate-suit" => Some(Stacking::AlternateSuit),
+            "alternate-color" => Some(Stacking::AlternateColor),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stacking::AlternateSuit => "alternate-suit",
+            Stacking::AlternateColor => "alternate-color",
+        }
+    }
+}
+
+/// Everything a game's rules can change: how many columns the deck is
+/// dealt into and how many free cells there are, the deck's suits, ranks
+/// and special cards, how cards stack, and how a seed is shuffled.  The
+/// original game's is
+/// [`Rules::STANDARD`]; other rules are for practice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    pub columns: usize,
+    pub free_cells: usize,
+    /// The first this many of [`Suit::ALL`] are in the deck.
+    pub suits: usize,
+    /// Numbered cards run from 1 to this.
+    pub ranks: usize,
+    /// Dragons of each suit; 0 for none.  All of a suit's merge at once.
+    pub dragons: usize,
+    pub flower: bool,
+    pub stacking: Stacking,
+    /// Limit stack moves to what the free cells and empty columns could
+    /// shuffle one card at a time, as in FreeCell.
+    pub supermove: bool,
+    /// How a seed is shuffled into this table's deal.  Part of the rules so
+    /// a game, its par and its bests stay with the deal it was played on.
+    pub deal_style: DealStyle,
+}
+
+impl Rules {
+    pub const STANDARD: Rules = Rules {
+        columns: 8,
+        free_cells: 3,
+        suits: 3,
+        ranks: 9,
+        dragons: 4,
+        flower: true,
+        stacking: Stacking::AlternateSuit,
+        supermove: false,
+        deal_style: DealStyle::Classic,
+    };
+    /// Classic 52-card FreeCell.
+    pub const FREECELL: Rules = Rules {
+        columns: 8,
+        free_cells: 4,
+        suits: 4,
+        ranks: 13,
+        dragons: 0,
+        flower: false,
+        stacking: Stacking::AlternateColor,
+        supermove: true,
+        deal_style: DealStyle::Classic,
+    };
+    /// Columns a table may have.
+    pub const COLUMNS: RangeInclusive<usize> = 4..=MAX_COLUMNS;
+    /// Free cells a table may have.  Every dragon merge locks a cell, so
+    /// fewer than three would leave some deals unwinnable.
+    pub const FREE_CELLS: RangeInclusive<usize> = 3..=MAX_FREE_CELLS;
+    /// Suits a deck may have; the fourth is the harder variant.
+    pub const SUITS: RangeInclusive<usize> = 3..=MAX_SUITS;
+    /// Ranks a deck may have; more make for a longer game.
+    pub const RANKS: RangeInclusive<usize> = 9..=MAX_RANK as usize;
+    /// Dragons each suit may have.
+    pub const DRAGONS: RangeInclusive<usize> = 0..=MAX_DRAGONS;
+
+    /// Whether this is the original game's table, whichever way its seeds
+    /// are dealt.
+    pub fn is_standard(self) -> bool {
+        Rules { deal_style: DealStyle::Classic, ..self } == Rules::STANDARD
+    }
+
+    /// Whether every count is in range, with a free cell for each suit's
+    /// dragons.
+    pub fn is_valid(self) -> bool {
+        self.check().is_ok()
+    }
+
+    /// Like `is_valid`, naming the first field that is wrong and why.
+    pub fn check(self) -> Result<(), (&'static str, String)> {
+        let in_range = |field: &'static str, value: usize, range: RangeInclusive<usize>| {
+            if range.contains(&value) {
+                Ok(())
+            } else {
+                Err((field, format!("expected {} to {}, found {}", range.start(), range.end(), value)))
+            }
+        };
+        in_range("columns", self.columns, Rules::COLUMNS)?;
+        in_range("free_cells", self.free_cells, Rules::FREE_CELLS)?;
+        in_range("suits", self.suits, Rules::SUITS)?;
+        in_range("ranks", self.ranks, Rules::RANKS)?;
+        in_range("dragons", self.dragons, Rules::DRAGONS)?;
+        if self.dragons > 0 && self.free_cells < self.suits {
+            return Err((
+                "free_cells",
+                format!("each suit's dragons lock a cell, so {} suits need at least {}", self.suits, self.suits),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The suits in play, in canonical order.
+    pub fn suits(self) -> &'static [Suit] {
+        &Suit::ALL[..self.suits]
+    }
+
+    /// The highest numbered card, the last on each foundation.
+    pub fn top_rank(self) -> u8 {
+        self.ranks as u8
+    }
+
+    /// Number of cards in the deck: each suit's numbered cards and
+    /// dragons, and the flower.
+    pub fn deck_size(self) -> usize {
+        self.suits * (self.ranks + self.dragons) + usize::from(self.flower)
+    }
+
+    /// Whether `card` may be placed on `onto` in a column.
+    pub fn can_stack(self, card: Card, onto: Card) -> bool {
+        match (self.stacking, card, onto) {
+            (Stacking::AlternateSuit, ..) => card.can_stack_on(onto),
+            (Stacking::AlternateColor, Card::Numbered(s1, v1), Card::Numbered(s2, v2)) => {
+                s1.is_red() != s2.is_red() && v1 + 1 == v2
+            }
+            (Stacking::AlternateColor, ..) => false,
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::STANDARD
+    }
+}
+
+/// Valid rules: every count in range, with a free cell for each suit's
+/// dragons when there are any.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Rules {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let suits = u.int_in_range(Rules::SUITS)?;
+        let dragons = u.int_in_range(Rules::DRAGONS)?;
+        let fewest_cells = if dragons > 0 { suits.max(*Rules::FREE_CELLS.start()) } else { *Rules::FREE_CELLS.start() };
+        Ok(Rules {
+            columns: u.int_in_range(Rules::COLUMNS)?,
+            free_cells: u.int_in_range(fewest_cells..=*Rules::FREE_CELLS.end())?,
+            suits,
+            ranks: u.int_in_range(Rules::RANKS)?,
+            dragons,
+            flower: u.arbitrary()?,
+            stacking: if u.arbitrary()? { Stacking::AlternateColor } else { Stacking::AlternateSuit },
+            supermove: u.arbitrary()?,
+            deal_style: if u.arbitrary()? { DealStyle::Shenzhen } else { DealStyle::Classic },
+        })
+    }
+}
+
+/// How a seed is turned into a deal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DealStyle {
+    /// This game's own shuffle, driven by Xoshiro256++: what `SmallRng`
+    /// is on 64-bit targets, named so 32-bit ones such as the browser
+    /// build deal the same boards.
+    #[default]
+    Classic,
+    /// .NET's `System.Random` seeded with the low 32 bits, driving a
+    /// forward Fisher-Yates shuffle of the deck, meant to reproduce
+    /// SHENZHEN I/O's deals.  The generator matches `System.Random`; the
+    /// deck order and shuffle have not been checked against boards from
+    /// the game.
+    Shenzhen,
+}
+
+impl DealStyle {
+    pub fn parse(value: &str) -> Option<DealStyle> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "classic" => Some(DealStyle::Classic),
+            "shenzhen" => Some(DealStyle::Shenzhen),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DealStyle::Classic => "classic",
+            DealStyle::Shenzhen => "shenzhen",
+        }
+    }
+}
+
+/// Puts a deck in dealing order for [`Board::deal_with`].  Every
+/// [`rand::Rng`] is one (a uniform shuffle drawn from it), so tests can deal
+/// from a fixed generator and other shuffles can be plugged in.
+pub trait DealStrategy {
+    fn shuffle(&mut self, deck: &mut [Card]);
+}
+
+impl<R: rand::Rng> DealStrategy for R {
+    fn shuffle(&mut self, deck: &mut [Card]) {
+        deck.shuffle(self);
+    }
+}
+
 /// Unified slot address used both for move operations and for events.
 ///
 /// `Column` and `FreeCell` are valid sources *and* destinations for hand
@@ -76,16 +357,115 @@ pub enum Location {
     Flower,
 }
 
+/// Why a move was rejected.  `Display` spells out the rule that was broken so
+/// the player can see what went wrong instead of a bare "Illegal move".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// The source slot holds no movable card.
+    EmptySource(Location),
+    /// Source and destination are the same column.
+    SameColumn,
+    /// Numbered cards of the same suit never stack.
+    SameSuit { card: Card, onto: Card },
+    /// In FreeCell, red goes on black and black on red.
+    SameColor { card: Card, onto: Card },
+    /// The card must be exactly one lower than the card it lands on.
+    WrongRank { card: Card, onto: Card },
+    /// Only numbered cards can be stacked on a column card.
+    NotStackable { card: Card, onto: Card },
+    /// The free cell already holds a card.
+    FreeCellOccupied { cell: usize, card: Card },
+    /// The free cell is permanently locked by merged dragons.
+    FreeCellLocked { cell: usize, suit: Suit },
+    /// The foundation for the suit expects a different card next.
+    FoundationOrder { card: Card, needed: Option<Card> },
+    /// Dragons never go to a foundation.
+    NotFoundationCard(Card),
+    /// Foundations and the flower slot are not valid targets for this move.
+    InvalidDestination(Location),
+    /// The requested stack start is past the end of the column.
+    StackOutOfBounds { col: usize, start_idx: usize },
+    /// The selected cards do not form a descending, alternating-suit run.
+    NotASequence { col: usize },
+    /// In FreeCell, a stack needs room to move one card at a time.
+    StackTooLong { len: usize, max: usize },
+    /// Not every dragon of the suit is on top of a column or in a free cell.
+    DragonsNotExposed { suit: Suit, exposed: usize, total: usize },
+    /// No free cell can receive the merged dragons.
+    NoCellForDragons(Suit),
+}
+
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::EmptySource(Location::Column(c)) => write!(f, "Column {} is empty", c),
+            MoveError::EmptySource(Location::FreeCell(c)) => write!(f, "Free cell {} is empty", c),
+            MoveError::EmptySource(_) => write!(f, "Cards cannot be taken from the foundation"),
+            MoveError::SameColumn => write!(f, "Source and destination columns are the same"),
+            MoveError::SameSuit { card, onto } => write!(
+                f, "{} cannot stack on {} (same suit)", card.label(), onto.label()
+            ),
+            MoveError::SameColor { card, onto } => write!(
+                f, "{} cannot stack on {} (same color)", card.label(), onto.label()
+            ),
+            MoveError::WrongRank { card, onto } => {
+                let needed = onto.value().map(|v| v.saturating_sub(1)).unwrap_or(0);
+                write!(f, "{} cannot stack on {} (needs a {})", card.label(), onto.label(), needed)
+            }
+            MoveError::NotStackable { card, onto } => write!(
+                f, "{} cannot stack on {} (only numbered cards stack)", card.label(), onto.label()
+            ),
+            MoveError::FreeCellOccupied { cell, card } => {
+                write!(f, "Free cell {} already holds {}", cell, card.label())
+            }
+            MoveError::FreeCellLocked { cell, suit } => write!(
+                f, "Free cell {} is dragon-locked ({})", cell, suit.name()
+            ),
+            MoveError::FoundationOrder { card, needed: Some(needed) } => write!(
+                f, "Foundation needs {} next, not {}", needed.label(), card.label()
+            ),
+            MoveError::FoundationOrder { card, needed: None } => {
+                write!(f, "Foundation for {} is already complete", card.label())
+            }
+            MoveError::NotFoundationCard(card) => {
+                write!(f, "{} cannot go to a foundation (dragons are merged instead)", card.label())
+            }
+            MoveError::InvalidDestination(_) => {
+                write!(f, "Use the foundation command to send cards to the foundation")
+            }
+            MoveError::StackOutOfBounds { col, start_idx } => {
+                write!(f, "Column {} has no card at position {}", col, start_idx)
+            }
+            MoveError::NotASequence { col } => write!(
+                f, "The selected cards in column {} are not a descending, alternating-suit run", col
+            ),
+            MoveError::StackTooLong { len, max } => write!(
+                f, "Only {} cards can move at once with the free space left, not {}", max, len
+            ),
+            MoveError::DragonsNotExposed { suit, exposed, total } => write!(
+                f, "Only {} of {} {} dragons are exposed", exposed, total, suit.name().to_lowercase()
+            ),
+            MoveError::NoCellForDragons(suit) => write!(
+                f, "No free cell is available to lock the {} dragons", suit.name().to_lowercase()
+            ),
+        }
+    }
+}
 
 /// The game board – the single source of truth for all game state.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Board {
-    /// 8 tableau columns; index 0 is leftmost.
-    pub columns: [Vec<Card>; NUM_COLUMNS],
-    /// 3 free-cell slots.
-    pub free_cells: [FreeCellState; NUM_FREE_CELLS],
+    /// The table's shape; `columns` and `free_cells` have its lengths.
+    /// JSON saves from before it existed read as the standard table.
+    #[serde(default)]
+    pub rules: Rules,
+    /// Tableau columns; index 0 is leftmost.
+    pub columns: Vec<Vec<Card>>,
+    /// Free-cell slots.
+    pub free_cells: Vec<FreeCellState>,
     /// Foundation progress per suit: the highest numbered card placed (0 = empty).
-    pub foundations: [u8; NUM_FOUNDATIONS],
+    /// One slot for each of `rules.suits()`.
+    pub foundations: Vec<u8>,
     /// Whether the flower slot is occupied.
     pub flower_placed: bool,
     /// The random seed used to generate this board.
@@ -98,6 +478,24 @@ fn suit_index(suit: Suit) -> usize {
         Suit::Red => 0,
         Suit::Green => 1,
         Suit::Black => 2,
+        Suit::Yellow => 3,
+    }
+}
+
+/// Explain why `card` may or may not be placed on `onto` in a column.
+fn check_stack(rules: Rules, card: Card, onto: Card) -> Result<(), MoveError> {
+    match (card, onto) {
+        _ if rules.can_stack(card, onto) => Ok(()),
+        (Card::Numbered(s1, _), Card::Numbered(s2, _)) if s1 == s2 => {
+            Err(MoveError::SameSuit { card, onto })
+        }
+        (Card::Numbered(s1, _), Card::Numbered(s2, _))
+            if rules.stacking == Stacking::AlternateColor && s1.is_red() == s2.is_red() =>
+        {
+            Err(MoveError::SameColor { card, onto })
+        }
+        (Card::Numbered(..), Card::Numbered(..)) => Err(MoveError::WrongRank { card, onto }),
+        _ => Err(MoveError::NotStackable { card, onto }),
     }
 }
 
@@ -107,38 +505,74 @@ impl Board {
     // -------------------------------------------------------------------------
 
     /// Deal a fresh shuffled board using a random seed.
-    pub fn deal_random() -> Self {
+    pub fn deal_random(rules: Rules) -> Self {
         // Use OS rng just to pick a random `u64` seed, then use that seed
         let seed = rand::random::<u64>();
-        Self::deal_seeded(seed)
+        Self::deal(seed, rules)
+    }
+
+    /// Deal `seed` onto a table of the given shape, shuffled the way the
+    /// rules' deal style says.
+    pub fn deal(seed: u64, rules: Rules) -> Self {
+        match rules.deal_style {
+            DealStyle::Classic => Self::deal_with(&mut Xoshiro256PlusPlus::seed_from_u64(seed), seed, rules),
+            DealStyle::Shenzhen => Self::deal_with(&mut DotNetRandom::new(seed as u32 as i32), seed, rules),
+        }
     }
 
-    /// Deal a board from a specific seed (useful for reproducible games).
-    pub fn deal_seeded(seed: u64) -> Self {
-        let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
-        let mut deck = full_deck();
-        deck.shuffle(&mut rng);
-        Self::deal_from_deck(deck, seed)
+    /// Deal a deck shuffled by `strategy` onto a table of the given shape.
+    /// `seed` is only recorded as the board's seed.
+    pub fn deal_with(strategy: &mut impl DealStrategy, seed: u64, rules: Rules) -> Self {
+        let mut deck = full_deck(rules);
+        strategy.shuffle(&mut deck);
+        Self::deal_from_deck(deck, seed, rules)
+    }
+
+    /// A position reached by dealing from `rng` onto a table with `rules`
+    /// and playing up to `moves` random legal moves, stopping early at a win
+    /// or a dead end.  Every board it returns can come up in play, which
+    /// makes it a source of realistic positions for property tests and
+    /// fuzzers; with proptest, for example,
+    /// `any::<u64>().prop_map(|s| Board::random_midgame(&mut SmallRng::seed_from_u64(s), Rules::STANDARD, 30))`.
+    pub fn random_midgame(rng: &mut impl rand::Rng, rules: Rules, moves: usize) -> Board {
+        let seed = rng.random();
+        let mut board = Board::deal_with(rng, seed, rules);
+        for _ in 0..moves {
+            let candidates = board.valid_moves();
+            if candidates.is_empty() {
+                break;
+            }
+            board.apply_move(candidates[rng.random_range(0..candidates.len())]);
+        }
+        board
+    }
+
+    /// Derive a numeric seed from an arbitrary phrase (e.g. "coffee break"),
+    /// so friends can share memorable seeds.  Uses the first 8 bytes of the
+    /// phrase's SHA-256 digest, which is stable across platforms and versions.
+    pub fn seed_from_phrase(phrase: &str) -> u64 {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(phrase.as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(bytes)
     }
 
     /// Deal a board from an already-ordered deck slice (for testing).
-    pub fn deal_from_deck(deck: Vec<Card>, seed: u64) -> Self {
-        assert_eq!(deck.len(), 40, "Need exactly 40 cards to deal");
+    pub fn deal_from_deck(deck: Vec<Card>, seed: u64, rules: Rules) -> Self {
+        assert_eq!(deck.len(), rules.deck_size(), "Need a full deck to deal");
 
-        // Distribute 40 cards across 8 columns: 5 cards per column.
-        let mut columns: [Vec<Card>; NUM_COLUMNS] = Default::default();
+        // Deal row by row, so the standard 8 columns get 5 cards each.
+        let mut columns = vec![Vec::new(); rules.columns];
         for (i, card) in deck.into_iter().enumerate() {
-            columns[i % NUM_COLUMNS].push(card);
+            columns[i % rules.columns].push(card);
         }
 
         Board {
+            rules,
             columns,
-            free_cells: [
-                FreeCellState::Empty,
-                FreeCellState::Empty,
-                FreeCellState::Empty,
-            ],
-            foundations: [0; NUM_FOUNDATIONS],
+            free_cells: vec![FreeCellState::Empty; rules.free_cells],
+            foundations: vec![0; rules.suits],
             flower_placed: false,
             seed,
         }
@@ -181,54 +615,104 @@ impl Board {
 
     /// Can the top card of `src` be moved to `dst`?
     pub fn can_move(&self, src: Location, dst: Location) -> bool {
-        let card = match self.card_at(src) {
-            Some(c) => c,
-            None => return false,
-        };
+        self.check_move(src, dst).is_ok()
+    }
+
+    /// Validate moving the top card of `src` to `dst`, explaining any failure.
+    pub fn check_move(&self, src: Location, dst: Location) -> Result<(), MoveError> {
+        let card = self.card_at(src).ok_or(MoveError::EmptySource(src))?;
 
         match dst {
             Location::FreeCell(f) => {
                 // Free cell must be empty (not locked, not occupied)
-                self.free_cells[f].is_empty()
+                match self.free_cells[f] {
+                    FreeCellState::Empty => Ok(()),
+                    FreeCellState::Card(held) => Err(MoveError::FreeCellOccupied { cell: f, card: held }),
+                    FreeCellState::DragonLocked(suit) => Err(MoveError::FreeCellLocked { cell: f, suit }),
+                }
             }
             Location::Column(c) => {
-                if let Location::Column(sc) = src {
-                    if sc == c { return false; } // same column
-                }
+                if src == Location::Column(c) { return Err(MoveError::SameColumn); }
                 match self.column_top(c) {
                     // Empty column: any card is accepted
-                    None => true,
+                    None => Ok(()),
                     // Non-empty: card must stack according to the rules
-                    Some(top) => card.can_stack_on(top),
+                    Some(top) => check_stack(self.rules, card, top),
                 }
             }
             // Foundation/Flower are not valid dst for move_card
-            Location::Foundation(_) | Location::Flower => false,
+            Location::Foundation(_) | Location::Flower => Err(MoveError::InvalidDestination(dst)),
         }
     }
 
 
     /// Can the top card of `src` be moved to the foundation?
     pub fn can_move_to_foundation(&self, src: Location) -> bool {
+        self.check_move_to_foundation(src).is_ok()
+    }
+
+    /// Validate sending the top card of `src` to its foundation, explaining any failure.
+    pub fn check_move_to_foundation(&self, src: Location) -> Result<(), MoveError> {
         match self.card_at(src) {
-            Some(Card::Flower) => !self.flower_placed,
-            Some(Card::Numbered(suit, v)) => {
-                self.foundations[suit_index(suit)] + 1 == v
+            None => Err(MoveError::EmptySource(src)),
+            Some(Card::Flower) if !self.flower_placed => Ok(()),
+            Some(card @ Card::Numbered(suit, v)) => {
+                let placed = self.foundations[suit_index(suit)];
+                if placed + 1 == v {
+                    Ok(())
+                } else {
+                    let needed = (placed < self.rules.top_rank()).then(|| Card::Numbered(suit, placed + 1));
+                    Err(MoveError::FoundationOrder { card, needed })
+                }
             }
-            _ => false,
+            Some(card) => Err(MoveError::NotFoundationCard(card)),
         }
     }
 
+    /// Pick the best legal destination for the top card of `src`.
+    ///
+    /// Ranking: foundation (or flower slot) first, then a non-empty column the
+    /// card stacks on, then the first empty free cell, and finally an empty
+    /// column.  Returns `None` when the card cannot go anywhere.
+    pub fn best_destination(&self, src: Location) -> Option<Location> {
+        let card = self.card_at(src)?;
+        if self.can_move_to_foundation(src) {
+            return Some(match card {
+                Card::Numbered(suit, _) => Location::Foundation(suit),
+                _ => Location::Flower,
+            });
+        }
+
+        let stacking = (0..self.rules.columns)
+            .map(Location::Column)
+            .find(|&dst| self.card_at(dst).is_some() && self.can_move(src, dst));
+        if stacking.is_some() {
+            return stacking;
+        }
+
+        // Parking a card that already sits in a free cell in another one is pointless.
+        if !matches!(src, Location::FreeCell(_)) {
+            let free = (0..self.rules.free_cells)
+                .map(Location::FreeCell)
+                .find(|&dst| self.can_move(src, dst));
+            if free.is_some() {
+                return free;
+            }
+        }
+
+        (0..self.rules.columns)
+            .find(|&c| self.columns[c].is_empty() && self.can_move(src, Location::Column(c)))
+            .map(Location::Column)
+    }
+
     // -------------------------------------------------------------------------
     // Move Execution
     // -------------------------------------------------------------------------
 
     /// Move the top card from `src` to `dst` in the tableau / free cells.
     /// Returns `Err(reason)` if the move is illegal.
-    pub fn move_card(&mut self, src: Location, dst: Location) -> Result<Vec<GameEvent>, &'static str> {
-        if !self.can_move(src, dst) {
-            return Err("Illegal move");
-        }
+    pub fn move_card(&mut self, src: Location, dst: Location) -> Result<Vec<GameEvent>, MoveError> {
+        self.check_move(src, dst)?;
 
         let card = self.take_card(src).unwrap();
         self.place_card(dst, card);
@@ -237,10 +721,8 @@ impl Board {
 
 
     /// Move the top card from `src` to the appropriate foundation / flower slot.
-    pub fn move_to_foundation(&mut self, src: Location) -> Result<Vec<GameEvent>, &'static str> {
-        if !self.can_move_to_foundation(src) {
-            return Err("Card cannot go to foundation yet");
-        }
+    pub fn move_to_foundation(&mut self, src: Location) -> Result<Vec<GameEvent>, MoveError> {
+        self.check_move_to_foundation(src)?;
 
         let card = self.take_card(src).unwrap();
         match card {
@@ -257,9 +739,19 @@ impl Board {
     }
 
 
-    /// Check whether all four dragons of `suit` are exposed (top of column or
+    /// Check whether all dragons of `suit` are exposed (top of column or
     /// in a free cell) and therefore the merge can be performed.
     pub fn can_merge_dragons(&self, suit: Suit) -> bool {
+        self.check_merge_dragons(suit).is_ok()
+    }
+
+    /// Validate a dragon merge, explaining any failure.
+    pub fn check_merge_dragons(&self, suit: Suit) -> Result<(), MoveError> {
+        let exposed = self.count_exposed_dragons(suit);
+        if exposed != self.rules.dragons || exposed == 0 {
+            return Err(MoveError::DragonsNotExposed { suit, exposed, total: self.rules.dragons });
+        }
+
         // Need a free cell that is either Empty or holding a dragon of the
         // same suit (it will be freed during the merge) to receive the lock.
         let dragon = Card::Dragon(suit);
@@ -268,18 +760,9 @@ impl Board {
             .iter()
             .any(|fc| fc.is_empty() || *fc == FreeCellState::Card(dragon));
         if !has_slot {
-            return false;
+            return Err(MoveError::NoCellForDragons(suit));
         }
-
-        let count = self.count_exposed_dragons(suit);
-        count == 4
-            && self
-                .free_cells
-                .iter()
-                .filter(|fc| **fc == FreeCellState::Card(dragon))
-                .count()
-                // (Already counted in count_exposed_dragons; just confirming)
-                <= 4
+        Ok(())
     }
 
     /// Count how many dragons of `suit` are currently exposed (column tops or free cells).
@@ -298,12 +781,10 @@ impl Board {
         in_cols + in_cells
     }
 
-    /// Merge all four exposed dragons of `suit` into a single locked free cell.
+    /// Merge all exposed dragons of `suit` into a single locked free cell.
     /// Returns `Err` if the merge is not currently possible.
-    pub fn merge_dragons(&mut self, suit: Suit) -> Result<Vec<GameEvent>, &'static str> {
-        if !self.can_merge_dragons(suit) {
-            return Err("Cannot merge dragons: not all four are exposed or no free cell");
-        }
+    pub fn merge_dragons(&mut self, suit: Suit) -> Result<Vec<GameEvent>, MoveError> {
+        self.check_merge_dragons(suit)?;
 
         let dragon = Card::Dragon(suit);
 
@@ -344,6 +825,15 @@ impl Board {
     /// `value - 1` in its foundation (so we'll never need that card to build
     /// on), matching the original game's heuristic.
     pub fn auto_move(&mut self) -> (usize, Vec<GameEvent>) {
+        self.auto_move_with(AutoMovePolicy::Safe)
+    }
+
+    /// `auto_move` under a player-chosen policy: `Off` moves nothing and
+    /// `Eager` sends every card home as soon as the foundation accepts it.
+    pub fn auto_move_with(&mut self, policy: AutoMovePolicy) -> (usize, Vec<GameEvent>) {
+        if policy == AutoMovePolicy::Off {
+            return (0, Vec::new());
+        }
         let mut moved = 0;
         let mut events = Vec::new();
 
@@ -352,13 +842,14 @@ impl Board {
             let before = moved;
 
             // Check all column tops and free cells.
-            let sources: Vec<Location> = (0..NUM_COLUMNS)
+            let sources: Vec<Location> = (0..self.rules.columns)
                 .map(Location::Column)
-                .chain((0..NUM_FREE_CELLS).map(Location::FreeCell))
+                .chain((0..self.rules.free_cells).map(Location::FreeCell))
                 .collect();
 
             for src in sources {
-                if self.can_move_to_foundation(src) && self.is_safe_to_auto(src) {
+                let safe = policy == AutoMovePolicy::Eager || self.is_safe_to_auto(src);
+                if self.can_move_to_foundation(src) && safe {
                     if let Ok(mut evs) = self.move_to_foundation(src) {
                         events.append(&mut evs);
                     }
@@ -390,18 +881,61 @@ impl Board {
         }
     }
 
+    /// Send every reachable card home: play any foundation move (safe or not)
+    /// and merge any fully exposed dragons until nothing changes.  This never
+    /// rearranges the tableau, so it only wins on boards that are already
+    /// "trivially winnable".
+    pub fn play_out(&mut self) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        loop {
+            let sources: Vec<Location> = (0..self.rules.columns)
+                .map(Location::Column)
+                .chain((0..self.rules.free_cells).map(Location::FreeCell))
+                .collect();
+
+            let mut progressed = false;
+            for src in sources {
+                if let Ok(mut evs) = self.move_to_foundation(src) {
+                    events.append(&mut evs);
+                    progressed = true;
+                }
+            }
+            for &suit in self.rules.suits() {
+                if let Ok(mut evs) = self.merge_dragons(suit) {
+                    events.append(&mut evs);
+                    progressed = true;
+                }
+            }
+
+            if !progressed {
+                break;
+            }
+        }
+        events
+    }
+
+    /// True when the game is not yet won but `play_out` would win it.
+    pub fn is_trivially_winnable(&self) -> bool {
+        if self.is_won() {
+            return false;
+        }
+        let mut probe = self.clone();
+        probe.play_out();
+        probe.is_won()
+    }
+
     // -------------------------------------------------------------------------
     // Win Condition
     // -------------------------------------------------------------------------
 
     /// The game is won when:
-    /// - All foundations are at 9.
+    /// - All foundations are at the top rank.
     /// - The flower is placed.
     /// - All free cells are either Empty or DragonLocked.
     /// - All columns are empty.
     pub fn is_won(&self) -> bool {
-        self.foundations.iter().all(|&f| f == 9)
-            && self.flower_placed
+        self.foundations.iter().all(|&f| f == self.rules.top_rank())
+            && (self.flower_placed || !self.rules.flower)
             && self.columns.iter().all(|col| col.is_empty())
             && self
                 .free_cells
@@ -424,7 +958,7 @@ impl Board {
         let mut len = 1;
         let mut i = from_idx;
         while i + 1 < col_cards.len() {
-            if col_cards[i + 1].can_stack_on(col_cards[i]) {
+            if self.rules.can_stack(col_cards[i + 1], col_cards[i]) {
                 len += 1;
                 i += 1;
             } else {
@@ -434,6 +968,42 @@ impl Board {
         len
     }
 
+    /// Whether column `col` holds cards and all of them form one ordered
+    /// run.
+    pub fn is_ordered(&self, col: usize) -> bool {
+        let len = self.columns[col].len();
+        len > 0 && self.stack_len(col, 0) == len
+    }
+
+    /// Returns the absolute index where the longest run in `src_col` that can
+    /// legally land on `dst_col` begins, or `None` if nothing can move there.
+    pub fn longest_movable_stack(&self, src_col: usize, dst_col: usize) -> Option<usize> {
+        if src_col == dst_col {
+            return None;
+        }
+        let col_len = self.columns[src_col].len();
+        (0..col_len).find(|&start_idx| {
+            self.stack_len(src_col, start_idx) == col_len - start_idx
+                && col_len - start_idx <= self.max_stack_move(dst_col)
+                && match self.column_top(dst_col) {
+                    None => true,
+                    Some(top) => self.rules.can_stack(self.columns[src_col][start_idx], top),
+                }
+        })
+    }
+
+    /// The longest stack that may move to `dst_col` at once.  SHENZHEN I/O
+    /// moves any run; FreeCell only as many cards as could be shuffled
+    /// through the empty free cells and the other empty columns.
+    pub fn max_stack_move(&self, dst_col: usize) -> usize {
+        if !self.rules.supermove {
+            return usize::MAX;
+        }
+        let cells = self.free_cells.iter().filter(|cell| cell.is_empty()).count();
+        let columns = (0..self.rules.columns).filter(|&c| c != dst_col && self.columns[c].is_empty()).count();
+        (cells + 1) << columns
+    }
+
     /// Move a stack of cards from column `src_col` starting at `start_idx`
     /// to column `dst_col`.  All cards from `start_idx` to the bottom of the
     /// column are moved.
@@ -442,30 +1012,34 @@ impl Board {
         src_col: usize,
         start_idx: usize,
         dst_col: usize,
-    ) -> Result<Vec<GameEvent>, &'static str> {
+    ) -> Result<Vec<GameEvent>, MoveError> {
         if src_col == dst_col {
-            return Err("Source and destination columns are the same");
+            return Err(MoveError::SameColumn);
         }
 
         let col_len = self.columns[src_col].len();
+        if col_len == 0 {
+            return Err(MoveError::EmptySource(Location::Column(src_col)));
+        }
         if start_idx >= col_len {
-            return Err("start_idx out of bounds");
+            return Err(MoveError::StackOutOfBounds { col: src_col, start_idx });
         }
 
         // Verify the stack is a valid sequence.
         let movable = self.stack_len(src_col, start_idx);
         let stack_size = col_len - start_idx;
         if movable < stack_size {
-            return Err("That slice is not a valid sequence");
+            return Err(MoveError::NotASequence { col: src_col });
+        }
+        let max = self.max_stack_move(dst_col);
+        if stack_size > max {
+            return Err(MoveError::StackTooLong { len: stack_size, max });
         }
 
         // Validate placement of the bottom card of the stack onto the dst column.
         let bottom_card = self.columns[src_col][start_idx];
-        if !match self.column_top(dst_col) {
-            None => true, // Empty column accepts anything
-            Some(top) => bottom_card.can_stack_on(top),
-        } {
-            return Err("Stack cannot be placed on destination column");
+        if let Some(top) = self.column_top(dst_col) {
+            check_stack(self.rules, bottom_card, top)?;
         }
 
         // Execute the move.
@@ -542,8 +1116,8 @@ impl Board {
                 self.free_cells[*locked_cell] = FreeCellState::DragonLocked(*suit);
             }
             GameEvent::Won => {}
-            GameEvent::Dealt { seed } => {
-                *self = Board::deal_seeded(*seed);
+            GameEvent::Dealt { seed, rules } => {
+                *self = Board::deal(*seed, *rules);
             }
             GameEvent::RestoreDealt { board } => {
                 *self = board.clone();
@@ -551,3 +1125,98 @@ impl Board {
         }
     }
 }
+
+/// .NET's `System.Random` as constructed with a seed: Knuth's subtractive
+/// generator, including its quirks, so a seed gives the same numbers here
+/// as it does in .NET (checked against `System.Random` for seeds 0, 1 and
+/// 42).
+struct DotNetRandom {
+    seeds: [i32; 56],
+    next: usize,
+    next_p: usize,
+}
+
+impl DotNetRandom {
+    const MBIG: i32 = i32::MAX;
+    const MSEED: i32 = 161_803_398;
+
+    fn new(seed: i32) -> Self {
+        let subtraction = if seed == i32::MIN { i32::MAX } else { seed.abs() };
+        let mut seeds = [0i32; 56];
+        let mut mj = Self::MSEED - subtraction;
+        seeds[55] = mj;
+        let mut mk = 1;
+        for i in 1..55 {
+            let ii = (21 * i) % 55;
+            seeds[ii] = mk;
+            mk = mj - mk;
+            if mk < 0 {
+                mk += Self::MBIG;
+            }
+            mj = seeds[ii];
+        }
+        for _ in 1..5 {
+            for i in 1..56 {
+                seeds[i] -= seeds[1 + (i + 30) % 55];
+                if seeds[i] < 0 {
+                    seeds[i] += Self::MBIG;
+                }
+            }
+        }
+        DotNetRandom { seeds, next: 0, next_p: 21 }
+    }
+
+    fn sample(&mut self) -> f64 {
+        self.next = if self.next + 1 >= 56 { 1 } else { self.next + 1 };
+        self.next_p = if self.next_p + 1 >= 56 { 1 } else { self.next_p + 1 };
+        let mut value = self.seeds[self.next] - self.seeds[self.next_p];
+        if value == Self::MBIG {
+            value -= 1;
+        }
+        if value < 0 {
+            value += Self::MBIG;
+        }
+        self.seeds[self.next] = value;
+        value as f64 * (1.0 / Self::MBIG as f64)
+    }
+
+    /// `Random.Next(min, max)`: an integer in `min..max`.
+    fn next_in(&mut self, min: i32, max: i32) -> i32 {
+        (self.sample() * (max - min) as f64) as i32 + min
+    }
+}
+
+/// A forward Fisher-Yates shuffle, as the original game is believed to
+/// deal; not yet checked against boards from the game.
+impl DealStrategy for DotNetRandom {
+    fn shuffle(&mut self, deck: &mut [Card]) {
+        for i in 0..deck.len() {
+            let j = self.next_in(i as i32, deck.len() as i32) as usize;
+            deck.swap(i, j);
+        }
+    }
+}
+
+/// A board that can come up in play: a seed dealt onto arbitrary rules,
+/// then up to [`Board::ARBITRARY_MOVES`] legal moves chosen by the input.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Board {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let rules: Rules = u.arbitrary()?;
+        let mut board = Board::deal(u.arbitrary()?, rules);
+        for _ in 0..u.int_in_range(0..=Board::ARBITRARY_MOVES)? {
+            let candidates = board.valid_moves();
+            if candidates.is_empty() {
+                break;
+            }
+            board.apply_move(candidates[u.choose_index(candidates.len())?]);
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl Board {
+    /// The most moves an arbitrary board is played from its deal.
+    pub const ARBITRARY_MOVES: usize = 200;
+}
@@ -0,0 +1,153 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The campaign: a ladder of deals from trivial to hard.  The stages were
+//! picked by running `analyze` over the first 500 seeds and taking deals
+//! spread evenly through each difficulty band, ordered by how many
+//! positions the solver explored.  Stage 1 is open from the start; winning
+//! a stage opens the next.  Attempts are kept in the history.
+use crate::history::History;
+use crate::i18n::tr;
+use crate::renderer::format_elapsed;
+
+/// Each stage's seed and the positions the solver explored to win it.
+pub const STAGES: [(u64, usize); 24] = [
+    (77, 24),
+    (119, 123),
+    (473, 302),
+    (1, 575),
+    (225, 996),
+    (498, 1_003),
+    (84, 1_465),
+    (351, 2_057),
+    (129, 2_762),
+    (368, 4_286),
+    (322, 5_798),
+    (212, 9_974),
+    (183, 10_193),
+    (47, 12_805),
+    (17, 17_911),
+    (125, 26_621),
+    (180, 33_416),
+    (434, 62_825),
+    (40, 95_346),
+    (216, 101_704),
+    (189, 142_036),
+    (476, 215_982),
+    (301, 372_896),
+    (123, 498_468),
+];
+
+/// Stages per row of the map.
+pub const MAP_COLUMNS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageState {
+    Cleared,
+    /// The next stage to win.
+    Open,
+    Locked,
+}
+
+/// One stage as the map shows it.
+#[derive(Debug, Clone)]
+pub struct StageView {
+    /// 1-based.
+    pub number: usize,
+    pub seed: u64,
+    pub band: &'static str,
+    pub state: StageState,
+    pub attempts: usize,
+    /// Best time and fewest moves of the stage's wins.
+    pub best: Option<(u64, u32)>,
+}
+
+impl StageView {
+    /// "Stage 7  seed 84  easy  2 tries  best 3:12, 45 moves".
+    pub fn summary(&self) -> String {
+        let line = tr!("Stage {}  seed {}  {}  {} tries", self.number, self.seed, self.band, self.attempts);
+        match self.best {
+            Some((secs, moves)) => tr!("{}  best {}, {} moves", line, format_elapsed(std::time::Duration::from_secs(secs)), moves),
+            None => line,
+        }
+    }
+
+    /// The stage's mark on the map: `✓` cleared, `▶` open, `·` locked.
+    pub fn mark(&self) -> char {
+        match self.state {
+            StageState::Cleared => '✓',
+            StageState::Open => '▶',
+            StageState::Locked => '·',
+        }
+    }
+}
+
+/// The difficulty of a row of the map, like `easy` or `easy–medium`.
+pub fn row_bands(row: &[StageView]) -> String {
+    match (row.first(), row.last()) {
+        (Some(first), Some(last)) if first.band != last.band => format!("{}–{}", first.band, last.band),
+        (Some(first), _) => first.band.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// The campaign map: every stage and how far the player got.
+#[derive(Debug, Clone)]
+pub struct CampaignMap {
+    pub stages: Vec<StageView>,
+    /// Stages won so far.
+    pub cleared: usize,
+}
+
+impl CampaignMap {
+    pub fn new(history: &History) -> Self {
+        let cleared = history.campaign_cleared();
+        let stages = STAGES
+            .iter()
+            .enumerate()
+            .map(|(i, &(seed, nodes))| {
+                let number = i + 1;
+                let attempts: Vec<_> = history.campaign.iter().filter(|a| a.stage == number && a.end_time.is_some()).collect();
+                let won = attempts.iter().filter(|a| a.won);
+                let best = won.clone().map(|a| a.elapsed_secs).min().zip(won.map(|a| a.moves).min());
+                let state = match number {
+                    n if n <= cleared => StageState::Cleared,
+                    n if n == cleared + 1 => StageState::Open,
+                    _ => StageState::Locked,
+                };
+                StageView { number, seed, band: crate::analyze::band(nodes), state, attempts: attempts.len(), best }
+            })
+            .collect();
+        CampaignMap { stages, cleared }
+    }
+
+    /// The stage to start the map on: the open one, or the last.
+    pub fn next_stage(&self) -> usize {
+        (self.cleared + 1).min(self.stages.len())
+    }
+
+    /// The map as text, for the CLI: rows of marks labelled with their
+    /// difficulty, then the stages played or open.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![tr!("Campaign: {} of {} stages cleared.", self.cleared, self.stages.len())];
+        for row in self.stages.chunks(MAP_COLUMNS) {
+            let cells: Vec<String> = row.iter().map(|s| format!("[{:>2} {}]", s.number, s.mark())).collect();
+            lines.push(format!("  {}  {}", cells.join("─"), row_bands(row)));
+        }
+        for stage in self.stages.iter().filter(|s| s.state != StageState::Locked) {
+            lines.push(format!("  {} {}", stage.mark(), stage.summary()));
+        }
+        lines.push(if self.cleared == self.stages.len() {
+            tr!("Every stage is cleared. Replay any with 'campaign <n>'.")
+        } else {
+            tr!("Type 'campaign {}' to play the next stage.", self.next_stage())
+        });
+        lines
+    }
+}
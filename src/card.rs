@@ -22,18 +22,24 @@
  */
 use serde::{Serialize, Deserialize};
 
+use crate::board::Rules;
+
 /// Suits used in SHENZHEN I/O Solitaire.
-/// There are three suits: Red (红), Green (绿), Black (黑).
+/// There are three suits: Red (红), Green (绿), Black (黑), plus Yellow (黄)
+/// for the optional four-suit variant.  In FreeCell they are diamonds,
+/// clubs, spades and hearts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Suit {
     Red,
     Green,
     Black,
+    Yellow,
 }
 
 impl Suit {
-    /// All three suits, in canonical order.
-    pub const ALL: [Suit; 3] = [Suit::Red, Suit::Green, Suit::Black];
+    /// Every suit, in canonical order; a game uses the first
+    /// `Rules::suits` of them.
+    pub const ALL: [Suit; 4] = [Suit::Red, Suit::Green, Suit::Black, Suit::Yellow];
 
     /// Single-character symbol used in CLI rendering.
     pub fn symbol(self) -> &'static str {
@@ -41,9 +47,15 @@ impl Suit {
             Suit::Red => "R",
             Suit::Green => "G",
             Suit::Black => "B",
+            Suit::Yellow => "Y",
         }
     }
 
+    /// Red in FreeCell's colors: diamonds (Red) and hearts (Yellow).
+    pub fn is_red(self) -> bool {
+        matches!(self, Suit::Red | Suit::Yellow)
+    }
+
     /// Full name (reserved for TUI/display use).
     #[allow(dead_code)]
     pub fn name(self) -> &'static str {
@@ -51,6 +63,7 @@ impl Suit {
             Suit::Red => "Red",
             Suit::Green => "Green",
             Suit::Black => "Black",
+            Suit::Yellow => "Yellow",
         }
     }
 }
@@ -113,22 +126,117 @@ impl Card {
             Card::Flower => "FL".to_string(),
         }
     }
+
+    /// Read a card back from its `label`, in either case.
+    pub fn from_label(label: &str) -> Option<Card> {
+        let label = label.to_ascii_uppercase();
+        if label == "FL" {
+            return Some(Card::Flower);
+        }
+        let (symbol, rest) = label.split_at_checked(1)?;
+        let suit = *Suit::ALL.iter().find(|s| s.symbol() == symbol)?;
+        match rest {
+            "D" => Some(Card::Dragon(suit)),
+            _ => match rest.parse() {
+                Ok(v @ 1..=crate::board::MAX_RANK) => Some(Card::Numbered(suit, v)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Supplies the short text printed on card faces, so renderers can switch
+/// between label sets without knowing their contents.
+pub trait LabelProvider {
+    /// Name used in config files.
+    fn name(&self) -> &'static str;
+    /// Face text of `card`, e.g. `R5`.
+    fn card(&self, card: Card) -> String;
+    /// Short tag for a suit, e.g. `R` in front of a foundation pile.
+    fn suit(&self, suit: Suit) -> String;
+}
+
+/// The default Latin labels: `R5`, `GD`, `FL`.
+pub struct AsciiLabels;
+
+impl LabelProvider for AsciiLabels {
+    fn name(&self) -> &'static str {
+        "ascii"
+    }
+
+    fn card(&self, card: Card) -> String {
+        card.label()
+    }
+
+    fn suit(&self, suit: Suit) -> String {
+        suit.symbol().to_string()
+    }
 }
 
-pub fn full_deck() -> Vec<Card> {
-    let mut deck = Vec::with_capacity(40);
+/// Mahjong-style labels as in the original game: a Chinese numeral and a
+/// suit character (萬 characters, 條 bamboo, 筒 dots), the 中/發/白 dragons
+/// and 花 for the flower.  The four-suit variant adds 風 winds with a 鳳
+/// dragon.  Numbered cards are four columns wide, six from 十一 on.
+pub struct ChineseLabels;
 
-    for &suit in &Suit::ALL {
-        for v in 1..=9 {
+impl ChineseLabels {
+    const NUMERALS: [&'static str; crate::board::MAX_RANK as usize] =
+        ["一", "二", "三", "四", "五", "六", "七", "八", "九", "十", "十一", "十二", "十三"];
+}
+
+impl LabelProvider for ChineseLabels {
+    fn name(&self) -> &'static str {
+        "chinese"
+    }
+
+    fn card(&self, card: Card) -> String {
+        match card {
+            Card::Numbered(s, v) => format!("{}{}", Self::NUMERALS[(v - 1) as usize], self.suit(s)),
+            Card::Dragon(Suit::Red) => "中".to_string(),
+            Card::Dragon(Suit::Green) => "發".to_string(),
+            Card::Dragon(Suit::Black) => "白".to_string(),
+            Card::Dragon(Suit::Yellow) => "鳳".to_string(),
+            Card::Flower => "花".to_string(),
+        }
+    }
+
+    fn suit(&self, suit: Suit) -> String {
+        match suit {
+            Suit::Red => "萬",
+            Suit::Green => "條",
+            Suit::Black => "筒",
+            Suit::Yellow => "風",
+        }
+        .to_string()
+    }
+}
+
+/// Look up a label set by its config name.
+pub fn label_provider(name: &str) -> Option<Box<dyn LabelProvider>> {
+    match name.to_ascii_lowercase().as_str() {
+        "ascii" => Some(Box::new(AsciiLabels)),
+        "chinese" => Some(Box::new(ChineseLabels)),
+        _ => None,
+    }
+}
+
+/// The deck dealt under `rules`: numbered cards up to the top rank in each
+/// suit and, in SHENZHEN I/O, four dragons per suit and the flower.
+pub fn full_deck(rules: Rules) -> Vec<Card> {
+    let mut deck = Vec::with_capacity(rules.deck_size());
+
+    for &suit in rules.suits() {
+        for v in 1..=rules.top_rank() {
             deck.push(Card::Numbered(suit, v));
         }
-        for _ in 0..4 {
+        for _ in 0..rules.dragons {
             deck.push(Card::Dragon(suit));
         }
     }
 
-    deck.push(Card::Flower);
+    if rules.flower {
+        deck.push(Card::Flower);
+    }
 
-    debug_assert_eq!(deck.len(), 40, "Deck must have exactly 40 cards");
     deck
 }
@@ -0,0 +1,30 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The system clipboard for `copy`, built only with `--features clipboard`.
+//!
+//! On X11 the copied text is served by the process that set it, so the
+//! clipboard is opened once and kept until szsol exits; a clipboard
+//! manager, if one runs, keeps the text after that.
+use std::sync::Mutex;
+
+use arboard::Clipboard;
+
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+/// Put `text` on the clipboard.  Fails when there is no clipboard to
+/// reach, such as over SSH or without a display.
+pub fn copy(text: &str) -> Result<(), String> {
+    let mut slot = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    let clipboard = match slot.as_mut() {
+        Some(clipboard) => clipboard,
+        None => slot.insert(Clipboard::new().map_err(|e| e.to_string())?),
+    };
+    clipboard.set_text(text).map_err(|e| e.to_string())
+}
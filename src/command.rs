@@ -20,6 +20,10 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use crate::board::Rules;
+use crate::i18n::tr;
+use crate::renderer::RenderMode;
+
 /// All commands a player can issue.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
@@ -31,6 +35,10 @@ pub enum Command {
         stack_start: usize,
         dst: usize,
     },
+    /// Move the longest run from `src` that fits on `dst` (shorthand `47`).
+    ColumnToColumnAuto { src: usize, dst: usize },
+    /// Move the top card of a column to the best legal destination.
+    AutoMove { src: usize },
     /// Move the top card of a column to a free cell.
     ColumnToFreeCell { src_col: usize, dst_cell: usize },
     /// Move the card in a free cell to a column.
@@ -39,22 +47,156 @@ pub enum Command {
     ColumnToFoundation { src: usize },
     /// Move the card in a free cell to the foundation.
     FreeCellToFoundation { src_cell: usize },
-    /// Merge all four exposed dragons of a suit.
+    /// Merge all exposed dragons of a suit.
     MergeDragons { suit: crate::card::Suit },
-    /// Undo the last move (optional, not yet implemented).
-    Undo,
+    /// Send every remaining card home when no tableau rearrangement is needed.
+    Finish,
+    /// Undo the last `steps` moves.
+    Undo { steps: usize },
+    /// Rewind all the way back to the initial deal.
+    UndoAll,
+    /// List alternative lines kept after undoing and playing differently.
+    Lines,
+    /// Switch to an alternative line (the current one is kept in its place).
+    SwitchLine { index: usize },
+    /// Bookmark the current position under a name (no name: list bookmarks).
+    Mark { name: Option<String> },
+    /// Restore a bookmarked position.
+    Goto { name: String },
+    /// Print the effective settings and where each came from.
+    Config,
+    /// List the active key bindings.
+    Keys,
+    /// Start capturing the commands that follow as the macro `name`.
+    MacroRecord { name: String },
+    /// Finish the macro being recorded.
+    MacroStop,
+    /// Run the commands of the macro `name`.
+    MacroPlay { name: String },
+    /// List the saved macros.
+    MacroList,
+    /// Read the board out as plain sentences (for screen readers).
+    Describe,
+    /// List the speedrun splits reached so far.
+    Splits,
+    /// Page through past games, newest first.  `page` starts at 1.
+    History { filter: crate::history::RecordFilter, page: usize },
+    /// Re-deal the seed of game number `id` from the history listing.
+    HistoryPlay { id: usize },
+    /// List the weekly challenges played, with the best result of each.
+    HistoryWeekly,
+    /// Write the statistics (CSV) or full history (JSON) to `path`.
+    Export { kind: ExportKind, path: String },
+    /// Merge the records of a JSON history export into this history.
+    Import { path: String },
+    /// Start a game from a board transcribed as a text grid.
+    ImportGrid { path: String },
+    /// List the unfinished games.
+    Games,
+    /// Switch to unfinished game `slot` from the `games` list (1-based).
+    Resume { slot: usize },
+    /// Print a position code for the current board.
+    Share,
+    /// Print the share text of the daily deal won in this session.
+    ShareDaily,
+    /// Put the seed, the position code or the daily share text on the
+    /// clipboard.
+    Copy { kind: CopyKind },
+    /// Start a game from a position code.
+    Load { code: String },
+    /// Print a challenge token for the current deal, or take up a friend's.
+    Challenge { token: Option<String> },
+    /// List challenges taken and how they went.
+    Challenges,
+    /// Rate the seeds played with the solver and set the difficulty
+    /// against the player's win rate.
+    ReportDifficulty,
+    /// Show the player's rating and its latest changes.
+    ReportRating,
+    /// List the puzzles of the current pack, first loading the pack at
+    /// `path` if given.
+    Puzzles { path: Option<String> },
+    /// Start puzzle `index` (1-based) of the current pack.
+    Puzzle { index: usize },
+    /// Show the campaign map, or play `stage` (1-based) if it is open.
+    Campaign { stage: Option<usize> },
+    /// Show a seed's best time and fewest moves (the current seed if `None`).
+    Best { seed: Option<u64> },
+    /// Post today's daily-deal win to the online leaderboard.
+    Submit,
+    /// Show today's top times from the online leaderboard.
+    Leaderboard,
+    /// Turn the usage statistics on or off, or (`None`) show them.
+    Telemetry { enable: Option<bool> },
+    /// Upload the usage statistics to `telemetry_url`.
+    TelemetrySend,
     /// Run the solver.
     Solve,
+    /// Show the current deal's par, working it out if needed.
+    Par,
     /// Quit the game.
     Quit,
-    /// Give up and start a new game.
-    NewGame,
+    /// Give up and start a new game, optionally from a specific seed or
+    /// from a phrase hashed into one.
+    NewGame { seed: Option<u64>, phrase: Option<String> },
+    /// Give up and start this week's challenge deal.
+    NewWeekly,
+    /// Throw away all moves and re-deal the current seed.
+    Restart,
+    /// Give up the current game and record it as a loss without dealing.
+    Resign,
+    /// After resigning or at a dead end, replay a winning line from the
+    /// last position that could still be won.
+    ShowSolution,
+    /// Change how much the CLI prints after each command.
+    SetRender { mode: RenderMode },
     /// Print help.
     Help,
 }
 
+impl Command {
+    /// Whether the command moves cards, so that doing nothing means it was
+    /// refused.
+    pub fn is_move(&self) -> bool {
+        matches!(
+            self,
+            Command::ColumnToColumn { .. }
+                | Command::ColumnToColumnAuto { .. }
+                | Command::AutoMove { .. }
+                | Command::ColumnToFreeCell { .. }
+                | Command::FreeCellToColumn { .. }
+                | Command::ColumnToFoundation { .. }
+                | Command::FreeCellToFoundation { .. }
+                | Command::MergeDragons { .. }
+                | Command::Finish
+        )
+    }
+}
+
+/// What `export` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    /// One CSV row per game.
+    Stats,
+    /// Every record as JSON.
+    History,
+}
+
+/// What `copy` puts on the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyKind {
+    /// The current deal's seed.
+    Seed,
+    /// The position code of the current board.
+    Share,
+    /// The share text of the daily deal won in this session.
+    Daily,
+}
+
 /// Parse a single line of text input into a `Command`.
 ///
+/// Column and free-cell numbers start at `base` (0, or 1 with `one_indexed`).
+///
 /// Syntax reference (case-insensitive):
 /// ```
 /// cc <src_col> <dst_col>            -- Move top card column→column
@@ -63,118 +205,542 @@ pub enum Command {
 /// fc <cell_idx> <dst_col>           -- Move free cell → column
 /// ctf <src_col>                     -- Move column top → foundation
 /// ftf <cell_idx>                    -- Move free cell → foundation
-/// dragon r|g|b                      -- Merge dragons of a suit
-/// undo                              -- Undo last move
+/// mv <src> <dst>                    -- Unified move: c<N>[:<depth>] | f<N> | fo
+/// <src><dst>                        -- Shorthand, e.g. `47`: longest run col 4 → col 7
+/// m <src_col>                       -- Move column top to its best destination
+/// dragon r|g|b|y                    -- Merge dragons of a suit (y: four suits)
+/// finish                            -- Play out a trivially winnable board
+/// undo [<n>|all]                    -- Undo last move, last n moves, or back to the deal
+/// lines                             -- List alternative lines kept after undo
+/// switch <n>                        -- Switch to alternative line n
+/// mark [<name>]                     -- Bookmark the position (no name: list bookmarks)
+/// goto <name>                       -- Restore a bookmarked position
+/// config                            -- Show effective settings and their sources
+/// keys                              -- List the key bindings and their config names
+/// macro record <name> | stop        -- Capture the following commands as a macro
+/// macro play <name> | macro         -- Replay a macro / list macros
+/// set render quiet|normal|verbose   -- Print only changed rows, the board, or also each auto-move
+/// describe | desc                   -- Describe the board in plain sentences
+/// splits                            -- List speedrun splits against personal bests
+/// history [won|lost] [seed <s>] [page <n>] -- List past games, newest first
+/// history play <#>                  -- Re-deal the seed of a listed game
+/// history weekly                    -- Weekly challenge results, week by week
+/// export stats|history <file>       -- Write stats as CSV or the history as JSON
+/// import history <file>             -- Merge games from a JSON history export
+/// import grid <file>                -- Play a board transcribed as 5 rows of 8 cards
+/// games                             -- List unfinished games
+/// resume <n>                        -- Switch to unfinished game n
+/// share [daily]                     -- Print a position code, or the daily deal's result to share
+/// copy seed|share|daily             -- Put the seed, position code or daily result on the clipboard
+/// challenge [<token>]               -- Send this deal as a challenge, or take one up
+/// challenges                        -- List challenges taken
+/// report difficulty                 -- Seeds played by solver difficulty vs. win rate
+/// report rating                     -- Current rating and its latest changes
+/// puzzles [<file>]                  -- List puzzles (of the pack in <file>)
+/// puzzle <n>                        -- Play puzzle n
+/// campaign [<n>]                    -- Show the campaign map, or play stage n
+/// best [<seed>]                     -- Best time and fewest moves on a seed
+/// submit                            -- Post today's daily win online (opt-in)
+/// leaderboard                       -- Today's top times online (opt-in)
+/// telemetry [status|on|off|send]    -- Show, start, stop or upload usage statistics
 /// solve                             -- Run solver (BFS)
-/// new                               -- New game
+/// par                               -- Show the deal's par (solver move count)
+/// new [<seed>|daily|weekly|<phrase>] -- New game (seed may be decimal, 0x-hex or any phrase)
+/// restart                           -- Re-deal the current seed
+/// resign | giveup                   -- Record the current game as a loss
+/// show solution                     -- After resigning or at a dead end, replay a winning line
 /// quit | q                          -- Quit
 /// help | h | ?                      -- Help
 /// ```
-pub fn parse_command(input: &str) -> Result<Command, String> {
+pub fn parse_command(input: &str, base: usize, rules: Rules) -> Result<Command, String> {
     let input = input.trim();
     if input.is_empty() {
-        return Err("Empty input".to_string());
+        return Err(tr!("Empty input"));
     }
 
     let tokens: Vec<&str> = input.split_whitespace().collect();
     let cmd = tokens[0].to_lowercase();
 
+    if tokens.len() == 1 && cmd.len() == 2 && cmd.chars().all(|c| c.is_ascii_digit()) {
+        let src = parse_col_idx(&cmd[..1], base, rules)?;
+        let dst = parse_col_idx(&cmd[1..], base, rules)?;
+        return Ok(Command::ColumnToColumnAuto { src, dst });
+    }
+
     match cmd.as_str() {
         "cc" => {
             if tokens.len() < 3 {
-                return Err("Usage: cc <src[:<depth>]> <dst>".to_string());
+                return Err(tr!("Usage: cc <src[:<depth>]> <dst>"));
             }
-            let dst: usize = parse_col_idx(tokens[2])?;
+            let dst: usize = parse_col_idx(tokens[2], base, rules)?;
             // Parse optional stack depth: "3:2" means column 3, starting 2 from top.
             if let Some((col_part, depth_part)) = tokens[1].split_once(':') {
-                let src: usize = parse_col_idx(col_part)?;
-                let stack_start: usize = depth_part.parse().map_err(|_| "Invalid depth".to_string())?;
+                let src: usize = parse_col_idx(col_part, base, rules)?;
+                let stack_start: usize = depth_part.parse().map_err(|_| tr!("Invalid depth"))?;
                 Ok(Command::ColumnToColumn { src, stack_start, dst })
             } else {
-                let src: usize = parse_col_idx(tokens[1])?;
+                let src: usize = parse_col_idx(tokens[1], base, rules)?;
                 Ok(Command::ColumnToColumn { src, stack_start: 0, dst })
             }
         }
         "cf" => {
             if tokens.len() < 3 {
-                return Err("Usage: cf <src_col> <cell_idx>".to_string());
+                return Err(tr!("Usage: cf <src_col> <cell_idx>"));
             }
             Ok(Command::ColumnToFreeCell {
-                src_col: parse_col_idx(tokens[1])?,
-                dst_cell: parse_cell_idx(tokens[2])?,
+                src_col: parse_col_idx(tokens[1], base, rules)?,
+                dst_cell: parse_cell_idx(tokens[2], base, rules)?,
             })
         }
         "fc" => {
             if tokens.len() < 3 {
-                return Err("Usage: fc <cell_idx> <dst_col>".to_string());
+                return Err(tr!("Usage: fc <cell_idx> <dst_col>"));
             }
             Ok(Command::FreeCellToColumn {
-                src_cell: parse_cell_idx(tokens[1])?,
-                dst_col: parse_col_idx(tokens[2])?,
+                src_cell: parse_cell_idx(tokens[1], base, rules)?,
+                dst_col: parse_col_idx(tokens[2], base, rules)?,
             })
         }
         "ctf" => {
             if tokens.len() < 2 {
-                return Err("Usage: ctf <src_col>".to_string());
+                return Err(tr!("Usage: ctf <src_col>"));
             }
-            Ok(Command::ColumnToFoundation { src: parse_col_idx(tokens[1])? })
+            Ok(Command::ColumnToFoundation { src: parse_col_idx(tokens[1], base, rules)? })
         }
         "ftf" => {
             if tokens.len() < 2 {
-                return Err("Usage: ftf <cell_idx>".to_string());
+                return Err(tr!("Usage: ftf <cell_idx>"));
             }
-            Ok(Command::FreeCellToFoundation { src_cell: parse_cell_idx(tokens[1])? })
+            Ok(Command::FreeCellToFoundation { src_cell: parse_cell_idx(tokens[1], base, rules)? })
+        }
+        "mv" | "move" => {
+            if tokens.len() < 3 {
+                return Err(tr!("Usage: mv <src> <dst>  (c<N>[:<depth>], f<N>, fo)"));
+            }
+            let (src, stack_start) = parse_location(tokens[1], base, rules)?;
+            let (dst, dst_depth) = parse_location(tokens[2], base, rules)?;
+            if dst_depth != 0 {
+                return Err(tr!("A stack depth is only allowed on the source"));
+            }
+            location_move(src, stack_start, dst)
+        }
+        "m" => {
+            if tokens.len() < 2 {
+                return Err(tr!("Usage: m <src_col>"));
+            }
+            Ok(Command::AutoMove { src: parse_col_idx(tokens[1], base, rules)? })
         }
         "dragon" | "dr" => {
             if tokens.len() < 2 {
-                return Err("Usage: dragon r|g|b".to_string());
+                return Err(tr!("Usage: dragon r|g|b|y"));
             }
-            let suit = parse_suit(tokens[1])?;
+            let suit = parse_suit(tokens[1], rules)?;
             Ok(Command::MergeDragons { suit })
         }
 
-        "undo" | "u" => Ok(Command::Undo),
+        "finish" | "fin" => Ok(Command::Finish),
+        "undo" | "u" => match tokens.get(1).map(|t| t.to_lowercase()) {
+            None => Ok(Command::Undo { steps: 1 }),
+            Some(t) if t == "all" => Ok(Command::UndoAll),
+            Some(t) => match t.parse::<usize>() {
+                Ok(steps) if steps > 0 => Ok(Command::Undo { steps }),
+                _ => Err(tr!("Usage: undo [<n>|all]")),
+            },
+        },
+        "lines" => Ok(Command::Lines),
+        "switch" => {
+            if tokens.len() < 2 {
+                return Err(tr!("Usage: switch <n>"));
+            }
+            let index = tokens[1].parse().map_err(|_| tr!("'{}' is not a valid line number", tokens[1]))?;
+            Ok(Command::SwitchLine { index })
+        }
+        "mark" => Ok(Command::Mark { name: tokens.get(1).map(|t| t.to_string()) }),
+        "goto" => {
+            if tokens.len() < 2 {
+                return Err(tr!("Usage: goto <name>"));
+            }
+            Ok(Command::Goto { name: tokens[1].to_string() })
+        }
+        "config" => Ok(Command::Config),
+        "keys" => Ok(Command::Keys),
+        "macro" => match (tokens.get(1).map(|t| t.to_lowercase()).as_deref(), tokens.get(2)) {
+            (None, _) => Ok(Command::MacroList),
+            (Some("stop"), None) => Ok(Command::MacroStop),
+            (Some("record"), Some(name)) if tokens.len() == 3 && crate::macros::is_valid_name(name) => {
+                Ok(Command::MacroRecord { name: name.to_string() })
+            }
+            (Some("play"), Some(name)) if tokens.len() == 3 => Ok(Command::MacroPlay { name: name.to_string() }),
+            _ => Err(tr!("Usage: macro record <name> | macro stop | macro play <name>")),
+        },
+        "describe" | "desc" => Ok(Command::Describe),
+        "splits" => Ok(Command::Splits),
+        "history" | "hist" => parse_history(&tokens[1..]),
+        "export" => {
+            let usage = || tr!("Usage: export stats <file.csv> | export history <file.json>");
+            let kind = match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+                Some("stats") => ExportKind::Stats,
+                Some("history") => ExportKind::History,
+                _ => return Err(usage()),
+            };
+            // The rest of the line is the path, so it may contain spaces.
+            let path = input.splitn(3, char::is_whitespace).nth(2).map(str::trim).unwrap_or_default();
+            if path.is_empty() {
+                return Err(usage());
+            }
+            Ok(Command::Export { kind, path: path.to_string() })
+        }
+        "games" => Ok(Command::Games),
+        "resume" => {
+            let slot = tokens.get(1).and_then(|t| t.parse().ok()).ok_or_else(|| tr!("Usage: resume <n>"))?;
+            Ok(Command::Resume { slot })
+        }
+        "import" => {
+            let path = input.splitn(3, char::is_whitespace).nth(2).map(str::trim).unwrap_or_default();
+            let usage = || tr!("Usage: import history <file.json> | import grid <file>");
+            if path.is_empty() {
+                return Err(usage());
+            }
+            match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+                Some("history") => Ok(Command::Import { path: path.to_string() }),
+                Some("grid") => Ok(Command::ImportGrid { path: path.to_string() }),
+                _ => Err(usage()),
+            }
+        }
+        "share" => match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+            None => Ok(Command::Share),
+            Some("daily") if tokens.len() == 2 => Ok(Command::ShareDaily),
+            _ => Err(tr!("Usage: share [daily]")),
+        },
+        "copy" => {
+            let kind = match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+                Some("seed") if tokens.len() == 2 => CopyKind::Seed,
+                Some("share") if tokens.len() == 2 => CopyKind::Share,
+                Some("daily") if tokens.len() == 2 => CopyKind::Daily,
+                _ => return Err(tr!("Usage: copy seed|share|daily")),
+            };
+            Ok(Command::Copy { kind })
+        }
+        "load" => {
+            let code = input.split_once(char::is_whitespace).map(|(_, rest)| rest.trim()).unwrap_or_default();
+            if code.is_empty() {
+                return Err(tr!("Usage: load <code>"));
+            }
+            Ok(Command::Load { code: code.to_string() })
+        }
+        "challenge" => {
+            let token = input.split_once(char::is_whitespace).map(|(_, rest)| rest.trim().to_string());
+            Ok(Command::Challenge { token: token.filter(|t| !t.is_empty()) })
+        }
+        "challenges" => Ok(Command::Challenges),
+        "report" => match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+            Some("difficulty") if tokens.len() == 2 => Ok(Command::ReportDifficulty),
+            Some("rating") if tokens.len() == 2 => Ok(Command::ReportRating),
+            _ => Err(tr!("Usage: report difficulty|rating")),
+        },
+        "puzzles" => {
+            let path = input.split_once(char::is_whitespace).map(|(_, rest)| rest.trim().to_string());
+            Ok(Command::Puzzles { path: path.filter(|p| !p.is_empty()) })
+        }
+        "puzzle" => {
+            let index = tokens.get(1).and_then(|t| t.parse().ok()).filter(|&n| n > 0);
+            Ok(Command::Puzzle { index: index.ok_or_else(|| tr!("Usage: puzzle <n>"))? })
+        }
+        "campaign" => match tokens.get(1) {
+            None => Ok(Command::Campaign { stage: None }),
+            Some(n) if tokens.len() == 2 => match n.parse() {
+                Ok(stage) if stage > 0 => Ok(Command::Campaign { stage: Some(stage) }),
+                _ => Err(tr!("Usage: campaign [<stage>]")),
+            },
+            Some(_) => Err(tr!("Usage: campaign [<stage>]")),
+        },
+        "submit" => Ok(Command::Submit),
+        "leaderboard" => Ok(Command::Leaderboard),
+        "telemetry" => match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+            None | Some("status") if tokens.len() <= 2 => Ok(Command::Telemetry { enable: None }),
+            Some("on") if tokens.len() == 2 => Ok(Command::Telemetry { enable: Some(true) }),
+            Some("off") if tokens.len() == 2 => Ok(Command::Telemetry { enable: Some(false) }),
+            Some("send") if tokens.len() == 2 => Ok(Command::TelemetrySend),
+            _ => Err(tr!("Usage: telemetry [status|on|off|send]")),
+        },
+        "best" => {
+            let seed = tokens.get(1).map(|s| parse_seed(s)).transpose()?;
+            Ok(Command::Best { seed })
+        }
         "solve" => Ok(Command::Solve),
-        "new" | "n" => Ok(Command::NewGame),
+        "par" => Ok(Command::Par),
+        "new" | "n" => {
+            if tokens.len() < 2 {
+                return Ok(Command::NewGame { seed: None, phrase: None });
+            }
+            if tokens.len() == 2 && tokens[1].eq_ignore_ascii_case("weekly") {
+                return Ok(Command::NewWeekly);
+            }
+            let (seed, phrase) = parse_seed_or_phrase(&tokens[1..].join(" "));
+            Ok(Command::NewGame { seed: Some(seed), phrase })
+        }
+        "restart" => Ok(Command::Restart),
+        "resign" | "giveup" => Ok(Command::Resign),
+        "show" => match tokens.get(1).map(|t| t.to_lowercase()).as_deref() {
+            Some("solution") if tokens.len() == 2 => Ok(Command::ShowSolution),
+            _ => Err(tr!("Usage: show solution")),
+        },
+        "set" => match (tokens.get(1).map(|t| t.to_lowercase()).as_deref(), tokens.get(2)) {
+            (Some("render"), Some(mode)) if tokens.len() == 3 => match RenderMode::parse(mode) {
+                Some(mode) => Ok(Command::SetRender { mode }),
+                None => Err(tr!("Usage: set render quiet|normal|verbose")),
+            },
+            _ => Err(tr!("Usage: set render quiet|normal|verbose")),
+        },
         "quit" | "q" | "exit" => Ok(Command::Quit),
         "help" | "h" | "?" => Ok(Command::Help),
-        _ => Err(format!("Unknown command '{}'. Type 'help' for help.", tokens[0])),
+        _ => Err(tr!("Unknown command '{}'. Type 'help' for help.", tokens[0])),
     }
 }
 
-fn parse_col_idx(s: &str) -> Result<usize, String> {
+/// Command names offered by tab completion (aliases are left out).
+const COMMAND_NAMES: &[&str] = &[
+    "cc", "cf", "fc", "ctf", "ftf", "mv", "m", "dragon", "finish", "undo", "lines", "switch",
+    "mark", "goto", "config", "keys", "macro", "set", "describe", "splits", "history", "export", "import", "games", "resume", "share", "copy",
+    "load", "challenge", "challenges", "report", "puzzles", "puzzle", "campaign", "best", "submit", "leaderboard", "telemetry", "solve", "par", "new", "restart", "resign", "show", "quit", "help",
+];
+
+/// Tab-completion candidates for the word being typed at the end of `text`.
+///
+/// Sources only offer slots that hold a card (non-empty columns, occupied
+/// free cells); destinations offer every column and the free cells that are
+/// still empty.
+pub fn complete(text: &str, board: &crate::board::Board, base: usize) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (arg, partial) = if text.ends_with(char::is_whitespace) || text.is_empty() {
+        (words.len(), "")
+    } else {
+        (words.len() - 1, words[words.len() - 1])
+    };
+
+    let cols = |nonempty: bool| -> Vec<String> {
+        (0..board.rules.columns)
+            .filter(|&c| !nonempty || !board.columns[c].is_empty())
+            .map(|c| (c + base).to_string())
+            .collect()
+    };
+    let cells = |occupied: bool| -> Vec<String> {
+        (0..board.rules.free_cells)
+            .filter(|&i| if occupied { board.free_cells[i].card().is_some() } else { board.free_cells[i].is_empty() })
+            .map(|i| (i + base).to_string())
+            .collect()
+    };
+    let prefixed = |prefix: &str, items: Vec<String>| -> Vec<String> {
+        items.into_iter().map(|i| format!("{}{}", prefix, i)).collect()
+    };
+
+    let candidates: Vec<String> = if arg == 0 {
+        COMMAND_NAMES.iter().map(|c| c.to_string()).collect()
+    } else {
+        match (words[0].to_lowercase().as_str(), arg) {
+            ("cc", 1) | ("cf", 1) | ("ctf", 1) | ("m", 1) => cols(true),
+            ("cc", 2) | ("fc", 2) => cols(false),
+            ("cf", 2) => cells(false),
+            ("fc", 1) | ("ftf", 1) => cells(true),
+            ("mv" | "move", 1) => {
+                let mut v = prefixed("c", cols(true));
+                v.extend(prefixed("f", cells(true)));
+                v
+            }
+            ("mv" | "move", 2) => {
+                let mut v = prefixed("c", cols(false));
+                v.extend(prefixed("f", cells(false)));
+                v.push("fo".to_string());
+                v
+            }
+            ("dragon" | "dr", 1) => {
+                let mut v: Vec<String> = vec!["r".into(), "g".into(), "b".into()];
+                if board.rules.suits > 3 {
+                    v.push("y".into());
+                }
+                v
+            }
+            ("undo" | "u", 1) => vec!["all".into()],
+            ("new" | "n", 1) => vec!["daily".into(), "weekly".into()],
+            ("export", 1) => vec!["stats".into(), "history".into()],
+            ("import", 1) => vec!["history".into(), "grid".into()],
+            ("show", 1) => vec!["solution".into()],
+            ("set", 1) => vec!["render".into()],
+            ("macro", 1) => vec!["record".into(), "stop".into(), "play".into()],
+            ("report", 1) => vec!["difficulty".into(), "rating".into()],
+            ("share", 1) => vec!["daily".into()],
+            ("copy", 1) => vec!["seed".into(), "share".into(), "daily".into()],
+            ("telemetry", 1) => vec!["status".into(), "on".into(), "off".into(), "send".into()],
+            ("set", 2) => vec!["quiet".into(), "normal".into(), "verbose".into()],
+            ("history" | "hist", _) => {
+                ["won", "lost", "seed", "page", "play", "weekly"].iter().map(|s| s.to_string()).collect()
+            }
+            _ => Vec::new(),
+        }
+    };
+    let partial = partial.to_lowercase();
+    candidates.into_iter().filter(|c| c.starts_with(&partial)).collect()
+}
+
+/// Parse the arguments of `history`: filters in any order, an optional
+/// page, or `play <#>`.
+fn parse_history(args: &[&str]) -> Result<Command, String> {
+    let usage = || tr!("Usage: history [won|lost] [seed <s>] [page <n>] | history play <#> | history weekly");
+    let mut filter = crate::history::RecordFilter::default();
+    let mut page = 1;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.to_lowercase().as_str() {
+            "won" | "wins" => filter.won = Some(true),
+            "lost" | "losses" => filter.won = Some(false),
+            "seed" => filter.seed = Some(parse_seed(args.next().ok_or_else(usage)?)?),
+            "page" => {
+                page = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0).ok_or_else(usage)?;
+            }
+            "play" => {
+                let id = args.next().and_then(|n| n.trim_start_matches('#').parse().ok()).ok_or_else(usage)?;
+                return Ok(Command::HistoryPlay { id });
+            }
+            "weekly" => return Ok(Command::HistoryWeekly),
+            _ => return Err(usage()),
+        }
+    }
+    Ok(Command::History { filter, page })
+}
+
+fn parse_col_idx(s: &str, base: usize, rules: Rules) -> Result<usize, String> {
     let n: usize = s
         .parse()
-        .map_err(|_| format!("'{}' is not a valid column index", s))?;
-    if n >= crate::board::NUM_COLUMNS {
-        return Err(format!(
-            "Column index {} out of range (0–{})",
+        .map_err(|_| tr!("'{}' is not a valid column index", s))?;
+    if n < base || n >= rules.columns + base {
+        return Err(tr!(
+            "Column index {} out of range ({}–{})",
             n,
-            crate::board::NUM_COLUMNS - 1
+            base,
+            rules.columns - 1 + base
         ));
     }
-    Ok(n)
+    Ok(n - base)
 }
 
-fn parse_cell_idx(s: &str) -> Result<usize, String> {
+fn parse_cell_idx(s: &str, base: usize, rules: Rules) -> Result<usize, String> {
     let n: usize = s
         .parse()
-        .map_err(|_| format!("'{}' is not a valid free-cell index", s))?;
-    if n >= crate::board::NUM_FREE_CELLS {
-        return Err(format!(
-            "Free-cell index {} out of range (0–{})",
+        .map_err(|_| tr!("'{}' is not a valid free-cell index", s))?;
+    if n < base || n >= rules.free_cells + base {
+        return Err(tr!(
+            "Free-cell index {} out of range ({}–{})",
             n,
-            crate::board::NUM_FREE_CELLS - 1
+            base,
+            rules.free_cells - 1 + base
         ));
     }
-    Ok(n)
+    Ok(n - base)
+}
+
+fn parse_suit(s: &str, rules: Rules) -> Result<crate::card::Suit, String> {
+    let suit = match s.to_lowercase().as_str() {
+        "r" | "red" => Some(crate::card::Suit::Red),
+        "g" | "green" => Some(crate::card::Suit::Green),
+        "b" | "black" => Some(crate::card::Suit::Black),
+        "y" | "yellow" => Some(crate::card::Suit::Yellow),
+        _ => None,
+    };
+    match suit.filter(|suit| rules.suits().contains(suit)) {
+        Some(suit) => Ok(suit),
+        None if rules.suits == 3 => Err(tr!("'{}' is not a valid suit. Use r, g, or b.", s)),
+        None => Err(tr!("'{}' is not a valid suit. Use r, g, b, or y.", s)),
+    }
+}
+
+/// A slot address accepted by `mv`.  The foundation is a single target: the
+/// card's suit decides which pile it lands on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Column(usize),
+    FreeCell(usize),
+    Foundation,
+}
+
+/// Parse a unified location token: `c4`, `c4:2` (stack depth), `f1`, `fo`.
+/// Returns the target and the stack depth (0 unless given).
+fn parse_location(s: &str, base: usize, rules: Rules) -> Result<(Target, usize), String> {
+    let lower = s.to_lowercase();
+    if lower == "fo" || lower == "found" {
+        return Ok((Target::Foundation, 0));
+    }
+    if let Some(rest) = lower.strip_prefix('c') {
+        if let Some((col_part, depth_part)) = rest.split_once(':') {
+            let col = parse_col_idx(col_part, base, rules)?;
+            let depth: usize = depth_part.parse().map_err(|_| tr!("Invalid depth"))?;
+            return Ok((Target::Column(col), depth));
+        }
+        return Ok((Target::Column(parse_col_idx(rest, base, rules)?), 0));
+    }
+    if let Some(rest) = lower.strip_prefix('f') {
+        return Ok((Target::FreeCell(parse_cell_idx(rest, base, rules)?), 0));
+    }
+    Err(tr!("'{}' is not a valid location. Use c<N>, f<N> or fo.", s))
 }
 
-fn parse_suit(s: &str) -> Result<crate::card::Suit, String> {
-    match s.to_lowercase().as_str() {
-        "r" | "red" => Ok(crate::card::Suit::Red),
-        "g" | "green" => Ok(crate::card::Suit::Green),
-        "b" | "black" => Ok(crate::card::Suit::Black),
-        _ => Err(format!("'{}' is not a valid suit. Use r, g, or b.", s)),
+/// Map a unified `mv` onto the concrete move command.
+fn location_move(src: Target, stack_start: usize, dst: Target) -> Result<Command, String> {
+    if stack_start != 0 && !matches!((src, dst), (Target::Column(_), Target::Column(_))) {
+        return Err(tr!("Stacks can only be moved between columns"));
     }
+    match (src, dst) {
+        (Target::Column(src), Target::Column(dst)) => {
+            Ok(Command::ColumnToColumn { src, stack_start, dst })
+        }
+        (Target::Column(src_col), Target::FreeCell(dst_cell)) => {
+            Ok(Command::ColumnToFreeCell { src_col, dst_cell })
+        }
+        (Target::FreeCell(src_cell), Target::Column(dst_col)) => {
+            Ok(Command::FreeCellToColumn { src_cell, dst_col })
+        }
+        (Target::Column(src), Target::Foundation) => Ok(Command::ColumnToFoundation { src }),
+        (Target::FreeCell(src_cell), Target::Foundation) => {
+            Ok(Command::FreeCellToFoundation { src_cell })
+        }
+        (Target::FreeCell(_), Target::FreeCell(_)) => {
+            Err(tr!("Cannot move between free cells"))
+        }
+        (Target::Foundation, _) => Err(tr!("Cards cannot be taken from the foundation")),
+    }
+}
+
+/// Parse a seed token: decimal, `0x`-prefixed hex, `daily` or `weekly`.
+pub fn parse_seed(s: &str) -> Result<u64, String> {
+    let lower = s.to_lowercase();
+    if lower == "daily" {
+        return Ok(daily_seed());
+    }
+    if lower == "weekly" {
+        return Ok(crate::weekly::current_seed());
+    }
+    let parsed = match lower.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => lower.parse(),
+    };
+    parsed.map_err(|_| tr!("'{}' is not a valid seed. Use a number, 0x<hex>, 'daily' or 'weekly'.", s))
+}
+
+/// Parse a seed, falling back to hashing the text as a seed phrase.
+/// Returns the numeric seed and the phrase it came from, if any.
+pub fn parse_seed_or_phrase(s: &str) -> (u64, Option<String>) {
+    let s = s.trim().trim_matches('"');
+    match parse_seed(s) {
+        Ok(seed) => (seed, None),
+        Err(_) => (crate::board::Board::seed_from_phrase(s), Some(s.to_string())),
+    }
+}
+
+/// The seed of today's daily deal: the number of whole days since the Unix
+/// epoch (UTC), so everyone gets the same board on the same day.
+pub fn daily_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / 86_400
 }
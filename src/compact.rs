@@ -0,0 +1,118 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! A compact serde form of [`Board`] for JSON.
+//!
+//! `Board`'s own derive spells every card out as a nested enum
+//! (`{"Numbered": ["Red", 5]}`).  [`CompactBoard`] writes each column as one
+//! string of card labels instead, bottom card first (`"R5 GD B9"`), each
+//! free cell as a label (`""` when empty, the suit's dragon and a `*` when
+//! locked: `"GD*"`), and leaves the rules out for the standard table.  A
+//! standard board takes about a fifth of the space.
+//!
+//! Leaving the rules out only works in self-describing formats such as
+//! JSON, not in bincode.
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, FreeCellState, Rules};
+use crate::card::Card;
+
+/// Serializes the board it wraps in the compact form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactBoard(pub Board);
+
+#[derive(Serialize, Deserialize)]
+struct Form {
+    #[serde(default, skip_serializing_if = "is_standard")]
+    rules: Rules,
+    columns: Vec<String>,
+    free_cells: Vec<String>,
+    foundations: Vec<u8>,
+    flower_placed: bool,
+    seed: u64,
+}
+
+fn is_standard(rules: &Rules) -> bool {
+    *rules == Rules::STANDARD
+}
+
+impl Serialize for CompactBoard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let board = &self.0;
+        let labels = |cards: &[Card]| cards.iter().map(|c| c.label()).collect::<Vec<_>>().join(" ");
+        Form {
+            rules: board.rules,
+            columns: board.columns.iter().map(|col| labels(col)).collect(),
+            free_cells: board
+                .free_cells
+                .iter()
+                .map(|cell| match cell {
+                    FreeCellState::Empty => String::new(),
+                    FreeCellState::Card(card) => card.label(),
+                    FreeCellState::DragonLocked(suit) => format!("{}*", Card::Dragon(*suit).label()),
+                })
+                .collect(),
+            foundations: board.foundations.clone(),
+            flower_placed: board.flower_placed,
+            seed: board.seed,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactBoard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let form = Form::deserialize(deserializer)?;
+        let rules = form.rules;
+        let card = |label: &str| Card::from_label(label).ok_or_else(|| de::Error::custom(format!("'{}' is not a card", label)));
+        let columns = form
+            .columns
+            .iter()
+            .map(|col| col.split_whitespace().map(card).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let free_cells = form
+            .free_cells
+            .iter()
+            .map(|cell| match cell.strip_suffix('*') {
+                _ if cell.is_empty() => Ok(FreeCellState::Empty),
+                Some(dragon) => match card(dragon)? {
+                    Card::Dragon(suit) => Ok(FreeCellState::DragonLocked(suit)),
+                    _ => Err(de::Error::custom(format!("'{}' is not a locked dragon cell", cell))),
+                },
+                None => card(cell).map(FreeCellState::Card),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if columns.len() != rules.columns || free_cells.len() != rules.free_cells || form.foundations.len() != rules.suits {
+            return Err(de::Error::custom("the columns, free cells or foundations do not match the rules"));
+        }
+        Ok(CompactBoard(Board {
+            rules,
+            columns,
+            free_cells,
+            foundations: form.foundations,
+            flower_placed: form.flower_placed,
+            seed: form.seed,
+        }))
+    }
+}
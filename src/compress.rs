@@ -0,0 +1,135 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! LZ77 compression for the save file, laid out like an LZ4 block.  Board
+//! snapshots repeat a lot, which is exactly what back-references capture.
+//!
+//! Layout: the uncompressed length as a little-endian `u32`, then
+//! sequences of `token, [literal length], literals, offset, [match length]`.
+//! The token's high nibble is the literal count and its low nibble the
+//! match length minus 4; 15 in either means more length bytes follow
+//! (255 = keep adding).  The last sequence has literals only.
+
+const MIN_MATCH: usize = 4;
+const HASH_BITS: u32 = 13;
+const MAX_OFFSET: usize = u16::MAX as usize;
+
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len() / 2 + 16);
+    out.extend_from_slice(&(input.len() as u32).to_le_bytes());
+
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut anchor = 0;
+    let mut i = 0;
+    while i + MIN_MATCH <= input.len() {
+        let word = u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+        let slot = (word.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize;
+        let candidate = table[slot];
+        table[slot] = i;
+        let found = candidate != usize::MAX
+            && i - candidate <= MAX_OFFSET
+            && input[candidate..candidate + MIN_MATCH] == input[i..i + MIN_MATCH];
+        if !found {
+            i += 1;
+            continue;
+        }
+        let mut len = MIN_MATCH;
+        while i + len < input.len() && input[candidate + len] == input[i + len] {
+            len += 1;
+        }
+        emit(&mut out, &input[anchor..i], Some((i - candidate, len)));
+        i += len;
+        anchor = i;
+    }
+    emit(&mut out, &input[anchor..], None);
+    out
+}
+
+fn emit(out: &mut Vec<u8>, literals: &[u8], matched: Option<(usize, usize)>) {
+    let extra = matched.map_or(0, |(_, len)| len - MIN_MATCH);
+    out.push(((literals.len().min(15) as u8) << 4) | extra.min(15) as u8);
+    if literals.len() >= 15 {
+        push_length(out, literals.len() - 15);
+    }
+    out.extend_from_slice(literals);
+    if let Some((offset, _)) = matched {
+        out.extend_from_slice(&(offset as u16).to_le_bytes());
+        if extra >= 15 {
+            push_length(out, extra - 15);
+        }
+    }
+}
+
+fn push_length(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 255 {
+        out.push(255);
+        n -= 255;
+    }
+    out.push(n as u8);
+}
+
+/// Undo `compress`.  `None` if the data is malformed.
+pub fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    let size = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let mut out = Vec::with_capacity(size.min(64 << 20));
+    let mut pos = 4;
+    let read_length = |pos: &mut usize| -> Option<usize> {
+        let mut n = 0;
+        loop {
+            let byte = *data.get(*pos)?;
+            *pos += 1;
+            n += byte as usize;
+            if byte != 255 {
+                return Some(n);
+            }
+        }
+    };
+    while pos < data.len() {
+        let token = data[pos];
+        pos += 1;
+        let mut literals = (token >> 4) as usize;
+        if literals == 15 {
+            literals += read_length(&mut pos)?;
+        }
+        out.extend_from_slice(data.get(pos..pos.checked_add(literals)?)?);
+        pos += literals;
+        if pos == data.len() {
+            break;
+        }
+        let offset = u16::from_le_bytes([*data.get(pos)?, *data.get(pos + 1)?]) as usize;
+        pos += 2;
+        let mut len = (token & 15) as usize;
+        if len == 15 {
+            len += read_length(&mut pos)?;
+        }
+        len += MIN_MATCH;
+        if offset == 0 || offset > out.len() || out.len() + len > size {
+            return None;
+        }
+        // Byte by byte: a match may overlap the bytes it produces.
+        let start = out.len() - offset;
+        for k in 0..len {
+            out.push(out[start + k]);
+        }
+    }
+    (out.len() == size).then_some(out)
+}
@@ -9,20 +9,34 @@
  */
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
 
+use crate::board::{AutoMovePolicy, DealStyle, Rules, Variant};
+use crate::card::{self, AsciiLabels, LabelProvider};
+use crate::i18n::Lang;
+use crate::input::InputMode;
+use crate::keymap::KeyMap;
+use crate::profile;
+use crate::renderer::{CardStyle, RenderMode};
+use crate::solver::{Budget, Strategy, NODE_LIMIT};
+use crate::theme::Theme;
+use crate::toml::{parse_toml_value, strip_comment, TomlValue};
 use crate::tui_renderer::AnimSpeed;
 
 #[derive(Debug, Clone, Copy)]
 pub struct AppConfig {
     pub anim_speed: AnimSpeed,
+    /// Check every move with a background solver and warn on fatal moves.
+    pub warn_unwinnable: bool,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             anim_speed: AnimSpeed::Normal,
+            warn_unwinnable: false,
         }
     }
 }
@@ -50,8 +64,14 @@ impl AppConfig {
             let key = key.trim();
             let value = value.trim();
 
-            if key == "anim_speed" {
-                config.anim_speed = parse_anim_speed(value).unwrap_or(AnimSpeed::Normal);
+            match key {
+                "anim_speed" => {
+                    config.anim_speed = parse_anim_speed(value).unwrap_or(AnimSpeed::Normal);
+                }
+                "warn_unwinnable" => {
+                    config.warn_unwinnable = parse_bool(value).unwrap_or(false);
+                }
+                _ => {}
             }
         }
 
@@ -68,16 +88,16 @@ impl AppConfig {
         }
 
         let content = format!(
-            "# szsol-rs config\nanim_speed = {}\n",
-            anim_speed_name(self.anim_speed)
+            "# szsol-rs config\nanim_speed = {}\nwarn_unwinnable = {}\n",
+            anim_speed_name(self.anim_speed),
+            bool_name(self.warn_unwinnable)
         );
 
         let _ = fs::write(path, content);
     }
 
     fn file_path() -> Option<PathBuf> {
-        let proj_dirs = ProjectDirs::from("com", "szsol", "szsol")?;
-        Some(proj_dirs.config_dir().join("config.txt"))
+        Some(profile::config_dir()?.join("config.txt"))
     }
 }
 
@@ -99,3 +119,685 @@ fn anim_speed_name(speed: AnimSpeed) -> &'static str {
         AnimSpeed::Slow => "slow",
     }
 }
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "on" | "true" | "yes" | "1" => Some(true),
+        "off" | "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn bool_name(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+// ---------------------------------------------------------------------------
+// User configuration (config.toml)
+// ---------------------------------------------------------------------------
+
+/// Where an effective setting came from; shown by the `config` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File,
+    CommandLine,
+    /// `NO_COLOR`, `LANG`, a redirected stdout or a console without ANSI support.
+    Environment,
+    /// Changed during play with `set`.
+    Session,
+}
+
+impl Source {
+    fn name(self) -> &'static str {
+        match self {
+            Source::Default => "default",
+            Source::File => "config.toml",
+            Source::CommandLine => "command line",
+            Source::Environment => "environment",
+            Source::Session => "set command",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn default(value: T) -> Self {
+        Self { value, source: Source::Default }
+    }
+
+    /// Override the value, recording where the override came from.
+    pub fn set(&mut self, value: T, source: Source) {
+        self.value = value;
+        self.source = source;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RendererKind {
+    Tui,
+    Cli,
+    /// Sentences only, for screen readers (`--plain`).
+    Plain,
+}
+
+/// How often moves reach the save file.  Moves not yet written are still
+/// saved on quit, Ctrl-C and SIGTERM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Autosave {
+    EveryMove,
+    /// Every N moves.
+    Every(u32),
+    /// Only when leaving the game.
+    OnExit,
+}
+
+/// How the history is written: `history.dat`, compressed and signed, or
+/// `history.json`, plain and unsigned for reading and hand-editing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveFormat {
+    Binary,
+    Json,
+}
+
+impl SaveFormat {
+    pub fn parse(value: &str) -> Option<SaveFormat> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "binary" => Some(SaveFormat::Binary),
+            "json" => Some(SaveFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SaveFormat::Binary => "binary",
+            SaveFormat::Json => "json",
+        }
+    }
+}
+
+/// Which moments ring the terminal bell.  All off by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bell {
+    /// A move the rules refuse.
+    pub illegal: bool,
+    /// Dragons merged, by hand or automatically.
+    pub dragons: bool,
+    pub wins: bool,
+}
+
+impl Bell {
+    const CUES: [&str; 3] = ["illegal", "dragons", "wins"];
+
+    /// `off`, `on` (every cue) or a comma-separated list of `illegal`,
+    /// `dragons` and `wins`.
+    pub fn parse(value: &str) -> Option<Bell> {
+        let value = value.trim().to_ascii_lowercase();
+        if let Some(on) = parse_bool(&value) {
+            return Some(Bell { illegal: on, dragons: on, wins: on });
+        }
+        let mut bell = Bell::default();
+        for cue in value.split(',').map(str::trim) {
+            match cue {
+                "illegal" => bell.illegal = true,
+                "dragons" => bell.dragons = true,
+                "wins" => bell.wins = true,
+                _ => return None,
+            }
+        }
+        Some(bell)
+    }
+
+    pub fn name(self) -> String {
+        let on = [self.illegal, self.dragons, self.wins];
+        if on.iter().all(|&c| !c) {
+            return "off".to_string();
+        }
+        let cues: Vec<&str> = Self::CUES.iter().zip(on).filter(|(_, c)| *c).map(|(n, _)| *n).collect();
+        cues.join(",")
+    }
+}
+
+/// Named bundles of assists, chosen when a game is dealt and kept on its
+/// record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssistPreset {
+    /// No help at all: nothing moves home by itself and no warnings.
+    Purist,
+    /// Safe auto-moves, dead-end warnings and hints.
+    Standard,
+    /// Everything on: eager auto-moves, fatal-move warnings and dragons
+    /// merged as soon as they can be.
+    Relaxed,
+}
+
+/// The individual assists a preset turns on.
+#[derive(Debug, Clone, Copy)]
+pub struct Assists {
+    pub auto_move: AutoMovePolicy,
+    /// Say so when no legal move is left.
+    pub dead_end_warnings: bool,
+    /// Say so when a move returns to a position already seen on this line.
+    pub repeat_warnings: bool,
+    /// Warn when a move makes the game unwinnable (background solver).
+    pub fatal_move_warnings: bool,
+    /// Allow `solve` and the TUI hint.
+    pub hints: bool,
+    pub auto_merge_dragons: bool,
+}
+
+impl AssistPreset {
+    pub fn parse(value: &str) -> Option<AssistPreset> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "purist" => Some(AssistPreset::Purist),
+            "standard" => Some(AssistPreset::Standard),
+            "relaxed" => Some(AssistPreset::Relaxed),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AssistPreset::Purist => "purist",
+            AssistPreset::Standard => "standard",
+            AssistPreset::Relaxed => "relaxed",
+        }
+    }
+
+    pub fn assists(self) -> Assists {
+        match self {
+            AssistPreset::Purist => Assists {
+                auto_move: AutoMovePolicy::Off,
+                dead_end_warnings: false,
+                repeat_warnings: false,
+                fatal_move_warnings: false,
+                hints: false,
+                auto_merge_dragons: false,
+            },
+            AssistPreset::Standard => Assists {
+                auto_move: AutoMovePolicy::Safe,
+                dead_end_warnings: true,
+                repeat_warnings: true,
+                fatal_move_warnings: false,
+                hints: true,
+                auto_merge_dragons: false,
+            },
+            AssistPreset::Relaxed => Assists {
+                auto_move: AutoMovePolicy::Eager,
+                dead_end_warnings: true,
+                repeat_warnings: true,
+                fatal_move_warnings: true,
+                hints: true,
+                auto_merge_dragons: true,
+            },
+        }
+    }
+}
+
+/// Hand-edited defaults from `config.toml` in the config directory.
+///
+/// Unlike `AppConfig`, which the game writes back whenever a setting is
+/// toggled in the TUI, this file is only ever read.  The supported syntax
+/// is the flat subset of TOML: `key = value` lines with quoted strings,
+/// integers and booleans, plus `#` comments.
+#[derive(Debug, Clone)]
+pub struct UserConfig {
+    pub theme: Setting<String>,
+    pub auto_move: Setting<AutoMovePolicy>,
+    /// Maximum number of undo steps kept per game.
+    pub undo_cap: Setting<usize>,
+    pub renderer: Setting<RendererKind>,
+    /// Number columns and free cells from 1 instead of 0 in the CLI.
+    pub one_indexed: Setting<bool>,
+    pub prompt: Setting<String>,
+    /// Emit ANSI styling in the CLI; off for `NO_COLOR`, `--no-color`, pipes and
+    /// Windows consoles that cannot show it.
+    pub color: Setting<bool>,
+    pub card_style: Setting<CardStyle>,
+    /// Card label set for the text card style (`ascii` or `chinese`).
+    pub labels: Setting<String>,
+    /// Language of messages and help; defaults to the locale (`LANG`).
+    pub language: Setting<Lang>,
+    /// Show a running timer and record foundation/dragon splits.
+    pub speedrun: Setting<bool>,
+    /// Play for the fewest moves, measured against the solver's count.
+    pub golf: Setting<bool>,
+    /// Work out each new deal's par in the background and show it in the header.
+    pub par: Setting<bool>,
+    /// Show in the header whether the position can still be won, checked in
+    /// the background after every move.
+    pub winnability: Setting<bool>,
+    /// Show the number of legal moves in the header, in red when few are left.
+    pub mobility: Setting<bool>,
+    /// Race the fastest earlier win on a seed: show in the header where it
+    /// stood at the same time and move.
+    pub ghost: Setting<bool>,
+    /// How much the CLI prints after each command: only changed rows, the
+    /// usual board, or also each auto-move and the foundations.
+    pub render: Setting<RenderMode>,
+    /// Redraw the CLI board in place at the top of the terminal instead of
+    /// scrolling.
+    pub in_place: Setting<bool>,
+    /// Type whole command lines, or make moves with one key per slot.
+    pub input: Setting<InputMode>,
+    /// In the TUI, h/j/k/l move a cursor, Space picks up and drops, `u`
+    /// undoes and `:` opens a command line.
+    pub vim_keys: Setting<bool>,
+    /// Key bindings, changed with `key_<action>` lines.
+    pub keys: KeyMap,
+    /// Deal new games without undo, hints or the solver.
+    pub hardcore: Setting<bool>,
+    /// Search used by `solve`: A* or iterative deepening.
+    pub solver: Setting<Strategy>,
+    /// Positions `solve` may explore before calling the deal unknown.
+    pub solver_nodes: Setting<usize>,
+    /// Seconds `solve` may run before calling the deal unknown; 0 for no limit.
+    pub solver_seconds: Setting<u64>,
+    /// Assist preset for newly dealt games.
+    pub assist: Setting<AssistPreset>,
+    /// Hints allowed in a daily, weekly or challenge game.
+    pub challenge_hints: Setting<u32>,
+    /// Finished games kept in the save file; older ones only count towards
+    /// the totals.  0 keeps everything.
+    pub history_limit: Setting<usize>,
+    /// How often progress is written: `"move"`, a number of moves, or `"exit"`.
+    pub autosave: Setting<Autosave>,
+    pub save_format: Setting<SaveFormat>,
+    /// How new deals are shuffled from their seeds: this game's shuffle or
+    /// one meant to match SHENZHEN I/O's.
+    pub deal_style: Setting<DealStyle>,
+    /// Tableau columns of newly dealt tables (4–10).
+    pub columns: Setting<usize>,
+    /// Free cells of newly dealt tables (3–6).
+    pub free_cells: Setting<usize>,
+    /// Suits in newly dealt decks (3, or 4 for the harder variant).
+    pub suits: Setting<usize>,
+    /// Highest rank in newly dealt decks (9–13).
+    pub ranks: Setting<usize>,
+    /// Which solitaire new deals are for.  FreeCell has a fixed table, so
+    /// the four counts above only shape SHENZHEN I/O deals.
+    pub game: Setting<Variant>,
+    /// Rules read from `--rules <file>`; they replace the game and the
+    /// four counts above.
+    pub custom_rules: Option<Rules>,
+    /// HTTPS endpoint for `submit` and `leaderboard`; empty keeps the game
+    /// offline.
+    pub leaderboard_url: Setting<String>,
+    /// Name shown on the leaderboard; empty uses the login name.
+    pub player_name: Setting<String>,
+    /// HTTPS endpoint `telemetry send` posts usage statistics to.
+    pub telemetry_url: Setting<String>,
+    /// Shell command run when a game is won or given up; empty runs
+    /// nothing.  See `hook`.
+    pub on_finish: Setting<String>,
+    /// Audio cues: which events ring the terminal bell.
+    pub bell: Setting<Bell>,
+    /// Problems found while reading the file, reported once the game starts.
+    pub warnings: Vec<String>,
+}
+
+impl Default for UserConfig {
+    fn default() -> Self {
+        Self {
+            theme: Setting::default("default".to_string()),
+            auto_move: Setting::default(AutoMovePolicy::Safe),
+            undo_cap: Setting::default(64),
+            renderer: Setting::default(RendererKind::Tui),
+            one_indexed: Setting::default(false),
+            prompt: Setting::default("> ".to_string()),
+            color: Setting::default(true),
+            card_style: Setting::default(CardStyle::Text),
+            labels: Setting::default("ascii".to_string()),
+            language: Setting::default(Lang::En),
+            speedrun: Setting::default(false),
+            golf: Setting::default(false),
+            par: Setting::default(true),
+            winnability: Setting::default(false),
+            mobility: Setting::default(true),
+            ghost: Setting::default(true),
+            render: Setting::default(RenderMode::Normal),
+            in_place: Setting::default(false),
+            input: Setting::default(InputMode::Line),
+            vim_keys: Setting::default(false),
+            keys: KeyMap::default(),
+            hardcore: Setting::default(false),
+            solver: Setting::default(Strategy::AStar),
+            solver_nodes: Setting::default(NODE_LIMIT),
+            solver_seconds: Setting::default(0),
+            assist: Setting::default(AssistPreset::Standard),
+            challenge_hints: Setting::default(3),
+            history_limit: Setting::default(1000),
+            autosave: Setting::default(Autosave::EveryMove),
+            save_format: Setting::default(SaveFormat::Binary),
+            deal_style: Setting::default(DealStyle::Classic),
+            columns: Setting::default(Rules::STANDARD.columns),
+            free_cells: Setting::default(Rules::STANDARD.free_cells),
+            suits: Setting::default(Rules::STANDARD.suits),
+            ranks: Setting::default(Rules::STANDARD.ranks),
+            game: Setting::default(Variant::Shenzhen),
+            custom_rules: None,
+            leaderboard_url: Setting::default(String::new()),
+            player_name: Setting::default(String::new()),
+            telemetry_url: Setting::default(String::new()),
+            on_finish: Setting::default(String::new()),
+            bell: Setting::default(Bell::default()),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl UserConfig {
+    pub fn load() -> Self {
+        let mut config = Self::default();
+        if let Some(lang) = Lang::from_env() {
+            config.language.set(lang, Source::Environment);
+        }
+        let Some(path) = Self::file_path() else {
+            return config;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return config;
+        };
+
+        for (n, raw_line) in content.lines().enumerate() {
+            let line = strip_comment(raw_line).trim();
+            // Table headers are accepted so the file can be organised, but
+            // keys are global.
+            if line.is_empty() || (line.starts_with('[') && line.ends_with(']')) {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                config.warnings.push(format!("config.toml:{}: expected `key = value`", n + 1));
+                continue;
+            };
+            if let Err(e) = config.apply(key.trim(), &parse_toml_value(value.trim())) {
+                config.warnings.push(format!("config.toml:{}: {}", n + 1, e));
+            }
+        }
+        config
+    }
+
+    fn apply(&mut self, key: &str, value: &TomlValue) -> Result<(), String> {
+        let src = Source::File;
+        match (key, value) {
+            ("theme", TomlValue::Str(v)) => {
+                let theme = Theme::by_name(v).ok_or(format!("unknown theme '{}' ({})", v, Theme::names()))?;
+                self.theme.set(theme.name.to_string(), src);
+            }
+            ("auto_move", TomlValue::Str(v)) => {
+                let policy = parse_auto_move(v).ok_or(format!("unknown auto_move policy '{}' (off, safe, eager)", v))?;
+                self.auto_move.set(policy, src);
+            }
+            ("undo_cap", TomlValue::Int(v)) if *v >= 0 => self.undo_cap.set(*v as usize, src),
+            ("renderer", TomlValue::Str(v)) => {
+                let kind = parse_renderer(v).ok_or(format!("unknown renderer '{}' (tui, cli, plain)", v))?;
+                self.renderer.set(kind, src);
+            }
+            ("one_indexed", TomlValue::Bool(v)) => self.one_indexed.set(*v, src),
+            ("prompt", TomlValue::Str(v)) => self.prompt.set(v.clone(), src),
+            ("color", TomlValue::Bool(v)) => self.color.set(*v, src),
+            ("card_style", TomlValue::Str(v)) => {
+                let style = parse_card_style(v).ok_or(format!("unknown card_style '{}' (text, unicode)", v))?;
+                self.card_style.set(style, src);
+            }
+            ("labels", TomlValue::Str(v)) => {
+                let labels = card::label_provider(v).ok_or(format!("unknown labels '{}' (ascii, chinese)", v))?;
+                self.labels.set(labels.name().to_string(), src);
+            }
+            ("language", TomlValue::Str(v)) => {
+                let lang = Lang::parse(v).ok_or(format!("unknown language '{}' (en, zh)", v))?;
+                self.language.set(lang, src);
+            }
+            ("speedrun", TomlValue::Bool(v)) => self.speedrun.set(*v, src),
+            ("golf", TomlValue::Bool(v)) => self.golf.set(*v, src),
+            ("par", TomlValue::Bool(v)) => self.par.set(*v, src),
+            ("winnability", TomlValue::Bool(v)) => self.winnability.set(*v, src),
+            ("mobility", TomlValue::Bool(v)) => self.mobility.set(*v, src),
+            ("ghost", TomlValue::Bool(v)) => self.ghost.set(*v, src),
+            ("render", TomlValue::Str(v)) => {
+                let mode = RenderMode::parse(v).ok_or(format!("unknown render mode '{}' (quiet, normal, verbose)", v))?;
+                self.render.set(mode, src);
+            }
+            ("in_place", TomlValue::Bool(v)) => self.in_place.set(*v, src),
+            ("input", TomlValue::Str(v)) => {
+                let mode = InputMode::parse(v).ok_or(format!("unknown input '{}' (line, keys)", v))?;
+                self.input.set(mode, src);
+            }
+            ("vim_keys", TomlValue::Bool(v)) => self.vim_keys.set(*v, src),
+            (k, TomlValue::Str(v)) if k.starts_with("key_") => self.keys.set(&k[4..], v)?,
+            (k, _) if k.starts_with("key_") => return Err(format!("invalid value for '{}'", key)),
+            ("hardcore", TomlValue::Bool(v)) => self.hardcore.set(*v, src),
+            ("solver", TomlValue::Str(v)) => {
+                let strategy = Strategy::parse(v).ok_or(format!("unknown solver '{}' (astar, deepening)", v))?;
+                self.solver.set(strategy, src);
+            }
+            ("solver_nodes", TomlValue::Int(v)) if *v > 0 => self.solver_nodes.set(*v as usize, src),
+            ("solver_seconds", TomlValue::Int(v)) if *v >= 0 => self.solver_seconds.set(*v as u64, src),
+            ("assist", TomlValue::Str(v)) => {
+                let preset = AssistPreset::parse(v).ok_or(format!("unknown assist preset '{}' (purist, standard, relaxed)", v))?;
+                self.assist.set(preset, src);
+            }
+            ("challenge_hints", TomlValue::Int(v)) if *v >= 0 && *v <= u32::MAX as i64 => {
+                self.challenge_hints.set(*v as u32, src)
+            }
+            ("history_limit", TomlValue::Int(v)) if *v >= 0 => self.history_limit.set(*v as usize, src),
+            ("autosave", TomlValue::Int(1)) => self.autosave.set(Autosave::EveryMove, src),
+            ("autosave", TomlValue::Int(v)) if *v > 1 && *v <= u32::MAX as i64 => {
+                self.autosave.set(Autosave::Every(*v as u32), src)
+            }
+            ("autosave", TomlValue::Str(v)) => {
+                let cadence = match v.trim().to_ascii_lowercase().as_str() {
+                    "move" => Autosave::EveryMove,
+                    "exit" => Autosave::OnExit,
+                    _ => return Err(format!("unknown autosave '{}' (move, exit, or a number of moves)", v)),
+                };
+                self.autosave.set(cadence, src);
+            }
+            ("leaderboard_url", TomlValue::Str(v)) => self.leaderboard_url.set(v.trim().to_string(), src),
+            ("player_name", TomlValue::Str(v)) => self.player_name.set(v.trim().to_string(), src),
+            ("telemetry_url", TomlValue::Str(v)) => self.telemetry_url.set(v.trim().to_string(), src),
+            ("on_finish", TomlValue::Str(v)) => self.on_finish.set(v.trim().to_string(), src),
+            ("bell", TomlValue::Bool(v)) => self.bell.set(Bell { illegal: *v, dragons: *v, wins: *v }, src),
+            ("bell", TomlValue::Str(v)) => {
+                let bell = Bell::parse(v).ok_or(format!("unknown bell '{}' (off, on, or a list of illegal, dragons, wins)", v))?;
+                self.bell.set(bell, src);
+            }
+            ("save_format", TomlValue::Str(v)) => {
+                let format = SaveFormat::parse(v).ok_or(format!("unknown save_format '{}' (binary, json)", v))?;
+                self.save_format.set(format, src);
+            }
+            ("deal_style", TomlValue::Str(v)) => {
+                let style = DealStyle::parse(v).ok_or(format!("unknown deal_style '{}' (classic, shenzhen)", v))?;
+                self.deal_style.set(style, src);
+            }
+            ("columns", TomlValue::Int(v)) if *v >= 0 && Rules::COLUMNS.contains(&(*v as usize)) => {
+                self.columns.set(*v as usize, src)
+            }
+            ("free_cells", TomlValue::Int(v)) if *v >= 0 && Rules::FREE_CELLS.contains(&(*v as usize)) => {
+                self.free_cells.set(*v as usize, src)
+            }
+            ("suits", TomlValue::Int(v)) if *v >= 0 && Rules::SUITS.contains(&(*v as usize)) => {
+                self.suits.set(*v as usize, src)
+            }
+            ("ranks", TomlValue::Int(v)) if *v >= 0 && Rules::RANKS.contains(&(*v as usize)) => {
+                self.ranks.set(*v as usize, src)
+            }
+            ("game", TomlValue::Str(v)) => {
+                let game = Variant::parse(v).ok_or(format!("unknown game '{}' (shenzhen, freecell)", v))?;
+                self.game.set(game, src);
+            }
+            (
+                "theme" | "auto_move" | "undo_cap" | "renderer" | "one_indexed" | "prompt" | "color" | "card_style"
+                | "labels" | "language" | "speedrun" | "golf" | "par" | "winnability" | "mobility" | "ghost" | "render"
+                | "in_place" | "input" | "vim_keys" | "hardcore" | "solver" | "solver_nodes" | "solver_seconds" | "assist" | "challenge_hints" | "history_limit"
+                | "autosave" | "save_format" | "deal_style" | "columns" | "free_cells" | "suits" | "ranks" | "game"
+                | "leaderboard_url" | "player_name" | "telemetry_url" | "on_finish" | "bell",
+                _,
+            ) => {
+                return Err(format!("invalid value for '{}'", key));
+            }
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+        Ok(())
+    }
+
+    /// The node and time budget for `solve`.
+    pub fn solver_budget(&self) -> Budget {
+        let seconds = self.solver_seconds.value;
+        Budget { nodes: self.solver_nodes.value, time: (seconds > 0).then(|| Duration::from_secs(seconds)) }
+    }
+
+    /// The rules for new deals, dealt in the chosen style.  Each suit's
+    /// dragons lock a free cell, so there are never fewer free cells than
+    /// suits.
+    pub fn rules(&self) -> Rules {
+        let deal_style = self.deal_style.value;
+        if let Some(rules) = self.custom_rules {
+            return Rules { deal_style, ..rules };
+        }
+        if self.game.value == Variant::FreeCell {
+            return Rules { deal_style, ..Variant::FreeCell.rules() };
+        }
+        let suits = self.suits.value;
+        Rules {
+            columns: self.columns.value,
+            free_cells: self.free_cells.value.max(suits),
+            suits,
+            ranks: self.ranks.value,
+            deal_style,
+            ..Variant::Shenzhen.rules()
+        }
+    }
+
+    /// The selected color theme for the CLI renderer.
+    pub fn theme(&self) -> Theme {
+        Theme::by_name(&self.theme.value).unwrap_or_default()
+    }
+
+    /// The selected card label set.
+    pub fn labels(&self) -> Box<dyn LabelProvider> {
+        card::label_provider(&self.labels.value).unwrap_or_else(|| Box::new(AsciiLabels))
+    }
+
+    /// First column/free-cell number shown and accepted by the CLI.
+    pub fn index_base(&self) -> usize {
+        if self.one_indexed.value { 1 } else { 0 }
+    }
+
+    /// `key = value  # source` lines describing the effective settings.
+    pub fn describe(&self) -> Vec<String> {
+        let line = |key: &str, value: String, source: Source| {
+            format!("{:<13} = {:<10} # {}", key, value, source.name())
+        };
+        let mut lines = Vec::new();
+        lines.push(match Self::file_path() {
+            Some(path) if path.exists() => format!("Config file: {}", path.display()),
+            Some(path) => format!("Config file: {} (not present)", path.display()),
+            None => "Config file: unavailable".to_string(),
+        });
+        lines.push(format!("Profile: {}", profile::name().unwrap_or("default")));
+        lines.push(line("theme", quote(&self.theme.value), self.theme.source));
+        lines.push(line("auto_move", quote(auto_move_name(self.auto_move.value)), self.auto_move.source));
+        lines.push(line("undo_cap", self.undo_cap.value.to_string(), self.undo_cap.source));
+        let renderer = match self.renderer.value {
+            RendererKind::Tui => "tui",
+            RendererKind::Cli => "cli",
+            RendererKind::Plain => "plain",
+        };
+        lines.push(line("renderer", quote(renderer), self.renderer.source));
+        lines.push(line("one_indexed", self.one_indexed.value.to_string(), self.one_indexed.source));
+        lines.push(line("prompt", quote(&self.prompt.value), self.prompt.source));
+        lines.push(line("color", self.color.value.to_string(), self.color.source));
+        let card_style = match self.card_style.value {
+            CardStyle::Text => "text",
+            CardStyle::Unicode => "unicode",
+        };
+        lines.push(line("card_style", quote(card_style), self.card_style.source));
+        lines.push(line("labels", quote(&self.labels.value), self.labels.source));
+        lines.push(line("language", quote(self.language.value.code()), self.language.source));
+        lines.push(line("speedrun", self.speedrun.value.to_string(), self.speedrun.source));
+        lines.push(line("golf", self.golf.value.to_string(), self.golf.source));
+        lines.push(line("par", self.par.value.to_string(), self.par.source));
+        lines.push(line("winnability", self.winnability.value.to_string(), self.winnability.source));
+        lines.push(line("mobility", self.mobility.value.to_string(), self.mobility.source));
+        lines.push(line("ghost", self.ghost.value.to_string(), self.ghost.source));
+        lines.push(line("render", quote(self.render.value.name()), self.render.source));
+        lines.push(line("in_place", self.in_place.value.to_string(), self.in_place.source));
+        lines.push(line("input", quote(self.input.value.name()), self.input.source));
+        lines.push(line("vim_keys", self.vim_keys.value.to_string(), self.vim_keys.source));
+        lines.push(line("hardcore", self.hardcore.value.to_string(), self.hardcore.source));
+        lines.push(line("solver", quote(self.solver.value.name()), self.solver.source));
+        lines.push(line("solver_nodes", self.solver_nodes.value.to_string(), self.solver_nodes.source));
+        lines.push(line("solver_seconds", self.solver_seconds.value.to_string(), self.solver_seconds.source));
+        lines.push(line("assist", quote(self.assist.value.name()), self.assist.source));
+        lines.push(line("challenge_hints", self.challenge_hints.value.to_string(), self.challenge_hints.source));
+        lines.push(line("history_limit", self.history_limit.value.to_string(), self.history_limit.source));
+        let autosave = match self.autosave.value {
+            Autosave::EveryMove => quote("move"),
+            Autosave::Every(n) => n.to_string(),
+            Autosave::OnExit => quote("exit"),
+        };
+        lines.push(line("autosave", autosave, self.autosave.source));
+        lines.push(line("save_format", quote(self.save_format.value.name()), self.save_format.source));
+        lines.push(line("deal_style", quote(self.deal_style.value.name()), self.deal_style.source));
+        lines.push(line("columns", self.columns.value.to_string(), self.columns.source));
+        lines.push(line("free_cells", self.free_cells.value.to_string(), self.free_cells.source));
+        lines.push(line("suits", self.suits.value.to_string(), self.suits.source));
+        lines.push(line("ranks", self.ranks.value.to_string(), self.ranks.source));
+        lines.push(line("game", quote(self.game.value.name()), self.game.source));
+        lines.push(line("leaderboard_url", quote(&self.leaderboard_url.value), self.leaderboard_url.source));
+        lines.push(line("player_name", quote(&self.player_name.value), self.player_name.source));
+        lines.push(line("telemetry_url", quote(&self.telemetry_url.value), self.telemetry_url.source));
+        lines.push(line("on_finish", quote(&self.on_finish.value), self.on_finish.source));
+        lines.push(line("bell", quote(&self.bell.value.name()), self.bell.source));
+        lines
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        Some(profile::config_dir()?.join("config.toml"))
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_auto_move(value: &str) -> Option<AutoMovePolicy> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" => Some(AutoMovePolicy::Off),
+        "safe" => Some(AutoMovePolicy::Safe),
+        "eager" => Some(AutoMovePolicy::Eager),
+        _ => None,
+    }
+}
+
+fn auto_move_name(policy: AutoMovePolicy) -> &'static str {
+    match policy {
+        AutoMovePolicy::Off => "off",
+        AutoMovePolicy::Safe => "safe",
+        AutoMovePolicy::Eager => "eager",
+    }
+}
+
+fn parse_card_style(value: &str) -> Option<CardStyle> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "text" => Some(CardStyle::Text),
+        "unicode" => Some(CardStyle::Unicode),
+        _ => None,
+    }
+}
+
+fn parse_renderer(value: &str) -> Option<RendererKind> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "tui" => Some(RendererKind::Tui),
+        "cli" => Some(RendererKind::Cli),
+        "plain" => Some(RendererKind::Plain),
+        _ => None,
+    }
+}
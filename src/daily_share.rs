@@ -0,0 +1,40 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The result of a daily deal as a few lines to paste into a chat, in the
+//! manner of Wordle: a coloured square per foundation in the order they
+//! were completed, then the moves and time.  Nothing in it gives the deal
+//! away to someone who hasn't played it yet.
+use std::time::Duration;
+
+use crate::card::Suit;
+use crate::i18n::tr;
+use crate::renderer::format_elapsed;
+
+/// A suit's square.
+fn square(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Red => "🟥",
+        Suit::Green => "🟩",
+        Suit::Black => "⬛",
+        Suit::Yellow => "🟨",
+    }
+}
+
+/// The share text of a daily deal won on `date` (`YYYY-MM-DD`):
+/// "szsol daily 2026-10-15", the squares of `foundation_order`, then
+/// "84 moves ⏱ 4:12" (with "💡 2" when hints were taken).
+pub fn text(date: &str, foundation_order: &[Suit], moves: u32, elapsed: Duration, hints: u32) -> String {
+    let squares: String = foundation_order.iter().map(|&s| square(s)).collect();
+    let mut stats = tr!("{} moves ⏱ {}", moves, format_elapsed(elapsed));
+    if hints > 0 {
+        stats.push_str(&format!(" 💡 {}", hints));
+    }
+    [format!("szsol daily {}", date), squares, stats].join("\n")
+}
@@ -0,0 +1,107 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The figures behind the TUI's statistics screen, worked out from the
+//! finished dealt games kept in the history.  Archived games only survive
+//! as totals, so they are left out.
+use crate::history::History;
+
+/// Games each point of the win-rate line averages over.
+pub const WIN_RATE_WINDOW: usize = 10;
+/// Points on the win-rate line, one per finished game.
+const WIN_RATE_POINTS: usize = 60;
+/// Points on the rating line, one per rated game.
+const RATING_POINTS: usize = 60;
+
+/// Upper bounds (exclusive, in minutes) of the solve-time bars but the
+/// last, which takes everything longer.
+const SOLVE_TIME_MINUTES: [u64; 6] = [2, 3, 5, 10, 20, 30];
+
+/// Pages of the statistics screen, in Tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsPage {
+    WinRate,
+    SolveTimes,
+    Weekdays,
+    Rating,
+}
+
+impl StatsPage {
+    pub const ALL: [StatsPage; 4] = [StatsPage::WinRate, StatsPage::SolveTimes, StatsPage::Weekdays, StatsPage::Rating];
+
+    pub fn next(self) -> StatsPage {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> StatsPage {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Dashboard {
+    pub games: usize,
+    pub wins: usize,
+    /// Win rate in percent over the `WIN_RATE_WINDOW` games ending at each
+    /// of the latest finished games, oldest first.
+    pub win_rate: Vec<u64>,
+    /// Wins per play-time range, labelled like `<2m` and `30m+`.
+    pub solve_times: Vec<(String, u64)>,
+    /// Games started on each weekday, Monday first (UTC, like the dates
+    /// `history` shows).
+    pub weekdays: [u64; 7],
+    /// The rating after each of the latest rated games, oldest first.
+    pub ratings: Vec<u64>,
+    pub rating: u32,
+}
+
+impl Dashboard {
+    pub fn new(history: &History) -> Self {
+        let finished: Vec<_> = history.records.iter().filter(|r| r.end_time.is_some() && r.is_dealt()).collect();
+
+        let won: Vec<bool> = finished.iter().map(|r| r.won).collect();
+        let first = won.len().saturating_sub(WIN_RATE_POINTS);
+        let win_rate = (first..won.len())
+            .map(|i| {
+                let window = &won[(i + 1).saturating_sub(WIN_RATE_WINDOW)..=i];
+                (window.iter().filter(|&&w| w).count() * 100 / window.len()) as u64
+            })
+            .collect();
+
+        let mut solve_times: Vec<(String, u64)> = SOLVE_TIME_MINUTES.iter().map(|m| (format!("<{}m", m), 0)).collect();
+        solve_times.push((format!("{}m+", SOLVE_TIME_MINUTES[SOLVE_TIME_MINUTES.len() - 1]), 0));
+        for record in finished.iter().filter(|r| r.won) {
+            let minutes = record.elapsed_secs / 60;
+            let bar = SOLVE_TIME_MINUTES.iter().position(|&m| minutes < m).unwrap_or(SOLVE_TIME_MINUTES.len());
+            solve_times[bar].1 += 1;
+        }
+
+        let mut weekdays = [0; 7];
+        for record in &finished {
+            // 1970-01-01 was a Thursday.
+            let day = (record.start_time.div_euclid(86_400) + 3).rem_euclid(7);
+            weekdays[day as usize] += 1;
+        }
+
+        let first = history.ratings.len().saturating_sub(RATING_POINTS);
+        let ratings = history.ratings[first..].iter().map(|r| u64::from(r.rating)).collect();
+
+        Dashboard {
+            games: finished.len(),
+            wins: won.iter().filter(|&&w| w).count(),
+            win_rate,
+            solve_times,
+            weekdays,
+            ratings,
+            rating: history.rating(),
+        }
+    }
+}
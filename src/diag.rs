@@ -0,0 +1,94 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Diagnostics for bug reports, through the `log` macros.  `-v` records
+//! refused commands and moves, loads, saves, resumes and solver runs; `-vv`
+//! adds every command as parsed and each save as it is queued.  The lines
+//! go to stderr, or to `szsol.log` in the data directory with `--log-file`
+//! and always in the TUI, which owns the screen.
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::profile;
+
+struct Logger {
+    out: Mutex<Box<dyn Write + Send>>,
+    start: Instant,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Only this game's messages, not its dependencies'.
+        metadata.level() <= log::max_level() && metadata.target().starts_with("szsol")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(
+            out,
+            "[{:9.3} {:<5} {}] {}",
+            self.start.elapsed().as_secs_f64(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = self.out.lock().unwrap_or_else(|e| e.into_inner()).flush();
+    }
+}
+
+/// How many `-v`s `args` hold: `-v` and `--verbose` count one each, `-vv`
+/// two.
+pub fn verbosity(args: &[String]) -> usize {
+    args.iter()
+        .map(|a| match a.as_str() {
+            "--verbose" => 1,
+            a if a.len() > 1 && a.starts_with('-') && a[1..].chars().all(|c| c == 'v') => a.len() - 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Start logging at `verbosity`, to the log file if `to_file`.  Nothing is
+/// logged at 0.
+pub fn init(verbosity: usize, to_file: bool) {
+    let level = match verbosity {
+        0 => return,
+        1 => Level::Info,
+        2 => Level::Debug,
+        _ => Level::Trace,
+    };
+    let out: Box<dyn Write + Send> = match to_file.then(open_log_file).flatten() {
+        Some(file) => Box::new(file),
+        None => Box::new(io::stderr()),
+    };
+    let logger = Logger { out: Mutex::new(out), start: Instant::now() };
+    if log::set_logger(Box::leak(Box::new(logger))).is_ok() {
+        log::set_max_level(level.to_level_filter());
+    } else {
+        log::set_max_level(LevelFilter::Off);
+    }
+    log::info!("szsol-rs {} started with {:?}", env!("CARGO_PKG_VERSION"), std::env::args().skip(1).collect::<Vec<_>>());
+}
+
+/// `szsol.log` in the active profile's data directory, appended to.
+fn open_log_file() -> Option<File> {
+    let dir = profile::data_dir()?;
+    fs::create_dir_all(&dir).ok()?;
+    OpenOptions::new().create(true).append(true).open(dir.join("szsol.log")).ok()
+}
@@ -21,7 +21,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 use crate::board::Location;
-use crate::board::Board;
+use crate::board::{Board, Rules};
 
 use crate::card::Card;
 
@@ -42,12 +42,12 @@ pub enum GameEvent {
     CardMoved { card: Card, src: Location, dst: Location },
     /// A valid sequence of cards moved between columns.
     StackMoved { stack: Vec<Card>, src_col: usize, dst_col: usize },
-    /// Four dragons of the same suit were merged and a free cell was locked.
+    /// All dragons of a suit were merged and a free cell was locked.
     DragonsMerged { suit: crate::card::Suit, locked_cell: usize },
     /// The game has been won.
     Won,
-    /// A new game was dealt with the given seed.
-    Dealt { seed: u64 },
+    /// A new game was dealt with the given seed and rules.
+    Dealt { seed: u64, rules: Rules },
     /// A saved game is being restored with a deal-style animation into the current board.
     RestoreDealt { board: Board },
 }
@@ -0,0 +1,228 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Writing the game history out for spreadsheets and other tools (a CSV
+//! with one row per game, and the full records as JSON), and reading the
+//! JSON back in.
+use crate::board::{Board, Rules};
+use crate::card::Suit;
+use crate::config::AssistPreset;
+use crate::history::{format_date, GameRecord, History, Split, SplitKind};
+use crate::json::Json;
+
+/// Bumped whenever a field is renamed or removed from the JSON export.
+pub const HISTORY_FORMAT_VERSION: u32 = 1;
+
+/// CSV columns, in order.  New columns are only ever appended.
+const CSV_COLUMNS: &[&str] = &[
+    "game",
+    "seed",
+    "seed_phrase",
+    "started_utc",
+    "ended_utc",
+    "result",
+    "moves",
+    "elapsed_secs",
+    "undos",
+    "score",
+    "hardcore",
+    "assist",
+    "dragon_order",
+    "position_code",
+    "hints",
+];
+
+/// One row per game, oldest first, with a header row.
+pub fn stats_csv(history: &History) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+    for (i, r) in history.records.iter().enumerate() {
+        let row = [
+            (i + 1).to_string(),
+            r.seed.to_string(),
+            r.seed_phrase.clone().unwrap_or_default(),
+            format_date(r.start_time),
+            r.end_time.map(format_date).unwrap_or_default(),
+            result(r).to_string(),
+            r.moves.to_string(),
+            r.elapsed_secs.to_string(),
+            r.undos.to_string(),
+            r.score().to_string(),
+            r.hardcore.to_string(),
+            r.assist.name().to_string(),
+            r.dragon_order.iter().map(|&s| suit_key(s)).collect::<Vec<_>>().join(" "),
+            r.position_code.clone().unwrap_or_default(),
+            r.hints.to_string(),
+        ];
+        let cells: Vec<String> = row.iter().map(|c| csv_cell(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Every record as JSON, oldest first.  Boards are left out: a deal is
+/// reproduced from its seed.
+pub fn history_json(history: &History) -> String {
+    let records = history.records.iter().map(record_json).collect();
+    Json::Object(vec![
+        ("format".into(), "szsol-history".into()),
+        ("version".into(), HISTORY_FORMAT_VERSION.into()),
+        ("records".into(), Json::Array(records)),
+    ])
+    .pretty()
+}
+
+/// Read the records of a `history_json` export.
+pub fn parse_history_json(text: &str) -> Result<Vec<GameRecord>, String> {
+    let doc = Json::parse(text)?;
+    if doc.get("format").and_then(Json::as_str) != Some("szsol-history") {
+        return Err("not an szsol history export".to_string());
+    }
+    let version = doc.get("version").and_then(Json::as_int).unwrap_or(0);
+    if version < 1 || version > i128::from(HISTORY_FORMAT_VERSION) {
+        return Err(format!("unsupported export version {}", version));
+    }
+    let records = doc.get("records").and_then(Json::as_array).ok_or("missing \"records\"")?;
+    records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| record_from_json(r).map_err(|e| format!("record {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// Rebuild a record.  Counters missing from the file default to zero and
+/// rules to the standard ones; the initial board is re-dealt from the seed.
+fn record_from_json(j: &Json) -> Result<GameRecord, String> {
+    let int = |key: &str| j.get(key).and_then(Json::as_int);
+    let seed = int("seed").and_then(|n| u64::try_from(n).ok()).ok_or("missing or bad \"seed\"")?;
+    let start_time = int("start_time").and_then(|n| i64::try_from(n).ok()).ok_or("missing or bad \"start_time\"")?;
+    let rules = match j.get("rules") {
+        Some(rules) => crate::json_serde::from_json(rules).map_err(|e| format!("bad \"rules\": {}", e))?,
+        None => Rules::STANDARD,
+    };
+    let mut record = GameRecord::new(seed, rules, start_time);
+    record.end_time = int("end_time").and_then(|n| i64::try_from(n).ok());
+    record.won = j.get("won").and_then(Json::as_bool).unwrap_or(false);
+    record.position_code = j.get("position_code").and_then(Json::as_str).map(str::to_string);
+    // A loaded game starts from its position code rather than the deal.
+    record.initial_board = match &record.position_code {
+        Some(code) => Some(crate::share::decode(code).map_err(|e| format!("bad \"position_code\": {}", e))?),
+        None => Some(Board::deal(seed, rules)),
+    };
+    record.seed_phrase = j.get("seed_phrase").and_then(Json::as_str).map(str::to_string);
+    record.moves = int("moves").and_then(|n| u32::try_from(n).ok()).unwrap_or(0);
+    record.elapsed_secs = int("elapsed_secs").and_then(|n| u64::try_from(n).ok()).unwrap_or(0);
+    record.undos = int("undos").and_then(|n| u32::try_from(n).ok()).unwrap_or(0);
+    record.hints = int("hints").and_then(|n| u32::try_from(n).ok()).unwrap_or(0);
+    record.hardcore = j.get("hardcore").and_then(Json::as_bool).unwrap_or(false);
+    record.weekly = j.get("weekly").and_then(Json::as_bool).unwrap_or(false);
+    if let Some(name) = j.get("assist").and_then(Json::as_str) {
+        record.assist = AssistPreset::parse(name).ok_or_else(|| format!("unknown assist preset '{}'", name))?;
+    }
+    for suit in j.get("dragon_order").and_then(Json::as_array).unwrap_or_default() {
+        record.dragon_order.push(suit_from_json(suit)?);
+    }
+    for split in j.get("splits").and_then(Json::as_array).unwrap_or_default() {
+        let suit = suit_from_json(split.get("suit").unwrap_or(&Json::Null))?;
+        let kind = match split.get("kind").and_then(Json::as_str) {
+            Some("foundation") => SplitKind::Foundation(suit),
+            Some("dragons") => SplitKind::Dragons(suit),
+            _ => return Err("bad split kind".to_string()),
+        };
+        let at_ms = split.get("at_ms").and_then(Json::as_int).and_then(|n| u64::try_from(n).ok());
+        record.splits.push(Split { kind, at_ms: at_ms.ok_or("bad split time")? });
+    }
+    Ok(record)
+}
+
+fn suit_from_json(j: &Json) -> Result<Suit, String> {
+    let key = j.as_str().unwrap_or_default();
+    Suit::ALL.into_iter().find(|&s| suit_key(s) == key).ok_or_else(|| format!("unknown suit '{}'", key))
+}
+
+fn record_json(r: &GameRecord) -> Json {
+    let splits = r
+        .splits
+        .iter()
+        .map(|split| {
+            let (kind, suit) = match split.kind {
+                SplitKind::Foundation(suit) => ("foundation", suit),
+                SplitKind::Dragons(suit) => ("dragons", suit),
+            };
+            Json::Object(vec![
+                ("kind".into(), kind.into()),
+                ("suit".into(), suit_key(suit).into()),
+                ("at_ms".into(), split.at_ms.into()),
+            ])
+        })
+        .collect();
+    let dragon_order = r.dragon_order.iter().map(|&s| suit_key(s).into()).collect();
+    Json::Object(vec![
+        ("seed".into(), r.seed.into()),
+        ("seed_phrase".into(), r.seed_phrase.clone().into()),
+        ("start_time".into(), r.start_time.into()),
+        ("end_time".into(), r.end_time.into()),
+        ("won".into(), r.won.into()),
+        ("moves".into(), r.moves.into()),
+        ("elapsed_secs".into(), r.elapsed_secs.into()),
+        ("undos".into(), r.undos.into()),
+        ("hints".into(), r.hints.into()),
+        ("hardcore".into(), r.hardcore.into()),
+        ("weekly".into(), r.weekly.into()),
+        ("assist".into(), r.assist.name().into()),
+        ("dragon_order".into(), Json::Array(dragon_order)),
+        ("splits".into(), Json::Array(splits)),
+        ("score".into(), r.score().into()),
+        ("position_code".into(), r.position_code.clone().into()),
+        ("rules".into(), crate::json_serde::to_json(&r.rules).unwrap_or(Json::Null)),
+    ])
+}
+
+/// "won", "lost" or "in progress"; untranslated so the files stay stable.
+fn result(r: &GameRecord) -> &'static str {
+    if r.won {
+        "won"
+    } else if r.end_time.is_some() {
+        "lost"
+    } else {
+        "in progress"
+    }
+}
+
+fn suit_key(suit: Suit) -> &'static str {
+    match suit {
+        Suit::Red => "red",
+        Suit::Green => "green",
+        Suit::Black => "black",
+        Suit::Yellow => "yellow",
+    }
+}
+
+/// Quote a cell if it holds a comma, quote or line break.
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! `--bot <program>`: agents that run as their own process.
+//!
+//! Each turn the agent is sent one line of JSON, `{"board": ..., "moves":
+//! [...]}`, where `moves` lists the moves worth considering, and answers
+//! with one line: one of those moves (any legal move will do), or `null`
+//! to give up.  Moves use the same JSON shape as in `moves`, e.g.
+//! `{"ColToFree": {"src": 1, "dst": 0}}`.
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::agent::{Agent, Move};
+use crate::board::Board;
+use crate::i18n::tr;
+use crate::json::Json;
+use crate::json_serde::{from_json, to_json};
+
+pub struct ProcessAgent {
+    name: String,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl ProcessAgent {
+    /// Start `program` with piped stdin and stdout.
+    pub fn spawn(program: &str) -> io::Result<Self> {
+        let mut child = Command::new(program).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let name = std::path::Path::new(program)
+            .file_name()
+            .map_or_else(|| program.to_string(), |name| name.to_string_lossy().into_owned());
+        Ok(ProcessAgent { name, child, stdin, stdout })
+    }
+
+    fn ask(&mut self, board: &Board) -> Result<Option<Move>, String> {
+        let request = Json::Object(vec![
+            ("board".to_string(), to_json(board).map_err(|e| e.to_string())?),
+            ("moves".to_string(), to_json(&board.valid_moves()).map_err(|e| e.to_string())?),
+        ]);
+        writeln!(self.stdin, "{}", request.to_line()).map_err(|e| e.to_string())?;
+        self.stdin.flush().map_err(|e| e.to_string())?;
+        let mut reply = String::new();
+        if self.stdout.read_line(&mut reply).map_err(|e| e.to_string())? == 0 {
+            return Err(tr!("the agent exited"));
+        }
+        match Json::parse(reply.trim())? {
+            Json::Null => Ok(None),
+            json => from_json(&json).map(Some).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl Agent for ProcessAgent {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        self.ask(board).unwrap_or_else(|e| {
+            eprintln!("{}: {}", self.name, e);
+            None
+        })
+    }
+}
+
+impl Drop for ProcessAgent {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! A C API for embedding the engine (declared in `include/szsol.h`,
+//! which cbindgen generates from this file; see `cbindgen.toml`).
+//!
+//! Boards are opaque handles from [`szsol_board_new`], freed with
+//! [`szsol_board_free`].  Moves are plain [`SzsolMove`] structs; after
+//! each one the safe auto-moves run, as in the game.  `cargo build --lib
+//! --release` builds the library both as a cdylib and a staticlib.
+use std::ffi::{CString, c_char};
+use std::ptr;
+
+use crate::board::{Board, Location, Rules};
+use crate::card::Suit;
+use crate::json::Json;
+use crate::solver::SolverMove;
+
+pub const SZSOL_COL_TO_COL: u8 = 0;
+pub const SZSOL_COL_TO_FREE: u8 = 1;
+pub const SZSOL_FREE_TO_COL: u8 = 2;
+pub const SZSOL_COL_TO_FOUNDATION: u8 = 3;
+pub const SZSOL_FREE_TO_FOUNDATION: u8 = 4;
+pub const SZSOL_MERGE_DRAGONS: u8 = 5;
+
+/// One move.  `src` and `dst` are 0-based column or free-cell indexes;
+/// `depth` counts cards below the top for a column-to-column stack move
+/// (0 = the top card only).  For a dragon merge `src` is the suit:
+/// 0 red, 1 green, 2 black.
+#[repr(C)]
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SzsolMove {
+    pub kind: u8,
+    pub src: u8,
+    pub dst: u8,
+    pub depth: u8,
+}
+
+/// A summary of a board for callers that do not want to parse JSON.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SzsolState {
+    pub seed: u64,
+    /// Highest card on each foundation (red, green, black, yellow); 0 when
+    /// empty or when the deal has no such suit.
+    pub foundations: [u8; 4],
+    pub flower_placed: bool,
+    /// Cards still in the columns and free cells.
+    pub cards_left: u32,
+    pub won: bool,
+    /// Not won, and no move is left.
+    pub stuck: bool,
+}
+
+impl From<SolverMove> for SzsolMove {
+    fn from(m: SolverMove) -> Self {
+        let (kind, src, dst, depth) = match m {
+            SolverMove::ColToCol { src, dst, depth_from_top } => (SZSOL_COL_TO_COL, src, dst, depth_from_top),
+            SolverMove::ColToFree { src, dst } => (SZSOL_COL_TO_FREE, src, dst, 0),
+            SolverMove::FreeToCol { src, dst } => (SZSOL_FREE_TO_COL, src, dst, 0),
+            SolverMove::ColToFound { src } => (SZSOL_COL_TO_FOUNDATION, src, 0, 0),
+            SolverMove::FreeToFound { src } => (SZSOL_FREE_TO_FOUNDATION, src, 0, 0),
+            SolverMove::Merge { suit } => {
+                let index = Suit::ALL.iter().position(|&s| s == suit).unwrap_or(0);
+                (SZSOL_MERGE_DRAGONS, index, 0, 0)
+            }
+        };
+        SzsolMove { kind, src: src as u8, dst: dst as u8, depth: depth as u8 }
+    }
+}
+
+/// Carry out `m` on `board` if it is legal.  Out-of-range indexes are
+/// rejected here rather than panicking in the board code.
+pub(crate) fn apply(board: &mut Board, m: SzsolMove) -> bool {
+    let (src, dst, depth) = (m.src as usize, m.dst as usize, m.depth as usize);
+    let (columns, cells) = (board.rules.columns, board.rules.free_cells);
+    let column = |i: usize| i < columns;
+    let cell = |i: usize| i < cells;
+    let result = match m.kind {
+        SZSOL_COL_TO_COL if column(src) && column(dst) => {
+            let len = board.columns[src].len();
+            if depth >= len {
+                return false;
+            }
+            board.move_stack(src, len - 1 - depth, dst).map(drop)
+        }
+        SZSOL_COL_TO_FREE if column(src) && cell(dst) => {
+            board.move_card(Location::Column(src), Location::FreeCell(dst)).map(drop)
+        }
+        SZSOL_FREE_TO_COL if cell(src) && column(dst) => {
+            board.move_card(Location::FreeCell(src), Location::Column(dst)).map(drop)
+        }
+        SZSOL_COL_TO_FOUNDATION if column(src) => board.move_to_foundation(Location::Column(src)).map(drop),
+        SZSOL_FREE_TO_FOUNDATION if cell(src) => board.move_to_foundation(Location::FreeCell(src)).map(drop),
+        SZSOL_MERGE_DRAGONS if src < board.rules.suits => board.merge_dragons(Suit::ALL[src]).map(drop),
+        _ => return false,
+    };
+    if result.is_err() {
+        return false;
+    }
+    board.auto_move();
+    true
+}
+
+fn state(board: &Board) -> SzsolState {
+    let cards_left = board.columns.iter().map(Vec::len).sum::<usize>()
+        + board.free_cells.iter().filter(|cell| cell.card().is_some()).count();
+    let mut foundations = [0; 4];
+    foundations[..board.foundations.len()].copy_from_slice(&board.foundations);
+    SzsolState {
+        seed: board.seed,
+        foundations,
+        flower_placed: board.flower_placed,
+        cards_left: cards_left as u32,
+        won: board.is_won(),
+        stuck: board.is_stuck(),
+    }
+}
+
+/// Deal the board for `seed` on a standard table, with the opening
+/// auto-moves made.  Free it with [`szsol_board_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn szsol_board_new(seed: u64) -> *mut Board {
+    let mut board = Board::deal(seed, Rules::STANDARD);
+    board.auto_move();
+    Box::into_raw(Box::new(board))
+}
+
+/// # Safety
+/// `board` must come from [`szsol_board_new`] and not be used afterwards.
+/// Null is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_board_free(board: *mut Board) {
+    if !board.is_null() {
+        drop(unsafe { Box::from_raw(board) });
+    }
+}
+
+/// Write up to `capacity` of the board's moves to `out` and return how many
+/// there are in all, so a caller can size its buffer with a first call
+/// passing `capacity` 0.  Moves that can never help (such as shuffling a
+/// card between empty columns) are left out.
+///
+/// # Safety
+/// `board` must be a live handle; `out` must have room for `capacity` moves
+/// (it may be null when `capacity` is 0).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_board_moves(board: *const Board, out: *mut SzsolMove, capacity: usize) -> usize {
+    let Some(board) = (unsafe { board.as_ref() }) else { return 0 };
+    let moves = board.valid_moves();
+    if !out.is_null() {
+        for (i, &m) in moves.iter().take(capacity).enumerate() {
+            unsafe { out.add(i).write(m.into()) };
+        }
+    }
+    moves.len()
+}
+
+/// Make `m` followed by the safe auto-moves.  Returns 0 on success and -1
+/// when the move is not legal (the board is then unchanged).
+///
+/// # Safety
+/// `board` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_board_apply(board: *mut Board, m: SzsolMove) -> i32 {
+    match unsafe { board.as_mut() }.map(|board| apply(board, m)) {
+        Some(true) => 0,
+        _ => -1,
+    }
+}
+
+/// # Safety
+/// `board` must be a live handle.  A null handle gives an all-zero state.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_board_state(board: *const Board) -> SzsolState {
+    unsafe { board.as_ref() }.map(state).unwrap_or_default()
+}
+
+/// The summary fields plus `"board"` with every column, free cell and
+/// foundation.
+pub(crate) fn json(board: &Board) -> Json {
+    let summary = state(board);
+    Json::Object(vec![
+        ("seed".to_string(), Json::from(summary.seed)),
+        ("won".to_string(), Json::from(summary.won)),
+        ("stuck".to_string(), Json::from(summary.stuck)),
+        ("cards_left".to_string(), Json::from(summary.cards_left)),
+        ("board".to_string(), crate::json_serde::to_json(board).unwrap_or(Json::Null)),
+    ])
+}
+
+/// The board as a JSON object: the summary fields plus `"board"` with every
+/// column, free cell and foundation.  Free the string with
+/// [`szsol_string_free`].
+///
+/// # Safety
+/// `board` must be a live handle.  Returns null for a null handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_board_json(board: *const Board) -> *mut c_char {
+    let Some(board) = (unsafe { board.as_ref() }) else { return ptr::null_mut() };
+    // JSON escapes control characters, so there is no interior NUL.
+    CString::new(json(board).pretty()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// # Safety
+/// `text` must come from [`szsol_board_json`] and not be used afterwards.
+/// Null is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn szsol_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
@@ -20,19 +20,68 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
-use std::io::{self, BufRead, Write};
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self as ct_event, Event};
 
 
-use crate::board::{Board, Location};
-use crate::config::AppConfig;
+use crate::agent::Agent;
+use crate::analyze::RatingJob;
+use crate::board::{Board, FreeCellState, Location, MoveError, Rules};
+use crate::card::Suit;
+use crate::config::{AppConfig, AssistPreset, Assists, Autosave, Source, UserConfig};
 use crate::event::GameEvent;
-use crate::command::{parse_command, Command};
-use crate::renderer::Renderer;
-use crate::history::{History, GameRecord};
+use crate::ghost::{Ghost, GhostPoint, GhostStatus};
+use crate::packed::PackedBoard;
+use crate::command::{parse_command, Command, CopyKind, ExportKind};
+use crate::renderer::{format_elapsed, format_split, HeaderStatus, Outlook, RenderMode, Renderer, WinSummary};
+use crate::history::{CampaignAttempt, ChallengeRecord, History, GameRecord, PuzzleRecord, Split, SplitKind, WeeklyRecord};
+use crate::i18n::{self, tr};
+use crate::input::{LineInput, ReadOutcome};
+use crate::keymap::pressed;
+use crate::macros::Macros;
+use crate::observer::{EventSink, PlayEvent};
+use crate::puzzle::Pack;
+use crate::rating::RatingChange;
+use crate::solver::{ParFinder, Strategy, Winnability, WinnabilityChecker};
+use crate::telemetry::Telemetry;
+
+
+/// Moves a `--bot` may try in one game, so an agent that goes round in
+/// circles still stops.
+pub const BOT_MOVE_LIMIT: usize = 1000;
+
+/// How a `--script` or `--bot` game ended; each has its own exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    NotWon,
+    /// The move on this script line (counted from 1) was refused.
+    IllegalMove { line: usize },
+    /// This script line is not a command.
+    ParseError { line: usize },
+}
+
+impl Outcome {
+    /// 0 won, 1 not won, 3 illegal move, 4 parse error; 2 is left to bad
+    /// command-line arguments.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Outcome::Won => 0,
+            Outcome::NotWon => 1,
+            Outcome::IllegalMove { .. } => 3,
+            Outcome::ParseError { .. } => 4,
+        }
+    }
+}
+
+/// How long a command waits for the winnability indicator's verdict before
+/// the header is drawn without it.
+const OUTLOOK_WAIT: Duration = Duration::from_millis(300);
 
+/// How many rating changes `report rating` lists.
+const RATING_LINES: usize = 10;
 
 /// The main game loop.  `renderer` is injected so the engine stays
 /// renderer-agnostic (CLI today, TUI tomorrow).
@@ -40,39 +89,131 @@ pub struct Game<R: Renderer> {
     board: Board,
     renderer: R,
     history: Vec<Board>, // for undo
+    /// Alternative lines kept when the player undoes and then plays differently.
+    lines: Vec<SavedLine>,
+    /// The line being rewound by consecutive undos, saved once a new move is made.
+    undone_tip: Option<SavedLine>,
+    /// Named positions saved with `mark`, valid for the current seed.
+    bookmarks: Vec<(String, SavedLine)>,
+    macros: Macros,
+    /// The macro being recorded with the commands typed so far.
+    recording: Option<(String, Vec<String>)>,
+    /// Commands of the macro being played, still to run.
+    macro_queue: VecDeque<String>,
+    /// The opt-in usage statistics (`telemetry`).
+    telemetry: Telemetry,
+    /// Told about each [`PlayEvent`]; see [`Game::subscribe`].
+    sinks: Vec<Box<dyn EventSink>>,
     save_data: History,
     app_config: AppConfig,
+    user_config: UserConfig,
     resumed_on_start: bool,
     should_quit: bool,
+    /// Set after `resign`: the board stays visible but accepts no moves.
+    resigned: bool,
     last_tui_click: Option<(Location, Instant)>,
+    /// Background solver for the "fatal move" warning (assist mode only)
+    /// and the header's winnability indicator.
+    checker: Option<WinnabilityChecker>,
+    checked_board: Option<Board>,
+    last_verdict: Option<Winnability>,
+    /// Set while `checked_board` awaits its verdict.
+    verdict_pending: bool,
+    /// Moves made in the current game.
+    moves: u32,
+    /// `moves` when progress was last written, for `autosave = N`.
+    moves_at_save: u32,
+    /// Play time before `clock` was last started (earlier sessions included).
+    played: Duration,
+    /// Running while the game is in progress; `None` once it is over.
+    clock: Option<Instant>,
+    /// Speedrun splits reached in the current game.
+    splits: Vec<Split>,
+    /// Undo steps taken in the current game.
+    undos: u32,
+    /// Hints asked of the solver in the current game.
+    hints: u32,
+    /// Suits in the order their dragons were merged.
+    dragon_order: Vec<Suit>,
+    /// Suits in the order their foundations were completed.  A resumed
+    /// game starts with those already complete, in suit order.
+    foundation_order: Vec<Suit>,
+    /// The share text of the daily deal won in this session, for
+    /// `share daily`.
+    daily_share: Option<String>,
+    /// The current game's foundations each time they changed, kept as the
+    /// seed's ghost if it becomes the fastest win.  `None` when the game
+    /// was resumed, as the start of the run is missing.
+    ghost_trace: Option<Vec<GhostPoint>>,
+    /// The solver's move count for the current deal, once known.
+    par: Option<usize>,
+    /// Works out pars of new deals in the background (`par` setting).
+    par_finder: Option<ParFinder>,
+    /// Rates played deals for `report difficulty` in the background.
+    rating_job: Option<RatingJob>,
+    /// The puzzles `puzzles` lists and `puzzle <n>` plays.
+    puzzle_pack: Pack,
+    /// Boards the current game has passed through, sent along by `submit`.
+    #[cfg(feature = "leaderboard")]
+    move_log: MoveLog,
+}
+
+/// The boards of one game in the order they were reached, undos included.
+#[cfg(feature = "leaderboard")]
+#[derive(Default)]
+struct MoveLog {
+    /// Start time of the record the log belongs to.
+    start_time: i64,
+    /// Whether the log starts at the deal.  A game resumed part-way has
+    /// no log of its earlier moves.
+    from_deal: bool,
+    boards: Vec<Board>,
 }
 
 
 impl<R: Renderer> Game<R> {
-    pub fn init(seed: Option<u64>, mut renderer: R) -> Self {
+    pub fn init(seed: Option<u64>, seed_phrase: Option<String>, mut renderer: R, user_config: UserConfig) -> Self {
         let mut save_data = History::load();
+        save_data.compact(user_config.history_limit.value);
         let app_config = AppConfig::load();
+        for warning in &user_config.warnings {
+            renderer.error(warning);
+        }
         
+        // A seed with an unfinished game anywhere in the history resumes
+        // that game: move it to the end, where the current game lives.
+        if let Some(seed) = seed {
+            let slot = save_data.records.iter().rposition(|r| {
+                r.seed == seed && r.end_time.is_none() && r.current_board.is_some() && r.is_dealt()
+            });
+            if let Some(i) = slot {
+                let record = save_data.records.remove(i);
+                save_data.records.push(record);
+            }
+        }
+
         // 1. Check if we can resume the last game
         let mut resumed_board = None;
         let mut resumed_history = Vec::new();
+        let mut resumed_progress = GameRecord::new(0, Rules::STANDARD, 0);
         let mut abandon_old = false;
 
-        if let Some(last) = save_data.records.last_mut() {
-            if last.end_time.is_none() {
-                // Determine if we should resume or abandon
-                if seed.is_none() || seed == Some(last.seed) {
-                    if let Some(cb) = &last.current_board {
-                        resumed_board = Some(cb.clone());
-                        resumed_history = last.undo_history.clone();
-                        renderer.info(&format!("Resumed game from seed {}.", last.seed));
-                    } else {
-                        abandon_old = true;
-                    }
+        if let Some(last) = save_data.records.last_mut() && last.end_time.is_none() {
+            // Determine if we should resume or abandon
+            if seed.is_none() || seed == Some(last.seed) {
+                if let Some(cb) = &last.current_board {
+                    resumed_board = Some(cb.clone());
+                    resumed_history = last.undo_history.clone();
+                    resumed_progress = last.clone();
+                    log::info!("resumed seed {} from the save", last.seed);
+                    renderer.info(&tr!("Resumed game from seed {}.", last.seed));
                 } else {
-                    // Given a new distinct seed, so we abandon the old unfinished run.
                     abandon_old = true;
                 }
+            } else {
+                // Given a new distinct seed: a game with moves in it is
+                // kept for `resume`, an untouched deal is abandoned.
+                abandon_old = last.moves == 0;
             }
         }
 
@@ -81,116 +222,777 @@ impl<R: Renderer> Game<R> {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs() as i64;
-            if let Some(last) = save_data.records.last_mut() {
-                if last.end_time.is_none() {
-                    last.end_time = Some(now);
-                    last.current_board = None;
-                    last.undo_history.clear();
-                }
+            if let Some(last) = save_data.records.last_mut() && last.end_time.is_none() {
+                last.end_time = Some(now);
+                last.current_board = None;
+                last.undo_history.clear();
             }
         }
 
         let resumed_on_start = resumed_board.is_some();
+        let waiting = save_data.unfinished().len() - usize::from(resumed_on_start);
+        if waiting > 0 {
+            renderer.info(&tr!("{} unfinished game(s) waiting; type 'games' to list them.", waiting));
+        }
         let board = match resumed_board {
             Some(b) => b,
             None => {
                 let new_board = match seed {
-                    Some(s) => Board::deal_seeded(s),
-                    None => Board::deal_random(),
+                    Some(s) => Board::deal(s, user_config.rules()),
+                    None => Board::deal_random(user_config.rules()),
                 };
                 let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
                     .as_secs() as i64;
-                let mut record = GameRecord::new(new_board.seed, now);
+                let mut record = GameRecord::new(new_board.seed, new_board.rules, now);
                 record.initial_board = Some(new_board.clone());
                 record.current_board = Some(new_board.clone());
+                record.position_code = practice_code(&new_board);
+                record.seed_phrase = seed_phrase;
+                record.hardcore = user_config.hardcore.value;
+                record.assist = user_config.assist.value;
                 save_data.records.push(record);
                 save_data.save();
                 new_board
             }
         };
 
-        Game {
+
+        let foundation_order = completed_foundations(&board);
+        let mut game = Game {
             board,
             renderer,
             history: resumed_history,
+            lines: Vec::new(),
+            undone_tip: None,
+            bookmarks: Vec::new(),
+            // Network sessions keep their macros in memory, like their history.
+            macros: if History::is_in_memory() { Macros::default() } else { Macros::load() },
+            recording: None,
+            macro_queue: VecDeque::new(),
+            telemetry: if History::is_in_memory() { Telemetry::default() } else { Telemetry::load() },
+            sinks: Vec::new(),
             save_data,
             app_config,
+            user_config,
             resumed_on_start,
             should_quit: false,
+            resigned: false,
             last_tui_click: None,
+            checker: None,
+            checked_board: None,
+            last_verdict: None,
+            verdict_pending: false,
+            moves: resumed_progress.moves,
+            moves_at_save: resumed_progress.moves,
+            played: Duration::from_secs(resumed_progress.elapsed_secs),
+            clock: Some(Instant::now()),
+            splits: resumed_progress.splits,
+            undos: resumed_progress.undos,
+            hints: resumed_progress.hints,
+            ghost_trace: (resumed_progress.moves == 0).then(Vec::new),
+            foundation_order,
+            daily_share: None,
+            dragon_order: resumed_progress.dragon_order,
+            par: None,
+            par_finder: None,
+            rating_job: None,
+            puzzle_pack: Pack::starter(),
+            #[cfg(feature = "leaderboard")]
+            move_log: MoveLog::default(),
+        };
+        game.compute_par();
+        game
+    }
+
+    /// Look up the current deal's par, or start working it out.  Golf mode
+    /// needs it before the first move, so it solves the deal right away;
+    /// otherwise the `par` setting solves it in the background.
+    fn compute_par(&mut self) {
+        self.par = None;
+        if let Some(finder) = &mut self.par_finder {
+            finder.cancel();
+        }
+        let dealt = self.current_is_dealt();
+        if dealt {
+            self.par = self.save_data.par(self.board.seed, self.board.rules).map(|p| p as usize);
+        }
+        if self.user_config.golf.value {
+            if self.par.is_none() {
+                self.solve_par();
+            }
+            match self.par {
+                Some(par) => self.renderer.info(&tr!("Golf: par for this deal is {} moves.", par)),
+                None => self.renderer.info(&tr!("Golf: the solver found no par for this deal.")),
+            }
+        } else if self.par.is_none() && dealt && self.user_config.par.value {
+            let start = self.start_board();
+            self.par_finder.get_or_insert_with(ParFinder::spawn).submit(&start);
         }
     }
 
+    /// Solve the current deal from its first position and keep the par.
+    fn solve_par(&mut self) {
+        let start = self.start_board();
+        if let Some(path) = crate::solver::solve(&start, |_| true) {
+            self.set_par(path.len());
+        }
+    }
 
-    /// Run the interactive game loop until the player quits.
-    pub fn run(&mut self) {
-        let stdin = io::stdin();
-        let mut stdout = io::stdout();
+    fn set_par(&mut self, par: usize) {
+        self.par = Some(par);
+        // A shared position's par is not the par of its seed's deal.
+        if self.current_is_dealt() {
+            self.save_data.set_par(self.board.seed, self.board.rules, par as u32);
+        }
+    }
 
-        // Auto-move any immediately playable cards on deal.
-        let (n, events) = self.board.auto_move();
-        self.renderer.push_events(events);
-        if n > 0 {
-            self.renderer.info(&format!("Auto-moved {} card(s) to foundation.", n));
+    /// Keep the deals `report difficulty` rated in the background, and show
+    /// the report once the last one is in.
+    fn poll_rating_job(&mut self) {
+        let Some(job) = &mut self.rating_job else { return };
+        let rated = job.poll();
+        let done = job.pending() == 0;
+        if rated.is_empty() && !done {
+            return;
+        }
+        for analysis in &rated {
+            self.save_data.set_effort(analysis.effort());
+        }
+        self.save_data.save();
+        if done {
+            self.rating_job = None;
+            for line in crate::analyze::difficulty_report(&self.save_data) {
+                self.renderer.info(&line);
+            }
         }
+    }
 
-        self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
-        self.renderer.render(&self.board);
+    /// Show a par worked out in the background.
+    fn poll_par(&mut self) {
+        let Some((seed, par)) = self.par_finder.as_mut().and_then(|f| f.poll()) else { return };
+        if seed != self.board.seed {
+            return;
+        }
+        if let Some(par) = par {
+            self.set_par(par);
+            self.renderer.info(&tr!("Par for this deal is {} moves.", par));
+        }
+    }
+
+    /// The current game's first position.
+    fn start_board(&self) -> Board {
+        self.save_data
+            .records
+            .last()
+            .filter(|r| r.seed == self.board.seed)
+            .and_then(|r| r.initial_board.clone())
+            .unwrap_or_else(|| self.board.clone())
+    }
+
+    /// Whether the current game was dealt from its seed rather than loaded
+    /// from a position code.
+    fn current_is_dealt(&self) -> bool {
+        self.save_data.records.last().is_none_or(|r| r.seed != self.board.seed || r.is_dealt())
+    }
+
+    /// Whether the current game is played in hardcore mode (fixed when it
+    /// was dealt, so a resumed game keeps its mode).
+    fn hardcore(&self) -> bool {
+        self.save_data.records.last().is_some_and(|r| r.hardcore && r.seed == self.board.seed)
+    }
+
+    /// Whether the current game is this week's challenge deal.
+    fn is_weekly(&self) -> bool {
+        let week = crate::weekly::current_week();
+        self.save_data
+            .records
+            .last()
+            .is_some_and(|r| r.weekly && r.seed == self.board.seed && crate::weekly::week(r.start_time) == week)
+    }
+
+    /// Hints the current game may still take, if it is a daily, weekly or
+    /// challenge game; others have no limit.
+    fn hints_left(&self) -> Option<u32> {
+        let record = self.save_data.records.last().filter(|r| r.seed == self.board.seed)?;
+        // A daily deal's seed is the day it was dealt for.
+        let daily = record.is_dealt() && record.seed == (record.start_time / 86_400) as u64;
+        let weekly = record.weekly;
+        let challenge = self.save_data.challenges.iter().any(|c| c.start_time == record.start_time);
+        (daily || weekly || challenge).then(|| self.user_config.challenge_hints.value.saturating_sub(self.hints))
+    }
+
+    /// Count a hint against the current game.  Returns `false`, with a
+    /// message, once a daily or challenge game has used up its hints.
+    fn take_hint(&mut self) -> bool {
+        let limit = self.user_config.challenge_hints.value;
+        match self.hints_left() {
+            Some(0) => {
+                self.renderer.error(&tr!("No hints left: daily, weekly and challenge games allow {} (challenge_hints).", limit));
+                return false;
+            }
+            Some(left) => self.renderer.info(&tr!("Hint {} of {} for this game.", limit - left + 1, limit)),
+            None => {}
+        }
+        self.hints += 1;
+        self.store_progress();
+        true
+    }
+
+    /// The preset the current game was dealt with.
+    fn assist_preset(&self) -> AssistPreset {
+        match self.save_data.records.last() {
+            Some(r) if r.seed == self.board.seed => r.assist,
+            _ => self.user_config.assist.value,
+        }
+    }
+
+    /// The assists the current game was dealt with.  An `auto_move` set in
+    /// the config file or on the command line overrides the preset's.
+    fn assists(&self) -> Assists {
+        let mut assists = self.assist_preset().assists();
+        if self.user_config.auto_move.source != Source::Default {
+            assists.auto_move = self.user_config.auto_move.value;
+        }
+        assists
+    }
+
+    /// Golf progress after a move: moves made against par.
+    fn report_golf(&mut self) {
+        let Some(par) = self.par.filter(|_| self.user_config.golf.value) else { return };
+        let left = par as i64 - self.moves as i64;
+        if left >= 0 {
+            self.renderer.info(&tr!("Golf: {} of {} moves used, {} left to par.", self.moves, par, left));
+        } else {
+            self.renderer.info(&tr!("Golf: {} of {} moves used, {} over par.", self.moves, par, -left));
+        }
+    }
+
+    /// Play time of the current game so far.
+    fn elapsed(&self) -> Duration {
+        self.played + self.clock.map(|started| started.elapsed()).unwrap_or_default()
+    }
+
+    /// Zero the move counter and restart the clock for a fresh deal.
+    fn reset_progress(&mut self) {
+        self.moves = 0;
+        self.moves_at_save = 0;
+        self.played = Duration::ZERO;
+        self.clock = Some(Instant::now());
+        self.splits.clear();
+        self.undos = 0;
+        self.hints = 0;
+        self.dragon_order.clear();
+        self.foundation_order.clear();
+        self.ghost_trace = Some(Vec::new());
+    }
+
+    /// Point out that the last move came back to a position already reached
+    /// on this line, counting how far back; shuffling cards between free
+    /// cells and columns is an easy way to go round in circles.
+    fn warn_repeat(&mut self) {
+        if !self.assists().repeat_warnings {
+            return;
+        }
+        let here = PackedBoard::from(&self.board);
+        if let Some(i) = self.history.iter().rposition(|b| PackedBoard::from(b) == here) {
+            let ago = self.history.len() - i;
+            self.renderer.info(&tr!("You've been here before, {} moves ago.", ago));
+        }
+    }
+
+    /// Note the suits whose dragons were merged since the last check.
+    fn track_dragon_merges(&mut self) {
+        for &suit in self.board.rules.suits() {
+            let merged = self.board.free_cells.contains(&FreeCellState::DragonLocked(suit));
+            if merged && !self.dragon_order.contains(&suit) {
+                self.dragon_order.push(suit);
+            }
+        }
+    }
+
+    /// Note the suits whose foundations were completed since the last
+    /// check.
+    fn track_foundations(&mut self) {
+        for suit in completed_foundations(&self.board) {
+            if !self.foundation_order.contains(&suit) {
+                self.foundation_order.push(suit);
+            }
+        }
+    }
+
+    /// Add the foundations to the ghost trace if they changed since the
+    /// last check.
+    fn trace_ghost(&mut self) {
+        let Some(mut trace) = self.ghost_trace.take() else { return };
+        if trace.is_empty() {
+            trace.push(GhostPoint { at_ms: 0, moves: 0, home: self.start_board().foundations });
+        }
+        if trace.last().is_some_and(|p| p.home != self.board.foundations) {
+            let at_ms = self.elapsed().as_millis() as u64;
+            trace.push(GhostPoint { at_ms, moves: self.moves, home: self.board.foundations.clone() });
+        }
+        self.ghost_trace = Some(trace);
+    }
+
+    /// Where the seed's ghost stands against the current game, if there
+    /// is one to race.
+    fn ghost_status(&self) -> Option<GhostStatus> {
+        if !self.user_config.ghost.value || !self.current_is_dealt() {
+            return None;
+        }
+        let ghost = self.save_data.ghost(self.board.seed, self.board.rules)?;
+        Some(ghost.status(&self.board, self.elapsed().as_millis() as u64, self.moves))
+    }
+
+    /// In speedrun mode, record and announce foundations completed and
+    /// dragons merged since the last check.
+    fn check_splits(&mut self) {
+        if !self.user_config.speedrun.value {
+            return;
+        }
+        for &suit in self.board.rules.suits() {
+            let reached = [
+                (SplitKind::Foundation(suit), self.board.foundations[suit as usize] == self.board.rules.top_rank()),
+                (
+                    SplitKind::Dragons(suit),
+                    self.board.free_cells.contains(&FreeCellState::DragonLocked(suit)),
+                ),
+            ];
+            for (kind, done) in reached {
+                if done && !self.splits.iter().any(|s| s.kind == kind) {
+                    let split = Split { kind, at_ms: self.elapsed().as_millis() as u64 };
+                    self.splits.push(split);
+                    let msg = self.describe_split(&split);
+                    self.renderer.info(&tr!("Split: {}", msg));
+                }
+            }
+        }
+    }
+
+    /// "Red foundation  1:23.4  (+2.1s vs best)" for one split.
+    fn describe_split(&self, split: &Split) -> String {
+        let name = match split.kind {
+            SplitKind::Foundation(suit) => tr!("{} foundation", i18n::suit_name(suit)),
+            SplitKind::Dragons(suit) => tr!("{} dragons", i18n::suit_name(suit)),
+        };
+        let at = format_split(Duration::from_millis(split.at_ms));
+        match self.save_data.best_split(split.kind) {
+            Some(best) => {
+                // Rounded to tenths first so a tie never shows as "-0.0s".
+                let tenths = ((split.at_ms as f64 - best as f64) / 100.0).round() as i64;
+                let sign = if tenths < 0 { '-' } else { '+' };
+                let delta = format!("{}{}.{}s", sign, tenths.abs() / 10, tenths.abs() % 10);
+                tr!("{}  {}  ({} vs best)", name, at, delta)
+            }
+            None => tr!("{}  {}", name, at),
+        }
+    }
+
+    /// Stop the clock once the game is won, resigned or abandoned.
+    fn stop_clock(&mut self) {
+        self.played = self.elapsed();
+        self.clock = None;
+    }
+
+
+    /// Replay a winning line from the last position of this game that could
+    /// still be won.  Winnability only ever goes from yes to no along a
+    /// game, so a binary search over the undo history finds that position.
+    fn show_solution(&mut self) {
+        let initial = self
+            .save_data
+            .records
+            .last()
+            .filter(|r| r.seed == self.board.seed)
+            .and_then(|r| r.initial_board.clone());
+        let mut positions = Vec::new();
+        positions.extend(initial.filter(|b| self.history.first() != Some(b)));
+        positions.extend(self.history.iter().cloned());
+        positions.push(self.board.clone());
+
+        self.renderer.info(&tr!("Looking for the last position that could still be won... (may take a moment)"));
+        let (strategy, budget) = (self.user_config.solver.value, self.user_config.solver_budget());
+        let solve = |board: &Board| {
+            crate::solver::solve_with(board, strategy, budget, |_| true)
+                .map(|path| path.iter().map(|step| step.next_move).collect::<Vec<_>>())
+        };
+        let Some(mut best) = solve(&positions[0]).map(|path| (0, path)) else {
+            self.renderer.error(&tr!("The solver found no winning line for this deal."));
+            return;
+        };
+        let (mut lo, mut hi) = (1, positions.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match solve(&positions[mid]) {
+                Some(path) => {
+                    best = (mid, path);
+                    lo = mid + 1;
+                }
+                None => hi = mid,
+            }
+        }
+
+        let (index, path) = best;
+        let back = positions.len() - 1 - index;
+        if back == 0 {
+            self.renderer.info(&tr!("This position can still be won in {} moves:", path.len()));
+        } else if index == 0 {
+            self.renderer.info(&tr!("A winning line of {} moves from the initial deal:", path.len()));
+        } else {
+            self.renderer.info(&tr!("The game could still be won {} step(s) back. A winning line of {} moves from there:", back, path.len()));
+        }
+        let base = self.user_config.index_base();
+        let mut board = positions.swap_remove(index);
+        self.renderer.render(&board);
+        for (i, m) in path.into_iter().enumerate() {
+            board.apply_move(m);
+            self.renderer.info(&format!("{:4}. {}", i + 1, m.command_in_base(base)));
+            self.renderer.render(&board);
+        }
+    }
+
+    /// In a puzzle, end the attempt once its goal is met or its moves run
+    /// out.  Either way the board stays in play.
+    fn check_puzzle(&mut self) {
+        let Some(puzzle) = self.save_data.current_puzzle().filter(|p| p.end_time.is_none()) else { return };
+        let (solved, allowed) = (puzzle.goal.is_met(&self.board), puzzle.moves_allowed);
+        if !solved && allowed.is_none_or(|n| self.moves < n) {
+            return;
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.save_data.finish_puzzle(solved, self.moves, now);
+        self.save_data.save();
+        if solved {
+            self.renderer.info(&tr!("Puzzle solved in {} moves!", self.moves));
+        } else {
+            self.renderer.error(&tr!("Out of moves: the puzzle wasn't solved. Type 'restart' to try again."));
+        }
+    }
+
+    /// Make the game just dealt an attempt at campaign stage `stage`.
+    fn open_campaign_attempt(&mut self, stage: usize) {
+        let Some(start_time) = self.save_data.records.last().map(|r| r.start_time) else { return };
+        self.save_data.campaign.push(CampaignAttempt { stage, start_time, end_time: None, won: false, moves: 0, elapsed_secs: 0 });
+        self.save_data.save();
+    }
+
+    /// Open an attempt at a puzzle for the game `start_loaded` just began.
+    fn open_puzzle(&mut self, pack: String, name: String, goal: crate::puzzle::Goal, moves_allowed: Option<u32>) {
+        let Some(start_time) = self.save_data.records.last().map(|r| r.start_time) else { return };
+        self.save_data.puzzles.push(PuzzleRecord {
+            pack,
+            name,
+            goal,
+            moves_allowed,
+            start_time,
+            end_time: None,
+            solved: false,
+            moves: 0,
+        });
+        self.save_data.save();
+    }
 
+    /// Auto-move cards home under the game's assists, merging dragons too
+    /// when they allow it.
+    fn auto_move(&mut self) -> (usize, Vec<GameEvent>) {
+        let (moved, events) = self.auto_move_cards();
+        if moved > 0 {
+            self.notify(PlayEvent::AutoMoved { cards: moved });
+        }
+        (moved, events)
+    }
+
+    fn auto_move_cards(&mut self) -> (usize, Vec<GameEvent>) {
+        let assists = self.assists();
+        let (mut moved, mut events) = self.board.auto_move_with(assists.auto_move);
+        if !assists.auto_merge_dragons {
+            return (moved, events);
+        }
         loop {
-            print!("> ");
-            stdout.flush().unwrap();
+            let suits = self.board.rules.suits();
+            let merged: Vec<GameEvent> =
+                suits.iter().filter_map(|&suit| self.board.merge_dragons(suit).ok()).flatten().collect();
+            if merged.is_empty() {
+                break;
+            }
+            events.extend(merged);
+            // A merge can uncover more cards that go home.
+            let (n, more) = self.board.auto_move_with(assists.auto_move);
+            moved += n;
+            events.extend(more);
+        }
+        (moved, events)
+    }
 
-            let mut line = String::new();
-            if stdin.lock().read_line(&mut line).unwrap() == 0 {
-                if let Some(last) = self.save_data.records.last_mut() {
-                    last.current_board = Some(self.board.clone());
-                    last.undo_history = self.history.clone();
+    /// Run the interactive game loop until the player quits.
+    pub fn run(&mut self, input: &mut dyn LineInput) {
+        self.start();
+        loop {
+            self.poll_background();
+            // Speedrun: the prompt doubles as the running timer.
+            let prompt = if self.user_config.speedrun.value {
+                format!("[{}] {}", format_split(self.elapsed()), self.user_config.prompt.value)
+            } else {
+                self.user_config.prompt.value.clone()
+            };
+            let (board, base) = (&self.board, self.user_config.index_base());
+            let line = match input.read_line(&prompt, &|text| crate::command::complete(text, board, base)) {
+                ReadOutcome::Line(line) => line,
+                ReadOutcome::Interrupted | ReadOutcome::Eof => {
+                    self.save_progress();
+                    break;
                 }
-                self.save_data.save();
+            };
+
+            if self.execute(&line) {
                 break;
             }
+        }
+    }
 
-            match parse_command(&line) {
-                Err(e) => self.renderer.error(&e),
-                Ok(cmd) => {
-                    let quit = self.handle(cmd);
-                    if quit {
-                        break;
-                    }
+    /// Let `agent` play the deal, each move rendered as if it had been
+    /// typed.  Stops when the game is won, the agent gives up or
+    /// [`BOT_MOVE_LIMIT`] moves have been tried.
+    pub fn run_bot(&mut self, agent: &mut dyn Agent) -> Outcome {
+        self.start();
+        let base = self.user_config.index_base();
+        for _ in 0..BOT_MOVE_LIMIT {
+            self.poll_background();
+            if self.board.is_won() {
+                return Outcome::Won;
+            }
+            let Some(m) = agent.choose_move(&self.board) else {
+                self.renderer.info(&tr!("{} has no move to make.", agent.name()));
+                return Outcome::NotWon;
+            };
+            let line = m.command_in_base(base);
+            self.renderer.info(&tr!("{} plays {}.", agent.name(), line));
+            if self.execute(&line) {
+                return Outcome::NotWon;
+            }
+        }
+        self.renderer.info(&tr!("{} stopped after {} moves.", agent.name(), BOT_MOVE_LIMIT));
+        if self.board.is_won() { Outcome::Won } else { Outcome::NotWon }
+    }
 
-                    // Auto-move after every successful command.
-                    let (n, events) = self.board.auto_move();
-                    self.renderer.push_events(events);
-                    if n > 0 {
-                        self.renderer
-                            .info(&format!("Auto-moved {} card(s) to foundation.", n));
-                    }
+    /// Run the commands of a script, one per line (blank lines and lines
+    /// starting with `#` are skipped), each echoed after the prompt.  Ends
+    /// with the final board and whether the game was won.  A line that is
+    /// not a command, or a move that is refused, stops the script there.
+    pub fn run_script(&mut self, script: &str) -> Outcome {
+        self.start();
+        let mut stopped = None;
+        for (n, line) in script.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            self.poll_background();
+            self.renderer.info(&format!("{}{}", self.user_config.prompt.value, line));
+            let cmd = match parse_command(line, self.user_config.index_base(), self.board.rules) {
+                Ok(cmd) => cmd,
+                Err(e) => {
+                    self.renderer.error(&e);
+                    stopped = Some((Outcome::ParseError { line: n + 1 }, line));
+                    break;
+                }
+            };
+            let moves_before = self.moves;
+            if self.execute(line) {
+                break;
+            }
+            if cmd.is_move() && self.moves == moves_before {
+                stopped = Some((Outcome::IllegalMove { line: n + 1 }, line));
+                break;
+            }
+        }
+        self.render_header();
+        self.renderer.render(&self.board);
+        match stopped {
+            Some((outcome @ Outcome::ParseError { line: n }, line)) => {
+                self.renderer.error(&tr!("Parse error at line {}: {}", n, line));
+                outcome
+            }
+            Some((outcome @ Outcome::IllegalMove { line: n }, line)) => {
+                self.renderer.error(&tr!("Illegal move at line {}: {}", n, line));
+                outcome
+            }
+            _ if self.board.is_won() => {
+                self.renderer.info(&tr!("Result: won in {} moves.", self.moves));
+                Outcome::Won
+            }
+            _ => {
+                self.renderer.info(&tr!("Result: not won after {} moves.", self.moves));
+                Outcome::NotWon
+            }
+        }
+    }
+
+    /// Auto-move any immediately playable cards on deal and draw the board;
+    /// [`Game::run`] does this before its first prompt.
+    pub fn start(&mut self) {
+        self.renderer.set_render_mode(self.user_config.render.value);
+        let (n, events) = self.auto_move();
+        self.report_auto_moves(n, &events);
+        self.push_events(events);
+
+        self.render_header();
+        self.renderer.render(&self.board);
+    }
+
+    /// Say what the auto-moves after a command did: nothing in quiet mode,
+    /// the count normally, and each card as well in verbose mode.
+    fn report_auto_moves(&mut self, n: usize, events: &[GameEvent]) {
+        let mode = self.user_config.render.value;
+        if n == 0 || mode == RenderMode::Quiet {
+            return;
+        }
+        self.renderer.info(&tr!("Auto-moved {} card(s) to foundation.", n));
+        if mode == RenderMode::Verbose {
+            for msg in events.iter().filter_map(describe_event) {
+                self.renderer.info(&msg);
+            }
+        }
+    }
+
+    /// Pick up results from the background solvers and log the position;
+    /// done before each prompt.
+    pub fn poll_background(&mut self) {
+        self.poll_winnability_check();
+        self.submit_winnability_check();
+        self.poll_par();
+        self.poll_rating_job();
+        self.log_position();
+    }
+
+    /// Run one line as typed at the prompt, followed by the auto-moves,
+    /// warnings, autosave and redraw that come after every command.
+    /// Returns `true` when the player quit.
+    pub fn execute(&mut self, line: &str) -> bool {
+        if self.execute_one(line) {
+            return true;
+        }
+        while let Some(line) = self.next_macro_command() {
+            if self.execute_one(&line) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn execute_one(&mut self, line: &str) -> bool {
+        self.renderer.begin_command();
+        match parse_command(line, self.user_config.index_base(), self.board.rules) {
+            Err(e) => {
+                log::info!("{:?} not parsed: {}", line.trim(), e);
+                self.renderer.error(&e);
+            }
+            Ok(cmd) => {
+                log::debug!("{:?} parsed as {:?}", line.trim(), cmd);
+                self.record_macro_command(line, &cmd);
+                self.telemetry.record_command(&cmd);
+                let moves_before = self.moves;
+                if self.handle(cmd) {
+                    return true;
+                }
+
+                // Auto-move after every successful command.
+                let (n, events) = self.auto_move();
+                self.report_auto_moves(n, &events);
+                self.push_events(events);
+                if self.moves > moves_before {
+                    self.warn_repeat();
+                }
 
 
-                    // Save progress to disk for resuming
-                    if let Some(last) = self.save_data.records.last_mut() {
-                        last.current_board = Some(self.board.clone());
-                        last.undo_history = self.history.clone();
+                if self.board.is_trivially_winnable() {
+                    self.renderer.info(&tr!("Every remaining card can go home. Type 'finish' to play it out."));
+                } else if self.assists().dead_end_warnings && self.board.is_stuck() {
+                    self.renderer.error(&tr!("No legal moves remain. Type 'undo' to back up, 'restart' to redeal, or 'new' for another deal."));
+                    if self.assists().hints {
+                        self.renderer.info(&tr!("Type 'show solution' to see a winning line."));
                     }
-                    self.save_data.save();
+                }
 
-                    if self.board.is_won() {
-                        self.record_win();
-                        self.renderer.win();
-                        // Handle post-win input (like typing "new" to deal another hand)
-                        self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
-                        self.renderer.render(&self.board);
-                        continue;
+                self.track_dragon_merges();
+                self.track_foundations();
+                self.check_splits();
+                self.trace_ghost();
+                self.check_puzzle();
+
+                // Save progress to disk for resuming
+                self.autosave();
+
+                if self.board.is_won() {
+                    let summary = self.record_win();
+                    self.renderer.win();
+                    if let Some(summary) = summary {
+                        self.renderer.win_summary(&summary);
                     }
+                    // The summary stands in for the empty board; post-win
+                    // input (like "new") is still handled.
+                    return false;
+                }
 
-                    self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
-                    self.renderer.render(&self.board);
+                // The prompt can't redraw the header when the verdict comes
+                // in later, so give the solver a moment now.
+                if self.user_config.winnability.value {
+                    self.submit_winnability_check();
+                    if let Some(verdict) = self.checker.as_mut().and_then(|c| c.wait(OUTLOOK_WAIT)) {
+                        self.note_verdict(verdict);
+                    }
+                }
+                self.render_header();
+                self.renderer.render(&self.board);
+                if self.user_config.render.value == RenderMode::Verbose {
+                    self.renderer.info(&crate::renderer::describe_foundations(&self.board));
                 }
             }
         }
+        false
+    }
+
+    /// Add `line` to the macro being recorded.  Macro commands themselves
+    /// and `quit` are left out.
+    fn record_macro_command(&mut self, line: &str, cmd: &Command) {
+        let Some((_, commands)) = &mut self.recording else {
+            return;
+        };
+        if !matches!(
+            cmd,
+            Command::MacroRecord { .. } | Command::MacroStop | Command::MacroPlay { .. } | Command::MacroList | Command::Quit
+        ) {
+            commands.push(line.trim().to_string());
+        }
+    }
+
+    /// The next command of the macro being played, echoed as it runs.
+    /// Playback ends early once the game is won.
+    fn next_macro_command(&mut self) -> Option<String> {
+        if self.board.is_won() {
+            self.macro_queue.clear();
+        }
+        let line = self.macro_queue.pop_front()?;
+        self.renderer.info(&tr!("macro> {}", line));
+        Some(line)
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Moves made in the current game.
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// The solver's move count for the current deal, once known.
+    pub fn par(&self) -> Option<usize> {
+        self.par
     }
 
     /// TUI tick-driven loop with direct keybinding → SelectionState dispatch.
@@ -199,19 +1001,21 @@ impl<R: Renderer> Game<R> {
         R: crate::tui_renderer::TuiRendererExt,
     {
         // Initial auto-move + render
-        self.renderer.info("Press ? for help.");
+        self.renderer.info(&tr!("Press ? for help."));
         self.renderer.set_anim_speed(self.app_config.anim_speed);
+        self.renderer.set_vim_keys(self.user_config.vim_keys.value);
+        self.renderer.set_keymap(self.user_config.keys.clone());
         if self.resumed_on_start {
-            self.renderer.push_events(vec![GameEvent::RestoreDealt { board: self.board.clone() }]);
+            self.push_events(vec![GameEvent::RestoreDealt { board: self.board.clone() }]);
         } else {
-            self.renderer.push_events(vec![GameEvent::Dealt { seed: self.board.seed }]);
+            self.push_events(vec![GameEvent::Dealt { seed: self.board.seed, rules: self.board.rules }]);
         }
-        let (n, events) = self.board.auto_move();
-        self.renderer.push_events(events);
+        let (n, events) = self.auto_move();
+        self.push_events(events);
         if n > 0 {
-            self.renderer.info(&format!("Auto-moved {} card(s) to foundation.", n));
+            self.renderer.info(&tr!("Auto-moved {} card(s) to foundation.", n));
         }
-        self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
+        self.render_header();
         self.renderer.render(&self.board);
 
         loop {
@@ -235,19 +1039,27 @@ impl<R: Renderer> Game<R> {
                             }
                         }
                     }
-                    Ok(Event::Mouse(me)) => {
-                        if !self.renderer.is_animating() {
-                            self.handle_tui_mouse(me);
-                        }
+                    Ok(Event::Mouse(me)) if !self.renderer.is_animating() => {
+                        self.handle_tui_mouse(me);
                     }
                     _ => {}
                 }
             }
 
-            if self.should_quit { break; }
+            if self.should_quit {
+                self.save_progress();
+                break;
+            }
 
+            if !self.renderer.is_animating() {
+                self.submit_winnability_check();
+            }
+            self.poll_winnability_check();
+            self.poll_par();
+            self.poll_rating_job();
+            self.log_position();
             self.renderer.tick();
-            self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
+            self.render_header();
             self.renderer.render(&self.board);
         }
     }
@@ -258,9 +1070,6 @@ impl<R: Renderer> Game<R> {
         R: crate::tui_renderer::TuiRendererExt,
     {
         use crossterm::event::{KeyCode, KeyModifiers};
-        use crate::tui_renderer::{SelectionState, COL_KEYS, FC_KEYS};
-        use crate::board::Location;
-        use crate::card::Suit;
 
         // Ctrl-C / Ctrl-D = hard quit
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -270,75 +1079,268 @@ impl<R: Renderer> Game<R> {
             }
         }
 
-        let c = match key.code {
-            KeyCode::Char(c) => c,
-            KeyCode::Enter => {
-                // Move selected card/stack to foundation
-                self.tui_move_to_foundation();
-                return;
+        if self.renderer.dismiss_summary() {
+            return;
+        }
+        if self.renderer.stats_open() {
+            match key.code {
+                KeyCode::BackTab => self.renderer.turn_stats_page(false),
+                code if pressed(code, self.user_config.keys.stats) => self.renderer.turn_stats_page(true),
+                _ => self.renderer.close_stats(),
             }
-            KeyCode::Esc => {
-                self.renderer.set_selection(SelectionState::Idle);
-                return;
+            return;
+        }
+        if self.renderer.campaign_open() {
+            let columns = crate::campaign::MAP_COLUMNS as isize;
+            match key.code {
+                KeyCode::Left => self.renderer.move_campaign_pick(-1),
+                KeyCode::Right => self.renderer.move_campaign_pick(1),
+                KeyCode::Up => self.renderer.move_campaign_pick(-columns),
+                KeyCode::Down => self.renderer.move_campaign_pick(columns),
+                KeyCode::Enter => {
+                    let stage = self.renderer.campaign_pick();
+                    self.renderer.close_campaign();
+                    if let Some(stage) = stage {
+                        self.tui_command(&format!("campaign {}", stage));
+                    }
+                }
+                _ => self.renderer.close_campaign(),
             }
-            _ => return,
-        };
+            return;
+        }
 
-        let sel = self.renderer.get_selection().clone();
+        if self.user_config.vim_keys.value && self.handle_vim_key(key.code) {
+            return;
+        }
+        self.handle_tui_binding(key.code);
+    }
 
-        match &sel {
-            // ── Idle: interpret key as source selection ──────────────────
-            SelectionState::Idle => {
-                if c == 'd' || c == 'D' {
-                    self.renderer.set_selection(SelectionState::WaitDragonSuit);
-                } else if c == 'z' || c == 'Z' {
-                    // Undo
+    /// The vim-style keys (`vim_keys`): h/j/k/l move a cursor across the
+    /// piles, Space presses the pile's own key (pick up, grow or drop),
+    /// `u` undoes and `:` opens a command line for the text commands.
+    /// Returns `false` for keys left to the usual bindings.
+    fn handle_vim_key(&mut self, code: crossterm::event::KeyCode) -> bool
+    where
+        R: crate::tui_renderer::TuiRendererExt,
+    {
+        use crossterm::event::KeyCode;
+        use crate::board::Location;
+
+        let keys = self.user_config.keys.clone();
+        if let Some(text) = self.renderer.command_line() {
+            let mut text = text.to_string();
+            match code {
+                KeyCode::Esc => self.renderer.set_command_line(None),
+                KeyCode::Enter => {
+                    self.renderer.set_command_line(None);
+                    self.tui_command(&text);
+                }
+                KeyCode::Backspace if text.is_empty() => self.renderer.set_command_line(None),
+                KeyCode::Backspace => {
+                    text.pop();
+                    self.renderer.set_command_line(Some(text));
+                }
+                KeyCode::Char(c) => {
+                    text.push(c);
+                    self.renderer.set_command_line(Some(text));
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // The top row holds the free cells and foundations, the bottom row
+        // the columns.
+        let rules = self.board.rules;
+        let top: Vec<Location> = (0..rules.free_cells)
+            .map(Location::FreeCell)
+            .chain(rules.suits().iter().map(|&suit| Location::Foundation(suit)))
+            .collect();
+        let bottom: Vec<Location> = (0..rules.columns).map(Location::Column).collect();
+        let cursor = self.renderer.cursor().unwrap_or(Location::Column(0));
+        let (row, i) = match top.iter().position(|&l| l == cursor) {
+            Some(i) => (&top, i),
+            None => (&bottom, bottom.iter().position(|&l| l == cursor).unwrap_or(0)),
+        };
+        let moved = match code {
+            _ if code == keys.left => row[i.saturating_sub(1)],
+            _ if code == keys.right => row[(i + 1).min(row.len() - 1)],
+            _ if code == keys.up => top[i.min(top.len() - 1)],
+            _ if code == keys.down => bottom[i.min(bottom.len() - 1)],
+            _ if code == keys.pick => {
+                let key = match cursor {
+                    Location::Column(c) => KeyCode::Char(keys.columns[c]),
+                    Location::FreeCell(i) => KeyCode::Char(keys.cells[i]),
+                    Location::Foundation(_) | Location::Flower => keys.foundation,
+                };
+                self.renderer.set_cursor(Some(cursor));
+                self.handle_tui_binding(key);
+                return true;
+            }
+            _ if code == keys.vim_undo => {
+                self.handle_tui_binding(keys.undo);
+                return true;
+            }
+            _ if code == keys.command => {
+                self.renderer.set_command_line(Some(String::new()));
+                return true;
+            }
+            _ => return false,
+        };
+        self.renderer.set_cursor(Some(moved));
+        true
+    }
+
+    /// Run a line typed on the vim command line as if at the CLI prompt.
+    fn tui_command(&mut self, line: &str)
+    where
+        R: crate::tui_renderer::TuiRendererExt,
+    {
+        use crate::tui_renderer::SelectionState;
+
+        self.renderer.set_selection(SelectionState::Idle);
+        self.tui_command_one(line);
+        while !self.should_quit && let Some(line) = self.next_macro_command() {
+            self.tui_command_one(&line);
+        }
+    }
+
+    fn tui_command_one(&mut self, line: &str)
+    where
+        R: crate::tui_renderer::TuiRendererExt,
+    {
+        let cmd = match parse_command(line, self.user_config.index_base(), self.board.rules) {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                log::info!("{:?} not parsed: {}", line.trim(), e);
+                self.renderer.error(&e);
+                return;
+            }
+        };
+        log::debug!("{:?} parsed as {:?}", line.trim(), cmd);
+        self.record_macro_command(line, &cmd);
+        self.telemetry.record_command(&cmd);
+        let moves_before = self.moves;
+        if self.handle(cmd) {
+            self.should_quit = true;
+        } else if self.moves > moves_before {
+            self.tui_settle();
+        } else {
+            // Undo, new deals and the like replace the board outright.
+            self.renderer.sync_board(&self.board);
+        }
+    }
+
+    /// The standard TUI key bindings.
+    fn handle_tui_binding(&mut self, code: crossterm::event::KeyCode)
+    where
+        R: crate::tui_renderer::TuiRendererExt,
+    {
+        use crossterm::event::KeyCode;
+        use crate::tui_renderer::SelectionState;
+        use crate::board::Location;
+        use crate::card::Suit;
+
+        let keys = self.user_config.keys.clone();
+        let is = |key: KeyCode| pressed(code, key);
+        // Only the keys for this table's columns and free cells.
+        let col_keys = &keys.columns[..self.board.rules.columns];
+        let fc_keys = &keys.cells[..self.board.rules.free_cells];
+
+        if is(keys.foundation) {
+            // Move selected card/stack to foundation
+            self.tui_move_to_foundation();
+            return;
+        }
+        if is(keys.cancel) {
+            self.renderer.set_selection(SelectionState::Idle);
+            return;
+        }
+        // Named keys only match the bindings compared with `is`.
+        let c = match code {
+            KeyCode::Char(c) => c,
+            _ => '\0',
+        };
+
+        if self.hardcore() && (is(keys.undo) || is(keys.hint)) {
+            self.renderer.error(&tr!("Undo and hints are disabled in hardcore mode."));
+            self.renderer.set_selection(SelectionState::Idle);
+            return;
+        }
+        if !self.assists().hints && is(keys.hint) {
+            self.renderer.error(&tr!("Hints are off under the '{}' assist preset.", self.assist_preset().name()));
+            self.renderer.set_selection(SelectionState::Idle);
+            return;
+        }
+
+        let sel = self.renderer.get_selection().clone();
+
+        match &sel {
+            // ── Idle: interpret key as source selection ──────────────────
+            SelectionState::Idle => {
+                if is(keys.dragon) {
+                    self.renderer.set_selection(SelectionState::WaitDragonSuit);
+                } else if is(keys.undo) {
+                    // Undo
+                    self.remember_line();
                     if let Some(prev) = self.history.pop() {
+                        self.undos += 1;
+                        self.notify(PlayEvent::UndoPerformed { steps: 1 });
                         self.board = prev;
                         self.renderer.sync_board(&self.board);
                         self.renderer.clear_status_log();
-                        self.renderer.info("Undo.");
+                        self.renderer.info(&tr!("Undo."));
                     } else {
-                        self.renderer.error("Nothing to undo.");
+                        self.renderer.error(&tr!("Nothing to undo."));
                     }
-                } else if c == 'n' || c == 'N' {
+                } else if is(keys.new_game) {
                     self.tui_new_game();
-                } else if c == 's' || c == 'S' {
+                } else if is(keys.speed) {
                     self.renderer.toggle_anim_speed();
                     self.app_config.anim_speed = self.renderer.anim_speed();
                     self.app_config.save();
-                } else if c == '?' {
+                } else if is(keys.help) {
                     self.renderer.toggle_help();
-                } else if c == 'h' || c == 'H' {
+                } else if is(keys.stats) {
+                    self.renderer.show_stats(crate::dashboard::Dashboard::new(&self.save_data));
+                } else if is(keys.campaign) {
+                    self.renderer.show_campaign(&crate::campaign::CampaignMap::new(&self.save_data));
+                } else if is(keys.hint) {
                     // Hint: run solver or toggle hint off
                     if self.renderer.is_hint_active() {
                         self.renderer.clear_hint();
-                        self.renderer.info("Hint mode deactivated.");
+                        self.renderer.info(&tr!("Hint mode deactivated."));
                     } else {
+                        if !self.take_hint() {
+                            return;
+                        }
                         // Show overlay, redraw, block on solve, hide overlay
                         self.renderer.show_solving();
-                        self.renderer.render_header(self.save_data.total_wins(), self.board.seed);
+                        self.render_header();
                         self.renderer.render(&self.board);
                         let board_snapshot = self.board.clone();
                         let wins = self.save_data.total_wins();
                         let seed = self.board.seed;
-                        let result = crate::solver::solve(&board_snapshot, |progress| {
-                            if ct_event::poll(Duration::from_millis(0)).unwrap_or(false) {
-                                if let Ok(Event::Key(key)) = ct_event::read() {
-                                    use crossterm::event::{KeyCode, KeyModifiers};
-                                    if key.modifiers.contains(KeyModifiers::CONTROL) {
-                                        match key.code {
-                                            KeyCode::Char('c') | KeyCode::Char('d') => {
-                                                self.should_quit = true;
-                                                return false;
-                                            }
-                                            _ => {}
+                        let phrase = self.seed_phrase().map(str::to_string);
+                        let (moves, par, elapsed) = (self.moves, self.par, self.elapsed());
+                        let (strategy, budget) = (self.user_config.solver.value, self.user_config.solver_budget());
+                        let result = crate::solver::solve_with(&board_snapshot, strategy, budget, |progress| {
+                            if ct_event::poll(Duration::from_millis(0)).unwrap_or(false)
+                                && let Ok(Event::Key(key)) = ct_event::read()
+                            {
+                                use crossterm::event::{KeyCode, KeyModifiers};
+                                if key.modifiers.contains(KeyModifiers::CONTROL) {
+                                    match key.code {
+                                        KeyCode::Char('c') | KeyCode::Char('d') => {
+                                            self.should_quit = true;
+                                            return false;
                                         }
+                                        _ => {}
                                     }
                                 }
                             }
                             self.renderer.update_solving_progress(progress);
-                            self.renderer.render_header(wins, seed);
+                            self.renderer.render_header(wins, seed, phrase.as_deref(), moves, par, elapsed);
                             self.renderer.render(&board_snapshot);
                             true
                         });
@@ -349,24 +1351,24 @@ impl<R: Renderer> Game<R> {
                         match result {
                             None => {
                                 self.renderer.show_solver_failed();
-                                self.renderer.error("No solution found for current board.");
+                                self.renderer.error(&tr!("No solution found for current board."));
                             }
                             Some(solution) => {
                                 let path: Vec<_> = solution.iter().map(|step| step.next_move).collect();
                                 let n = path.len();
                                 self.renderer.set_hint_steps(path);
-                                self.renderer.info(&format!("Hint active: {} step(s). Green = next card. H to exit.", n));
+                                self.renderer.info(&tr!("Hint active: {} step(s). Green = next card. H to exit.", n));
                             }
                         }
                     }
-                } else if let Some(col) = COL_KEYS.iter().position(|&k| k == c) {
+                } else if let Some(col) = col_keys.iter().position(|&k| k == c) {
                     if !self.board.columns[col].is_empty() {
                         self.renderer.set_selection(SelectionState::Column { col, depth: 1 });
                     }
-                } else if let Some(fc) = FC_KEYS.iter().position(|&k| k == c) {
-                    if self.board.free_cells[fc].card().is_some() {
-                        self.renderer.set_selection(SelectionState::FreeCell { idx: fc });
-                    }
+                } else if let Some(fc) = fc_keys.iter().position(|&k| k == c)
+                    && self.board.free_cells[fc].card().is_some()
+                {
+                    self.renderer.set_selection(SelectionState::FreeCell { idx: fc });
                 }
             }
 
@@ -376,17 +1378,19 @@ impl<R: Renderer> Game<R> {
                     'r' | 'R' => Some(Suit::Red),
                     'g' | 'G' => Some(Suit::Green),
                     'b' | 'B' => Some(Suit::Black),
+                    'y' | 'Y' => Some(Suit::Yellow),
                     _ => None,
-                };
+                }
+                .filter(|suit| self.board.rules.suits().contains(suit));
                 if let Some(suit) = suit {
                     self.save_history();
                     match self.board.merge_dragons(suit) {
                         Ok(events) => {
-                            self.renderer.push_events(events);
+                            self.push_events(events);
                             self.tui_post_move();
                         }
                         Err(e) => {
-                            self.renderer.error(e);
+                            self.move_error(&e);
                             self.history.pop();
                         }
                     }
@@ -400,11 +1404,11 @@ impl<R: Renderer> Game<R> {
                 let depth = *depth;
 
                 // Same column key again → try to extend selection upward
-                if COL_KEYS.get(col) == Some(&c) {
+                if col_keys.get(col) == Some(&c) {
                     let col_len = self.board.columns[col].len();
                     let next_start = col_len.saturating_sub(depth + 1);
                     let max_stack = self.board.stack_len(col, next_start);
-                    if max_stack >= depth + 1 {
+                    if max_stack > depth {
                         // Can extend one more card
                         self.renderer.set_selection(SelectionState::Column { col, depth: depth + 1 });
                     } else {
@@ -415,17 +1419,17 @@ impl<R: Renderer> Game<R> {
                 }
 
                 // Target is another column
-                if let Some(dst_col) = COL_KEYS.iter().position(|&k| k == c) {
+                if let Some(dst_col) = col_keys.iter().position(|&k| k == c) {
                     let col_len = self.board.columns[col].len();
                     let start_idx = col_len.saturating_sub(depth);
                     self.save_history();
                     match self.board.move_stack(col, start_idx, dst_col) {
                         Ok(events) => {
-                            self.renderer.push_events(events);
+                            self.push_events(events);
                             self.tui_post_move();
                         }
                         Err(e) => {
-                            self.renderer.error(e);
+                            self.move_error(&e);
                             self.history.pop();
                         }
                     }
@@ -434,39 +1438,42 @@ impl<R: Renderer> Game<R> {
                 }
 
                 // Target is a free cell (only depth==1 allowed)
-                if let Some(dst_fc) = FC_KEYS.iter().position(|&k| k == c) {
+                if let Some(dst_fc) = fc_keys.iter().position(|&k| k == c) {
                     if depth == 1 {
                         let src = Location::Column(col);
                         let dst = Location::FreeCell(dst_fc);
                         self.save_history();
                         match self.board.move_card(src, dst) {
                             Ok(events) => {
-                                self.renderer.push_events(events);
+                                self.push_events(events);
                                 self.tui_post_move();
                             }
                             Err(e) => {
-                                self.renderer.error(e);
+                                self.move_error(&e);
                                 self.history.pop();
                             }
                         }
                     } else {
-                        self.renderer.error("Only single cards can be moved to a free cell.");
+                        self.renderer.error(&tr!("Only single cards can be moved to a free cell."));
                     }
                     self.renderer.set_selection(SelectionState::Idle);
                     return;
                 }
 
                 // 'n' / 'z' etc. still work even when something is selected
-                if c == 'z' || c == 'Z' {
+                if is(keys.undo) {
+                    self.remember_line();
                     if let Some(prev) = self.history.pop() {
+                        self.undos += 1;
+                        self.notify(PlayEvent::UndoPerformed { steps: 1 });
                         self.board = prev;
                         self.renderer.sync_board(&self.board);
                         self.renderer.clear_status_log();
-                        self.renderer.info("Undo.");
+                        self.renderer.info(&tr!("Undo."));
                         // Deviation from hint on undo
                         if self.renderer.is_hint_active() {
                             self.renderer.clear_hint();
-                            self.renderer.info("Hint mode exited after undo.");
+                            self.renderer.info(&tr!("Hint mode exited after undo."));
                         }
                     }
                     self.renderer.set_selection(SelectionState::Idle);
@@ -478,17 +1485,17 @@ impl<R: Renderer> Game<R> {
                 let idx = *idx;
 
                 // Target column
-                if let Some(dst_col) = COL_KEYS.iter().position(|&k| k == c) {
+                if let Some(dst_col) = col_keys.iter().position(|&k| k == c) {
                     let src = Location::FreeCell(idx);
                     let dst = Location::Column(dst_col);
                     self.save_history();
                     match self.board.move_card(src, dst) {
                         Ok(events) => {
-                            self.renderer.push_events(events);
+                            self.push_events(events);
                             self.tui_post_move();
                         }
                         Err(e) => {
-                            self.renderer.error(e);
+                            self.move_error(&e);
                             self.history.pop();
                         }
                     }
@@ -497,22 +1504,25 @@ impl<R: Renderer> Game<R> {
                 }
 
                 // Same FC key = deselect
-                if FC_KEYS.get(idx) == Some(&c) {
+                if fc_keys.get(idx) == Some(&c) {
                     self.renderer.set_selection(SelectionState::Idle);
                     return;
                 }
 
                 // z = undo
-                if c == 'z' || c == 'Z' {
+                if is(keys.undo) {
+                    self.remember_line();
                     if let Some(prev) = self.history.pop() {
+                        self.undos += 1;
+                        self.notify(PlayEvent::UndoPerformed { steps: 1 });
                         self.board = prev;
                         self.renderer.sync_board(&self.board);
                         self.renderer.clear_status_log();
-                        self.renderer.info("Undo.");
+                        self.renderer.info(&tr!("Undo."));
                         // Deviation from hint on undo
                         if self.renderer.is_hint_active() {
                             self.renderer.clear_hint();
-                            self.renderer.info("Hint mode exited after undo.");
+                            self.renderer.info(&tr!("Hint mode exited after undo."));
                         }
                     }
                     self.renderer.set_selection(SelectionState::Idle);
@@ -532,6 +1542,17 @@ impl<R: Renderer> Game<R> {
         if me.kind != MouseEventKind::Down(crossterm::event::MouseButton::Left) {
             return;
         }
+        if self.renderer.dismiss_summary() {
+            return;
+        }
+        if self.renderer.stats_open() {
+            self.renderer.close_stats();
+            return;
+        }
+        if self.renderer.campaign_open() {
+            self.renderer.close_campaign();
+            return;
+        }
         // hit-test against the last rendered layout
         if let Some(loc) = self.renderer.hit_test(me.column, me.row) {
             if self.tui_handle_double_click(loc, me.row) {
@@ -561,11 +1582,11 @@ impl<R: Renderer> Game<R> {
                             self.save_history();
                             match self.board.move_stack(src_col, start_idx, dst_col) {
                                 Ok(events) => {
-                                    self.renderer.push_events(events);
+                                    self.push_events(events);
                                     self.tui_post_move();
                                 }
                                 Err(e) => {
-                                    self.renderer.error(e);
+                                    self.move_error(&e);
                                     self.history.pop();
                                 }
                             }
@@ -581,8 +1602,8 @@ impl<R: Renderer> Game<R> {
                             let dst = crate::board::Location::FreeCell(dst_fc);
                             self.save_history();
                             match self.board.move_card(src, dst) {
-                                Ok(events) => { self.renderer.push_events(events); self.tui_post_move(); }
-                                Err(e) => { self.renderer.error(e); self.history.pop(); }
+                                Ok(events) => { self.push_events(events); self.tui_post_move(); }
+                                Err(e) => { self.move_error(&e); self.history.pop(); }
                             }
                             self.renderer.set_selection(SelectionState::Idle);
                         }
@@ -596,8 +1617,8 @@ impl<R: Renderer> Game<R> {
                             let dst = crate::board::Location::Column(dst_col);
                             self.save_history();
                             match self.board.move_card(src, dst) {
-                                Ok(events) => { self.renderer.push_events(events); self.tui_post_move(); }
-                                Err(e) => { self.renderer.error(e); self.history.pop(); }
+                                Ok(events) => { self.push_events(events); self.tui_post_move(); }
+                                Err(e) => { self.move_error(&e); self.history.pop(); }
                             }
                             self.renderer.set_selection(SelectionState::Idle);
                         }
@@ -654,11 +1675,11 @@ impl<R: Renderer> Game<R> {
         self.save_history();
         match self.board.merge_dragons(suit) {
             Ok(events) => {
-                self.renderer.push_events(events);
+                self.push_events(events);
                 self.tui_post_move();
             }
             Err(e) => {
-                self.renderer.error(e);
+                self.move_error(&e);
                 self.history.pop();
             }
         }
@@ -699,7 +1720,18 @@ impl<R: Renderer> Game<R> {
         R: crate::tui_renderer::TuiRendererExt,
     {
         self.renderer.clear_status_log();
+        self.moves += 1;
+        self.notify(PlayEvent::MoveApplied { moves: self.moves });
+        self.report_golf();
+        self.tui_settle();
+    }
 
+    /// Everything after a move is counted: auto-moves, warnings, autosave,
+    /// hint tracking and the win check.
+    fn tui_settle(&mut self)
+    where
+        R: crate::tui_renderer::TuiRendererExt,
+    {
         // Read hint move BEFORE auto_move so we can compare expected vs actual.
         let hint_mv = self.renderer.hint_next_move();
 
@@ -708,16 +1740,24 @@ impl<R: Renderer> Game<R> {
         // of history is the board state the solver's hint move was designed for.
         let pre_move_board = self.history.last().cloned();
 
-        let (n, events) = self.board.auto_move();
-        self.renderer.push_events(events);
+        let (n, events) = self.auto_move();
+        self.push_events(events);
         if n > 0 {
-            self.renderer.info(&format!("Auto-moved {} card(s).", n));
+            self.renderer.info(&tr!("Auto-moved {} card(s).", n));
         }
-        if let Some(last) = self.save_data.records.last_mut() {
-            last.current_board = Some(self.board.clone());
-            last.undo_history = self.history.clone();
+        self.warn_repeat();
+        // Like the original game: once nothing needs rearranging, cascade home.
+        if self.board.is_trivially_winnable() {
+            let events = self.board.play_out();
+            self.push_events(events);
+            self.renderer.info(&tr!("Playing out the remaining cards."));
         }
-        self.save_data.save();
+        self.track_dragon_merges();
+        self.track_foundations();
+        self.check_splits();
+        self.trace_ghost();
+        self.check_puzzle();
+        self.autosave();
 
         // Check hint deviation: simulate expected result and compare with actual board.
         if let (Some(mv), Some(pre)) = (hint_mv, pre_move_board) {
@@ -730,11 +1770,11 @@ impl<R: Renderer> Game<R> {
                 if expected == self.board {
                     let done = self.renderer.advance_hint();
                     if done {
-                        self.renderer.info("Hint path complete.");
+                        self.renderer.info(&tr!("Hint path complete."));
                     }
                 } else {
                     self.renderer.clear_hint();
-                    self.renderer.info("Deviated from hint. Hint mode exited.");
+                    self.renderer.info(&tr!("Deviated from hint. Hint mode exited."));
                 }
             } else {
                 // Hint is stale (board drifted from expected); clear silently.
@@ -743,8 +1783,13 @@ impl<R: Renderer> Game<R> {
         }
 
         if self.board.is_won() {
-            self.record_win();
+            let summary = self.record_win();
             self.renderer.win();
+            if let Some(summary) = summary {
+                self.renderer.win_summary(&summary);
+            }
+        } else if self.assists().dead_end_warnings && self.board.is_stuck() {
+            self.renderer.error(&tr!("No legal moves remain. Z to undo, N for a new game."));
         }
     }
 
@@ -764,11 +1809,11 @@ impl<R: Renderer> Game<R> {
             self.save_history();
             match self.board.move_to_foundation(src) {
                 Ok(events) => {
-                    self.renderer.push_events(events);
+                    self.push_events(events);
                     self.tui_post_move();
                 }
                 Err(e) => {
-                    self.renderer.error(e);
+                    self.move_error(&e);
                     self.history.pop();
                 }
             }
@@ -781,28 +1826,35 @@ impl<R: Renderer> Game<R> {
     where
         R: crate::tui_renderer::TuiRendererExt,
     {
-        self.record_abandon();
-        self.board = Board::deal_random();
+        self.leave_current_game();
+        self.board = Board::deal_random(self.user_config.rules());
         self.history.clear();
+        self.reset_lines();
+        self.bookmarks.clear();
+        self.reset_progress();
         self.renderer.clear_hint();
-        self.renderer.push_events(vec![GameEvent::Dealt { seed: self.board.seed }]);
+        self.push_events(vec![GameEvent::Dealt { seed: self.board.seed, rules: self.board.rules }]);
 
         let initial_board = self.board.clone();
-        let (n, events) = self.board.auto_move();
-        self.renderer.push_events(events);
+        let (n, events) = self.auto_move();
+        self.push_events(events);
 
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default().as_secs() as i64;
-        let mut record = crate::history::GameRecord::new(self.board.seed, now);
+        let mut record = crate::history::GameRecord::new(self.board.seed, self.board.rules, now);
+        record.position_code = practice_code(&initial_board);
         record.initial_board = Some(initial_board);
         record.current_board = Some(self.board.clone());
+        record.hardcore = self.user_config.hardcore.value;
+        record.assist = self.user_config.assist.value;
         self.save_data.records.push(record);
         self.save_data.save();
         self.renderer.clear_status_log();
-        self.renderer.info("New game dealt.");
+        self.renderer.info(&tr!("New game dealt."));
+        self.compute_par();
         if n > 0 {
-            self.renderer.info(&format!("Auto-moved {} card(s) to foundation.", n));
+            self.renderer.info(&tr!("Auto-moved {} card(s) to foundation.", n));
         }
         self.renderer.set_selection(crate::tui_renderer::SelectionState::Idle);
     }
@@ -810,163 +1862,1580 @@ impl<R: Renderer> Game<R> {
 
 
     
-    fn record_abandon(&mut self) {
+    /// Step away from the current game before dealing or resuming another.
+    /// A game with moves in it stays unfinished so `resume` can return to
+    /// it; an untouched deal is abandoned.
+    fn leave_current_game(&mut self) {
+        let unfinished = self.save_data.records.last().is_some_and(|r| r.end_time.is_none());
+        if !unfinished {
+            return;
+        }
+        if self.moves == 0 {
+            self.record_abandon(false);
+            return;
+        }
+        self.stop_clock();
+        self.save_progress();
+        self.renderer.info(&tr!("Seed {} kept as an unfinished game; type 'games' to list them.", self.board.seed));
+    }
+
+    /// Take over the state of the last record after `resume` moved it there.
+    fn load_current_record(&mut self) {
+        let Some(record) = self.save_data.records.last().cloned() else { return };
+        self.board = record.current_board.unwrap_or_else(|| Board::deal(record.seed, record.rules));
+        self.history = record.undo_history;
+        self.reset_lines();
+        self.bookmarks.clear();
+        self.resigned = false;
+        self.moves = record.moves;
+        self.moves_at_save = record.moves;
+        self.played = Duration::from_secs(record.elapsed_secs);
+        self.clock = Some(Instant::now());
+        self.splits = record.splits;
+        self.undos = record.undos;
+        self.hints = record.hints;
+        self.dragon_order = record.dragon_order;
+        self.ghost_trace = (record.moves == 0).then(Vec::new);
+        self.foundation_order = completed_foundations(&self.board);
+        self.compute_par();
+    }
+
+    /// Close the current game as lost.  A deal left untouched was not
+    /// played, unless it was resigned.
+    fn record_abandon(&mut self, resigned: bool) {
+        self.stop_clock();
+        self.store_progress();
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs() as i64;
             
-        if let Some(last) = self.save_data.records.last_mut() {
-            if last.end_time.is_none() {
-                last.end_time = Some(now);
-                last.current_board = None;
-                last.undo_history.clear();
-                self.save_data.save();
+        if let Some(last) = self.save_data.records.last_mut() && last.end_time.is_none() {
+            last.end_time = Some(now);
+            last.current_board = None;
+            last.undo_history.clear();
+            let last = last.clone();
+            self.save_data.sync_challenge();
+            self.save_data.sync_puzzle();
+            let played = self.moves > 0 || resigned;
+            if played {
+                self.save_data.sync_campaign();
+                self.save_data.record_weekly(&last);
+                self.rate_game(false);
+            }
+            self.save_data.save();
+            if played {
+                self.telemetry.record_game(false);
+                self.run_finish_hook(false);
             }
         }
     }
 
-    fn record_win(&mut self) {
+    /// Mark the current game won and build its summary.  Returns `None`
+    /// if the win was already recorded.
+    fn record_win(&mut self) -> Option<WinSummary> {
+        self.stop_clock();
+        self.store_progress();
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs() as i64;
-            
-        if let Some(last) = self.save_data.records.last_mut() {
-            if !last.won {
-                last.end_time = Some(now);
-                last.won = true;
-                last.current_board = None;
-                last.undo_history.clear();
-                self.save_data.save();
-            }
+
+        let last = self.save_data.records.last_mut().filter(|r| !r.won)?;
+        last.end_time = Some(now);
+        last.won = true;
+        last.current_board = None;
+        last.undo_history.clear();
+        let last = last.clone();
+        self.save_data.sync_challenge();
+        self.save_data.sync_puzzle();
+        let cleared = self.save_data.campaign_cleared();
+        self.save_data.sync_campaign();
+        if self.save_data.campaign_cleared() > cleared {
+            self.announce_stage_cleared();
+        }
+        let seed_records = self.save_data.record_seed_best(&last);
+        if seed_records.time && let Some(points) = self.ghost_trace.take() {
+            self.save_data.set_ghost(Ghost { seed: last.seed, rules: last.rules, elapsed_secs: last.elapsed_secs, moves: last.moves, points });
+        }
+        self.save_data.record_weekly(&last);
+        self.rate_game(true);
+        self.save_data.save();
+        if last.hardcore {
+            let count = self.save_data.hardcore_wins();
+            self.renderer.info(&tr!("Hardcore win! {} hardcore win(s) so far.", count));
+        }
+        self.telemetry.record_game(true);
+        self.run_finish_hook(true);
+        if self.user_config.bell.value.wins {
+            self.renderer.bell();
         }
+        self.notify(PlayEvent::GameWon { seed: last.seed, moves: last.moves });
+        // A daily deal's seed is the day it was dealt for.
+        let share = (last.is_dealt() && last.seed == (last.start_time / 86_400) as u64).then(|| {
+            crate::daily_share::text(&daily_date(last.seed), &self.foundation_order, last.moves, self.played, last.hints)
+        });
+        if share.is_some() {
+            self.daily_share = share.clone();
+        }
+        Some(WinSummary {
+            seed: last.seed,
+            elapsed: self.played,
+            moves: last.moves,
+            par: self.par,
+            undos: last.undos,
+            hints: last.hints,
+            dragon_order: last.dragon_order.clone(),
+            score: last.score(),
+            assist: last.assist,
+            hardcore: last.hardcore,
+            bests: self.save_data.latest_bests(),
+            seed_records,
+            share,
+        })
     }
 
     /// Dispatch a command.  Returns `true` if the game should exit.
     fn handle(&mut self, cmd: Command) -> bool {
+        if self.resigned
+            && !matches!(
+                cmd,
+                Command::Quit
+                    | Command::Help
+                    | Command::NewGame { .. }
+                    | Command::NewWeekly
+                    | Command::Restart
+                    | Command::Describe
+                    | Command::Splits
+                    | Command::History { .. }
+                    | Command::HistoryPlay { .. }
+                    | Command::HistoryWeekly
+                    | Command::Export { .. }
+                    | Command::Import { .. }
+                    | Command::ImportGrid { .. }
+                    | Command::Games
+                    | Command::Resume { .. }
+                    | Command::Share
+                    | Command::ShareDaily
+                    | Command::Copy { .. }
+                    | Command::Load { .. }
+                    | Command::Challenge { .. }
+                    | Command::Challenges
+                    | Command::ReportDifficulty
+                    | Command::ReportRating
+                    | Command::Puzzles { .. }
+                    | Command::Puzzle { .. }
+                    | Command::Campaign { .. }
+                    | Command::Best { .. }
+                    | Command::Submit
+                    | Command::Leaderboard
+                    | Command::Telemetry { .. }
+                    | Command::TelemetrySend
+                    | Command::Par
+                    | Command::ShowSolution
+                    | Command::SetRender { .. }
+                    | Command::Keys
+                    | Command::MacroRecord { .. }
+                    | Command::MacroStop
+                    | Command::MacroList
+            )
+        {
+            self.renderer.error(&tr!("This game was resigned. Type 'new' or 'restart' to play again."));
+            return false;
+        }
+        // A resigned game's solution is no help any more; a dead end's is.
+        if self.hardcore()
+            && (matches!(
+                cmd,
+                Command::Undo { .. } | Command::UndoAll | Command::SwitchLine { .. } | Command::Goto { .. } | Command::Solve
+            ) || (cmd == Command::ShowSolution && !self.resigned))
+        {
+            self.renderer.error(&tr!("Undo and hints are disabled in hardcore mode."));
+            return false;
+        }
+        if cmd == Command::ShowSolution && !self.resigned && !self.board.is_stuck() {
+            self.renderer.error(&tr!("The solution is shown once you resign or reach a dead end."));
+            return false;
+        }
+        if (cmd == Command::Solve || (cmd == Command::ShowSolution && !self.resigned)) && !self.assists().hints {
+            self.renderer.error(&tr!("Hints are off under the '{}' assist preset.", self.assist_preset().name()));
+            return false;
+        }
+        if (cmd == Command::Solve || (cmd == Command::ShowSolution && !self.resigned)) && !self.take_hint() {
+            return false;
+        }
+
         match cmd {
             Command::Quit => {
                 // Do not mark as abandoned, so it can be resumed. Just save current state.
-                if let Some(last) = self.save_data.records.last_mut() {
-                    last.current_board = Some(self.board.clone());
-                    last.undo_history = self.history.clone();
-                }
-                self.save_data.save();
+                self.save_progress();
                 
-                self.renderer.info("Thanks for playing. Goodbye!");
+                self.renderer.info(&tr!("Thanks for playing. Goodbye!"));
                 return true;
             }
             Command::Help => {
                 self.renderer.help();
             }
-            Command::NewGame => {
-                self.record_abandon(); // Finish the previous game
-                
-                self.board = Board::deal_random();
+            Command::NewGame { seed, phrase } => {
+                let rules = self.user_config.rules();
+                let board = match seed {
+                    Some(s) => Board::deal(s, rules),
+                    None => Board::deal_random(rules),
+                };
+                self.start_dealt(board, phrase, false);
+            }
+            Command::NewWeekly => {
+                // Everyone plays the week's deal on the original table.
+                let board = Board::deal(crate::weekly::current_seed(), Rules::STANDARD);
+                self.start_dealt(board, None, true);
+            }
+            Command::Finish => {
+                if !self.board.is_trivially_winnable() {
+                    self.renderer.error(&tr!("Not every remaining card can go home yet."));
+                    return false;
+                }
+                self.save_history();
+                let events = self.board.play_out();
+                for event in &events {
+                    if let Some(msg) = describe_event(event) {
+                        self.renderer.info(&msg);
+                    }
+                }
+                self.push_events(events);
+            }
+            Command::Resign => {
+                self.record_abandon(true);
+                self.resigned = true;
+                self.renderer.info(&tr!("Game resigned and recorded as a loss. Streak: {} (best {}). Type 'new' for another deal.",
+                    self.save_data.current_streak(),
+                    self.save_data.best_streak()
+                ));
+                self.renderer.info(&tr!("Type 'show solution' to see a winning line."));
+            }
+            Command::Restart => {
+                // Close this attempt and open a fresh record for the same seed.
+                let phrase = self.seed_phrase().map(str::to_string);
+                let loaded = self.save_data.records.last().and_then(|r| r.position_code.clone());
+                let puzzle = self.save_data.current_puzzle().cloned();
+                let stage = self.save_data.current_campaign_stage();
+                let weekly = self.is_weekly();
+                self.record_abandon(false);
+                self.resigned = false;
+
+                // A loaded position restarts from where it was loaded, and
+                // a puzzle with a fresh attempt.
+                if let Some(board) = loaded.and_then(|code| crate::share::decode(&code).ok()) {
+                    self.start_loaded(board);
+                    match puzzle {
+                        Some(p) => {
+                            self.open_puzzle(p.pack, p.name, p.goal, p.moves_allowed);
+                            self.renderer.info(&tr!("Restarted the puzzle."));
+                            self.renderer.info(&crate::puzzle::task(p.goal, p.moves_allowed));
+                        }
+                        None => self.renderer.info(&tr!("Restarted the shared position.")),
+                    }
+                    self.compute_par();
+                    return false;
+                }
+                self.board = Board::deal(self.board.seed, self.board.rules);
                 self.history.clear();
-                
+                self.reset_lines();
+                self.reset_progress();
+
                 let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
                     .as_secs() as i64;
-                self.save_data.records.push(GameRecord::new(self.board.seed, now));
+                let mut record = GameRecord::new(self.board.seed, self.board.rules, now);
+                record.initial_board = Some(self.board.clone());
+                record.position_code = practice_code(&self.board);
+                record.seed_phrase = phrase;
+                record.hardcore = self.user_config.hardcore.value;
+                record.assist = self.user_config.assist.value;
+                record.weekly = weekly;
+                self.save_data.records.push(record);
                 self.save_data.save();
-                
-                self.renderer.info("A new game has been dealt.");
+                if let Some(stage) = stage {
+                    self.open_campaign_attempt(stage);
+                }
+
+                let attempt = self
+                    .save_data
+                    .records
+                    .iter()
+                    .filter(|r| r.seed == self.board.seed)
+                    .count();
+                self.renderer.info(&tr!("Restarted seed {} (attempt {}).", self.board.seed, attempt));
+                self.compute_par();
             }
-            Command::Undo => {
-                if let Some(prev) = self.history.pop() {
-                    self.board = prev;
-                    self.renderer.info("Undo successful.");
+            Command::Undo { steps } => {
+                self.remember_line();
+                let n = steps.min(self.history.len());
+                if n == 0 {
+                    self.renderer.error(&tr!("Nothing to undo."));
                 } else {
-                    self.renderer.error("Nothing to undo.");
+                    let keep = self.history.len() - n;
+                    self.undos += n as u32;
+                    self.notify(PlayEvent::UndoPerformed { steps: n });
+                    self.board = self.history[keep].clone();
+                    self.history.truncate(keep);
+                    if n < steps {
+                        self.renderer.info(&tr!("Rewound {} step(s) (all available history).", n));
+                    } else {
+                        self.renderer.info(&tr!("Rewound {} step(s).", n));
+                    }
                 }
             }
-            Command::Solve => {
-                self.renderer.info("Running A* solver... (may take a moment)");
-
-                if let Some(path) = crate::solver::solve(&self.board, |progress| {
-                    self.renderer.info(&progress.message());
-                    true
-                }) {
-                    let path: Vec<_> = path.iter().map(|step| step.next_move).collect();
-                    self.renderer.info(&format!("Found a solution in {} steps!", path.len()));
-                    for (i, m) in path.iter().enumerate() {
-                        self.renderer.info(&format!("{:4}. {}", i + 1, m.to_command_str()));
+            Command::UndoAll => {
+                let initial = self
+                    .save_data
+                    .records
+                    .last()
+                    .filter(|r| r.seed == self.board.seed)
+                    .and_then(|r| r.initial_board.clone())
+                    .or_else(|| self.history.first().cloned());
+                match initial {
+                    Some(initial) if initial != self.board => {
+                        self.remember_line();
+                        let n = self.history.len();
+                        self.undos += 1;
+                        self.notify(PlayEvent::UndoPerformed { steps: n });
+                        self.board = initial;
+                        self.history.clear();
+                        self.renderer.info(&tr!("Returned to the initial deal ({} step(s) rewound).", n));
                     }
+                    _ => self.renderer.error(&tr!("Nothing to undo.")),
+                }
+            }
+            Command::Lines => {
+                if self.lines.is_empty() {
+                    self.renderer.info(&tr!("No alternative lines. Undo and play differently to create one."));
                 } else {
-                    self.renderer.error("No solution found by BFS.");
+                    self.renderer.info(&tr!("Current: {}", describe_line(&self.history, &self.board)));
+                    for (i, line) in self.lines.iter().enumerate() {
+                        self.renderer.info(&format!("{:4}. {}", i, describe_line(&line.history, &line.board)));
+                    }
                 }
             }
-            Command::ColumnToColumn { src, stack_start, dst } => {
-                self.save_history();
-                let col_len = self.board.columns[src].len();
-                // stack_start is depth from top; convert to absolute index.
-                let abs_idx = if col_len == 0 {
-                    self.renderer.error("Source column is empty.");
-                    self.history.pop();
+            Command::SwitchLine { index } => {
+                if index >= self.lines.len() {
+                    self.renderer.error(&tr!("No line {}. Type 'lines' to list them.", index));
+                    return false;
+                }
+                // Swap so the line we leave becomes switchable in its place.
+                self.save_pending_line();
+                let mut current = SavedLine {
+                    history: std::mem::take(&mut self.history),
+                    board: self.board.clone(),
+                };
+                std::mem::swap(&mut current, &mut self.lines[index]);
+                self.history = current.history;
+                self.board = current.board;
+                self.renderer.info(&tr!("Switched to line {}.", index));
+            }
+            Command::Mark { name: None } => {
+                if self.bookmarks.is_empty() {
+                    self.renderer.info(&tr!("No bookmarks. Use 'mark <name>' to save this position."));
+                }
+                for (name, line) in &self.bookmarks {
+                    self.renderer.info(&format!("{}: {}", name, describe_line(&line.history, &line.board)));
+                }
+            }
+            Command::Mark { name: Some(name) } => {
+                let line = SavedLine { history: self.history.clone(), board: self.board.clone() };
+                match self.bookmarks.iter_mut().find(|(n, _)| *n == name) {
+                    Some(entry) => entry.1 = line,
+                    None => self.bookmarks.push((name.clone(), line)),
+                }
+                self.renderer.info(&tr!("Bookmarked this position as '{}'.", name));
+            }
+            Command::MacroList => {
+                if self.macros.iter().next().is_none() {
+                    self.renderer.info(&tr!("No macros. Use 'macro record <name>' to record one."));
+                }
+                for (name, commands) in self.macros.iter() {
+                    self.renderer.info(&format!("{}: {}", name, commands.join("; ")));
+                }
+            }
+            Command::MacroRecord { name } => {
+                if let Some((recording, _)) = &self.recording {
+                    self.renderer.error(&tr!("Already recording '{}'. Type 'macro stop' first.", recording));
+                    return false;
+                }
+                self.renderer.info(&tr!("Recording macro '{}'. Type 'macro stop' to finish.", name));
+                self.recording = Some((name, Vec::new()));
+            }
+            Command::MacroStop => match self.recording.take() {
+                None => self.renderer.error(&tr!("No macro is being recorded.")),
+                Some((name, commands)) if commands.is_empty() => {
+                    self.renderer.info(&tr!("Nothing was recorded for '{}'.", name));
+                }
+                Some((name, commands)) => {
+                    self.renderer.info(&tr!("Saved macro '{}' ({} command(s)).", name, commands.len()));
+                    self.macros.set(&name, commands);
+                }
+            },
+            Command::MacroPlay { name } => {
+                let Some(commands) = self.macros.get(&name) else {
+                    self.renderer.error(&tr!("No macro named '{}'. Type 'macro' to list them.", name));
+                    return false;
+                };
+                // Run by the caller, each as if typed.
+                self.macro_queue = commands.iter().cloned().collect();
+            }
+            Command::Goto { name } => {
+                let Some(line) = self.bookmarks.iter().find(|(n, _)| *n == name).map(|(_, l)| l.clone()) else {
+                    self.renderer.error(&tr!("No bookmark named '{}'. Type 'mark' to list them.", name));
                     return false;
+                };
+                // Keep the positions we leave reachable through `lines`:
+                // the tip undone from, if any, and the current one.
+                self.save_pending_line();
+                self.file_line(SavedLine { history: self.history.clone(), board: self.board.clone() });
+                self.history = line.history;
+                self.board = line.board;
+                self.renderer.info(&tr!("Restored bookmark '{}'.", name));
+            }
+            Command::Splits => {
+                if self.splits.is_empty() {
+                    if self.user_config.speedrun.value {
+                        self.renderer.info(&tr!("No splits yet."));
+                    } else {
+                        self.renderer.info(&tr!("Speedrun mode is off. Start with --speedrun or set speedrun = true in config.toml."));
+                    }
+                }
+                for split in &self.splits {
+                    self.renderer.info(&self.describe_split(split));
+                }
+            }
+            Command::History { filter, page } => {
+                const PAGE: usize = 10;
+                let listed = self.save_data.list(filter);
+                let pages = listed.len().div_ceil(PAGE);
+                if listed.is_empty() {
+                    self.renderer.info(&tr!("No games match."));
+                } else if page > pages {
+                    self.renderer.error(&tr!("There are only {} page(s).", pages));
                 } else {
-                    col_len.saturating_sub(1 + stack_start)
+                    let start = (page - 1) * PAGE;
+                    let end = (start + PAGE).min(listed.len());
+                    self.renderer.info(&tr!("Games {}–{} of {}, newest first (times in UTC):", start + 1, end, listed.len()));
+                    for &(id, record) in &listed[start..end] {
+                        let par = self.save_data.par(record.seed, record.rules).filter(|_| record.is_dealt());
+                        self.renderer.info(&history_line(id, record, par));
+                    }
+                    if end < listed.len() {
+                        self.renderer.info(&tr!("{} more; add 'page {}' to see them.", listed.len() - end, page + 1));
+                    }
+                    self.renderer.info(&tr!("Type 'history play <#>' to re-deal a listed game."));
+                }
+                if self.save_data.archive.games > 0 {
+                    let archived = self.save_data.archive.games;
+                    self.renderer.info(&tr!("{} older game(s) are kept only in the totals (history_limit).", archived));
+                }
+            }
+            Command::HistoryWeekly => {
+                let left = crate::renderer::format_countdown(crate::weekly::time_left());
+                self.renderer.info(&tr!("This week's deal is seed {}; the next one comes in {}. Play it with 'new weekly'.",
+                    crate::weekly::current_seed(), left));
+                if self.save_data.weeklies.is_empty() {
+                    self.renderer.info(&tr!("No weekly challenge finished yet."));
+                } else {
+                    self.renderer.info(&tr!("Weekly challenges, newest first (weeks start Monday, UTC):"));
+                    for weekly in self.save_data.weeklies.iter().rev() {
+                        self.renderer.info(&weekly_line(weekly));
+                    }
+                }
+            }
+            Command::HistoryPlay { id } => {
+                let record = id.checked_sub(1).and_then(|i| self.save_data.records.get(i));
+                match record {
+                    Some(record) => {
+                        if let Some(code) = record.position_code.clone() {
+                            return self.handle(Command::Load { code });
+                        }
+                        // The rules it was dealt under, not today's.
+                        let (board, phrase) = (Board::deal(record.seed, record.rules), record.seed_phrase.clone());
+                        self.start_dealt(board, phrase, false);
+                    }
+                    None => self.renderer.error(&tr!("There is no game #{} in the history.", id)),
+                }
+            }
+            // Files on the host are off limits to network clients.
+            Command::Export { .. } | Command::Import { .. } | Command::ImportGrid { .. } | Command::Puzzles { path: Some(_) }
+                if History::is_in_memory() =>
+            {
+                self.renderer.error(&tr!("Files on the host are not available over the network."));
+            }
+            Command::Export { kind, path } => {
+                let text = match kind {
+                    ExportKind::Stats => crate::export::stats_csv(&self.save_data),
+                    ExportKind::History => crate::export::history_json(&self.save_data),
                 };
-
-                match self.board.move_stack(src, abs_idx, dst) {
-                    Ok(_) => {}
-                    Err(e) => {
-                        self.renderer.error(e);
-                        self.history.pop();
+                match std::fs::write(&path, text) {
+                    Ok(()) => {
+                        let count = self.save_data.records.len();
+                        self.renderer.info(&tr!("Exported {} game(s) to {}.", count, path));
                     }
+                    Err(e) => self.renderer.error(&tr!("Could not write {}: {}", path, e)),
                 }
             }
-            Command::ColumnToFreeCell { src_col, dst_cell } => {
-                self.save_history();
-                let src = Location::Column(src_col);
-                let dst = Location::FreeCell(dst_cell);
-                if let Err(e) = self.board.move_card(src, dst) {
-                    self.renderer.error(e);
-                    self.history.pop();
+            Command::Import { path } => {
+                let records = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| crate::export::parse_history_json(&text));
+                match records {
+                    Ok(records) => {
+                        let report = self.save_data.merge(records);
+                        self.save_data.save();
+                        self.renderer.info(&tr!(
+                            "Imported {} game(s); {} already present, {} unfinished skipped.",
+                            report.added,
+                            report.duplicates,
+                            report.unfinished
+                        ));
+                        self.renderer.info(&tr!(
+                            "History now has {} game(s), {} win(s), best streak {}.",
+                            self.save_data.records.len(),
+                            self.save_data.total_wins(),
+                            self.save_data.best_streak()
+                        ));
+                    }
+                    Err(e) => self.renderer.error(&tr!("Could not import {}: {}", path, e)),
                 }
             }
-            Command::FreeCellToColumn { src_cell, dst_col } => {
-                self.save_history();
-                let src = Location::FreeCell(src_cell);
-                let dst = Location::Column(dst_col);
-                if let Err(e) = self.board.move_card(src, dst) {
-                    self.renderer.error(e);
-                    self.history.pop();
+            Command::ImportGrid { path } => {
+                let board = std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| crate::share::parse_grid(&text));
+                match board {
+                    Ok(board) => {
+                        self.leave_current_game();
+                        self.resigned = false;
+                        self.start_loaded(board);
+                        self.renderer.info(&tr!("Imported the board from {}. It won't count towards your wins.", path));
+                        self.compute_par();
+                    }
+                    Err(e) => self.renderer.error(&tr!("Could not read the grid in {}: {}", path, e)),
                 }
             }
-            Command::ColumnToFoundation { src } => {
-                self.save_history();
-                if let Err(e) = self.board.move_to_foundation(Location::Column(src)) {
-                    self.renderer.error(e);
-                    self.history.pop();
+            Command::Games => {
+                let games = self.save_data.unfinished();
+                if games.is_empty() {
+                    self.renderer.info(&tr!("No unfinished games."));
+                }
+                let current = self.save_data.records.len() - 1;
+                for (n, &i) in games.iter().enumerate() {
+                    let mut line = slot_line(n + 1, &self.save_data.records[i]);
+                    if i == current {
+                        line = tr!("{} (current)", line);
+                    }
+                    self.renderer.info(&line);
+                }
+                if games.len() > 1 {
+                    self.renderer.info(&tr!("Type 'resume <n>' to switch games."));
                 }
             }
-            Command::FreeCellToFoundation { src_cell } => {
-                self.save_history();
-                if let Err(e) = self.board.move_to_foundation(Location::FreeCell(src_cell)) {
-                    self.renderer.error(e);
-                    self.history.pop();
+            Command::ShareDaily => match &self.daily_share {
+                Some(text) => {
+                    for line in text.lines() {
+                        self.renderer.info(line);
+                    }
                 }
+                None => self.renderer.error(&tr!("Win a daily deal first (start today's with 'new daily').")),
+            },
+            Command::Copy { kind } => {
+                let text = match kind {
+                    CopyKind::Seed if !self.current_is_dealt() => {
+                        self.renderer.error(&tr!("A shared position has no seed to deal it from; 'copy share' copies its code instead."));
+                        return false;
+                    }
+                    CopyKind::Seed => self.board.seed.to_string(),
+                    CopyKind::Share => crate::share::encode(&self.board),
+                    CopyKind::Daily => match &self.daily_share {
+                        Some(text) => text.clone(),
+                        None => {
+                            self.renderer.error(&tr!("Win a daily deal first (start today's with 'new daily')."));
+                            return false;
+                        }
+                    },
+                };
+                self.copy_to_clipboard(&text);
             }
-            Command::MergeDragons { suit } => {
-                self.save_history();
-                if let Err(e) = self.board.merge_dragons(suit) {
-                    self.renderer.error(e);
-                    self.history.pop();
+            Command::Share => {
+                let code = crate::share::encode(&self.board);
+                self.renderer.info(&tr!("Position code: {}", code));
+                self.renderer.info(&tr!("Anyone can play on from here with 'load <code>'."));
+            }
+            Command::Load { code } => match crate::share::decode(&code) {
+                Ok(board) => {
+                    self.leave_current_game();
+                    self.resigned = false;
+                    self.start_loaded(board);
+                    self.renderer.info(&tr!("Loaded a shared position (seed {}). It won't count towards your wins.", self.board.seed));
+                    self.compute_par();
+                }
+                Err(e) => self.renderer.error(&tr!("Could not load the position: {}", e)),
+            },
+            Command::Challenge { token: Some(token) } => self.take_challenge(&token),
+            Command::Challenge { token: None } => {
+                if !self.current_is_dealt() {
+                    self.renderer.error(&tr!("A shared position can't be sent as a challenge; 'share' its code instead."));
+                    return false;
+                }
+                if self.par.is_none() {
+                    self.renderer.info(&tr!("Working out par for this deal... (may take a moment)"));
+                    self.solve_par();
                 }
+                let challenge = crate::share::Challenge {
+                    seed: self.board.seed,
+                    rules: self.board.rules,
+                    par: self.par.map(|p| p as u32),
+                    assist: self.assist_preset(),
+                    hardcore: self.hardcore(),
+                };
+                let token = crate::share::encode_challenge(&challenge);
+                self.renderer.info(&tr!("Challenge token: {}", token));
+                self.renderer.info(&tr!("A friend can take it up with 'szsol-rs challenge <token>'."));
             }
-        }
-        false
-    }
-
-    fn save_history(&mut self) {
+            Command::Puzzles { path } => {
+                if let Some(path) = path {
+                    let name = std::path::Path::new(&path)
+                        .file_stem()
+                        .map_or_else(|| path.clone(), |stem| stem.to_string_lossy().into_owned());
+                    let pack = std::fs::read_to_string(&path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|text| Pack::parse(&name, &text));
+                    match pack {
+                        Ok(pack) => self.puzzle_pack = pack,
+                        Err(e) => {
+                            self.renderer.error(&tr!("Could not read the puzzles in {}: {}", path, e));
+                            return false;
+                        }
+                    }
+                }
+                self.renderer.info(&tr!("Puzzle pack '{}':", self.puzzle_pack.name));
+                for (i, puzzle) in self.puzzle_pack.puzzles.iter().enumerate() {
+                    let status = match self.save_data.puzzle_progress(&self.puzzle_pack.name, &puzzle.name) {
+                        (_, true) => tr!("  (solved)"),
+                        (0, false) => String::new(),
+                        (attempts, false) => tr!("  ({} attempt(s))", attempts),
+                    };
+                    self.renderer.info(&format!("{:3}. {}: {}{}", i + 1, puzzle.title, puzzle.task(), status));
+                }
+                self.renderer.info(&tr!("Type 'puzzle <n>' to play one."));
+            }
+            Command::Puzzle { index } => {
+                let Some(puzzle) = self.puzzle_pack.puzzles.get(index - 1).cloned() else {
+                    self.renderer.error(&tr!("There is no puzzle {}; type 'puzzles' to list them.", index));
+                    return false;
+                };
+                self.leave_current_game();
+                self.resigned = false;
+                self.start_loaded(puzzle.board.clone());
+                self.open_puzzle(self.puzzle_pack.name.clone(), puzzle.name.clone(), puzzle.goal, puzzle.moves);
+                self.renderer.info(&tr!("Puzzle: {}. It won't count towards your wins.", puzzle.title));
+                self.renderer.info(&puzzle.task());
+                self.compute_par();
+            }
+            Command::Campaign { stage: None } => {
+                let map = crate::campaign::CampaignMap::new(&self.save_data);
+                self.renderer.show_campaign(&map);
+            }
+            Command::Campaign { stage: Some(stage) } => {
+                let stages = crate::campaign::STAGES.len();
+                let Some(&(seed, nodes)) = crate::campaign::STAGES.get(stage - 1) else {
+                    self.renderer.error(&tr!("There is no stage {}; the campaign has {}.", stage, stages));
+                    return false;
+                };
+                if stage > self.save_data.campaign_cleared() + 1 {
+                    self.renderer.error(&tr!("Stage {} is locked. Win stage {} to open it.", stage, stage - 1));
+                    return false;
+                }
+                // The stages were rated on the original table, so they are
+                // dealt on it whatever the configured rules.
+                self.start_dealt(Board::deal(seed, Rules::STANDARD), None, false);
+                self.open_campaign_attempt(stage);
+                self.renderer.info(&tr!("Campaign stage {} of {}, a deal the solver rates '{}'.", stage, stages, crate::analyze::band(nodes)));
+            }
+            Command::ReportDifficulty => {
+                if let Some(job) = &self.rating_job {
+                    self.renderer.info(&tr!("Still rating your seeds with the solver: {} to go.", job.pending()));
+                    return false;
+                }
+                let unrated = crate::analyze::unrated_seeds(&self.save_data);
+                if unrated.is_empty() {
+                    for line in crate::analyze::difficulty_report(&self.save_data) {
+                        self.renderer.info(&line);
+                    }
+                } else {
+                    self.renderer.info(&tr!("Rating {} seeds with the solver in the background; the report follows when it is done.", unrated.len()));
+                    self.rating_job = Some(RatingJob::spawn(unrated));
+                }
+            }
+            Command::ReportRating => {
+                let ratings = &self.save_data.ratings;
+                if ratings.is_empty() {
+                    self.renderer.info(&tr!("No rated games yet. Every finished dealt game is rated against the deal."));
+                } else {
+                    self.renderer.info(&tr!("Rating {} after {} rated games. Latest changes, newest first (times in UTC):",
+                        self.save_data.rating(), ratings.len()));
+                    for (i, change) in ratings.iter().enumerate().rev().take(RATING_LINES) {
+                        let before = i.checked_sub(1).map_or(crate::rating::START, |j| ratings[j].rating);
+                        self.renderer.info(&rating_line(change, before));
+                    }
+                }
+            }
+            Command::Challenges => {
+                if self.save_data.challenges.is_empty() {
+                    self.renderer.info(&tr!("No challenges taken yet. Take one up with 'challenge <token>'."));
+                } else {
+                    self.renderer.info(&tr!("Challenges taken, newest first (times in UTC):"));
+                    for challenge in self.save_data.challenges.iter().rev() {
+                        self.renderer.info(&challenge_line(challenge));
+                    }
+                }
+            }
+            Command::Submit => self.submit_daily(),
+            Command::Leaderboard => self.show_leaderboard(),
+            Command::Telemetry { .. } | Command::TelemetrySend if History::is_in_memory() => {
+                self.renderer.error(&tr!("Usage statistics are not kept for this session."));
+            }
+            Command::Telemetry { enable: None } => {
+                if self.telemetry.is_enabled() {
+                    self.renderer.info(&tr!("Usage statistics are on. This is everything recorded, exactly as 'telemetry send' would upload it:"));
+                } else {
+                    self.renderer.info(&tr!("Usage statistics are off. 'telemetry on' would record only this, and nothing is uploaded until 'telemetry send':"));
+                }
+                for line in self.telemetry.report().pretty().lines() {
+                    self.renderer.info(line);
+                }
+            }
+            Command::Telemetry { enable: Some(true) } => {
+                if !self.telemetry.is_enabled() {
+                    self.telemetry.set_enabled(true);
+                }
+                self.renderer.info(&tr!("Usage statistics are on. 'telemetry' shows what is recorded."));
+            }
+            Command::Telemetry { enable: Some(false) } => {
+                self.telemetry.set_enabled(false);
+                self.renderer.info(&tr!("Usage statistics are off, and everything recorded was deleted."));
+            }
+            Command::TelemetrySend => self.send_telemetry(),
+            Command::Best { seed } => {
+                // Another seed is looked up under the rules new deals get.
+                let (seed, rules) = match seed {
+                    Some(seed) => (seed, self.user_config.rules()),
+                    None => (self.board.seed, self.board.rules),
+                };
+                match self.save_data.seed_best(seed, rules) {
+                    Some(best) => {
+                        let time = format_elapsed(Duration::from_secs(best.best_secs));
+                        let moves_date = crate::history::format_date(best.fewest_moves_date);
+                        let time_date = crate::history::format_date(best.best_secs_date);
+                        self.renderer.info(&tr!("Seed {}: {} win(s).", seed, best.wins));
+                        self.renderer.info(&tr!("Best time: {} ({}).", time, time_date));
+                        self.renderer.info(&tr!("Fewest moves: {} ({}).", best.fewest_moves, moves_date));
+                    }
+                    None => self.renderer.info(&tr!("No wins recorded on seed {} yet.", seed)),
+                }
+                if let Some(par) = self.save_data.par(seed, rules) {
+                    self.renderer.info(&tr!("Par: {} moves.", par));
+                }
+            }
+            Command::Resume { slot } => {
+                let games = self.save_data.unfinished();
+                match slot.checked_sub(1).and_then(|n| games.get(n)) {
+                    None => self.renderer.error(&tr!("There is no unfinished game {}. Type 'games' to list them.", slot)),
+                    Some(&i) if i == self.save_data.records.len() - 1 => {
+                        self.renderer.info(&tr!("That is the current game."));
+                    }
+                    Some(&i) => {
+                        self.leave_current_game();
+                        let record = self.save_data.records.remove(i);
+                        self.save_data.records.push(record);
+                        self.save_data.save();
+                        self.load_current_record();
+                        log::info!("resumed seed {} from slot {}", self.board.seed, slot);
+                        self.renderer.info(&tr!("Resumed game from seed {}.", self.board.seed));
+                    }
+                }
+            }
+            Command::Describe => {
+                for line in crate::renderer::describe_board(&self.board, self.user_config.index_base()) {
+                    self.renderer.info(&line);
+                }
+            }
+            Command::Config => {
+                for line in self.user_config.describe() {
+                    self.renderer.info(&line);
+                }
+            }
+            Command::Keys => {
+                for line in self.user_config.keys.lines() {
+                    self.renderer.info(&line);
+                }
+            }
+            Command::SetRender { mode } => {
+                self.user_config.render.set(mode, Source::Session);
+                self.renderer.set_render_mode(mode);
+                self.renderer.info(&tr!("Render mode: {}.", mode.name()));
+            }
+            Command::Solve => {
+                let (strategy, budget) = (self.user_config.solver.value, self.user_config.solver_budget());
+                match strategy {
+                    Strategy::AStar => self.renderer.info(&tr!("Running A* solver... (may take a moment)")),
+                    Strategy::Deepening => {
+                        self.renderer.info(&tr!("Running the iterative-deepening solver... (may take a moment)"))
+                    }
+                }
+
+                if let Some(path) = crate::solver::solve_with(&self.board, strategy, budget, |progress| {
+                    self.renderer.info(&progress.message());
+                    true
+                }) {
+                    let path: Vec<_> = path.iter().map(|step| step.next_move).collect();
+                    self.renderer.info(&tr!("Found a solution in {} steps!", path.len()));
+                    let base = self.user_config.index_base();
+                    for (i, m) in path.iter().enumerate() {
+                        self.renderer.info(&format!("{:4}. {}", i + 1, m.command_in_base(base)));
+                    }
+                } else {
+                    self.renderer.error(&tr!("No solution found by BFS."));
+                }
+            }
+            Command::ShowSolution => self.show_solution(),
+            Command::Par => {
+                if self.par.is_none() {
+                    self.renderer.info(&tr!("Working out par for this deal... (may take a moment)"));
+                    if let Some(finder) = &mut self.par_finder {
+                        finder.cancel();
+                    }
+                    self.solve_par();
+                }
+                match self.par {
+                    Some(par) => self.renderer.info(&tr!("Par for this deal is {} moves.", par)),
+                    None => self.renderer.error(&tr!("The solver found no par for this deal.")),
+                }
+            }
+            Command::ColumnToColumn { src, stack_start, dst } => {
+                self.save_history();
+                let col_len = self.board.columns[src].len();
+                // stack_start is depth from top; convert to absolute index.
+                let abs_idx = if col_len == 0 {
+                    self.renderer.error(&tr!("Source column is empty."));
+                    self.history.pop();
+                    return false;
+                } else {
+                    col_len.saturating_sub(1 + stack_start)
+                };
+
+                let result = self.board.move_stack(src, abs_idx, dst);
+                self.finish_move(result);
+            }
+            Command::ColumnToColumnAuto { src, dst } => {
+                let Some(start_idx) = self.board.longest_movable_stack(src, dst) else {
+                    self.renderer.error(&tr!("No card or run in that column can move there."));
+                    return false;
+                };
+                self.save_history();
+                let result = self.board.move_stack(src, start_idx, dst);
+                self.finish_move(result);
+            }
+            Command::AutoMove { src } => {
+                let src = Location::Column(src);
+                let Some(dst) = self.board.best_destination(src) else {
+                    self.renderer.error(&tr!("That card has no legal destination."));
+                    return false;
+                };
+                self.save_history();
+                let result = match dst {
+                    Location::Foundation(_) | Location::Flower => self.board.move_to_foundation(src),
+                    _ => self.board.move_card(src, dst),
+                };
+                self.finish_move(result);
+            }
+            Command::ColumnToFreeCell { src_col, dst_cell } => {
+                self.save_history();
+                let src = Location::Column(src_col);
+                let dst = Location::FreeCell(dst_cell);
+                let result = self.board.move_card(src, dst);
+                self.finish_move(result);
+            }
+            Command::FreeCellToColumn { src_cell, dst_col } => {
+                self.save_history();
+                let src = Location::FreeCell(src_cell);
+                let dst = Location::Column(dst_col);
+                let result = self.board.move_card(src, dst);
+                self.finish_move(result);
+            }
+            Command::ColumnToFoundation { src } => {
+                self.save_history();
+                let result = self.board.move_to_foundation(Location::Column(src));
+                self.finish_move(result);
+            }
+            Command::FreeCellToFoundation { src_cell } => {
+                self.save_history();
+                let result = self.board.move_to_foundation(Location::FreeCell(src_cell));
+                self.finish_move(result);
+            }
+            Command::MergeDragons { suit } => {
+                self.save_history();
+                let result = self.board.merge_dragons(suit);
+                self.finish_move(result);
+            }
+        }
+        false
+    }
+
+    /// Let `sink` hear about everything that happens from now on.
+    pub fn subscribe(&mut self, sink: Box<dyn EventSink>) {
+        self.sinks.push(sink);
+    }
+
+    fn notify(&mut self, event: PlayEvent) {
+        for sink in &mut self.sinks {
+            sink.on_event(&event);
+        }
+    }
+
+    /// Pass board events on to the renderer, ringing for and telling the
+    /// sinks about dragon merges among them.
+    fn push_events(&mut self, events: Vec<GameEvent>) {
+        for event in &events {
+            if let GameEvent::DragonsMerged { suit, .. } = event {
+                if self.user_config.bell.value.dragons {
+                    self.renderer.bell();
+                }
+                self.notify(PlayEvent::DragonsMerged { suit: *suit });
+            }
+        }
+        self.renderer.push_events(events);
+    }
+
+    /// Report a refused move, with the bell if the player asked for it.
+    fn move_error(&mut self, e: &MoveError) {
+        if self.user_config.bell.value.illegal {
+            self.renderer.bell();
+        }
+        self.renderer.move_error(e);
+    }
+
+    /// Hand a move's events to the renderer, or report why it failed and
+    /// drop the history entry saved for it.
+    fn finish_move(&mut self, result: Result<Vec<GameEvent>, MoveError>) {
+        match result {
+            Ok(events) => {
+                self.moves += 1;
+                log::debug!("move {} applied", self.moves);
+                self.notify(PlayEvent::MoveApplied { moves: self.moves });
+                self.push_events(events);
+                self.report_golf();
+            }
+            Err(e) => {
+                log::info!("move refused: {:?}", e);
+                self.move_error(&e);
+                self.history.pop();
+            }
+        }
+    }
+
+    /// The phrase the current deal's seed was derived from, if any.
+    fn seed_phrase(&self) -> Option<&str> {
+        self.save_data
+            .records
+            .last()
+            .filter(|r| r.seed == self.board.seed)
+            .and_then(|r| r.seed_phrase.as_deref())
+    }
+
+    fn render_header(&mut self) {
+        let phrase = self.seed_phrase().map(str::to_string);
+        let (moves, elapsed) = (self.moves, self.elapsed());
+        let wins = self.save_data.total_wins();
+        let outlook = match self.last_verdict {
+            Some(verdict) if !self.verdict_pending => Outlook::Known(verdict),
+            _ => Outlook::Checking,
+        };
+        self.renderer.set_status(HeaderStatus {
+            outlook: self.user_config.winnability.value.then_some(outlook),
+            mobility: self.user_config.mobility.value.then(|| self.board.valid_moves().len()),
+            weekly: self.is_weekly().then(crate::weekly::time_left),
+            ghost: self.ghost_status(),
+        });
+        self.renderer.render_header(wins, self.board.seed, phrase.as_deref(), moves, self.par, elapsed);
+    }
+
+    /// Queue the current board for a background winnability check (assist
+    /// mode or the `winnability` indicator).
+    fn submit_winnability_check(&mut self) {
+        if !(self.warns_unwinnable() || self.user_config.winnability.value) {
+            return;
+        }
+        let checker = self.checker.get_or_insert_with(WinnabilityChecker::spawn);
+        match &self.checked_board {
+            Some(checked) if *checked == self.board => return,
+            Some(checked) if checked.seed != self.board.seed => self.last_verdict = None,
+            _ => {}
+        }
+        self.checked_board = Some(self.board.clone());
+        self.verdict_pending = true;
+        checker.submit(&self.board);
+    }
+
+    /// Whether moves that lose the game are pointed out.
+    fn warns_unwinnable(&self) -> bool {
+        self.app_config.warn_unwinnable || self.assists().fatal_move_warnings
+    }
+
+    /// Pick up a finished background check.
+    fn poll_winnability_check(&mut self) {
+        let Some(verdict) = self.checker.as_mut().and_then(|c| c.poll()) else { return };
+        self.note_verdict(verdict);
+    }
+
+    /// Keep a check's verdict, warning once when the game turns unwinnable.
+    fn note_verdict(&mut self, verdict: Winnability) {
+        self.verdict_pending = false;
+        let previous = self.last_verdict.replace(verdict);
+        if verdict != Winnability::Unwinnable || !self.warns_unwinnable() {
+            return;
+        }
+        match previous {
+            Some(Winnability::Unwinnable) => {}
+            Some(_) => self.renderer.error(&tr!("This move made the game unwinnable (undo to fix).")),
+            None => self.renderer.error(&tr!("This position cannot be won.")),
+        }
+    }
+
+    /// Persist the in-progress board so the game can be resumed later.
+    /// Finished records (won, abandoned or resigned) are left untouched.
+    fn save_progress(&mut self) {
+        self.snapshot_progress();
+        self.save_data.save();
+        self.moves_at_save = self.moves;
+    }
+
+    /// Save after a move as often as the `autosave` setting asks; in between,
+    /// the snapshot waits in memory for the next save or the exit.
+    fn autosave(&mut self) {
+        self.snapshot_progress();
+        let due = match self.user_config.autosave.value {
+            Autosave::EveryMove => true,
+            Autosave::Every(n) => self.moves.abs_diff(self.moves_at_save) >= n,
+            Autosave::OnExit => false,
+        };
+        if due {
+            self.save_data.save();
+            self.moves_at_save = self.moves;
+        } else {
+            self.save_data.save_later();
+        }
+    }
+
+    /// Copy the board and undo history into the unfinished last record.
+    fn snapshot_progress(&mut self) {
+        self.store_progress();
+        if let Some(last) = self.save_data.records.last_mut() && last.end_time.is_none() {
+            last.current_board = Some(self.board.clone());
+            last.undo_history = self.history.clone();
+        }
+    }
+
+    /// Step away from the current game and start `board`, freshly dealt
+    /// from its seed, with a new record.
+    fn start_dealt(&mut self, board: Board, phrase: Option<String>, weekly: bool) {
+        self.leave_current_game();
+        self.resigned = false;
+        self.board = board;
+        self.history.clear();
+        self.reset_lines();
+        self.bookmarks.clear();
+        self.reset_progress();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let mut record = GameRecord::new(self.board.seed, self.board.rules, now);
+        record.initial_board = Some(self.board.clone());
+        record.position_code = practice_code(&self.board);
+        record.seed_phrase = phrase;
+        record.hardcore = self.user_config.hardcore.value;
+        record.assist = self.user_config.assist.value;
+        record.weekly = weekly;
+        self.save_data.records.push(record);
+        self.save_data.save();
+
+        self.renderer.info(&tr!("A new game has been dealt."));
+        if self.is_weekly() {
+            let left = crate::renderer::format_countdown(crate::weekly::time_left());
+            self.renderer.info(&tr!("This is the weekly challenge, a hard deal; the next one comes in {}. 'history weekly' lists your results.", left));
+        }
+        self.compute_par();
+    }
+
+    /// Start a game from a position code's board, with a record marking
+    /// it as loaded.
+    fn start_loaded(&mut self, board: Board) {
+        self.board = board;
+        self.history.clear();
+        self.reset_lines();
+        self.bookmarks.clear();
+        self.reset_progress();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let mut record = GameRecord::new(self.board.seed, self.board.rules, now);
+        record.initial_board = Some(self.board.clone());
+        record.position_code = Some(crate::share::encode(&self.board));
+        record.hardcore = self.user_config.hardcore.value;
+        record.assist = self.user_config.assist.value;
+        self.save_data.records.push(record);
+        self.save_data.save();
+    }
+
+    /// Deal a friend's challenge with the assists it was sent with, and
+    /// open its entry in the challenge section of the history.
+    pub fn take_challenge(&mut self, token: &str) {
+        let challenge = match crate::share::decode_challenge(token) {
+            Ok(challenge) => challenge,
+            Err(e) => {
+                self.renderer.error(&tr!("Could not take up the challenge: {}", e));
+                return;
+            }
+        };
+        self.leave_current_game();
+        self.resigned = false;
+        self.board = Board::deal(challenge.seed, challenge.rules);
+        self.history.clear();
+        self.reset_lines();
+        self.bookmarks.clear();
+        self.reset_progress();
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let mut record = GameRecord::new(challenge.seed, challenge.rules, now);
+        record.position_code = practice_code(&self.board);
+        record.initial_board = Some(self.board.clone());
+        record.hardcore = challenge.hardcore;
+        record.assist = challenge.assist;
+        self.save_data.records.push(record);
+        // The sender's par saves solving the deal again.
+        if let Some(par) = challenge.par.filter(|_| self.save_data.par(challenge.seed, challenge.rules).is_none()) {
+            self.save_data.set_par(challenge.seed, challenge.rules, par);
+        }
+        self.save_data.challenges.push(ChallengeRecord {
+            token: token.split_whitespace().collect(),
+            seed: challenge.seed,
+            par: challenge.par,
+            assist: challenge.assist,
+            hardcore: challenge.hardcore,
+            start_time: now,
+            end_time: None,
+            won: false,
+            moves: 0,
+            elapsed_secs: 0,
+        });
+        self.save_data.save();
+
+        self.renderer.info(&tr!("Challenge accepted: seed {} with the '{}' assist preset.", challenge.seed, challenge.assist.name()));
+        if challenge.hardcore {
+            self.renderer.info(&tr!("Hardcore: no undo, hints or solver."));
+        }
+        if let Some(par) = challenge.par {
+            self.renderer.info(&tr!("Par to beat: {} moves.", par));
+        }
+        self.compute_par();
+    }
+
+    /// Add the board to the move log if it changed.
+    #[cfg(feature = "leaderboard")]
+    fn log_position(&mut self) {
+        let Some(start_time) = self.save_data.records.last().map(|r| r.start_time) else { return };
+        // No moves yet: a fresh deal (or a restart within the same second).
+        if self.move_log.start_time != start_time || self.moves == 0 {
+            self.move_log = MoveLog { start_time, from_deal: self.moves == 0, boards: Vec::new() };
+        }
+        if self.move_log.boards.last() != Some(&self.board) {
+            self.move_log.boards.push(self.board.clone());
+        }
+    }
+
+    #[cfg(not(feature = "leaderboard"))]
+    fn log_position(&mut self) {}
+
+    /// Put `text` on the system clipboard.  Without one (or over the
+    /// network, where it would be the server's) the text is printed to
+    /// copy by hand.
+    #[cfg(feature = "clipboard")]
+    fn copy_to_clipboard(&mut self, text: &str) {
+        let result = if History::is_in_memory() { Err(tr!("not over the network")) } else { crate::clipboard::copy(text) };
+        match result {
+            Ok(()) => self.renderer.info(&tr!("Copied to the clipboard: {}", text.lines().next().unwrap_or_default())),
+            Err(e) => {
+                self.renderer.info(&tr!("No clipboard to copy to ({}); here it is to copy by hand:", e));
+                for line in text.lines() {
+                    self.renderer.info(line);
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn copy_to_clipboard(&mut self, text: &str) {
+        self.renderer.info(&tr!("This szsol was built without the clipboard (cargo build --features clipboard); here it is to copy by hand:"));
+        for line in text.lines() {
+            self.renderer.info(line);
+        }
+    }
+
+    /// The leaderboard endpoint, or `None` after telling the player why
+    /// there is none.
+    fn leaderboard_url(&mut self) -> Option<String> {
+        if !cfg!(feature = "leaderboard") {
+            self.renderer.error(&tr!("This szsol was built without the online leaderboard (cargo build --features leaderboard)."));
+            return None;
+        }
+        let url = self.user_config.leaderboard_url.value.clone();
+        if url.is_empty() {
+            self.renderer.error(&tr!("Set leaderboard_url in config.toml to take part in the online leaderboard."));
+            return None;
+        }
+        Some(url)
+    }
+
+    /// Rate the game just ended against its deal.  Only dealt games are
+    /// rated; a deal whose par is not known yet is solved first.
+    fn rate_game(&mut self, won: bool) {
+        if !self.current_is_dealt() || History::is_in_memory() {
+            return;
+        }
+        if self.par.is_none() {
+            self.solve_par();
+        }
+        let time = self.save_data.records.last().and_then(|r| r.end_time).unwrap_or_default();
+        let opponent = crate::rating::deal_rating(self.par.map(|p| p as u32));
+        let before = self.save_data.rating();
+        let change = self.save_data.rate_game(self.board.seed, time, opponent, won);
+        let delta = i64::from(change.rating) - i64::from(before);
+        self.renderer.info(&tr!("Rating: {} ({}) against a deal rated {}.", change.rating, format!("{:+}", delta), opponent));
+    }
+
+    /// Tell the player the stage just won opened the next, or finished
+    /// the campaign.
+    fn announce_stage_cleared(&mut self) {
+        let cleared = self.save_data.campaign_cleared();
+        let stages = crate::campaign::STAGES.len();
+        if cleared >= stages {
+            self.renderer.info(&tr!("Campaign complete: all {} stages cleared!", stages));
+        } else {
+            self.renderer.info(&tr!("Stage {} cleared! Stage {} is open; type 'campaign {}' to play it.", cleared, cleared + 1, cleared + 1));
+        }
+    }
+
+    /// Run the `on_finish` hook, if one is set, for the game just ended.
+    /// Network sessions and scripts never run it.
+    fn run_finish_hook(&self, won: bool) {
+        let command = &self.user_config.on_finish.value;
+        if command.is_empty() || History::is_in_memory() {
+            return;
+        }
+        let finish = crate::hook::Finish { won, seed: self.board.seed, elapsed: self.played, moves: self.moves };
+        crate::hook::run(command, &finish);
+    }
+
+    /// Upload the usage statistics, if the player opted in and named
+    /// where to.
+    fn send_telemetry(&mut self) {
+        if !self.telemetry.is_enabled() {
+            self.renderer.error(&tr!("Usage statistics are off. Type 'telemetry on' to start recording them."));
+            return;
+        }
+        let url = self.user_config.telemetry_url.value.clone();
+        if url.is_empty() {
+            self.renderer.error(&tr!("Set telemetry_url in config.toml to send usage statistics."));
+            return;
+        }
+        match self.telemetry.send(&url) {
+            Ok(reply) if reply.is_empty() => self.renderer.info(&tr!("Usage statistics sent; counting starts afresh.")),
+            Ok(reply) => self.renderer.info(&tr!("Usage statistics sent; counting starts afresh. The server says: {}", reply)),
+            Err(e) => self.renderer.error(&tr!("Could not send usage statistics: {}", e)),
+        }
+    }
+
+    #[cfg(feature = "leaderboard")]
+    fn submit_daily(&mut self) {
+        let Some(url) = self.leaderboard_url() else { return };
+        let seed = crate::command::daily_seed();
+        let Some(record) = self.save_data.records.last().filter(|r| r.seed == seed && r.is_dealt() && r.won) else {
+            self.renderer.error(&tr!("Only a win of today's daily deal can be submitted (start it with 'new daily')."));
+            return;
+        };
+        if !self.move_log.from_deal || self.move_log.start_time != record.start_time {
+            self.renderer.error(&tr!("Only a daily game played from the deal in this session can be submitted."));
+            return;
+        }
+        let submission = crate::leaderboard::Submission {
+            seed,
+            date: daily_date(seed),
+            player: self.player_name(),
+            elapsed_secs: record.elapsed_secs,
+            moves: record.moves,
+            hints: record.hints,
+            log: self.move_log.boards.iter().map(crate::share::encode).collect(),
+        };
+        self.renderer.info(&tr!("Submitting to {}...", url));
+        match crate::leaderboard::submit(&url, &submission) {
+            Ok(reply) if reply.is_empty() => self.renderer.info(&tr!("Submitted.")),
+            Ok(reply) => self.renderer.info(&tr!("Submitted. The server says: {}", reply)),
+            Err(e) => self.renderer.error(&tr!("Could not submit: {}", e)),
+        }
+    }
+
+    #[cfg(not(feature = "leaderboard"))]
+    fn submit_daily(&mut self) {
+        self.leaderboard_url();
+    }
+
+    #[cfg(feature = "leaderboard")]
+    fn show_leaderboard(&mut self) {
+        const SHOWN: usize = 10;
+        let Some(url) = self.leaderboard_url() else { return };
+        let seed = crate::command::daily_seed();
+        match crate::leaderboard::fetch(&url, &daily_date(seed)) {
+            Ok(entries) if entries.is_empty() => self.renderer.info(&tr!("No times posted for today's deal yet.")),
+            Ok(entries) => {
+                self.renderer.info(&tr!("Today's top times (seed {}):", seed));
+                for (i, entry) in entries.iter().take(SHOWN).enumerate() {
+                    let time = format_elapsed(Duration::from_secs(entry.elapsed_secs));
+                    let hints = if entry.hints > 0 { tr!("  ({} hint(s))", entry.hints) } else { String::new() };
+                    let row = tr!("{}. {}  {}  {} moves", format!("{:>2}", i + 1), entry.player, time, entry.moves);
+                    self.renderer.info(&format!("{}{}", row, hints));
+                }
+            }
+            Err(e) => self.renderer.error(&tr!("Could not fetch the leaderboard: {}", e)),
+        }
+    }
+
+    #[cfg(not(feature = "leaderboard"))]
+    fn show_leaderboard(&mut self) {
+        self.leaderboard_url();
+    }
+
+    /// `player_name` from the config, else the login name.
+    #[cfg(feature = "leaderboard")]
+    fn player_name(&self) -> String {
+        let configured = Some(self.user_config.player_name.value.clone()).filter(|n| !n.is_empty());
+        configured
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "anonymous".to_string())
+    }
+
+    /// Copy the game's counters into its record while it is in progress.
+    fn store_progress(&mut self) {
+        let elapsed = self.elapsed();
+        if let Some(last) = self.save_data.records.last_mut() && last.end_time.is_none() {
+            last.moves = self.moves;
+            last.elapsed_secs = elapsed.as_secs();
+            last.splits = self.splits.clone();
+            last.undos = self.undos;
+            last.hints = self.hints;
+            last.dragon_order = self.dragon_order.clone();
+        }
+    }
+
+    /// Called before undoing: remember the position being rewound from so a
+    /// different follow-up move does not throw that line away.
+    fn remember_line(&mut self) {
+        if self.undone_tip.is_none() && !self.history.is_empty() {
+            self.undone_tip = Some(SavedLine {
+                history: self.history.clone(),
+                board: self.board.clone(),
+            });
+        }
+    }
+
+    /// File the rewound line under `lines` (a new branch is about to start).
+    fn save_pending_line(&mut self) {
+        let Some(line) = self.undone_tip.take() else { return };
+        if line.board != self.board {
+            self.file_line(line);
+        }
+    }
+
+    /// Add `line` to `lines`, unless one already ends on its board.
+    fn file_line(&mut self, line: SavedLine) {
+        if self.lines.iter().any(|l| l.board == line.board) {
+            return;
+        }
+        self.lines.push(line);
+        // Keep memory bounded, like the undo history itself.
+        if self.lines.len() > MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+
+    fn reset_lines(&mut self) {
+        self.lines.clear();
+        self.undone_tip = None;
+    }
+
+    fn save_history(&mut self) {
+        self.save_pending_line();
         self.history.push(self.board.clone());
-        // Cap history at 64 steps to bound memory usage.
-        if self.history.len() > 64 {
+        // Cap history (64 steps unless configured) to bound memory usage.
+        while self.history.len() > self.user_config.undo_cap.value {
             self.history.remove(0);
         }
     }
 }
+
+/// Maximum number of alternative lines kept per game.
+const MAX_LINES: usize = 16;
+
+/// A branch of play: the undo path leading to `board`, and `board` itself.
+#[derive(Clone)]
+struct SavedLine {
+    history: Vec<Board>,
+    board: Board,
+}
+
+/// Summarise a line for the `lines` listing.
+fn describe_line(history: &[Board], board: &Board) -> String {
+    let home: u32 = board.foundations.iter().map(|&f| f as u32).sum();
+    let left: usize = board.columns.iter().map(|c| c.len()).sum();
+    tr!(
+        "{} move(s) deep, {} card(s) home, {} left in the tableau",
+        history.len(),
+        home,
+        left
+    )
+}
+
+/// "#12  2026-10-15 14:03  seed 42  won  3:12  57 moves" for `history`.
+/// The suits whose foundations are complete on `board`, in suit order.
+fn completed_foundations(board: &Board) -> Vec<Suit> {
+    board.rules.suits().iter().copied().filter(|&s| board.foundations[s as usize] == board.rules.top_rank()).collect()
+}
+
+/// The position code kept for a deal on a non-standard table: its seed
+/// alone doesn't reproduce it, so it is replayed like a loaded position and
+/// likewise doesn't count towards wins.
+fn practice_code(board: &Board) -> Option<String> {
+    (!board.rules.is_standard()).then(|| crate::share::encode(board))
+}
+
+/// How a record that wasn't dealt from its seed is marked in listings.
+fn loaded_tag(record: &GameRecord) -> String {
+    match &record.position_code {
+        None => String::new(),
+        Some(code) if crate::share::decode(code).is_ok_and(|b| !b.rules.is_standard()) => tr!("  (practice table)"),
+        Some(_) => tr!("  (shared position)"),
+    }
+}
+
+fn history_line(id: usize, record: &GameRecord, par: Option<u32>) -> String {
+    let result = if record.won {
+        tr!("won")
+    } else if record.end_time.is_some() {
+        tr!("lost")
+    } else {
+        tr!("in progress")
+    };
+    let date = crate::history::format_date(record.start_time);
+    let loaded = loaded_tag(record);
+    // Saves from before move counting have no moves or time to show.
+    if record.moves == 0 {
+        return tr!("#{}  {}  seed {}  {}", id, date, record.seed, result) + &loaded;
+    }
+    let time = format_elapsed(Duration::from_secs(record.elapsed_secs));
+    let par = match par {
+        Some(par) if record.won && record.moves < par => tr!("  (under par {})", par),
+        Some(par) => tr!("  (par {})", par),
+        None => String::new(),
+    };
+    tr!("#{}  {}  seed {}  {}  {}  {} moves", id, date, record.seed, result, time, record.moves) + &par + &loaded
+}
+
+/// The date of a daily seed, `YYYY-MM-DD`.
+fn daily_date(seed: u64) -> String {
+    let mut date = crate::history::format_date(seed as i64 * 86_400);
+    date.truncate(10);
+    date
+}
+
+/// "2026-10-15 14:03  seed 42  standard  won  3:12  40 moves  (par 38, +2)"
+/// for `challenges`.
+fn challenge_line(challenge: &ChallengeRecord) -> String {
+    let date = crate::history::format_date(challenge.start_time);
+    let mut mode = challenge.assist.name().to_string();
+    if challenge.hardcore {
+        mode = tr!("{}, hardcore", mode);
+    }
+    let line = tr!("{}  seed {}  {}", date, challenge.seed, mode);
+    if challenge.end_time.is_none() {
+        return format!("{}  {}", line, tr!("in progress"));
+    }
+    let result = if challenge.won { tr!("won") } else { tr!("lost") };
+    let time = format_elapsed(Duration::from_secs(challenge.elapsed_secs));
+    let par = match challenge.par {
+        Some(par) if challenge.won => {
+            let diff = challenge.moves as i64 - par as i64;
+            let sign = if diff < 0 { '-' } else { '+' };
+            tr!("  (par {}, {}{})", par, sign, diff.abs())
+        }
+        Some(par) => tr!("  (par {})", par),
+        None => String::new(),
+    };
+    tr!("{}  {}  {}  {} moves", line, result, time, challenge.moves) + &par
+}
+
+/// "week of 2026-10-12  seed 74  3 tries, 1 won  best 6:12, 71 moves" for
+/// `history weekly`.
+fn weekly_line(weekly: &WeeklyRecord) -> String {
+    let date = crate::history::format_date(crate::weekly::week_start(weekly.week));
+    let date = date.split(' ').next().unwrap_or_default();
+    let line = tr!("week of {}  seed {}  {} tries, {} won", date, weekly.seed, weekly.attempts, weekly.wins);
+    match (weekly.best_secs, weekly.fewest_moves) {
+        (Some(secs), Some(moves)) => {
+            tr!("{}  best {}, {} moves", line, format_elapsed(Duration::from_secs(secs)), moves)
+        }
+        _ => line,
+    }
+}
+
+/// "2026-10-15 14:03  seed 42  won against 1620  1516 (+16)" for `report
+/// rating`.
+fn rating_line(change: &RatingChange, before: u32) -> String {
+    let date = crate::history::format_date(change.time);
+    let result = if change.won { tr!("won") } else { tr!("lost") };
+    let delta = format!("{:+}", i64::from(change.rating) - i64::from(before));
+    tr!("{}  seed {}  {} against {}  {} ({})", date, change.seed, result, change.opponent, change.rating, delta)
+}
+
+/// "1. seed 42  started 2026-10-15 14:03  12 moves  3:04" for `games`.
+fn slot_line(n: usize, record: &GameRecord) -> String {
+    let date = crate::history::format_date(record.start_time);
+    let time = format_elapsed(Duration::from_secs(record.elapsed_secs));
+    let loaded = loaded_tag(record);
+    tr!("{}. seed {}  started {}  {} moves  {}", n, record.seed, date, record.moves, time) + &loaded
+}
+
+/// One-line description of a board event, used for per-card CLI messages.
+fn describe_event(event: &GameEvent) -> Option<String> {
+    match event {
+        GameEvent::CardMoved { card, dst: Location::Foundation(_) | Location::Flower, .. } => {
+            Some(tr!("{} → foundation", card.label()))
+        }
+        GameEvent::DragonsMerged { suit, locked_cell } => {
+            Some(tr!("{} dragons merged into free cell {}", i18n::suit_name(*suit), locked_cell))
+        }
+        _ => None,
+    }
+}
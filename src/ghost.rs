@@ -0,0 +1,83 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Racing a ghost: the fastest win on each seed keeps a trace of its
+//! foundations, one point whenever they changed.  Replaying the seed, the
+//! header shows where that run stood after as much play time and as many
+//! moves as the current game, and how many cards ahead or behind it is.
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, Rules};
+use crate::card::Suit;
+
+/// The foundations of a run at one moment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GhostPoint {
+    /// Play time so far.
+    pub at_ms: u64,
+    pub moves: u32,
+    /// Highest card home on each suit's foundation, in the board's order.
+    pub home: Vec<u8>,
+}
+
+/// The fastest win on a seed under some rules, to race against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ghost {
+    pub seed: u64,
+    #[serde(default)]
+    pub rules: Rules,
+    pub elapsed_secs: u64,
+    pub moves: u32,
+    /// Oldest first, starting from the deal.
+    pub points: Vec<GhostPoint>,
+}
+
+/// What the header shows of the ghost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostStatus {
+    pub suits: &'static [Suit],
+    /// The ghost's foundations at the current play time.
+    pub at_time: Vec<u8>,
+    /// The ghost's foundations after the current number of moves.
+    pub at_moves: Vec<u8>,
+    /// Cards home in the current game less the ghost's at this time.
+    pub lead: i64,
+}
+
+impl Ghost {
+    /// The ghost's foundations after `ms` of play.
+    fn at_time(&self, ms: u64) -> &[u8] {
+        self.points.iter().take_while(|p| p.at_ms <= ms).last().map_or(&[], |p| &p.home)
+    }
+
+    /// The ghost's foundations after `moves` moves.
+    fn at_moves(&self, moves: u32) -> &[u8] {
+        self.points.iter().take_while(|p| p.moves <= moves).last().map_or(&[], |p| &p.home)
+    }
+
+    /// How the game on `board`, `ms` and `moves` in, stands against the
+    /// ghost.
+    pub fn status(&self, board: &Board, ms: u64, moves: u32) -> GhostStatus {
+        let suits = board.rules.suits();
+        let pad = |home: &[u8]| (0..suits.len()).map(|i| home.get(i).copied().unwrap_or(0)).collect::<Vec<u8>>();
+        let at_time = pad(self.at_time(ms));
+        let total = |home: &[u8]| home.iter().map(|&n| i64::from(n)).sum::<i64>();
+        GhostStatus { suits, lead: total(&board.foundations) - total(&at_time), at_time, at_moves: pad(self.at_moves(moves)) }
+    }
+}
+
+/// Foundations as `R3 G0 B5`.
+pub fn describe(suits: &[Suit], home: &[u8]) -> String {
+    suits.iter().zip(home).map(|(suit, n)| format!("{}{}", suit.symbol(), n)).collect::<Vec<_>>().join(" ")
+}
+
+/// A lead as `+2`, `-1` or `±0`.
+pub fn lead(lead: i64) -> String {
+    if lead == 0 { "±0".to_string() } else { format!("{:+}", lead) }
+}
@@ -20,25 +20,146 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
-use std::fs::{self, File};
-use std::io::{Read, Write};
-use std::path::PathBuf;
-use directories::ProjectDirs;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
 use serde::{Deserialize, Serialize};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 
-use crate::board::Board;
+use crate::board::{Board, FreeCellState, Rules};
+use crate::compact::CompactBoard;
+use crate::card::{Card, Suit};
+use crate::compress;
+use crate::config::{AssistPreset, SaveFormat};
+use crate::json::Json;
+use crate::json_serde;
+use crate::ghost::Ghost;
+use crate::puzzle::Goal;
+use crate::rating::{self, RatingChange};
+use crate::weekly;
 
 type HmacSha256 = Hmac<Sha256>;
 
 // NOTE: This HMAC is not a security measure against a determined attacker.
-// The key being in the binary is intentional: this is a single-player game with
-// no secrets at stake. The sole purpose is to detect accidental file corruption
-// (e.g. from a crash mid-write) so we never silently load a broken save.
-const SECRET_KEY: &[u8] = b"szsol_secret_key_123_do_not_cheat";
+// Its main purpose is to detect accidental file corruption (e.g. from a crash
+// mid-write) so we never silently load a broken save.  Saves are signed with a
+// random key made on first run and kept in `save.key` in the config
+// directory, so each install's signature is its own.
+//
+// The built-in key signed every save before that.  It is still accepted when
+// reading, so those saves keep loading (and are re-signed on the next save),
+// and it is used when no key file can be written.
+const LEGACY_KEY: &[u8] = b"szsol_secret_key_123_do_not_cheat";
+const KEY_FILE: &str = "save.key";
 const HMAC_SIZE: usize = 32;
-const SNAPSHOT_COUNT: usize = 3;
+static SIGNING_KEY: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Saves start with this magic and a little-endian `u32` format version;
+/// files without it predate versioning.
+const SAVE_MAGIC: &[u8; 4] = b"SZSH";
+/// Bump when `History`'s encoding changes, and teach `decode` to read the
+/// previous version.  Files without the header hold the original layout,
+/// uncompressed.
+const SAVE_VERSION: u32 = 1;
+const BACKUP_COUNT: usize = 3;
+
+/// Held until the process exits; the OS drops the lock then, so a crash
+/// never leaves a stale one behind.
+static INSTANCE_LOCK: OnceLock<File> = OnceLock::new();
+/// Set when another instance holds the lock: this one never writes the
+/// save file, so neither clobbers the other's games.
+static LOCKED_OUT: AtomicBool = AtomicBool::new(false);
+/// Set in server mode: every history lives in memory only, so clients
+/// neither see nor change the host's games.
+static IN_MEMORY: AtomicBool = AtomicBool::new(false);
+
+/// Marks a `history.json` save; `JSON_SAVE_VERSION` is bumped like
+/// `SAVE_VERSION` when the layout changes.
+const JSON_SAVE_FORMAT: &str = "szsol-save";
+const JSON_SAVE_VERSION: i128 = 1;
+static SAVE_FORMAT: OnceLock<SaveFormat> = OnceLock::new();
+
+/// Choose how saves are written.  Called once at startup.
+pub fn set_format(format: SaveFormat) {
+    let _ = SAVE_FORMAT.set(format);
+}
+
+/// How long the saver waits for commands to stop before writing.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+static SAVER: OnceLock<Saver> = OnceLock::new();
+
+/// The background saver: the newest unwritten snapshot, handed over by
+/// `History::save` and written by the saver thread or `History::flush`.
+#[derive(Default)]
+struct Saver {
+    state: Mutex<SaverState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct SaverState {
+    pending: Option<(PathBuf, Vec<u8>, SaveFormat)>,
+    /// `pending` waits for a later save or `flush` (see `save_later`).
+    deferred: bool,
+    /// A write is in progress; the next one waits so they never interleave.
+    writing: bool,
+}
+
+impl Saver {
+    fn write_pending(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        while state.writing {
+            state = self.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        let Some((path, encoded, format)) = state.pending.take() else { return };
+        state.writing = true;
+        drop(state);
+
+        let written = match format {
+            SaveFormat::Binary => History::write_encoded(&path, &encoded),
+            SaveFormat::Json => History::write_file(&path, &encoded),
+        };
+        match written {
+            Ok(()) => log::info!("saved {}", path.display()),
+            Err(e) => log::warn!("could not save {}: {}", path.display(), e),
+        }
+
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).writing = false;
+        self.changed.notify_all();
+    }
+}
+
+/// Why a save file could not be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unreadable {
+    Truncated,
+    Signature,
+    Newer,
+    Corrupt,
+}
+
+impl Unreadable {
+    pub fn message(self) -> &'static str {
+        match self {
+            Unreadable::Truncated => "Save file is truncated.",
+            Unreadable::Signature => "Save file signature mismatched!",
+            Unreadable::Newer => "Save file was written by a newer version of szsol.",
+            Unreadable::Corrupt => "Save file could not be read.",
+        }
+    }
+}
+
+/// A rotated copy of the save file; backup 1 is the newest.
+pub struct Backup {
+    pub index: usize,
+    /// Last modified, in seconds since the epoch.
+    pub modified: Option<i64>,
+    pub contents: Result<History, Unreadable>,
+}
 
 /// A single recorded game session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,10 +171,58 @@ pub struct GameRecord {
     pub initial_board: Option<Board>,
     pub current_board: Option<Board>,
     pub undo_history: Vec<Board>,
+    /// The phrase the seed was derived from, if the player gave one.
+    pub seed_phrase: Option<String>,
+    /// Moves the player made (auto-moves excluded).
+    pub moves: u32,
+    /// Seconds of play, summed over every session the game was open.
+    pub elapsed_secs: u64,
+    /// Speedrun splits in the order they were reached (empty outside
+    /// speedrun mode).
+    pub splits: Vec<Split>,
+    /// Played in hardcore mode: no undo, hints or solver.
+    pub hardcore: bool,
+    /// Assists the game was dealt with.
+    pub assist: AssistPreset,
+    /// Undo steps taken (undo-all counts once).
+    pub undos: u32,
+    /// Suits in the order their dragons were merged.
+    pub dragon_order: Vec<Suit>,
+    /// The position code the game was loaded from; `None` for a dealt
+    /// game.  Loaded games don't count towards wins, streaks or bests.
+    #[serde(default)]
+    pub position_code: Option<String>,
+    /// Times the solver was asked for a hint (`solve`, `H` in the TUI).
+    #[serde(default)]
+    pub hints: u32,
+    /// The rules the game was dealt under.  Kept apart from the boards,
+    /// which `History::compact` drops, so a finished game can still be
+    /// dealt again as it was.
+    #[serde(default)]
+    pub rules: Rules,
+    /// Dealt with `new weekly` as its week's challenge.
+    #[serde(default)]
+    pub weekly: bool,
+}
+
+/// A speedrun checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitKind {
+    /// A suit's foundation reached 9.
+    Foundation(Suit),
+    /// A suit's dragons were merged.
+    Dragons(Suit),
+}
+
+/// When a checkpoint was reached, in milliseconds of play time.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Split {
+    pub kind: SplitKind,
+    pub at_ms: u64,
 }
 
 impl GameRecord {
-    pub fn new(seed: u64, start_time: i64) -> Self {
+    pub fn new(seed: u64, rules: Rules, start_time: i64) -> Self {
         Self {
             seed,
             start_time,
@@ -62,156 +231,1147 @@ impl GameRecord {
             initial_board: None,
             current_board: None,
             undo_history: Vec::new(),
+            seed_phrase: None,
+            moves: 0,
+            elapsed_secs: 0,
+            splits: Vec::new(),
+            hardcore: false,
+            assist: AssistPreset::Standard,
+            undos: 0,
+            dragon_order: Vec::new(),
+            position_code: None,
+            hints: 0,
+            rules,
+            weekly: false,
+        }
+    }
+
+    /// A game dealt from a seed rather than loaded from a position code.
+    pub fn is_dealt(&self) -> bool {
+        self.position_code.is_none()
+    }
+
+    /// Points for a win: fewer moves, less time and fewer undos score
+    /// higher, and playing with fewer assists multiplies the result.
+    pub fn score(&self) -> u32 {
+        if !self.won {
+            return 0;
+        }
+        let penalty = 20 * u64::from(self.moves) + self.elapsed_secs + 100 * u64::from(self.undos);
+        let base = 5000u64.saturating_sub(penalty).max(500);
+        let mut percent = match self.assist {
+            AssistPreset::Purist => 125,
+            AssistPreset::Standard => 100,
+            AssistPreset::Relaxed => 75,
+        };
+        if self.hardcore {
+            percent += 50;
+        }
+        (base * percent / 100) as u32
+    }
+}
+
+/// What `History::merge` did with the incoming records.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeReport {
+    pub added: usize,
+    /// Already present: same seed and start time.
+    pub duplicates: usize,
+    /// Still in progress on the other machine, so left out.
+    pub unfinished: usize,
+}
+
+/// Which records the `history` command lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordFilter {
+    /// `Some(true)`: wins only; `Some(false)`: finished games that were lost.
+    pub won: Option<bool>,
+    pub seed: Option<u64>,
+}
+
+impl RecordFilter {
+    pub fn matches(&self, record: &GameRecord) -> bool {
+        let result = match self.won {
+            Some(true) => record.won,
+            Some(false) => record.end_time.is_some() && !record.won,
+            None => true,
+        };
+        result && self.seed.is_none_or(|seed| record.seed == seed)
+    }
+}
+
+/// "2026-10-15 14:03" for a Unix timestamp, in UTC.
+pub fn format_date(unix: i64) -> String {
+    // Days since the epoch to a civil date (Howard Hinnant's algorithm).
+    let days = unix.div_euclid(86_400);
+    let secs = unix.rem_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, secs / 3600, secs % 3600 / 60)
+}
+
+/// Which of the latest game's results beat every earlier win.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PersonalBests {
+    pub score: bool,
+    pub time: bool,
+    pub moves: bool,
+}
+
+// The layout saved before the file had a header.  bincode is not
+// self-describing, so it is spelled out here as it was written.
+
+/// `Board` as saved before the table's shape could change: always eight
+/// columns and three free cells.
+#[derive(Deserialize)]
+struct LegacyBoard {
+    columns: [Vec<Card>; 8],
+    free_cells: [FreeCellState; 3],
+    foundations: [u8; 3],
+    flower_placed: bool,
+    seed: u64,
+}
+
+impl From<LegacyBoard> for Board {
+    fn from(b: LegacyBoard) -> Self {
+        Board {
+            rules: Rules::STANDARD,
+            columns: b.columns.into(),
+            free_cells: b.free_cells.into(),
+            foundations: b.foundations.into(),
+            flower_placed: b.flower_placed,
+            seed: b.seed,
         }
     }
 }
 
+/// Record layout written before the file had a header.
+#[derive(Deserialize)]
+struct LegacyGameRecord {
+    seed: u64,
+    start_time: i64,
+    end_time: Option<i64>,
+    won: bool,
+    initial_board: Option<LegacyBoard>,
+    current_board: Option<LegacyBoard>,
+    undo_history: Vec<LegacyBoard>,
+}
+
+impl From<LegacyGameRecord> for GameRecord {
+    fn from(r: LegacyGameRecord) -> Self {
+        GameRecord {
+            end_time: r.end_time,
+            won: r.won,
+            initial_board: r.initial_board.map(Board::from),
+            current_board: r.current_board.map(Board::from),
+            undo_history: r.undo_history.into_iter().map(Board::from).collect(),
+            ..GameRecord::new(r.seed, Rules::STANDARD, r.start_time)
+        }
+    }
+}
+
+/// A history file from before the file had a header.
+#[derive(Deserialize)]
+struct LegacyHistory {
+    records: Vec<LegacyGameRecord>,
+}
+
+impl From<LegacyHistory> for History {
+    fn from(old: LegacyHistory) -> Self {
+        History { records: old.records.into_iter().map(GameRecord::from).collect(), ..History::default() }
+    }
+}
+
+/// Rewrite every board in `json` in its compact form, for a JSON save.
+/// Boards are the objects with `columns` and `free_cells` arrays.
+fn compact_boards(json: &mut Json) -> Result<(), json_serde::Error> {
+    if is_board(json, false) {
+        let board: Board = json_serde::from_json(json)?;
+        *json = json_serde::to_json(&CompactBoard(board))?;
+        return Ok(());
+    }
+    for child in children(json) {
+        compact_boards(child)?;
+    }
+    Ok(())
+}
+
+/// Undo [`compact_boards`] when reading a JSON save.
+fn expand_boards(json: &mut Json) -> Result<(), json_serde::Error> {
+    if is_board(json, true) {
+        let CompactBoard(board) = json_serde::from_json(json)?;
+        *json = json_serde::to_json(&board)?;
+        return Ok(());
+    }
+    for child in children(json) {
+        expand_boards(child)?;
+    }
+    Ok(())
+}
+
+/// Whether `json` is a board, in the compact form (columns as strings) or
+/// the full one (columns as arrays of cards).
+fn is_board(json: &Json, compact: bool) -> bool {
+    let column = |c: &Json| if compact { c.as_str().is_some() } else { c.as_array().is_some() };
+    json.get("free_cells").and_then(Json::as_array).is_some()
+        && json.get("columns").and_then(Json::as_array).is_some_and(|cols| cols.first().is_some_and(column))
+}
+
+fn children(json: &mut Json) -> Vec<&mut Json> {
+    match json {
+        Json::Object(members) => members.iter_mut().map(|(_, value)| value).collect(),
+        Json::Array(items) => items.iter_mut().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Totals of the records dropped by the retention limit, so the win
+/// counter and streaks survive compaction.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Archive {
+    pub games: usize,
+    pub wins: usize,
+    pub hardcore_wins: usize,
+    pub best_streak: usize,
+    /// Wins in a row at the end of the archived games; a streak in the
+    /// kept records continues it.
+    pub trailing_streak: usize,
+}
+
+impl Archive {
+    /// Fold one finished record into the totals (oldest first).
+    fn absorb(&mut self, record: &GameRecord) {
+        self.games += 1;
+        if record.won {
+            self.wins += 1;
+            self.hardcore_wins += usize::from(record.hardcore);
+            self.trailing_streak += 1;
+            self.best_streak = self.best_streak.max(self.trailing_streak);
+        } else {
+            self.trailing_streak = 0;
+        }
+    }
+}
+
+/// The solver's move count for a seed's deal under some rules.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Par {
+    pub seed: u64,
+    #[serde(default)]
+    pub rules: Rules,
+    pub moves: u32,
+}
+
+/// How hard the solver found a seed's deal under some rules: the
+/// positions it explored, and whether it found a win within its budget.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Effort {
+    pub seed: u64,
+    pub rules: Rules,
+    pub nodes: u64,
+    pub solved: bool,
+}
+
 /// The entire game history.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct History {
     pub records: Vec<GameRecord>,
+    /// Games dropped by the retention limit.
+    pub archive: Archive,
+    /// Pars worked out so far, so a seed is only solved once.  They outlive
+    /// the records, so a replayed deal keeps its par.
+    #[serde(default)]
+    pub pars: Vec<Par>,
+    /// How hard the solver found each deal `report difficulty` rated, so
+    /// it is only rated once.  Like `pars`, they outlive the records.
+    #[serde(default)]
+    pub efforts: Vec<Effort>,
+    /// Challenges taken from friends' tokens, oldest first.
+    #[serde(default)]
+    pub challenges: Vec<ChallengeRecord>,
+    /// Best time and fewest moves on each seed won.  Like `pars`, they
+    /// outlive the records.
+    #[serde(default)]
+    pub seed_bests: Vec<SeedBest>,
+    /// Puzzle attempts, oldest first.
+    #[serde(default)]
+    pub puzzles: Vec<PuzzleRecord>,
+    /// Every rated game, oldest first.  Like challenges, they outlive the
+    /// records.
+    #[serde(default)]
+    pub ratings: Vec<RatingChange>,
+    /// How each weekly challenge went, oldest week first.
+    #[serde(default)]
+    pub weeklies: Vec<WeeklyRecord>,
+    /// Campaign stage attempts, oldest first.
+    #[serde(default)]
+    pub campaign: Vec<CampaignAttempt>,
+    /// The fastest win on each seed, traced to race against.  Like
+    /// `seed_bests`, they outlive the records.
+    #[serde(default)]
+    pub ghosts: Vec<Ghost>,
+}
+
+/// A campaign stage played with `campaign <n>` and how it went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignAttempt {
+    /// 1-based.
+    pub stage: usize,
+    /// Start time of the game record it is played in.
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub won: bool,
+    pub moves: u32,
+    pub elapsed_secs: u64,
+}
+
+/// The local leaderboard for one week's challenge deal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyRecord {
+    /// The week, as counted by `weekly::week`.
+    pub week: i64,
+    pub seed: u64,
+    /// Games finished on the deal that week, won or lost.
+    pub attempts: u32,
+    pub wins: u32,
+    pub best_secs: Option<u64>,
+    pub fewest_moves: Option<u32>,
+}
+
+/// The local leaderboard for one seed under some rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedBest {
+    pub seed: u64,
+    #[serde(default)]
+    pub rules: Rules,
+    pub wins: u32,
+    pub best_secs: u64,
+    /// Start time of the game that set `best_secs`.
+    pub best_secs_date: i64,
+    pub fewest_moves: u32,
+    pub fewest_moves_date: i64,
+}
+
+/// Which of a seed's records a win beat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeedRecords {
+    pub time: bool,
+    pub moves: bool,
+}
+
+/// A challenge taken with `challenge <token>` and how it went.  It is kept
+/// apart from the records so the result stays after compaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeRecord {
+    pub token: String,
+    pub seed: u64,
+    /// The par the token was sent with.
+    pub par: Option<u32>,
+    pub assist: AssistPreset,
+    pub hardcore: bool,
+    /// Start time of the game record it is played in.
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub won: bool,
+    pub moves: u32,
+    pub elapsed_secs: u64,
+}
+
+/// A puzzle played with `puzzle <n>` and how it went.  Like challenges,
+/// attempts are kept apart from the records so progress outlives them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzleRecord {
+    /// The pack's name and the puzzle's within it.
+    pub pack: String,
+    pub name: String,
+    pub goal: Goal,
+    /// The move limit it was played under.
+    pub moves_allowed: Option<u32>,
+    /// Start time of the game record it is played in.
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub solved: bool,
+    pub moves: u32,
 }
 
 impl History {
     pub fn total_wins(&self) -> usize {
-        self.records.iter().filter(|r| r.won).count()
+        self.archive.wins + self.records.iter().filter(|r| r.won && r.is_dealt()).count()
+    }
+
+    /// Consecutive wins ending with the most recently finished game.
+    pub fn current_streak(&self) -> usize {
+        let finished: Vec<&GameRecord> =
+            self.records.iter().rev().filter(|r| r.end_time.is_some() && r.is_dealt()).collect();
+        let streak = finished.iter().take_while(|r| r.won).count();
+        // Every kept game was a win: the streak reaches into the archive.
+        if streak == finished.len() {
+            streak + self.archive.trailing_streak
+        } else {
+            streak
+        }
+    }
+
+    /// Longest run of consecutive wins across all finished games.
+    pub fn best_streak(&self) -> usize {
+        let mut best = self.archive.best_streak;
+        let mut run = self.archive.trailing_streak;
+        for record in self.records.iter().filter(|r| r.end_time.is_some() && r.is_dealt()) {
+            if record.won {
+                run += 1;
+                best = best.max(run);
+            } else {
+                run = 0;
+            }
+        }
+        best
+    }
+
+    /// Wins played in hardcore mode.
+    pub fn hardcore_wins(&self) -> usize {
+        self.archive.hardcore_wins + self.records.iter().filter(|r| r.won && r.hardcore && r.is_dealt()).count()
+    }
+
+    /// Shrink the history: finished games lose their board snapshots (a
+    /// deal is reproduced from its seed), and with a non-zero `limit` the
+    /// oldest finished games beyond it are folded into the archive.
+    pub fn compact(&mut self, limit: usize) {
+        for record in self.records.iter_mut().filter(|r| r.end_time.is_some()) {
+            record.initial_board = None;
+            record.current_board = None;
+            record.undo_history = Vec::new();
+        }
+        let mut excess = if limit == 0 { 0 } else { self.records.len().saturating_sub(limit) };
+        let archive = &mut self.archive;
+        self.records.retain(|r| {
+            if excess == 0 || r.end_time.is_none() {
+                return true;
+            }
+            if r.is_dealt() {
+                archive.absorb(r);
+            }
+            excess -= 1;
+            false
+        });
+    }
+
+    /// Personal best for a split: the earliest it was reached in any game
+    /// before the latest one.
+    pub fn best_split(&self, kind: SplitKind) -> Option<u64> {
+        let (_, earlier) = self.records.split_last()?;
+        earlier
+            .iter()
+            .flat_map(|r| &r.splits)
+            .filter(|s| s.kind == kind)
+            .map(|s| s.at_ms)
+            .min()
+    }
+
+    /// The par of a seed's deal under `rules`, if it has been worked out.
+    pub fn par(&self, seed: u64, rules: Rules) -> Option<u32> {
+        self.pars.iter().find(|p| p.seed == seed && p.rules == rules).map(|p| p.moves)
+    }
+
+    /// The player's current rating.
+    pub fn rating(&self) -> u32 {
+        self.ratings.last().map_or(rating::START, |r| r.rating)
+    }
+
+    /// Rate a game on `seed` that ended at `time` against a deal rated
+    /// `opponent`, and keep the change.
+    pub fn rate_game(&mut self, seed: u64, time: i64, opponent: u32, won: bool) -> RatingChange {
+        let change = RatingChange { seed, time, opponent, won, rating: rating::update(self.rating(), opponent, won) };
+        self.ratings.push(change);
+        change
+    }
+
+    pub fn set_par(&mut self, seed: u64, rules: Rules, moves: u32) {
+        match self.pars.iter_mut().find(|p| p.seed == seed && p.rules == rules) {
+            Some(par) => par.moves = moves,
+            None => self.pars.push(Par { seed, rules, moves }),
+        }
+    }
+
+    /// How hard the solver found `seed`'s deal under `rules`, if it was
+    /// rated.
+    pub fn effort(&self, seed: u64, rules: Rules) -> Option<Effort> {
+        self.efforts.iter().find(|e| e.seed == seed && e.rules == rules).copied()
+    }
+
+    pub fn set_effort(&mut self, effort: Effort) {
+        match self.efforts.iter_mut().find(|e| e.seed == effort.seed && e.rules == effort.rules) {
+            Some(kept) => *kept = effort,
+            None => self.efforts.push(effort),
+        }
+    }
+
+    /// Copy the current game's result into its challenge, if it is one.
+    pub fn sync_challenge(&mut self) {
+        let Some(record) = self.records.last() else { return };
+        let entry = self.challenges.iter_mut().find(|c| c.seed == record.seed && c.start_time == record.start_time);
+        if let Some(entry) = entry {
+            entry.end_time = record.end_time;
+            entry.won = record.won;
+            entry.moves = record.moves;
+            entry.elapsed_secs = record.elapsed_secs;
+        }
+    }
+
+    /// The puzzle the current game is an attempt at, while the game is
+    /// open.  The attempt itself may already be over.
+    pub fn current_puzzle(&self) -> Option<&PuzzleRecord> {
+        let record = self.records.last().filter(|r| r.end_time.is_none())?;
+        self.puzzles.iter().rev().find(|p| p.start_time == record.start_time)
+    }
+
+    /// Close the current game's puzzle attempt, if it is still open.
+    pub fn finish_puzzle(&mut self, solved: bool, moves: u32, now: i64) {
+        let Some(start_time) = self.current_puzzle().map(|p| p.start_time) else { return };
+        let entry = self.puzzles.iter_mut().rev().find(|p| p.start_time == start_time && p.end_time.is_none());
+        if let Some(entry) = entry {
+            entry.end_time = Some(now);
+            entry.solved = solved;
+            entry.moves = moves;
+        }
+    }
+
+    /// A finished game ends its puzzle attempt, if it was still open: a
+    /// win meets any goal, anything else leaves it unsolved.
+    pub fn sync_puzzle(&mut self) {
+        let Some(record) = self.records.last().filter(|r| r.end_time.is_some()) else { return };
+        let entry = self.puzzles.iter_mut().find(|p| p.start_time == record.start_time && p.end_time.is_none());
+        if let Some(entry) = entry {
+            entry.end_time = record.end_time;
+            entry.solved = record.won;
+            entry.moves = record.moves;
+        }
+    }
+
+    /// How many campaign stages have been won; the one after is open.
+    pub fn campaign_cleared(&self) -> usize {
+        self.campaign.iter().filter(|a| a.won).map(|a| a.stage).max().unwrap_or(0)
+    }
+
+    /// The campaign stage the current game is an attempt at, while the
+    /// game is open.
+    pub fn current_campaign_stage(&self) -> Option<usize> {
+        let record = self.records.last().filter(|r| r.end_time.is_none())?;
+        self.campaign.iter().rev().find(|a| a.start_time == record.start_time).map(|a| a.stage)
+    }
+
+    /// Close the finished game's campaign attempt, if it was one.
+    pub fn sync_campaign(&mut self) {
+        let Some(record) = self.records.last().filter(|r| r.end_time.is_some()) else { return };
+        let entry = self.campaign.iter_mut().find(|a| a.start_time == record.start_time && a.end_time.is_none());
+        if let Some(entry) = entry {
+            entry.end_time = record.end_time;
+            entry.won = record.won;
+            entry.moves = record.moves;
+            entry.elapsed_secs = record.elapsed_secs;
+        }
+    }
+
+    /// Attempts at a puzzle and whether any solved it.
+    pub fn puzzle_progress(&self, pack: &str, name: &str) -> (usize, bool) {
+        let attempts = self.puzzles.iter().filter(|p| p.pack == pack && p.name == name);
+        attempts.fold((0, false), |(n, solved), p| (n + 1, solved || p.solved))
+    }
+
+    /// Indices of the games still in progress, most recently played first.
+    /// Only the last record is the current game; the others wait for
+    /// `resume`.
+    pub fn unfinished(&self) -> Vec<usize> {
+        (0..self.records.len()).rev().filter(|&i| self.records[i].end_time.is_none()).collect()
+    }
+
+    /// Records matching `filter`, newest first, with their 1-based number
+    /// in the whole history.
+    pub fn list(&self, filter: RecordFilter) -> Vec<(usize, &GameRecord)> {
+        self.records
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, r)| filter.matches(r))
+            .map(|(i, r)| (i + 1, r))
+            .collect()
+    }
+
+    /// Add finished records from another history, skipping ones already
+    /// present (same seed and start time), and keep the whole list in start
+    /// order.  An unfinished latest record stays last: it is the current
+    /// game.
+    pub fn merge(&mut self, incoming: Vec<GameRecord>) -> MergeReport {
+        let mut report = MergeReport::default();
+        let current = self.records.pop_if(|r| r.end_time.is_none());
+        for record in incoming {
+            let known = |r: &GameRecord| r.seed == record.seed && r.start_time == record.start_time;
+            if record.end_time.is_none() {
+                report.unfinished += 1;
+            } else if self.records.iter().chain(&current).any(known) {
+                report.duplicates += 1;
+            } else {
+                self.record_seed_best(&record);
+                self.records.push(record);
+                report.added += 1;
+            }
+        }
+        self.records.sort_by_key(|r| r.start_time);
+        self.records.extend(current);
+        report
+    }
+
+    pub fn seed_best(&self, seed: u64, rules: Rules) -> Option<&SeedBest> {
+        self.seed_bests.iter().find(|b| b.seed == seed && b.rules == rules)
+    }
+
+    /// Enter a finished game into its week's challenge leaderboard, if it
+    /// was dealt as that week's challenge.
+    pub fn record_weekly(&mut self, record: &GameRecord) {
+        let week = weekly::week(record.start_time);
+        if !record.weekly || record.end_time.is_none() {
+            return;
+        }
+        let entry = match self.weeklies.iter().position(|w| w.week == week) {
+            Some(i) => &mut self.weeklies[i],
+            None => {
+                let at = self.weeklies.partition_point(|w| w.week < week);
+                let empty = WeeklyRecord { week, seed: record.seed, attempts: 0, wins: 0, best_secs: None, fewest_moves: None };
+                self.weeklies.insert(at, empty);
+                &mut self.weeklies[at]
+            }
+        };
+        entry.attempts += 1;
+        if record.won {
+            entry.wins += 1;
+            entry.best_secs = Some(entry.best_secs.map_or(record.elapsed_secs, |b| b.min(record.elapsed_secs)));
+            entry.fewest_moves = Some(entry.fewest_moves.map_or(record.moves, |b| b.min(record.moves)));
+        }
+    }
+
+    /// The ghost of the fastest win on `seed` under `rules`, if one was
+    /// traced.
+    pub fn ghost(&self, seed: u64, rules: Rules) -> Option<&Ghost> {
+        self.ghosts.iter().find(|g| g.seed == seed && g.rules == rules)
+    }
+
+    /// Keep `ghost` as its deal's, replacing the one it beat.
+    pub fn set_ghost(&mut self, ghost: Ghost) {
+        match self.ghosts.iter_mut().find(|g| g.seed == ghost.seed && g.rules == ghost.rules) {
+            Some(old) => *old = ghost,
+            None => self.ghosts.push(ghost),
+        }
+    }
+
+    /// Enter a win into its seed's leaderboard, returning the records it
+    /// beat.  A seed's first win sets both.
+    pub fn record_seed_best(&mut self, record: &GameRecord) -> SeedRecords {
+        // Shared positions are not their seed's deal, and older saves have
+        // no move count or time for their wins.
+        if !record.won || !record.is_dealt() || record.moves == 0 {
+            return SeedRecords::default();
+        }
+        let Some(best) = self.seed_bests.iter_mut().find(|b| b.seed == record.seed && b.rules == record.rules) else {
+            self.seed_bests.push(SeedBest {
+                seed: record.seed,
+                rules: record.rules,
+                wins: 1,
+                best_secs: record.elapsed_secs,
+                best_secs_date: record.start_time,
+                fewest_moves: record.moves,
+                fewest_moves_date: record.start_time,
+            });
+            return SeedRecords { time: true, moves: true };
+        };
+        best.wins += 1;
+        let beat = SeedRecords { time: record.elapsed_secs < best.best_secs, moves: record.moves < best.fewest_moves };
+        if beat.time {
+            best.best_secs = record.elapsed_secs;
+            best.best_secs_date = record.start_time;
+        }
+        if beat.moves {
+            best.fewest_moves = record.moves;
+            best.fewest_moves_date = record.start_time;
+        }
+        beat
+    }
+
+    /// Build the per-seed bests from the kept records, for saves from
+    /// before they were tracked.
+    fn backfill_seed_bests(&mut self) {
+        let records = std::mem::take(&mut self.records);
+        for record in &records {
+            self.record_seed_best(record);
+        }
+        self.records = records;
+    }
+
+    /// Compare the latest game against every earlier win.  A first win is
+    /// a personal best on every count.
+    pub fn latest_bests(&self) -> PersonalBests {
+        let Some((last, earlier)) = self.records.split_last() else {
+            return PersonalBests::default();
+        };
+        if !last.won || !last.is_dealt() {
+            return PersonalBests::default();
+        }
+        let mut bests = PersonalBests { score: true, time: true, moves: true };
+        // Older saves have no move count or time for their wins.
+        for r in earlier.iter().filter(|r| r.won && r.moves > 0 && r.is_dealt()) {
+            bests.score &= last.score() > r.score();
+            bests.time &= last.elapsed_secs < r.elapsed_secs;
+            bests.moves &= last.moves < r.moves;
+        }
+        bests
     }
 
     /// Load the history from disk. If the file doesn't exist or is corrupted/tampered,
     /// returns an empty new History to avoid crashing the game.
+    ///
+    /// Of `history.dat` and `history.json`, the newer one is read, so
+    /// switching `save_format` either way carries the games over.
     pub fn load() -> Self {
-        let Some(path) = Self::file_path() else {
+        if IN_MEMORY.load(Ordering::Relaxed) {
+            return Self::default();
+        }
+        let Some(dir) = crate::profile::data_dir() else {
             return Self::default();
         };
-
-        if !path.exists() {
+        // Left behind by a write that was cut short; never the real save.
+        if !LOCKED_OUT.load(Ordering::Relaxed) {
+            let _ = fs::remove_file(dir.join("history.tmp"));
+        }
+        let other = match Self::format() {
+            SaveFormat::Binary => SaveFormat::Json,
+            SaveFormat::Json => SaveFormat::Binary,
+        };
+        // On a tie the configured format wins (`max_by_key` keeps the last).
+        let newest = [other, Self::format()]
+            .into_iter()
+            .filter_map(Self::path_for)
+            .filter_map(|p| Some((fs::metadata(&p).and_then(|m| m.modified()).ok()?, p)))
+            .max_by_key(|(modified, _)| *modified);
+        let Some((_, path)) = newest else {
+            log::info!("no save in {}", dir.display());
+            return Self::default();
+        };
+        let Ok(data) = fs::read(&path) else {
+            log::warn!("could not read {}", path.display());
             return Self::default();
+        };
+        match Self::parse(&data) {
+            Ok(history) => {
+                log::info!("loaded {} games from {}", history.records.len(), path.display());
+                history
+            }
+            // Too small to even contain the HMAC: nothing worth keeping.
+            Err(Unreadable::Truncated) => Self::default(),
+            Err(problem) => {
+                // Point a hand-editor at the mistake.
+                if Self::is_json(&data) && let Err(detail) = Self::parse_json(&data) {
+                    eprintln!("[WARN] {}: {}", path.display(), detail);
+                }
+                Self::set_aside(&path, problem.message());
+                Self::default()
+            }
         }
+    }
 
-        Self::snapshot_current_file(&path);
-
-        let mut file = match File::open(&path) {
-            Ok(f) => f,
-            Err(_) => return Self::default(),
-        };
+    fn is_json(data: &[u8]) -> bool {
+        data.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+    }
 
-        let mut data = Vec::new();
-        if file.read_to_end(&mut data).is_err() {
-            return Self::default();
+    /// Decode a `history.json` save.
+    fn parse_json(data: &[u8]) -> Result<History, String> {
+        let text = std::str::from_utf8(data).map_err(|e| e.to_string())?;
+        let doc = Json::parse(text)?;
+        if doc.get("format").and_then(Json::as_str) != Some(JSON_SAVE_FORMAT) {
+            return Err(format!("not an szsol save (\"format\" should be \"{}\")", JSON_SAVE_FORMAT));
+        }
+        match doc.get("version").and_then(Json::as_int) {
+            Some(JSON_SAVE_VERSION) => {}
+            Some(v) if v > JSON_SAVE_VERSION => return Err(format!("save version {} is newer than this szsol", v)),
+            _ => return Err("missing or unknown \"version\"".to_string()),
         }
+        let mut history = doc.get("history").ok_or("missing \"history\"")?.clone();
+        expand_boards(&mut history).map_err(|e| format!("history: {}", e))?;
+        let history = &history;
+        let mut parsed: History = json_serde::from_json(history).map_err(|e| format!("history: {}", e))?;
+        if history.get("seed_bests").is_none() {
+            parsed.backfill_seed_bests();
+        }
+        Ok(parsed)
+    }
 
+    /// The whole history as pretty JSON, for `save_format = "json"`.
+    fn to_json_save(&self) -> Option<Vec<u8>> {
+        let mut history = json_serde::to_json(self).ok()?;
+        compact_boards(&mut history).ok()?;
+        let doc = Json::Object(vec![
+            ("format".into(), JSON_SAVE_FORMAT.into()),
+            ("version".into(), Json::Int(JSON_SAVE_VERSION)),
+            ("history".into(), history),
+        ]);
+        Some(doc.pretty().into_bytes())
+    }
+
+    /// Verify a save file's signature and decode it.  JSON saves carry no
+    /// signature.
+    fn parse(data: &[u8]) -> Result<History, Unreadable> {
+        if Self::is_json(data) {
+            return Self::parse_json(data).map_err(|_| Unreadable::Corrupt);
+        }
         if data.len() < HMAC_SIZE {
-            // File is too small to even contain the HMAC
-            return Self::default();
+            return Err(Unreadable::Truncated);
         }
 
+        // The signature covers everything before it, header included.
         let split_idx = data.len() - HMAC_SIZE;
-        let payload = &data[..split_idx];
+        let signed = &data[..split_idx];
         let signature = &data[split_idx..];
 
         // Verify HMAC
-        let mut mac = match HmacSha256::new_from_slice(SECRET_KEY) {
-            Ok(m) => m,
-            Err(_) => return Self::default(),
+        let verifies = |key: &[u8]| {
+            HmacSha256::new_from_slice(key).is_ok_and(|mut mac| {
+                mac.update(signed);
+                mac.verify_slice(signature).is_ok()
+            })
         };
-        mac.update(payload);
-        if mac.verify_slice(signature).is_err() {
+        if !verifies(Self::signing_key()) && !verifies(LEGACY_KEY) {
             // Tampered or corrupted file
-            eprintln!("[WARN] Save file signature mismatched! Starting with fresh history.");
-            return Self::default();
+            return Err(Unreadable::Signature);
+        }
+
+        let (version, payload) = match signed.strip_prefix(SAVE_MAGIC.as_slice()) {
+            Some(rest) if rest.len() >= 4 => {
+                let version = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+                (Some(version), &rest[4..])
+            }
+            _ => (None, signed),
+        };
+        if version.is_some_and(|v| v > SAVE_VERSION) {
+            return Err(Unreadable::Newer);
+        }
+        Self::decode(version, payload).ok_or(Unreadable::Corrupt)
+    }
+
+    /// Decode a payload of the given format version; `None` is a save from
+    /// before the header, which is upgraded.
+    fn decode(version: Option<u32>, payload: &[u8]) -> Option<History> {
+        match version {
+            None => {
+                let mut history = History::from(bincode::deserialize::<LegacyHistory>(payload).ok()?);
+                history.backfill_seed_bests();
+                Some(history)
+            }
+            Some(SAVE_VERSION) => bincode::deserialize(&compress::decompress(payload)?).ok(),
+            Some(_) => None,
         }
+    }
 
-        match bincode::deserialize(payload) {
-            Ok(history) => history,
-            Err(_) => Self::default(),
+    /// Keep a copy of an unreadable save next to it before a fresh history
+    /// overwrites it, so nothing is lost for good.
+    fn set_aside(path: &Path, problem: &str) {
+        log::warn!("{} is unreadable: {}", path.display(), problem);
+        let mut kept = path.as_os_str().to_os_string();
+        kept.push(".unreadable");
+        let kept = PathBuf::from(kept);
+        match fs::copy(path, &kept) {
+            Ok(_) => eprintln!(
+                "[WARN] {} Starting with fresh history; the old file was kept as {}.",
+                problem,
+                kept.display()
+            ),
+            Err(_) => eprintln!("[WARN] {} Starting with fresh history.", problem),
         }
     }
 
-    /// Save the history to disk atomically to prevent corruption.
+    /// Keep every history in memory from now on: nothing is read from or
+    /// written to the save file.
+    pub fn in_memory() {
+        IN_MEMORY.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`History::in_memory`] is in effect.
+    pub fn is_in_memory() -> bool {
+        IN_MEMORY.load(Ordering::Relaxed)
+    }
+
+    /// Take the instance lock on the data directory.  `false` means another
+    /// running szsol holds it, and this one's saves are skipped.
+    pub fn lock() -> bool {
+        let Some(dir) = crate::profile::data_dir() else {
+            return true;
+        };
+        let _ = fs::create_dir_all(&dir);
+        let opened = OpenOptions::new().create(true).truncate(false).write(true).open(dir.join("history.lock"));
+        let Ok(file) = opened else {
+            return true;
+        };
+        match file.try_lock() {
+            Ok(()) => {
+                let _ = INSTANCE_LOCK.set(file);
+                true
+            }
+            Err(TryLockError::WouldBlock) => {
+                LOCKED_OUT.store(true, Ordering::Relaxed);
+                false
+            }
+            // Filesystems without locking: carry on unguarded.
+            Err(TryLockError::Error(_)) => true,
+        }
+    }
+
+    /// Queue the history for saving.  Only the encoding happens here; the
+    /// background saver compresses, signs and writes it once commands stop
+    /// arriving for a moment, so a long history never lags input.
     pub fn save(&self) {
-        let Some(path) = Self::file_path() else { return };
-        
+        self.queue(false);
+    }
+
+    /// Queue the history without writing it yet: it reaches the disk with
+    /// the next `save` or at exit.
+    pub fn save_later(&self) {
+        self.queue(true);
+    }
+
+    fn queue(&self, deferred: bool) {
+        if LOCKED_OUT.load(Ordering::Relaxed) || IN_MEMORY.load(Ordering::Relaxed) {
+            return;
+        }
+        let format = Self::format();
+        let Some(path) = Self::path_for(format) else { return };
+        let encoded = match format {
+            SaveFormat::Binary => bincode::serialize(self).ok(),
+            SaveFormat::Json => self.to_json_save(),
+        };
+        let Some(encoded) = encoded else { return };
+
+        let saver = SAVER.get_or_init(|| {
+            std::thread::spawn(Self::save_in_background);
+            Saver::default()
+        });
+        let mut state = saver.state.lock().unwrap_or_else(|e| e.into_inner());
+        // A newer snapshot simply replaces one that hasn't been written yet,
+        // and stays due if that one was.
+        state.deferred = deferred && (state.pending.is_none() || state.deferred);
+        log::debug!("queued {} bytes for {}{}", encoded.len(), path.display(), if deferred { " (deferred)" } else { "" });
+        state.pending = Some((path, encoded, format));
+        saver.changed.notify_all();
+    }
+
+    /// Write any queued save right away.  Call before the process exits.
+    pub fn flush() {
+        if let Some(saver) = SAVER.get() {
+            saver.write_pending();
+        }
+    }
+
+    fn save_in_background() {
+        let saver = SAVER.wait();
+        loop {
+            let mut state = saver.state.lock().unwrap_or_else(|e| e.into_inner());
+            while state.pending.is_none() || state.deferred {
+                state = saver.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+            }
+            drop(state);
+            std::thread::sleep(SAVE_DEBOUNCE);
+            saver.write_pending();
+        }
+    }
+
+    /// Save the history to disk atomically to prevent corruption.
+    fn write_encoded(path: &Path, encoded: &[u8]) -> io::Result<()> {
         // Ensure the directory exists
         if let Some(dir) = path.parent() {
             let _ = fs::create_dir_all(dir);
         }
 
-        let payload = match bincode::serialize(self) {
-            Ok(p) => p,
-            Err(_) => return,
-        };
+        let mut payload = SAVE_MAGIC.to_vec();
+        payload.extend_from_slice(&SAVE_VERSION.to_le_bytes());
+        payload.extend_from_slice(&compress::compress(encoded));
 
-        let mut mac = match HmacSha256::new_from_slice(SECRET_KEY) {
+        let mut mac = match HmacSha256::new_from_slice(Self::signing_key()) {
             Ok(m) => m,
-            Err(_) => return,
+            Err(e) => return Err(io::Error::other(e)),
         };
         mac.update(&payload);
         let signature = mac.finalize().into_bytes();
 
         let mut final_data = payload.clone();
         final_data.extend_from_slice(&signature);
+        Self::write_file(path, &final_data)
+    }
+
+    /// Replace the save file, keeping the previous one as backup 1.
+    fn write_file(path: &Path, data: &[u8]) -> io::Result<()> {
+        // Rewriting identical bytes would only push a real backup out.
+        if fs::read(path).is_ok_and(|current| current == data) {
+            return Ok(());
+        }
+        Self::rotate_backups(path);
 
         // Atomic write: write to temp file, then rename.
         // On Unix, `rename` is atomic. On Windows, `rename` is also mostly atomic,
         // but can fail if the target is held open. Standard Rust `fs::rename` uses `MoveFileExW` 
         // with `MOVEFILE_REPLACE_EXISTING`, which is atomic enough for this use-case.
-        let mut temp_path = path.clone();
+        let mut temp_path = path.to_path_buf();
         temp_path.set_extension("tmp");
 
-        let mut temp_file = match File::create(&temp_path) {
-            Ok(f) => f,
-            Err(_) => return,
-        };
-
-        if temp_file.write_all(&final_data).is_err() {
-            let _ = fs::remove_file(&temp_path);
-            return;
-        }
+        let mut temp_file = File::create(&temp_path)?;
 
         // Flush all OS buffers to disk before renaming to ensure data integrity
         // in case of a sudden power loss exactly during or after rename.
-        if temp_file.sync_all().is_err() {
+        if let Err(e) = temp_file.write_all(data).and_then(|_| temp_file.sync_all()) {
             let _ = fs::remove_file(&temp_path);
-            return;
+            return Err(e);
         }
 
-        let _ = fs::rename(&temp_path, &path);
+        fs::rename(&temp_path, path)
     }
 
-    /// Get the path to the save file (`history.dat`).
+    /// Get the path to the save file (`history.dat` or `history.json`).
     fn file_path() -> Option<PathBuf> {
-        let proj_dirs = ProjectDirs::from("com", "szsol", "szsol")?;
-        Some(proj_dirs.data_dir().join("history.dat"))
+        Self::path_for(Self::format())
+    }
+
+    fn signing_key() -> &'static [u8] {
+        SIGNING_KEY.get_or_init(|| Self::load_or_create_key().unwrap_or_else(|| LEGACY_KEY.to_vec()))
+    }
+
+    /// Sign `data` with this install's key.  Returns the signature and an
+    /// id for the install (a hash of its key), both as hex.
+    #[cfg(feature = "leaderboard")]
+    pub fn sign(data: &[u8]) -> (String, String) {
+        use sha2::Digest;
+        let key = Self::signing_key();
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(data);
+        let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        (hex(&mac.finalize().into_bytes()), hex(&Sha256::digest(key)[..8]))
+    }
+
+    /// Read this install's key, making one on first use.  The file holds
+    /// 32 random bytes as hex.
+    fn load_or_create_key() -> Option<Vec<u8>> {
+        let path = crate::profile::config_dir()?.join(KEY_FILE);
+        if let Ok(text) = fs::read_to_string(&path) {
+            return decode_hex(text.trim()).filter(|key| key.len() == 32);
+        }
+        let key: [u8; 32] = rand::random();
+        let hex: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+        fs::create_dir_all(path.parent()?).ok()?;
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path).ok()?;
+        file.write_all(format!("{}\n", hex).as_bytes()).ok()?;
+        file.sync_all().ok()?;
+        Some(key.to_vec())
+    }
+
+    fn path_for(format: SaveFormat) -> Option<PathBuf> {
+        let name = match format {
+            SaveFormat::Binary => "history.dat",
+            SaveFormat::Json => "history.json",
+        };
+        Some(crate::profile::data_dir()?.join(name))
     }
 
-    fn snapshot_current_file(path: &PathBuf) {
-        if Self::same_as_latest_snapshot(path) {
+    fn format() -> SaveFormat {
+        SAVE_FORMAT.get().copied().unwrap_or(SaveFormat::Binary)
+    }
+
+    /// The backups on disk, newest first, each checked like a save file.
+    pub fn backups() -> Vec<Backup> {
+        let Some(path) = Self::file_path() else {
+            return Vec::new();
+        };
+        Self::adopt_legacy_backups(&path);
+        (1..=BACKUP_COUNT)
+            .filter_map(|index| {
+                let backup = Self::backup_path(&path, index);
+                let data = fs::read(&backup).ok()?;
+                let modified = fs::metadata(&backup)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64);
+                Some(Backup { index, modified, contents: Self::parse(&data) })
+            })
+            .collect()
+    }
+
+    /// Put backup `index` back as the save file once its signature checks
+    /// out.  The save it replaces becomes backup 1.
+    pub fn restore_backup(index: usize) -> Result<History, &'static str> {
+        if LOCKED_OUT.load(Ordering::Relaxed) {
+            return Err("Another szsol is using this profile; close it first.");
+        }
+        let path = Self::file_path().ok_or("No save directory is available.")?;
+        Self::adopt_legacy_backups(&path);
+        let data = fs::read(Self::backup_path(&path, index)).map_err(|_| "No such backup.")?;
+        let history = Self::parse(&data).map_err(Unreadable::message)?;
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        Self::write_file(&path, &data).map_err(|_| "Could not write the save file.")?;
+        Ok(history)
+    }
+
+    /// Shift `history.dat.1` … `.N` down one and copy the current save to
+    /// `.1`, so a bad write never takes every copy of the progress with it.
+    fn rotate_backups(path: &Path) {
+        Self::adopt_legacy_backups(path);
+        if !path.exists() || Self::same_as_latest_backup(path) {
             return;
         }
 
-        for idx in (1..=SNAPSHOT_COUNT).rev() {
-            let src = Self::snapshot_path(path, idx);
-            let dst = Self::snapshot_path(path, idx + 1);
+        for idx in (1..=BACKUP_COUNT).rev() {
+            let src = Self::backup_path(path, idx);
+            let dst = Self::backup_path(path, idx + 1);
 
-            if idx == SNAPSHOT_COUNT {
+            if idx == BACKUP_COUNT {
                 let _ = fs::remove_file(&src);
             } else if src.exists() {
                 let _ = fs::rename(&src, &dst);
             }
         }
 
-        let newest = Self::snapshot_path(path, 1);
+        let newest = Self::backup_path(path, 1);
         let _ = fs::copy(path, newest);
     }
 
-    fn snapshot_path(path: &PathBuf, idx: usize) -> PathBuf {
-        let mut snapshot = path.clone().into_os_string();
-        snapshot.push(format!(".bak{idx}"));
-        PathBuf::from(snapshot)
+    /// Older versions named the backups `history.dat.bak1` … `.bakN`; give
+    /// any still lying around the current names so they rotate and restore
+    /// like the rest.
+    fn adopt_legacy_backups(path: &Path) {
+        for idx in 1..=BACKUP_COUNT {
+            let mut legacy = path.as_os_str().to_os_string();
+            legacy.push(format!(".bak{idx}"));
+            let legacy = PathBuf::from(legacy);
+            let current = Self::backup_path(path, idx);
+            if legacy.exists() && !current.exists() {
+                let _ = fs::rename(&legacy, &current);
+            }
+        }
     }
 
-    fn same_as_latest_snapshot(path: &PathBuf) -> bool {
-        let latest = Self::snapshot_path(path, 1);
+    fn backup_path(path: &Path, idx: usize) -> PathBuf {
+        let mut backup = path.as_os_str().to_os_string();
+        backup.push(format!(".{idx}"));
+        PathBuf::from(backup)
+    }
+
+    fn same_as_latest_backup(path: &Path) -> bool {
+        let latest = Self::backup_path(path, 1);
         let Ok(current_meta) = fs::metadata(path) else {
             return false;
         };
@@ -233,3 +1393,13 @@ impl History {
         current == previous
     }
 }
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
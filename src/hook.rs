@@ -0,0 +1,63 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The `on_finish` hook: a shell command run whenever a game is won or
+//! given up, with the result in its environment:
+//!
+//! * `SZSOL_RESULT`: `won` or `lost`
+//! * `SZSOL_SEED`: the deal's seed
+//! * `SZSOL_SECONDS`: play time in whole seconds
+//! * `SZSOL_MOVES`: moves made
+//!
+//! The command runs in the background with no terminal, so a slow or
+//! chatty one cannot hold up or garble the game.
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How a game ended, for the hook.
+pub struct Finish {
+    pub won: bool,
+    pub seed: u64,
+    pub elapsed: Duration,
+    pub moves: u32,
+}
+
+/// Start `command` for `finish`.  Failures only reach the diagnostics log.
+pub fn run(command: &str, finish: &Finish) {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let spawned = shell
+        .arg(command)
+        .env("SZSOL_RESULT", if finish.won { "won" } else { "lost" })
+        .env("SZSOL_SEED", finish.seed.to_string())
+        .env("SZSOL_SECONDS", finish.elapsed.as_secs().to_string())
+        .env("SZSOL_MOVES", finish.moves.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        // Reap it once it is done, without waiting here.
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                if let Ok(status) = child.wait() {
+                    log::debug!("on_finish hook exited with {}", status);
+                }
+            });
+        }
+        Err(e) => log::warn!("could not run the on_finish hook: {}", e),
+    }
+}
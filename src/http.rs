@@ -0,0 +1,48 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! HTTPS requests through the system `curl`, so no build links network or
+//! TLS code.  Used by the leaderboard and `telemetry send`.
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// `url` without a trailing slash, or an error naming the config key
+/// `setting` it came from.  Requests are only ever sent over HTTPS.
+pub fn https_base<'a>(url: &'a str, setting: &str) -> Result<&'a str, String> {
+    let url = url.trim().trim_end_matches('/');
+    if !url.starts_with("https://") {
+        return Err(format!("{} must start with https:// (got '{}')", setting, url));
+    }
+    Ok(url)
+}
+
+/// GET `url`, or POST `body` to it as JSON, and return the response body.
+pub fn request(url: &str, body: Option<&str>) -> Result<String, String> {
+    let mut curl = Command::new("curl");
+    curl.args(["--silent", "--show-error", "--fail", "--max-time", "15", "--proto", "=https"]);
+    if body.is_some() {
+        curl.args(["--header", "Content-Type: application/json", "--data-binary", "@-"]);
+    }
+    let mut child = curl
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    if let (Some(body), Some(mut stdin)) = (body, child.stdin.take()) {
+        stdin.write_all(body.as_bytes()).map_err(|e| format!("could not send the request: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("curl failed: {}", e))?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(error.trim().trim_start_matches("curl: ").to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|_| "unreadable reply".to_string())
+}
@@ -0,0 +1,709 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Message catalog for user-facing text.
+//!
+//! Messages are written in English at the call site with [`tr!`], which looks
+//! the English text up in the catalog of the active language and falls back
+//! to it when there is no translation.  Placeholders are `{}` (in order) or
+//! `{0}`, `{1}`, … so a translation can reorder its arguments.
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::board::{Location, MoveError};
+use crate::card::{LabelProvider, Suit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Zh,
+}
+
+impl Lang {
+    /// Parse a config value or a POSIX locale such as `zh_CN.UTF-8`.
+    pub fn parse(value: &str) -> Option<Lang> {
+        let value = value.trim().to_ascii_lowercase();
+        if value.starts_with("zh") {
+            Some(Lang::Zh)
+        } else if value.starts_with("en") || value == "c" || value == "posix" {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    /// The language requested by the locale environment (`LC_ALL`,
+    /// `LC_MESSAGES`, then `LANG`), if it is one we ship.
+    pub fn from_env() -> Option<Lang> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.is_empty())
+            .and_then(|v| Lang::parse(&v))
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Zh => "zh",
+        }
+    }
+}
+
+/// Set while Chinese is active; English otherwise.
+static ZH_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Select the language for messages from now on.  Startup sets it from the
+/// locale first (for the profile prompt), then from the profile's config.
+pub fn set_lang(lang: Lang) {
+    ZH_ACTIVE.store(lang == Lang::Zh, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    if ZH_ACTIVE.load(Ordering::Relaxed) { Lang::Zh } else { Lang::En }
+}
+
+/// Translate `msg` into the active language (or return it unchanged).
+pub fn lookup(msg: &'static str) -> &'static str {
+    match lang() {
+        Lang::En => msg,
+        Lang::Zh => ZH.iter().find(|(en, _)| *en == msg).map_or(msg, |(_, zh)| zh),
+    }
+}
+
+/// Substitute `{}` / `{N}` placeholders in `template`.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        let inner = &rest[open + 1..open + close];
+        let index = if inner.is_empty() {
+            next += 1;
+            Some(next - 1)
+        } else {
+            inner.parse::<usize>().ok()
+        };
+        match index.and_then(|i| args.get(i)) {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `tr!("Rewound {} step(s).", n)`: look up and fill a catalog message.
+#[macro_export]
+macro_rules! tr {
+    ($msg:literal) => {
+        $crate::i18n::lookup($msg).to_string()
+    };
+    ($msg:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::lookup($msg), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+pub use crate::tr;
+
+pub fn suit_name(suit: Suit) -> String {
+    match suit {
+        Suit::Red => tr!("Red"),
+        Suit::Green => tr!("Green"),
+        Suit::Black => tr!("Black"),
+        Suit::Yellow => tr!("Yellow"),
+    }
+}
+
+/// Localized explanation of a rejected move.  Cards use the renderer's
+/// label set and slots its numbering base, so messages match the board.
+pub fn move_error(e: &MoveError, labels: &dyn LabelProvider, base: usize) -> String {
+    let l = |card| labels.card(card);
+    match e {
+        MoveError::EmptySource(Location::Column(c)) => tr!("Column {} is empty", c + base),
+        MoveError::EmptySource(Location::FreeCell(c)) => tr!("Free cell {} is empty", c + base),
+        MoveError::EmptySource(_) => tr!("Cards cannot be taken from the foundation"),
+        MoveError::SameColumn => tr!("Source and destination columns are the same"),
+        MoveError::SameSuit { card, onto } => tr!("{} cannot stack on {} (same suit)", l(*card), l(*onto)),
+        MoveError::SameColor { card, onto } => tr!("{} cannot stack on {} (same color)", l(*card), l(*onto)),
+        MoveError::WrongRank { card, onto } => {
+            let needed = onto.value().map(|v| v.saturating_sub(1)).unwrap_or(0);
+            tr!("{} cannot stack on {} (needs a {})", l(*card), l(*onto), needed)
+        }
+        MoveError::NotStackable { card, onto } => {
+            tr!("{} cannot stack on {} (only numbered cards stack)", l(*card), l(*onto))
+        }
+        MoveError::FreeCellOccupied { cell, card } => {
+            tr!("Free cell {} already holds {}", cell + base, l(*card))
+        }
+        MoveError::FreeCellLocked { cell, suit } => {
+            tr!("Free cell {} is dragon-locked ({})", cell + base, suit_name(*suit))
+        }
+        MoveError::FoundationOrder { card, needed: Some(needed) } => {
+            tr!("Foundation needs {} next, not {}", l(*needed), l(*card))
+        }
+        MoveError::FoundationOrder { card, needed: None } => {
+            tr!("Foundation for {} is already complete", l(*card))
+        }
+        MoveError::NotFoundationCard(card) => {
+            tr!("{} cannot go to a foundation (dragons are merged instead)", l(*card))
+        }
+        MoveError::InvalidDestination(_) => tr!("Use the foundation command to send cards to the foundation"),
+        MoveError::StackOutOfBounds { col, start_idx } => {
+            tr!("Column {} has no card at position {}", col + base, start_idx)
+        }
+        MoveError::NotASequence { col } => {
+            tr!("The selected cards in column {} are not a descending, alternating-suit run", col + base)
+        }
+        MoveError::StackTooLong { len, max } => {
+            tr!("Only {} cards can move at once with the free space left, not {}", max, len)
+        }
+        MoveError::DragonsNotExposed { suit, exposed, total } => {
+            tr!("Only {} of {} {} dragons are exposed", exposed, total, suit_name(*suit).to_lowercase())
+        }
+        MoveError::NoCellForDragons(suit) => {
+            tr!("No free cell is available to lock the {} dragons", suit_name(*suit).to_lowercase())
+        }
+    }
+}
+
+/// English → Simplified Chinese.
+const ZH: &[(&str, &str)] = &[
+    // Suits
+    ("Red", "红"),
+    ("Green", "绿"),
+    ("Black", "黑"),
+    ("Yellow", "黄"),
+    // Move errors
+    ("Column {} is empty", "第 {} 列是空的"),
+    ("Free cell {} is empty", "空位 {} 是空的"),
+    ("Cards cannot be taken from the foundation", "不能从收牌区取回卡牌"),
+    ("Source and destination columns are the same", "起点列和终点列相同"),
+    ("{} cannot stack on {} (same suit)", "{} 不能叠在 {} 上（同花色）"),
+    ("{} cannot stack on {} (same color)", "{} 不能叠在 {} 上（同颜色）"),
+    (
+        "Only {} cards can move at once with the free space left, not {}",
+        "剩余空位一次只能移动 {} 张牌，不能移动 {} 张",
+    ),
+    ("{} cannot stack on {} (needs a {})", "{} 不能叠在 {} 上（需要 {}）"),
+    ("{} cannot stack on {} (only numbered cards stack)", "{} 不能叠在 {} 上（只有数字牌可以叠放）"),
+    ("Free cell {} already holds {}", "空位 {} 已经放着 {}"),
+    ("Free cell {} is dragon-locked ({})", "空位 {} 已被{}龙锁定"),
+    ("Foundation needs {} next, not {}", "收牌区下一张需要 {}，而不是 {}"),
+    ("Foundation for {} is already complete", "{} 所在的收牌区已经收满"),
+    ("{} cannot go to a foundation (dragons are merged instead)", "{} 不能进入收牌区（龙牌需要合并）"),
+    ("Use the foundation command to send cards to the foundation", "请用收牌命令把卡牌送到收牌区"),
+    ("Column {} has no card at position {}", "第 {} 列在位置 {} 没有卡牌"),
+    (
+        "The selected cards in column {} are not a descending, alternating-suit run",
+        "第 {} 列选中的卡牌不是花色交替的递减序列",
+    ),
+    ("Only {} of {} {} dragons are exposed", "{2}龙只有 {0} / {1} 张露出"),
+    ("No free cell is available to lock the {} dragons", "没有可用的空位来锁定{}龙"),
+    // Command parsing
+    ("Empty input", "输入为空"),
+    ("Usage: cc <src[:<depth>]> <dst>", "用法：cc <起点[:<深度>]> <终点>"),
+    ("Invalid depth", "深度无效"),
+    ("Usage: cf <src_col> <cell_idx>", "用法：cf <起点列> <空位>"),
+    ("Usage: fc <cell_idx> <dst_col>", "用法：fc <空位> <终点列>"),
+    ("Usage: ctf <src_col>", "用法：ctf <起点列>"),
+    ("Usage: ftf <cell_idx>", "用法：ftf <空位>"),
+    ("Usage: mv <src> <dst>  (c<N>[:<depth>], f<N>, fo)", "用法：mv <起点> <终点>  (c<N>[:<深度>], f<N>, fo)"),
+    ("A stack depth is only allowed on the source", "只有起点可以指定叠牌深度"),
+    ("Usage: m <src_col>", "用法：m <起点列>"),
+    ("Usage: dragon r|g|b|y", "用法：dragon r|g|b|y"),
+    ("Usage: undo [<n>|all]", "用法：undo [<步数>|all]"),
+    ("Usage: switch <n>", "用法：switch <编号>"),
+    ("'{}' is not a valid line number", "'{}' 不是有效的分支编号"),
+    ("Usage: goto <name>", "用法：goto <名称>"),
+    ("Unknown command '{}'. Type 'help' for help.", "未知命令 '{}'。输入 'help' 查看帮助。"),
+    ("'{}' is not a valid column index", "'{}' 不是有效的列号"),
+    ("Column index {} out of range ({}–{})", "列号 {} 超出范围（{}–{}）"),
+    ("'{}' is not a valid free-cell index", "'{}' 不是有效的空位编号"),
+    ("Free-cell index {} out of range ({}–{})", "空位编号 {} 超出范围（{}–{}）"),
+    ("'{}' is not a valid suit. Use r, g, or b.", "'{}' 不是有效的花色，请使用 r、g 或 b。"),
+    ("'{}' is not a valid suit. Use r, g, b, or y.", "'{}' 不是有效的花色，请使用 r、g、b 或 y。"),
+    ("'{}' is not a valid location. Use c<N>, f<N> or fo.", "'{}' 不是有效的位置，请使用 c<N>、f<N> 或 fo。"),
+    ("Stacks can only be moved between columns", "叠牌只能在列之间移动"),
+    ("Cannot move between free cells", "不能在空位之间移动"),
+    ("'{}' is not a valid seed. Use a number, 0x<hex>, 'daily' or 'weekly'.", "'{}' 不是有效的种子，请使用数字、0x<十六进制>、'daily' 或 'weekly'。"),
+    // Game messages
+    ("Resumed game from seed {}.", "已恢复种子 {} 的对局。"),
+    ("Auto-moved {} card(s) to foundation.", "已自动将 {} 张牌送入收牌区。"),
+    ("Auto-moved {} card(s).", "已自动移动 {} 张牌。"),
+    ("Every remaining card can go home. Type 'finish' to play it out.", "剩余的牌都能直接收走，输入 'finish' 一键完成。"),
+    (
+        "No legal moves remain. Type 'undo' to back up, 'restart' to redeal, or 'new' for another deal.",
+        "已无合法走法。输入 'undo' 悔棋，'restart' 重开本局，或 'new' 换一局。",
+    ),
+    ("No legal moves remain. Z to undo, N for a new game.", "已无合法走法。按 Z 悔棋，按 N 开新局。"),
+    ("Press ? for help.", "按 ? 查看帮助。"),
+    ("Undo.", "已悔棋。"),
+    ("Nothing to undo.", "没有可以悔的棋。"),
+    ("Hint mode deactivated.", "已关闭提示模式。"),
+    ("No solution found for current board.", "当前局面找不到解法。"),
+    ("Hint active: {} step(s). Green = next card. H to exit.", "提示已开启：共 {} 步。绿色 = 下一张要动的牌，按 H 退出。"),
+    ("Only single cards can be moved to a free cell.", "只能把单张牌放进空位。"),
+    ("Hint mode exited after undo.", "悔棋后已退出提示模式。"),
+    ("Playing out the remaining cards.", "正在收走剩余的牌。"),
+    ("Hint path complete.", "提示路线已走完。"),
+    ("Deviated from hint. Hint mode exited.", "偏离了提示路线，已退出提示模式。"),
+    ("New game dealt.", "已发新局。"),
+    ("A new game has been dealt.", "已发新局。"),
+    ("This game was resigned. Type 'new' or 'restart' to play again.", "本局已认输。输入 'new' 或 'restart' 再来一局。"),
+    ("Thanks for playing. Goodbye!", "感谢游玩，再见！"),
+    ("Not every remaining card can go home yet.", "还有牌不能直接收走。"),
+    (
+        "Game resigned and recorded as a loss. Streak: {} (best {}). Type 'new' for another deal.",
+        "已认输并记为失败。连胜：{}（最佳 {}）。输入 'new' 再来一局。",
+    ),
+    ("Restarted seed {} (attempt {}).", "已重开种子 {}（第 {} 次尝试）。"),
+    ("Rewound {} step(s) (all available history).", "已回退 {} 步（全部可用历史）。"),
+    ("Rewound {} step(s).", "已回退 {} 步。"),
+    ("Returned to the initial deal ({} step(s) rewound).", "已回到开局（回退了 {} 步）。"),
+    ("No alternative lines. Undo and play differently to create one.", "没有其他分支。悔棋后换一种走法即可产生分支。"),
+    ("Current: {}", "当前：{}"),
+    ("No line {}. Type 'lines' to list them.", "没有分支 {}。输入 'lines' 查看列表。"),
+    ("Switched to line {}.", "已切换到分支 {}。"),
+    ("No bookmarks. Use 'mark <name>' to save this position.", "没有书签。输入 'mark <名称>' 保存当前局面。"),
+    ("Bookmarked this position as '{}'.", "已将当前局面保存为书签 '{}'。"),
+    ("Usage: macro record <name> | macro stop | macro play <name>", "用法：macro record <名称> | macro stop | macro play <名称>"),
+    ("No macros. Use 'macro record <name>' to record one.", "还没有宏。用 'macro record <名称>' 录制一个。"),
+    ("Already recording '{}'. Type 'macro stop' first.", "正在录制 '{}'。请先输入 'macro stop'。"),
+    ("Recording macro '{}'. Type 'macro stop' to finish.", "开始录制宏 '{}'。输入 'macro stop' 结束。"),
+    ("No macro is being recorded.", "当前没有在录制宏。"),
+    ("Nothing was recorded for '{}'.", "'{}' 没有录到任何命令。"),
+    ("Saved macro '{}' ({} command(s)).", "已保存宏 '{}'（{} 条命令）。"),
+    ("No macro named '{}'. Type 'macro' to list them.", "没有名为 '{}' 的宏。输入 'macro' 查看列表。"),
+    ("macro> {}", "宏> {}"),
+    ("No bookmark named '{}'. Type 'mark' to list them.", "没有名为 '{}' 的书签。输入 'mark' 查看列表。"),
+    ("Restored bookmark '{}'.", "已恢复书签 '{}'。"),
+    ("Running A* solver... (may take a moment)", "正在运行 A* 求解器……（可能需要一会儿）"),
+    ("Found a solution in {} steps!", "找到了 {} 步的解法！"),
+    ("No solution found by BFS.", "求解器没有找到解法。"),
+    ("Source column is empty.", "起点列是空的。"),
+    ("No card or run in that column can move there.", "该列没有能移动到那里的牌或序列。"),
+    ("That card has no legal destination.", "这张牌没有合法的去处。"),
+    ("This move made the game unwinnable (undo to fix).", "这一步让本局无法获胜（悔棋可补救）。"),
+    ("This position cannot be won.", "当前局面无法获胜。"),
+    ("{} move(s) deep, {} card(s) home, {} left in the tableau", "已走 {} 步，收牌 {} 张，牌桌剩余 {} 张"),
+    ("{} → foundation", "{} → 收牌区"),
+    ("{} dragons merged into free cell {}", "{}龙已合并到空位 {}"),
+    // Solver progress
+    ("Solver: started A* search.", "求解器：开始 A* 搜索。"),
+    (
+        "Solver: cache hit for seed {}. Reusing remaining solution ({} moves).",
+        "求解器：种子 {} 命中缓存，沿用剩余解法（{} 步）。",
+    ),
+    (
+        "Solver: cached solution for seed {} does not match current board. Keeping cache and recomputing.",
+        "求解器：种子 {} 的缓存解法与当前局面不符，保留缓存并重新计算。",
+    ),
+    ("Solver: {} / {} nodes explored.", "求解器：已搜索 {} / {} 个节点。"),
+    ("Solver: found solution in {} moves after exploring {} nodes.", "求解器：搜索 {1} 个节点后找到 {0} 步的解法。"),
+    ("Solver: node limit ({}) reached after exploring {} nodes.", "求解器：搜索 {1} 个节点后达到节点上限（{0}）。"),
+    ("Solver: search exhausted after exploring {} nodes.", "求解器：搜索 {} 个节点后已穷尽所有可能。"),
+    // CLI renderer
+    ("Wins", "胜场"),
+    ("Seed", "种子"),
+    ("Rank", "职级"),
+    ("Phrase", "短语"),
+    ("FREE CELLS", "空位"),
+    ("FLOWER", "花位"),
+    ("FOUND", "收牌区"),
+    ("COL", "列"),
+    ("(all columns empty)", "（所有列都已清空）"),
+    ("/// KERNEL PANIC: TOO MUCH FREE TIME ///", "/// 内核恐慌：摸鱼时间过多 ///"),
+    ("[!] EMPLOYEE OF THE MONTH [!]", "[!] 本月最佳员工 [!]"),
+    ("Congratulations! You solved it!  Type 'new' for another game.", "恭喜通关！输入 'new' 再来一局。"),
+    ("C", "列"),
+    ("Moves", "步数"),
+    ("Time", "用时"),
+    ("Moves: {}. Time: {}.", "步数：{}。用时：{}。"),
+    ("Split: {}", "分段：{}"),
+    ("{} foundation", "{}收牌区"),
+    ("{} dragons", "{}龙"),
+    ("{}  {}  ({} vs best)", "{}  {}  （比最佳 {}）"),
+    ("{}  {}", "{}  {}"),
+    ("No splits yet.", "尚无分段记录。"),
+    (
+        "Speedrun mode is off. Start with --speedrun or set speedrun = true in config.toml.",
+        "速通模式未开启。使用 --speedrun 启动，或在 config.toml 中设置 speedrun = true。",
+    ),
+    ("Golf: par for this deal is {} moves.", "高尔夫：本局标准杆为 {} 步。"),
+    ("Golf: the solver found no par for this deal.", "高尔夫：求解器未能为本局算出标准杆。"),
+    ("Golf: {} of {} moves used, {} left to par.", "高尔夫：已用 {} / {} 步，距标准杆还剩 {} 步。"),
+    ("Golf: {} of {} moves used, {} over par.", "高尔夫：已用 {} / {} 步，超出标准杆 {} 步。"),
+    ("Undo and hints are disabled in hardcore mode.", "硬核模式下不能悔棋，也不能使用提示。"),
+    ("Hardcore win! {} hardcore win(s) so far.", "硬核胜利！累计硬核胜场：{}。"),
+    ("Hints are off under the '{}' assist preset.", "“{}”辅助预设下不能使用提示。"),
+    ("{} (par {}, {}{})", "{}（标准杆 {}，{}{}）"),
+    ("none", "无"),
+    ("{}, hardcore", "{}，硬核"),
+    ("score", "得分"),
+    ("time", "用时"),
+    ("moves", "步数"),
+    ("no", "否"),
+    ("yes ({})", "是（{}）"),
+    ("Undos", "悔棋"),
+    ("Hints", "提示"),
+    ("Winnable", "可胜"),
+    ("Legal moves", "可走步数"),
+    ("Legal moves: {}.", "可走步数：{}。"),
+    ("Weekly", "每周挑战"),
+    ("next deal in {}", "{} 后换新牌局"),
+    ("Weekly challenge: next deal in {}.", "每周挑战：{} 后换新牌局。"),
+    ("Ghost now", "幽灵此刻"),
+    ("Ghost at this move", "幽灵同步数"),
+    ("you {}", "你 {}"),
+    ("Ghost: {} at this time, {} after as many moves; your lead: {}.", "幽灵：此刻 {}，同样步数时 {}；你领先：{}。"),
+    ("Checking whether the game can still be won.", "正在检查本局是否仍能获胜。"),
+    ("The game can still be won.", "本局仍能获胜。"),
+    ("The game can no longer be won.", "本局已无法获胜。"),
+    ("The solver could not tell whether the game can still be won.", "求解器无法判断本局是否仍能获胜。"),
+    ("Dragons merged", "龙牌合并顺序"),
+    ("Assists", "辅助"),
+    ("Score", "得分"),
+    ("Personal best", "个人最佳"),
+    ("GAME SUMMARY", "对局总结"),
+    ("Statistics", "统计"),
+    ("Win rate", "胜率"),
+    ("Solve times", "通关用时"),
+    ("Weekdays", "星期"),
+    ("No finished games yet.", "还没有结束的对局。"),
+    ("{} of {} games won. Win rate over the last {} games, after each of the latest {}:", "{1} 局中胜 {0} 局。最近 {3} 局每局结束时，其前 {2} 局的胜率："),
+    ("Wins by play time ({} wins):", "按用时统计的胜局（共 {} 局）："),
+    ("Games by the weekday they were started (UTC):", "按开局星期统计的对局（UTC）："),
+    ("Now {}%", "当前 {}%"),
+    ("Rating", "等级分"),
+    ("Your rating after each of the latest {} rated games, between {} and {}:", "最近 {0} 局计分对局后的等级分，介于 {1} 与 {2} 之间："),
+    ("No rated games yet. Finish a dealt game to get a rating.", "还没有计分的对局。下完一局发牌对局即可获得等级分。"),
+    ("Now {}", "当前 {}"),
+    ("Mon", "周一"),
+    ("Tue", "周二"),
+    ("Wed", "周三"),
+    ("Thu", "周四"),
+    ("Fri", "周五"),
+    ("Sat", "周六"),
+    ("Sun", "周日"),
+    ("{}: next page, Shift-Tab: previous, any other key closes", "{}：下一页，Shift-Tab：上一页，其他键关闭"),
+    ("Press any key to continue.", "按任意键继续。"),
+    ("Usage: history [won|lost] [seed <s>] [page <n>] | history play <#> | history weekly", "用法：history [won|lost] [seed <s>] [page <n>] | history play <#> | history weekly"),
+    ("This is the weekly challenge, a hard deal; the next one comes in {}. 'history weekly' lists your results.", "这是每周挑战，一局难度较高的牌局；{} 后换新。'history weekly' 列出你的成绩。"),
+    ("This week's deal is seed {}; the next one comes in {}. Play it with 'new weekly'.", "本周牌局是种子 {}，{} 后换新。用 'new weekly' 开始。"),
+    ("No weekly challenge finished yet.", "还没有完成过每周挑战。"),
+    ("Weekly challenges, newest first (weeks start Monday, UTC):", "每周挑战，从新到旧（每周从周一开始，UTC）："),
+    ("week of {}  seed {}  {} tries, {} won", "{} 当周  种子 {}  尝试 {} 次，胜 {} 次"),
+    ("{}  best {}, {} moves", "{}  最佳 {}，{} 步"),
+    ("No games match.", "没有符合条件的对局。"),
+    ("There are only {} page(s).", "只有 {} 页。"),
+    ("Games {}–{} of {}, newest first (times in UTC):", "第 {}–{} 局，共 {} 局，最新在前（UTC 时间）："),
+    ("{} more; add 'page {}' to see them.", "还有 {} 局；加上“page {}”查看。"),
+    ("Type 'history play <#>' to re-deal a listed game.", "输入“history play <#>”重开列出的对局。"),
+    ("There is no game #{} in the history.", "历史中没有第 {} 局。"),
+    ("won", "胜"),
+    ("lost", "负"),
+    ("in progress", "进行中"),
+    ("#{}  {}  seed {}  {}", "#{}  {}  种子 {}  {}"),
+    ("#{}  {}  seed {}  {}  {}  {} moves", "#{}  {}  种子 {}  {}  {}  {} 步"),
+    ("Usage: export stats <file.csv> | export history <file.json>", "用法：export stats <文件.csv> | export history <文件.json>"),
+    ("Exported {} game(s) to {}.", "已将 {} 局导出到 {}。"),
+    ("Could not write {}: {}", "无法写入 {}：{}"),
+    ("Usage: import history <file.json> | import grid <file>", "用法：import history <文件.json> | import grid <文件>"),
+    ("Imported {} game(s); {} already present, {} unfinished skipped.", "已导入 {} 局；{} 局已存在，跳过 {} 局未完成的对局。"),
+    ("History now has {} game(s), {} win(s), best streak {}.", "现有 {} 局记录，{} 胜，最长连胜 {}。"),
+    ("Could not import {}: {}", "无法导入 {}：{}"),
+    ("{} older game(s) are kept only in the totals (history_limit).", "另有 {} 局较早的对局只计入总数（history_limit）。"),
+    ("{} unfinished game(s) waiting; type 'games' to list them.", "还有 {} 局未完成的对局；输入“games”查看。"),
+    ("Seed {} kept as an unfinished game; type 'games' to list them.", "种子 {} 的对局已保留为未完成；输入“games”查看。"),
+    ("No unfinished games.", "没有未完成的对局。"),
+    ("{} (current)", "{}（当前）"),
+    ("Type 'resume <n>' to switch games.", "输入“resume <n>”切换对局。"),
+    ("There is no unfinished game {}. Type 'games' to list them.", "没有第 {} 个未完成的对局。输入“games”查看。"),
+    ("That is the current game.", "这就是当前对局。"),
+    ("Usage: resume <n>", "用法：resume <n>"),
+    ("{}. seed {}  started {}  {} moves  {}", "{}. 种子 {}  开始于 {}  {} 步  {}"),
+    ("Profiles:", "用户档案："),
+    ("default", "默认"),
+    ("Choose a profile (number or a new name, Enter for default):", "选择档案（输入编号或新名称，回车使用默认）："),
+    ("Profile names may use letters, digits, '-' and '_'.", "档案名只能包含字母、数字、“-”和“_”。"),
+    ("There are no backups of the save file yet.", "存档还没有备份。"),
+    ("Backups of the save file, newest first:", "存档备份（最新的在前）："),
+    ("{} game(s)", "{} 局"),
+    ("Restore one with: szsol-rs restore-backup <n>", "恢复备份：szsol-rs restore-backup <n>"),
+    ("restore-backup expects a backup number", "restore-backup 需要备份编号"),
+    ("Restored backup {} ({} game(s)).", "已恢复备份 {}（{} 局）。"),
+    ("Save file is truncated.", "存档文件不完整。"),
+    ("Save file signature mismatched!", "存档签名不匹配！"),
+    ("Save file was written by a newer version of szsol.", "存档由更新版本的 szsol 写入。"),
+    ("Save file could not be read.", "无法读取存档。"),
+    ("No save directory is available.", "没有可用的存档目录。"),
+    ("No such backup.", "没有这个备份。"),
+    ("Could not write the save file.", "无法写入存档。"),
+    ("Another szsol is using this profile; close it first.", "另一个 szsol 正在使用此档案，请先关闭它。"),
+    ("Another szsol is already running with this profile; this session will not be saved.", "另一个 szsol 已在使用此档案运行；本次游戏不会被保存。"),
+    ("Usage: load <code>", "用法：load <分享码>"),
+    ("Position code: {}", "局面分享码：{}"),
+    ("Anyone can play on from here with 'load <code>'.", "任何人都可以用“load <分享码>”从这里继续。"),
+    ("Loaded a shared position (seed {}). It won't count towards your wins.", "已载入分享的局面（种子 {}）。它不计入你的胜场。"),
+    ("Could not load the position: {}", "无法载入局面：{}"),
+    ("Restarted the shared position.", "已重新开始分享的局面。"),
+    ("  (shared position)", "  （分享局面）"),
+    ("  (practice table)", "  （练习牌桌）"),
+    ("No hints left: daily, weekly and challenge games allow {} (challenge_hints).", "提示已用完：每日挑战、每周挑战和挑战局只允许 {} 次（challenge_hints）。"),
+    ("Hint {} of {} for this game.", "本局第 {} 次提示，共 {} 次。"),
+    ("  ({} hint(s))", "  （{} 次提示）"),
+    ("Usage: show solution", "用法：show solution"),
+    ("Usage: set render quiet|normal|verbose", "用法：set render quiet|normal|verbose"),
+    ("Render mode: {}.", "输出模式：{}。"),
+    (
+        "Keys: a column's number or a free cell's letter (a, b, c), then where it goes; Enter sends it home, Esc cancels, u undoes, : types a command.",
+        "按键：先按列号或空位字母（a、b、c），再按目标；Enter 送入收牌区，Esc 取消，u 悔棋，: 输入命令。",
+    ),
+    ("Type 'show solution' to see a winning line.", "输入 'show solution' 查看一条获胜路线。"),
+    ("The solution is shown once you resign or reach a dead end.", "认输或走投无路后才会显示解法。"),
+    ("Looking for the last position that could still be won... (may take a moment)", "正在寻找最后一个仍能获胜的局面……（可能需要一会儿）"),
+    ("The solver found no winning line for this deal.", "求解器没有找到本局的获胜路线。"),
+    ("This position can still be won in {} moves:", "当前局面仍可在 {} 步内获胜："),
+    ("A winning line of {} moves from the initial deal:", "从初始发牌开始的一条 {} 步获胜路线："),
+    ("The game could still be won {} step(s) back. A winning line of {} moves from there:", "退回 {} 步时本局仍可获胜。从那里开始的一条 {} 步获胜路线："),
+    ("Usage: puzzle <n>", "用法：puzzle <编号>"),
+    ("Usage: campaign [<stage>]", "用法：campaign [<关卡>]"),
+    ("Stage {}  seed {}  {}  {} tries", "第 {} 关  种子 {}  {}  尝试 {} 次"),
+    ("Campaign: {} of {} stages cleared.", "战役：已通过 {} / {} 关。"),
+    ("Every stage is cleared. Replay any with 'campaign <n>'.", "所有关卡均已通过。可用 'campaign <n>' 重玩任意一关。"),
+    ("Type 'campaign {}' to play the next stage.", "输入 'campaign {}' 挑战下一关。"),
+    ("There is no stage {}; the campaign has {}.", "没有第 {} 关；战役共 {} 关。"),
+    ("Stage {} is locked. Win stage {} to open it.", "第 {} 关尚未解锁。赢下第 {} 关即可解锁。"),
+    ("Campaign stage {} of {}, a deal the solver rates '{}'.", "战役第 {} / {} 关，求解器评定难度为 '{}'。"),
+    ("Campaign complete: all {} stages cleared!", "战役完成：{} 关全部通过！"),
+    ("Stage {} cleared! Stage {} is open; type 'campaign {}' to play it.", "第 {} 关通过！第 {} 关已解锁，输入 'campaign {}' 开始。"),
+    ("Puzzle pack '{}':", "谜题包 '{}'："),
+    ("  (solved)", "  （已解开）"),
+    ("  ({} attempt(s))", "  （尝试 {} 次）"),
+    ("Type 'puzzle <n>' to play one.", "输入 'puzzle <编号>' 来挑战一题。"),
+    ("Could not read the puzzles in {}: {}", "无法读取 {} 中的谜题：{}"),
+    ("There is no puzzle {}; type 'puzzles' to list them.", "没有第 {} 题；输入 'puzzles' 查看列表。"),
+    ("Puzzle: {}. It won't count towards your wins.", "谜题：{}。它不计入你的胜场。"),
+    ("Restarted the puzzle.", "已重新开始谜题。"),
+    ("Puzzle solved in {} moves!", "用 {} 步解开了谜题！"),
+    ("Out of moves: the puzzle wasn't solved. Type 'restart' to try again.", "步数用完，谜题没有解开。输入 'restart' 再试一次。"),
+    ("Merge the {} dragons", "合并{}龙"),
+    ("Build the {} foundation to {}", "把{}花色的收牌堆叠到 {}"),
+    ("Place the flower", "放好花牌"),
+    ("Win the game", "赢下这一局"),
+    ("{} within {} moves.", "{}，限 {} 步。"),
+    ("Par", "标准杆"),
+    ("Par: {} moves.", "标准杆：{} 步。"),
+    ("Par for this deal is {} moves.", "本局标准杆为 {} 步。"),
+    ("Working out par for this deal... (may take a moment)", "正在计算本局标准杆……（可能需要一会儿）"),
+    ("The solver found no par for this deal.", "求解器未能为本局算出标准杆。"),
+    ("  (par {})", "  （标准杆 {}）"),
+    ("  (under par {})", "  （低于标准杆 {}）"),
+    ("A shared position can't be sent as a challenge; 'share' its code instead.", "分享的局面不能作为挑战发出；请用 'share' 分享它的分享码。"),
+    ("Challenge token: {}", "挑战码：{}"),
+    ("A friend can take it up with 'szsol-rs challenge <token>'.", "好友可以用 'szsol-rs challenge <挑战码>' 接受挑战。"),
+    ("No challenges taken yet. Take one up with 'challenge <token>'.", "还没有接受过挑战。用 'challenge <挑战码>' 接受一个。"),
+    ("Challenges taken, newest first (times in UTC):", "已接受的挑战，最新的在前（UTC 时间）："),
+    ("Could not take up the challenge: {}", "无法接受挑战：{}"),
+    ("Challenge accepted: seed {} with the '{}' assist preset.", "已接受挑战：种子 {}，辅助预设 '{}'。"),
+    ("Hardcore: no undo, hints or solver.", "硬核模式：不能撤销、提示或求解。"),
+    ("Par to beat: {} moves.", "要挑战的标准杆：{} 步。"),
+    ("{}  seed {}  {}", "{}  种子 {}  {}"),
+    ("{}  {}  {}  {} moves", "{}  {}  {}  {} 步"),
+    ("  (par {}, {}{})", "  （标准杆 {}，{}{}）"),
+    ("Usage: szsol-rs challenge <token>", "用法：szsol-rs challenge <挑战码>"),
+    ("Seed {}: {} win(s).", "种子 {}：胜 {} 局。"),
+    ("Best time: {} ({}).", "最快用时：{}（{}）。"),
+    ("Fewest moves: {} ({}).", "最少步数：{}（{}）。"),
+    ("No wins recorded on seed {} yet.", "种子 {} 还没有胜局记录。"),
+    ("This szsol was built without the online leaderboard (cargo build --features leaderboard).", "此版本的 szsol 未包含线上排行榜（请用 cargo build --features leaderboard 构建）。"),
+    ("Set leaderboard_url in config.toml to take part in the online leaderboard.", "在 config.toml 中设置 leaderboard_url 即可参与线上排行榜。"),
+    ("Only a win of today's daily deal can be submitted (start it with 'new daily').", "只能提交今日每日牌局的胜局（用 'new daily' 开始）。"),
+    ("Only a daily game played from the deal in this session can be submitted.", "只能提交本次运行中从发牌开始完成的每日牌局。"),
+    ("Submitting to {}...", "正在提交到 {}……"),
+    ("Submitted.", "已提交。"),
+    ("Submitted. The server says: {}", "已提交。服务器回复：{}"),
+    ("Could not submit: {}", "提交失败：{}"),
+    ("No times posted for today's deal yet.", "今日牌局还没有人提交成绩。"),
+    ("Today's top times (seed {}):", "今日最快成绩（种子 {}）："),
+    ("{}. {}  {}  {} moves", "{}. {}  {}  {} 步"),
+    ("Could not fetch the leaderboard: {}", "无法获取排行榜：{}"),
+    ("Usage: telemetry [status|on|off|send]", "用法：telemetry [status|on|off|send]"),
+    ("Usage statistics are not kept for this session.", "本次会话不记录使用统计。"),
+    ("Usage statistics are on. This is everything recorded, exactly as 'telemetry send' would upload it:", "使用统计已开启。以下是记录的全部内容，与 'telemetry send' 上传的完全一致："),
+    ("Usage statistics are off. 'telemetry on' would record only this, and nothing is uploaded until 'telemetry send':", "使用统计已关闭。'telemetry on' 只会记录以下内容，且在 'telemetry send' 之前不会上传任何数据："),
+    ("Usage statistics are on. 'telemetry' shows what is recorded.", "使用统计已开启。输入 'telemetry' 查看记录的内容。"),
+    ("Usage statistics are off, and everything recorded was deleted.", "使用统计已关闭，已记录的内容全部删除。"),
+    ("Usage statistics are off. Type 'telemetry on' to start recording them.", "使用统计已关闭。输入 'telemetry on' 开始记录。"),
+    ("Set telemetry_url in config.toml to send usage statistics.", "在 config.toml 中设置 telemetry_url 才能发送使用统计。"),
+    ("Usage statistics sent; counting starts afresh.", "使用统计已发送，重新开始计数。"),
+    ("Usage statistics sent; counting starts afresh. The server says: {}", "使用统计已发送，重新开始计数。服务器回复：{}"),
+    ("Could not send usage statistics: {}", "无法发送使用统计：{}"),
+    ("Could not listen on port {}: {}", "无法监听端口 {}：{}"),
+    ("Serving szsol on port {}. Connect with: telnet <host> {}", "szsol 正在端口 {} 上提供服务。连接方式：telnet <主机> {}"),
+    ("Serving the szsol API on http://localhost:{}/games", "szsol API 已在 http://localhost:{}/games 上提供服务"),
+    ("{} connected.", "{} 已连接。"),
+    ("{} disconnected.", "{} 已断开。"),
+    ("{} turned away: the server is full.", "{} 被拒绝：服务器已满。"),
+    ("The server is full; try again later.", "服务器已满，请稍后再试。"),
+    ("--port expects a port number", "--port 需要一个端口号"),
+    ("Files on the host are not available over the network.", "通过网络游玩时不能读写主机上的文件。"),
+    ("{} has no move to make.", "{} 无棋可走。"),
+    ("{} plays {}.", "{} 走 {}。"),
+    ("{} stopped after {} moves.", "{} 在 {} 步后停止。"),
+    ("Could not start {}: {}", "无法启动 {}：{}"),
+    ("--bot expects one of: {}, or an agent program", "--bot 需要以下之一：{}，或一个代理程序"),
+    ("--bot expects one of: {}", "--bot 需要以下之一：{}"),
+    ("--script expects a file of commands, or - for stdin", "--script 需要一个命令文件，或用 - 表示标准输入"),
+    ("--events expects a file to write", "--events 需要一个要写入的文件"),
+    ("Result: won in {} moves.", "结果：{} 步获胜。"),
+    ("Result: not won after {} moves.", "结果：{} 步后未获胜。"),
+    ("Parse error at line {}: {}", "第 {} 行无法解析：{}"),
+    ("Illegal move at line {}: {}", "第 {} 行的走法不合法：{}"),
+    ("the agent exited", "代理程序已退出"),
+    ("Played {} of {} games ({} won).", "已完成 {}/{} 局（胜 {} 局）。"),
+    ("Wrote {} moves to {}.", "已将 {} 步写入 {}。"),
+    ("--games expects a number", "--games 需要一个数字"),
+    ("--agent expects 'solver' or a --bot agent", "--agent 需要 'solver' 或一个 --bot 代理"),
+    ("Analyzed {} of {} seeds.", "已分析 {}/{} 个种子。"),
+    ("Solvable: {} of {} ({}%).", "可解：{}/{}（{}%）。"),
+    ("Solution length: {} on average, {} to {}.", "解法长度：平均 {}，{} 到 {}。"),
+    ("Difficulty:", "难度："),
+    ("Usage: report difficulty|rating", "用法：report difficulty|rating"),
+    ("Usage: share [daily]", "用法：share [daily]"),
+    ("Usage: copy seed|share|daily", "用法：copy seed|share|daily"),
+    ("A shared position has no seed to deal it from; 'copy share' copies its code instead.", "分享的局面没有可发牌的种子；请用 'copy share' 复制它的分享码。"),
+    ("not over the network", "网络会话中不可用"),
+    ("Copied to the clipboard: {}", "已复制到剪贴板：{}"),
+    ("No clipboard to copy to ({}); here it is to copy by hand:", "没有可用的剪贴板（{}），请手动复制："),
+    ("This szsol was built without the clipboard (cargo build --features clipboard); here it is to copy by hand:", "此 szsol 构建时未启用剪贴板（cargo build --features clipboard），请手动复制："),
+    ("Win a daily deal first (start today's with 'new daily').", "请先赢下一局每日挑战（用 'new daily' 开始今天的）。"),
+    ("Share your result:", "分享你的成绩："),
+    ("{} moves ⏱ {}", "{} 步 ⏱ {}"),
+    ("Rating: {} ({}) against a deal rated {}.", "等级分：{0}（{1}），本局牌面难度 {2}。"),
+    ("No rated games yet. Every finished dealt game is rated against the deal.", "还没有计分的对局。每局下完的发牌对局都会以牌面难度为对手计分。"),
+    ("Rating {} after {} rated games. Latest changes, newest first (times in UTC):", "{1} 局计分对局后等级分为 {0}。最近的变化，从新到旧（UTC 时间）："),
+    ("{}  seed {}  {} against {}  {} ({})", "{0}  种子 {1}  {2}，对手 {3}  {4}（{5}）"),
+    ("Rating {} seeds with the solver in the background; the report follows when it is done.", "正在后台用求解器评定 {} 个种子的难度，完成后显示报告。"),
+    ("Still rating your seeds with the solver: {} to go.", "仍在用求解器评定种子难度：还剩 {} 个。"),
+    ("No finished games to rate yet.", "还没有可评定的已结束对局。"),
+    ("Your last {} seeds by solver difficulty and how often you won them:", "最近 {} 个种子的求解难度与你的胜率："),
+    ("games", "局数"),
+    ("win rate", "胜率"),
+    ("Skill ceiling: you win most deals up to '{}'.", "技术上限：'{}' 及以下难度的牌局你大多能赢。"),
+    ("Skill ceiling: no difficulty won half the time yet.", "技术上限：还没有哪个难度的胜率达到一半。"),
+    ("Wrote {} rows to {}.", "已将 {} 行写入 {}。"),
+    ("--seeds expects a range such as 1..1000", "--seeds 需要一个范围，例如 1..1000"),
+    ("--threads expects a number", "--threads 需要一个数字"),
+    ("Running the iterative-deepening solver... (may take a moment)", "正在运行迭代加深求解器……（可能需要一点时间）"),
+    ("Solver: started iterative-deepening search.", "求解器：已开始迭代加深搜索。"),
+    ("Solver: time limit reached after exploring {} nodes; whether the deal can be won is unknown.", "求解器：探索 {} 个节点后达到时间上限；无法确定此局能否取胜。"),
+    ("You've been here before, {} moves ago.", "这个局面 {} 步之前出现过。"),
+    ("Imported the board from {}. It won't count towards your wins.", "已从 {} 导入局面，胜局不计入统计。"),
+    ("Could not read the grid in {}: {}", "无法读取 {} 中的牌面：{}"),
+    // Plain renderer and `describe`
+    ("{} {}", "{}{}"),
+    ("{} Dragon", "{}龙"),
+    ("Flower", "花"),
+    ("Column {}: empty.", "第 {} 列：空。"),
+    ("Column {}: {} alone.", "第 {} 列：只有 {}。"),
+    ("Column {}: {}, {} on top.", "第 {} 列：{}，顶部是 {}。"),
+    ("Free cell {}: empty.", "空位 {}：空。"),
+    ("Free cell {}: {}.", "空位 {}：{}。"),
+    ("Free cell {}: locked by {} dragons.", "空位 {}：被{}龙锁定。"),
+    ("{} none", "{}无"),
+    ("{} up to {}", "{}到 {}"),
+    ("Foundations: {}.", "收牌区：{}。"),
+    ("Flower: placed.", "花牌：已放入。"),
+    ("Flower: not placed.", "花牌：未放入。"),
+    ("Error:", "错误："),
+    ("Wins: {}. Seed: {} (phrase \"{}\").", "胜场：{}。种子：{}（短语“{}”）。"),
+    ("Wins: {}. Seed: {}.", "胜场：{}。种子：{}。"),
+    // TUI
+    ("YOU WIN!  Press N for another game.", "你赢了！按 N 再来一局。"),
+    ("Screen too small", "窗口太小"),
+    ("Current size: {}x{}", "当前尺寸：{}x{}"),
+    ("Required size: at least {}x{}", "最小尺寸：{}x{}"),
+    ("Resize the terminal to continue.", "调整终端大小后即可继续。"),
+    ("The game is still running and will redraw automatically.", "游戏仍在运行，会自动重绘。"),
+    ("Seed: {}   Wins: {}", "种子：{}   胜场：{}"),
+    ("No solution found", "找不到解法"),
+    (
+        " HINT  |  Green = next card to move  |  H = exit  |  wrong move auto-exits",
+        " 提示  |  绿色 = 下一张要动的牌  |  H = 退出  |  走错自动退出",
+    ),
+    (
+        " cols: {}  |  cells: {}  |  {}=drgn {}=hint {}=spd({}) {}=undo {}=new Ctrl-C=exit",
+        " 列：{}  |  空位：{}  |  {}=龙 {}=提示 {}=速度({}) {}=悔棋 {}=新局 Ctrl-C=退出",
+    ),
+    (
+        " {} {} {} {}=move  {}=pick up/drop  |  {}=command  |  {}=drgn {}=hint {}=spd({}) {}=undo {}=new Ctrl-C=exit",
+        " {} {} {} {}=移动  {}=拿起/放下  |  {}=命令  |  {}=龙 {}=提示 {}=速度({}) {}=悔棋 {}=新局 Ctrl-C=退出",
+    ),
+    (
+        " Selected col {} ×{}  |  same key → grow stack  |  dest key → move  |  {}=cancel",
+        " 已选列 {} ×{}  |  同一键 → 扩大选择  |  目标键 → 移动  |  {}=取消",
+    ),
+    (" Selected cell {}  |  col key → move  |  {}=cancel", " 已选空位 {}  |  列键 → 移动  |  {}=取消"),
+    (" Dragon merge: press r / g / b for suit  |  {}=cancel", " 合并龙牌：按 r / g / b 选择花色  |  {}=取消"),
+    (" Dragon merge: press r / g / b / y for suit  |  {}=cancel", " 合并龙牌：按 r / g / b / y 选择花色  |  {}=取消"),
+    ("Help", "帮助"),
+    ("TUI Help", "TUI 帮助"),
+    ("Keyboard", "键盘"),
+    ("Mouse", "鼠标"),
+    ("select a tableau column", "选择一列"),
+    ("grow selection up a valid ordered stack", "沿有序叠牌向上扩大选择"),
+    ("select / target a free cell", "选择空位 / 以空位为目标"),
+    ("move selected card(s)", "移动选中的牌"),
+    ("send selected single card to foundation", "把选中的单张牌送入收牌区"),
+    ("cancel selection", "取消选择"),
+    ("merge dragons by suit", "按花色合并龙牌"),
+    ("undo", "悔棋"),
+    ("toggle animation speed", "切换动画速度"),
+    ("new game", "新局"),
+    ("run solver hint / exit hint mode", "运行求解提示 / 退出提示模式"),
+    ("toggle this help", "显示 / 隐藏帮助"),
+    ("statistics screen / next page", "统计界面 / 下一页"),
+    ("campaign map", "战役地图"),
+    ("Campaign", "战役"),
+    ("Locked: win stage {} to open it.", "未解锁：赢下第 {} 关即可解锁。"),
+    ("Arrows: pick a stage, Enter: play it, any other key closes", "方向键：选择关卡，回车：开始，其他键关闭"),
+    ("cursor, pick up / drop, command line (vim_keys)", "移动光标、拿起 / 放下、命令行（vim_keys）"),
+    ("select from clicked card up to the top", "从点击的牌选到顶部"),
+    ("select that card", "选中那张牌"),
+    ("move selection there", "把选中的牌移到那里"),
+    ("try merge that dragon suit", "尝试合并该花色的龙牌"),
+    ("quit", "退出"),
+    ("Press {} to close", "按 {} 关闭"),
+    ("vim_keys: cursor left", "vim_keys：光标左移"),
+    ("vim_keys: cursor to the columns", "vim_keys：光标移到牌列"),
+    ("vim_keys: cursor to the free cells and foundations", "vim_keys：光标移到空位和收牌区"),
+    ("vim_keys: cursor right", "vim_keys：光标右移"),
+    ("vim_keys: pick up or drop at the cursor", "vim_keys：在光标处拿起或放下"),
+    ("vim_keys: undo", "vim_keys：悔棋"),
+    ("vim_keys: open the command line", "vim_keys：打开命令行"),
+];
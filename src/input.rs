@@ -0,0 +1,425 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Line input for the CLI prompt.
+//!
+//! `Game::run` reads commands through [`LineInput`], so the editing layer can
+//! be swapped out: an interactive terminal gets [`LineEditor`] (history and
+//! Emacs-style editing) or, with `input = "keys"`, [`KeyInput`] (one key per
+//! slot, no Enter), while pipes and scripts get plain [`StdinInput`].
+//! The TUI never goes through here; it reads key events directly.
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crossterm::cursor::MoveToColumn;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::queue;
+
+use crate::i18n::tr;
+
+/// Maximum number of remembered input lines.
+const MAX_HISTORY: usize = 100;
+
+/// What a single prompt produced.
+pub enum ReadOutcome {
+    Line(String),
+    /// Ctrl-C at the prompt.
+    Interrupted,
+    /// End of input (Ctrl-D on an empty line, or a closed pipe).
+    Eof,
+}
+
+/// Returns completions for the last word of the text before the cursor.
+pub type Completer<'a> = &'a dyn Fn(&str) -> Vec<String>;
+
+pub trait LineInput {
+    fn read_line(&mut self, prompt: &str, complete: Completer) -> ReadOutcome;
+}
+
+/// How commands are typed at an interactive CLI prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Whole lines, ended with Enter.
+    Line,
+    /// A source key and a destination key make a move ([`KeyInput`]).
+    Keys,
+}
+
+impl InputMode {
+    pub fn parse(value: &str) -> Option<InputMode> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "line" => Some(InputMode::Line),
+            "keys" => Some(InputMode::Keys),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputMode::Line => "line",
+            InputMode::Keys => "keys",
+        }
+    }
+}
+
+/// Pick the editor for an interactive terminal, plain reads otherwise.
+/// Columns are numbered from `base` for [`KeyInput`].
+pub fn default_input(mode: InputMode, base: usize) -> Box<dyn LineInput> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Box::new(StdinInput);
+    }
+    match mode {
+        InputMode::Line => Box::new(LineEditor::new()),
+        InputMode::Keys => Box::new(KeyInput::new(base)),
+    }
+}
+
+/// Plain `read_line` on stdin; used when input is piped or redirected.
+pub struct StdinInput;
+
+impl LineInput for StdinInput {
+    fn read_line(&mut self, prompt: &str, _complete: Completer) -> ReadOutcome {
+        print!("{}", prompt);
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => ReadOutcome::Eof,
+            Ok(_) => ReadOutcome::Line(line),
+        }
+    }
+}
+
+/// A small readline: arrow-key history, Tab completion, cursor movement and
+/// the usual Ctrl-A/E/B/F/K/U/W/D bindings, drawn on a single terminal row.
+pub struct LineEditor {
+    history: Vec<String>,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        Self { history: Vec::new() }
+    }
+
+    fn remember(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().is_some_and(|l| l == line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    fn redraw(out: &mut impl Write, prompt: &str, buf: &[char], cursor: usize) -> io::Result<()> {
+        let text: String = buf.iter().collect();
+        queue!(out, MoveToColumn(0), Clear(ClearType::CurrentLine))?;
+        write!(out, "{}{}", prompt, text)?;
+        let col = prompt.chars().count() + cursor;
+        queue!(out, MoveToColumn(col as u16))?;
+        out.flush()
+    }
+
+    fn edit(&mut self, prompt: &str, complete: Completer) -> io::Result<ReadOutcome> {
+        let mut out = io::stdout();
+        let mut buf: Vec<char> = Vec::new();
+        let mut cursor = 0;
+        // Index into `history` while browsing; `history.len()` means the live line.
+        let mut browse = self.history.len();
+        let mut live: Vec<char> = Vec::new();
+
+        Self::redraw(&mut out, prompt, &buf, cursor)?;
+        loop {
+            let Event::Key(KeyEvent { code, modifiers, kind, .. }) = event::read()? else {
+                continue;
+            };
+            if kind == KeyEventKind::Release {
+                continue;
+            }
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            match code {
+                KeyCode::Enter => {
+                    write!(out, "\r\n")?;
+                    return Ok(ReadOutcome::Line(buf.into_iter().collect()));
+                }
+                KeyCode::Char('c') if ctrl => {
+                    write!(out, "^C\r\n")?;
+                    return Ok(ReadOutcome::Interrupted);
+                }
+                KeyCode::Char('d') if ctrl => {
+                    if buf.is_empty() {
+                        write!(out, "\r\n")?;
+                        return Ok(ReadOutcome::Eof);
+                    }
+                    if cursor < buf.len() {
+                        buf.remove(cursor);
+                    }
+                }
+                KeyCode::Char('a') if ctrl => cursor = 0,
+                KeyCode::Char('e') if ctrl => cursor = buf.len(),
+                KeyCode::Char('b') if ctrl => cursor = cursor.saturating_sub(1),
+                KeyCode::Char('f') if ctrl => cursor = (cursor + 1).min(buf.len()),
+                KeyCode::Char('k') if ctrl => buf.truncate(cursor),
+                KeyCode::Char('u') if ctrl => {
+                    buf.drain(..cursor);
+                    cursor = 0;
+                }
+                KeyCode::Char('w') if ctrl => {
+                    let mut start = cursor;
+                    while start > 0 && buf[start - 1] == ' ' {
+                        start -= 1;
+                    }
+                    while start > 0 && buf[start - 1] != ' ' {
+                        start -= 1;
+                    }
+                    buf.drain(start..cursor);
+                    cursor = start;
+                }
+                KeyCode::Char('p') if ctrl => {
+                    Self::browse(&self.history, &mut browse, &mut live, &mut buf, -1);
+                    cursor = buf.len();
+                }
+                KeyCode::Char('n') if ctrl => {
+                    Self::browse(&self.history, &mut browse, &mut live, &mut buf, 1);
+                    cursor = buf.len();
+                }
+                KeyCode::Tab => {
+                    let before: String = buf[..cursor].iter().collect();
+                    let candidates = complete(&before);
+                    let word_start = buf[..cursor].iter().rposition(|&c| c == ' ').map_or(0, |i| i + 1);
+                    let typed = cursor - word_start;
+                    let fill: Vec<char> = match candidates.as_slice() {
+                        [] => Vec::new(),
+                        [only] => only.chars().skip(typed).chain(std::iter::once(' ')).collect(),
+                        many => {
+                            let common = common_prefix(many);
+                            if common.chars().count() == typed {
+                                write!(out, "\r\n{}\r\n", many.join("  "))?;
+                            }
+                            common.chars().skip(typed).collect()
+                        }
+                    };
+                    for ch in fill {
+                        buf.insert(cursor, ch);
+                        cursor += 1;
+                    }
+                }
+                KeyCode::Char(ch) if !ctrl => {
+                    buf.insert(cursor, ch);
+                    cursor += 1;
+                }
+                KeyCode::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    buf.remove(cursor);
+                }
+                KeyCode::Delete if cursor < buf.len() => {
+                    buf.remove(cursor);
+                }
+                KeyCode::Left => cursor = cursor.saturating_sub(1),
+                KeyCode::Right => cursor = (cursor + 1).min(buf.len()),
+                KeyCode::Home => cursor = 0,
+                KeyCode::End => cursor = buf.len(),
+                KeyCode::Up => {
+                    Self::browse(&self.history, &mut browse, &mut live, &mut buf, -1);
+                    cursor = buf.len();
+                }
+                KeyCode::Down => {
+                    Self::browse(&self.history, &mut browse, &mut live, &mut buf, 1);
+                    cursor = buf.len();
+                }
+                _ => {}
+            }
+            Self::redraw(&mut out, prompt, &buf, cursor)?;
+        }
+    }
+
+    /// Step through history; the unsent line is kept aside and restored
+    /// when browsing back past the newest entry.
+    fn browse(history: &[String], browse: &mut usize, live: &mut Vec<char>, buf: &mut Vec<char>, step: isize) {
+        let target = *browse as isize + step;
+        if target < 0 || target > history.len() as isize {
+            return;
+        }
+        if *browse == history.len() {
+            *live = buf.clone();
+        }
+        *browse = target as usize;
+        *buf = match history.get(*browse) {
+            Some(line) => line.chars().collect(),
+            None => live.clone(),
+        };
+    }
+}
+
+fn common_prefix(words: &[String]) -> String {
+    let first = &words[0];
+    let len = words[1..].iter().fold(first.len(), |len, w| {
+        first.bytes().zip(w.bytes()).take(len).take_while(|(a, b)| a == b).count()
+    });
+    first[..len].to_string()
+}
+
+impl LineInput for LineEditor {
+    fn read_line(&mut self, prompt: &str, complete: Completer) -> ReadOutcome {
+        if terminal::enable_raw_mode().is_err() {
+            return StdinInput.read_line(prompt, complete);
+        }
+        let outcome = self.edit(prompt, complete);
+        let _ = terminal::disable_raw_mode();
+        match outcome {
+            Ok(ReadOutcome::Line(line)) => {
+                self.remember(&line);
+                ReadOutcome::Line(line)
+            }
+            Ok(other) => other,
+            Err(_) => ReadOutcome::Eof,
+        }
+    }
+}
+
+/// A slot picked with a single key.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Column(usize),
+    Cell(usize),
+    Foundation,
+}
+
+impl Slot {
+    /// The slot a key names: a column by its shown number, a free cell by
+    /// letter (`a` for the first), the foundation by Enter.
+    fn from_key(code: KeyCode, base: usize) -> Option<Slot> {
+        match code {
+            KeyCode::Char(ch @ '0'..='9') => (ch as usize - '0' as usize).checked_sub(base).map(Slot::Column),
+            KeyCode::Char(ch @ 'a'..='f') => Some(Slot::Cell(ch as usize - 'a' as usize)),
+            KeyCode::Enter => Some(Slot::Foundation),
+            _ => None,
+        }
+    }
+
+    /// The slot as an argument of `mv`.
+    fn arg(self, base: usize) -> String {
+        match self {
+            Slot::Column(c) => format!("c{}", c + base),
+            Slot::Cell(i) => format!("f{}", i + base),
+            Slot::Foundation => "fo".to_string(),
+        }
+    }
+}
+
+/// Single-keystroke moves: a source key, then a destination key, with no
+/// Enter.  Columns are their shown numbers, free cells `a`–`f`, and Enter
+/// sends the picked card to the foundation.  Esc drops the source, `u`
+/// undoes, and `:` opens the line editor for any other command.
+pub struct KeyInput {
+    editor: LineEditor,
+    base: usize,
+    /// The key reference has been shown.
+    greeted: bool,
+}
+
+impl KeyInput {
+    pub fn new(base: usize) -> Self {
+        Self { editor: LineEditor::new(), base, greeted: false }
+    }
+
+    /// The command for a move; column to column uses the `<src><dst>`
+    /// shorthand so whole runs move, as long as both numbers are one digit.
+    fn command(&self, src: Slot, dst: Slot) -> String {
+        match (src, dst) {
+            (Slot::Column(s), Slot::Column(d)) if s + self.base < 10 && d + self.base < 10 => {
+                format!("{}{}", s + self.base, d + self.base)
+            }
+            _ => format!("mv {} {}", src.arg(self.base), dst.arg(self.base)),
+        }
+    }
+
+    fn keys(&mut self, prompt: &str, complete: Completer) -> io::Result<ReadOutcome> {
+        let mut out = io::stdout();
+        let mut picked: Option<Slot> = None;
+        loop {
+            let shown: Vec<char> = match picked {
+                Some(src) => format!("{} → ", src.arg(self.base)).chars().collect(),
+                None => Vec::new(),
+            };
+            LineEditor::redraw(&mut out, prompt, &shown, shown.len())?;
+            let Event::Key(KeyEvent { code, modifiers, kind, .. }) = event::read()? else {
+                continue;
+            };
+            if kind == KeyEventKind::Release {
+                continue;
+            }
+            let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+            let line = match code {
+                KeyCode::Char('c') if ctrl => {
+                    write!(out, "^C\r\n")?;
+                    return Ok(ReadOutcome::Interrupted);
+                }
+                KeyCode::Char('d') if ctrl => {
+                    write!(out, "\r\n")?;
+                    return Ok(ReadOutcome::Eof);
+                }
+                KeyCode::Esc => {
+                    picked = None;
+                    continue;
+                }
+                KeyCode::Char(':') if picked.is_none() => {
+                    let outcome = self.editor.edit(prompt, complete)?;
+                    if let ReadOutcome::Line(line) = &outcome {
+                        self.editor.remember(line);
+                    }
+                    return Ok(outcome);
+                }
+                KeyCode::Char('u') if picked.is_none() => "undo".to_string(),
+                _ => match (picked, Slot::from_key(code, self.base)) {
+                    (None, Some(Slot::Foundation)) | (_, None) => continue,
+                    (None, Some(src)) => {
+                        picked = Some(src);
+                        continue;
+                    }
+                    (Some(src), Some(dst)) => self.command(src, dst),
+                },
+            };
+            let typed: Vec<char> = line.chars().collect();
+            LineEditor::redraw(&mut out, prompt, &typed, typed.len())?;
+            write!(out, "\r\n")?;
+            return Ok(ReadOutcome::Line(line));
+        }
+    }
+}
+
+impl LineInput for KeyInput {
+    fn read_line(&mut self, prompt: &str, complete: Completer) -> ReadOutcome {
+        if !self.greeted {
+            self.greeted = true;
+            println!(
+                "{}",
+                tr!("Keys: a column's number or a free cell's letter (a, b, c), then where it goes; Enter sends it home, Esc cancels, u undoes, : types a command.")
+            );
+        }
+        if terminal::enable_raw_mode().is_err() {
+            return StdinInput.read_line(prompt, complete);
+        }
+        let outcome = self.keys(prompt, complete);
+        let _ = terminal::disable_raw_mode();
+        outcome.unwrap_or(ReadOutcome::Eof)
+    }
+}
@@ -0,0 +1,434 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Serde support for [`Json`]: any `Serialize` type to a JSON value and back.
+//!
+//! The layout follows serde_json's: structs and maps are objects, tuples
+//! and fixed arrays are arrays, `None` is `null`, and enum variants are
+//! externally tagged (`"Flower"`, `{"Dragon": "Red"}`).  Only integers are
+//! supported, like `Json` itself.
+use std::fmt::{self, Display};
+
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+
+use crate::json::Json;
+
+/// Why a value could not be converted.
+#[derive(Debug)]
+pub struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Json, Error> {
+    value.serialize(Serializer)
+}
+
+pub fn from_json<T: DeserializeOwned>(json: &Json) -> Result<T, Error> {
+    T::deserialize(json)
+}
+
+struct Serializer;
+
+/// Collects the elements of a sequence, tuple or variant.
+struct SeqBuilder {
+    items: Vec<Json>,
+    /// Set for tuple variants: the result is wrapped as `{variant: [...]}`.
+    variant: Option<&'static str>,
+}
+
+/// Collects the members of a map, struct or struct variant.
+struct MapBuilder {
+    members: Vec<(String, Json)>,
+    next_key: Option<String>,
+    variant: Option<&'static str>,
+}
+
+fn tagged(variant: &str, value: Json) -> Json {
+    Json::Object(vec![(variant.to_string(), value)])
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Json;
+    type Error = Error;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = SeqBuilder;
+    type SerializeMap = MapBuilder;
+    type SerializeStruct = MapBuilder;
+    type SerializeStructVariant = MapBuilder;
+
+    fn serialize_bool(self, v: bool) -> Result<Json, Error> {
+        Ok(Json::Bool(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_i16(self, v: i16) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_i32(self, v: i32) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_i64(self, v: i64) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_i128(self, v: i128) -> Result<Json, Error> {
+        Ok(Json::Int(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_u16(self, v: u16) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_u32(self, v: u32) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_u64(self, v: u64) -> Result<Json, Error> {
+        Ok(Json::Int(v.into()))
+    }
+    fn serialize_f32(self, _: f32) -> Result<Json, Error> {
+        Err(Error("floating-point numbers are not supported".to_string()))
+    }
+    fn serialize_f64(self, _: f64) -> Result<Json, Error> {
+        Err(Error("floating-point numbers are not supported".to_string()))
+    }
+    fn serialize_char(self, v: char) -> Result<Json, Error> {
+        Ok(Json::Str(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Json, Error> {
+        Ok(Json::Str(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Json, Error> {
+        Ok(Json::Array(v.iter().map(|&b| Json::Int(b.into())).collect()))
+    }
+    fn serialize_none(self) -> Result<Json, Error> {
+        Ok(Json::Null)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Json, Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Json, Error> {
+        Ok(Json::Null)
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Json, Error> {
+        Ok(Json::Null)
+    }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Json, Error> {
+        Ok(Json::Str(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Json, Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Json, Error> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, Error> {
+        Ok(SeqBuilder { items: Vec::with_capacity(len.unwrap_or(0)), variant: None })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<SeqBuilder, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, Error> {
+        Ok(SeqBuilder { items: Vec::with_capacity(len), variant: Some(variant) })
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<MapBuilder, Error> {
+        Ok(MapBuilder { members: Vec::new(), next_key: None, variant: None })
+    }
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<MapBuilder, Error> {
+        self.serialize_map(None)
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<MapBuilder, Error> {
+        Ok(MapBuilder { members: Vec::new(), next_key: None, variant: Some(variant) })
+    }
+}
+
+impl SeqBuilder {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Json, Error> {
+        let array = Json::Array(self.items);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, array),
+            None => array,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl MapBuilder {
+    fn member<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.members.push((key.to_string(), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Json, Error> {
+        let object = Json::Object(self.members);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, object),
+            None => object,
+        })
+    }
+}
+
+impl ser::SerializeMap for MapBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        // Object keys are strings; integer keys are written as digits.
+        self.next_key = Some(match key.serialize(Serializer)? {
+            Json::Str(s) => s,
+            Json::Int(n) => n.to_string(),
+            _ => return Err(Error("map keys must be strings or integers".to_string())),
+        });
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.next_key.take().ok_or_else(|| Error("map value without a key".to_string()))?;
+        self.member(&key, value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.member(key, value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapBuilder {
+    type Ok = Json;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.member(key, value)
+    }
+    fn end(self) -> Result<Json, Error> {
+        self.finish()
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &'de Json {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Json::Null => visitor.visit_unit(),
+            Json::Bool(b) => visitor.visit_bool(*b),
+            Json::Int(n) => {
+                if let Ok(n) = u64::try_from(*n) {
+                    visitor.visit_u64(n)
+                } else if let Ok(n) = i64::try_from(*n) {
+                    visitor.visit_i64(n)
+                } else {
+                    visitor.visit_i128(*n)
+                }
+            }
+            Json::Str(s) => visitor.visit_borrowed_str(s),
+            Json::Array(items) => {
+                let mut seq = de::value::SeqDeserializer::new(items.iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Json::Object(members) => {
+                let entries = members.iter().map(|(k, v)| (k.as_str(), v));
+                let mut map = de::value::MapDeserializer::new(entries);
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Json::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Json::Str(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+            Json::Object(members) if members.len() == 1 => {
+                let (variant, value) = &members[0];
+                visitor.visit_enum(Variant { name: variant, value })
+            }
+            _ => Err(Error("expected an enum variant: a string or a one-member object".to_string())),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Json {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// A variant written as `{name: value}`.
+struct Variant<'de> {
+    name: &'de str,
+    value: &'de Json,
+}
+
+impl<'de> de::EnumAccess<'de> for Variant<'de> {
+    type Error = Error;
+    type Variant = &'de Json;
+
+    fn variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, &'de Json), Error> {
+        let name = seed.deserialize(de::value::BorrowedStrDeserializer::<Error>::new(self.name))?;
+        Ok((name, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &'de Json {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self {
+            Json::Null => Ok(()),
+            _ => Err(Error("unit variants take no value".to_string())),
+        }
+    }
+
+    fn newtype_variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
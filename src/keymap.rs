@@ -0,0 +1,200 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Key bindings for the TUI.  Each action is remapped with a
+//! `key_<action> = "<key>"` line in config.toml, e.g. `key_cancel = "enter"`
+//! or `key_columns = "wasdfghjkl"`; `keys` lists the active bindings.
+use crossterm::event::KeyCode;
+
+use crate::board::{MAX_COLUMNS, MAX_FREE_CELLS};
+use crate::i18n::tr;
+use crate::tui_renderer::{COL_KEYS, FC_KEYS};
+
+/// Parse a key name: a single character, or `enter`, `esc`, `space`,
+/// `tab` or `backspace`.
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => return Some(KeyCode::Char(c)),
+        (None, _) => return None,
+        _ => {}
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => Some(KeyCode::Enter),
+        "esc" | "escape" => Some(KeyCode::Esc),
+        "space" => Some(KeyCode::Char(' ')),
+        "tab" => Some(KeyCode::Tab),
+        "backspace" => Some(KeyCode::Backspace),
+        _ => None,
+    }
+}
+
+/// The name `parse_key` reads back.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// Whether `code` is `key`, either case for letters (`Z` undoes like `z`).
+pub fn pressed(code: KeyCode, key: KeyCode) -> bool {
+    match (code, key) {
+        (KeyCode::Char(a), KeyCode::Char(b)) => a.to_lowercase().eq(b.to_lowercase()),
+        _ => code == key,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    /// Selects or targets each tableau column, left to right.
+    pub columns: [char; MAX_COLUMNS],
+    /// Selects or targets each free cell.
+    pub cells: [char; MAX_FREE_CELLS],
+    pub foundation: KeyCode,
+    pub cancel: KeyCode,
+    pub dragon: KeyCode,
+    pub undo: KeyCode,
+    pub new_game: KeyCode,
+    pub hint: KeyCode,
+    pub speed: KeyCode,
+    pub help: KeyCode,
+    /// Opens the statistics screen, then turns its pages.
+    pub stats: KeyCode,
+    /// Opens the campaign map.
+    pub campaign: KeyCode,
+    // `vim_keys`; these match case exactly, so `H` still asks for a hint.
+    pub left: KeyCode,
+    pub down: KeyCode,
+    pub up: KeyCode,
+    pub right: KeyCode,
+    pub pick: KeyCode,
+    pub vim_undo: KeyCode,
+    pub command: KeyCode,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap {
+            columns: COL_KEYS,
+            cells: FC_KEYS,
+            foundation: KeyCode::Enter,
+            cancel: KeyCode::Esc,
+            dragon: KeyCode::Char('d'),
+            undo: KeyCode::Char('z'),
+            new_game: KeyCode::Char('n'),
+            hint: KeyCode::Char('h'),
+            speed: KeyCode::Char('s'),
+            help: KeyCode::Char('?'),
+            stats: KeyCode::Tab,
+            campaign: KeyCode::Char('m'),
+            left: KeyCode::Char('h'),
+            down: KeyCode::Char('j'),
+            up: KeyCode::Char('k'),
+            right: KeyCode::Char('l'),
+            pick: KeyCode::Char(' '),
+            vim_undo: KeyCode::Char('u'),
+            command: KeyCode::Char(':'),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Bind `action` (a config key without its `key_` prefix) to `value`.
+    pub fn set(&mut self, action: &str, value: &str) -> Result<(), String> {
+        match action {
+            "columns" => {
+                self.columns = key_row(self.columns, value)
+                    .ok_or(format!("key_columns needs up to {} different characters", MAX_COLUMNS))?
+            }
+            "cells" => {
+                self.cells = key_row(self.cells, value)
+                    .ok_or(format!("key_cells needs up to {} different characters", MAX_FREE_CELLS))?
+            }
+            _ => {
+                let key = parse_key(value).ok_or(format!("unknown key name '{}' (a character, enter, esc, space, tab or backspace)", value))?;
+                *self.action_mut(action).ok_or(format!("unknown key binding 'key_{}'", action))? = key;
+            }
+        }
+        Ok(())
+    }
+
+    fn action_mut(&mut self, action: &str) -> Option<&mut KeyCode> {
+        Some(match action {
+            "foundation" => &mut self.foundation,
+            "cancel" => &mut self.cancel,
+            "dragon" => &mut self.dragon,
+            "undo" => &mut self.undo,
+            "new" => &mut self.new_game,
+            "hint" => &mut self.hint,
+            "speed" => &mut self.speed,
+            "help" => &mut self.help,
+            "stats" => &mut self.stats,
+            "campaign" => &mut self.campaign,
+            "left" => &mut self.left,
+            "down" => &mut self.down,
+            "up" => &mut self.up,
+            "right" => &mut self.right,
+            "pick" => &mut self.pick,
+            "vim_undo" => &mut self.vim_undo,
+            "command" => &mut self.command,
+            _ => return None,
+        })
+    }
+
+    /// The active bindings for `keys`, one per line with its config key.
+    pub fn lines(&self) -> Vec<String> {
+        let row = |keys: &[char]| keys.iter().map(char::to_string).collect::<Vec<_>>().join(" ");
+        let line = |action: &str, key: String, what: String| format!("key_{:<11} {:<20} {}", action, key, what);
+        let mut lines = vec![
+            line("columns", row(&self.columns), tr!("select a tableau column")),
+            line("cells", row(&self.cells), tr!("select / target a free cell")),
+        ];
+        let actions = [
+            ("foundation", self.foundation, tr!("send selected single card to foundation")),
+            ("cancel", self.cancel, tr!("cancel selection")),
+            ("dragon", self.dragon, tr!("merge dragons by suit")),
+            ("undo", self.undo, tr!("undo")),
+            ("new", self.new_game, tr!("new game")),
+            ("hint", self.hint, tr!("run solver hint / exit hint mode")),
+            ("speed", self.speed, tr!("toggle animation speed")),
+            ("help", self.help, tr!("toggle this help")),
+            ("stats", self.stats, tr!("statistics screen / next page")),
+            ("campaign", self.campaign, tr!("campaign map")),
+            ("left", self.left, tr!("vim_keys: cursor left")),
+            ("down", self.down, tr!("vim_keys: cursor to the columns")),
+            ("up", self.up, tr!("vim_keys: cursor to the free cells and foundations")),
+            ("right", self.right, tr!("vim_keys: cursor right")),
+            ("pick", self.pick, tr!("vim_keys: pick up or drop at the cursor")),
+            ("vim_undo", self.vim_undo, tr!("vim_keys: undo")),
+            ("command", self.command, tr!("vim_keys: open the command line")),
+        ];
+        for (action, key, what) in actions {
+            lines.push(line(action, key_name(key), what));
+        }
+        lines
+    }
+}
+
+/// `row` with its first keys replaced by the characters of `value`, as long
+/// as every key in the result is different.
+fn key_row<const N: usize>(mut row: [char; N], value: &str) -> Option<[char; N]> {
+    let keys: Vec<char> = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if keys.is_empty() || keys.len() > N {
+        return None;
+    }
+    row[..keys.len()].copy_from_slice(&keys);
+    let distinct = row.iter().enumerate().all(|(i, c)| !row[..i].contains(c));
+    distinct.then_some(row)
+}
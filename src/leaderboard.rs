@@ -0,0 +1,119 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! The optional online leaderboard for the daily deal (`submit` and
+//! `leaderboard`), built only with `--features leaderboard`.
+//!
+//! The endpoint is `leaderboard_url` from the config.  Requests go through
+//! [`crate::http`].
+//!
+//! * `POST <url>/daily` with a JSON submission.  The server's reply text
+//!   is shown to the player.
+//! * `GET <url>/daily/<YYYY-MM-DD>` answers with a JSON array of
+//!   `{"player", "time_secs", "moves", "hints"}`, best first; `hints` may
+//!   be left out for an unassisted win.
+use crate::http::{https_base, request};
+use crate::json::Json;
+
+/// A daily-deal win.  `log` holds the position code of every board the
+/// game passed through, undos included, so the server can replay it.
+pub struct Submission {
+    pub seed: u64,
+    pub date: String,
+    pub player: String,
+    pub elapsed_secs: u64,
+    pub moves: u32,
+    /// Hints the win took, so the table can tell assisted wins apart.
+    pub hints: u32,
+    pub log: Vec<String>,
+}
+
+impl Submission {
+    /// The text the signature covers: every field, in order, one per line.
+    fn signed_text(&self) -> String {
+        let mut text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n",
+            self.seed, self.date, self.player, self.elapsed_secs, self.moves, self.hints
+        );
+        for code in &self.log {
+            text.push_str(code);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn to_json(&self) -> Json {
+        let (signature, install) = crate::history::History::sign(self.signed_text().as_bytes());
+        Json::Object(vec![
+            ("seed".into(), self.seed.into()),
+            ("date".into(), self.date.as_str().into()),
+            ("player".into(), self.player.as_str().into()),
+            ("time_secs".into(), self.elapsed_secs.into()),
+            ("moves".into(), self.moves.into()),
+            ("hints".into(), self.hints.into()),
+            ("log".into(), Json::Array(self.log.iter().map(|c| c.as_str().into()).collect())),
+            ("install".into(), install.into()),
+            ("signature".into(), signature.into()),
+        ])
+    }
+}
+
+/// One row of the day's table.
+pub struct Entry {
+    pub player: String,
+    pub elapsed_secs: u64,
+    pub moves: u32,
+    pub hints: u32,
+}
+
+/// Send a win; returns the server's reply.
+pub fn submit(url: &str, submission: &Submission) -> Result<String, String> {
+    let body = submission.to_json().pretty();
+    let reply = request(&format!("{}/daily", base(url)?), Some(&body))?;
+    Ok(reply.trim().to_string())
+}
+
+/// The table for `date` (`YYYY-MM-DD`), best first.
+pub fn fetch(url: &str, date: &str) -> Result<Vec<Entry>, String> {
+    let reply = request(&format!("{}/daily/{}", base(url)?, date), None)?;
+    let doc = Json::parse(&reply).map_err(|e| format!("unreadable reply: {}", e))?;
+    let rows = doc.as_array().ok_or("unreadable reply: expected a list")?;
+    rows.iter()
+        .map(|row| {
+            Some(Entry {
+                player: row.get("player")?.as_str()?.to_string(),
+                elapsed_secs: u64::try_from(row.get("time_secs")?.as_int()?).ok()?,
+                moves: u32::try_from(row.get("moves")?.as_int()?).ok()?,
+                hints: match row.get("hints") {
+                    Some(hints) => u32::try_from(hints.as_int()?).ok()?,
+                    None => 0,
+                },
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| "unreadable reply: bad entry".to_string())
+}
+
+/// The endpoint without a trailing slash.
+fn base(url: &str) -> Result<&str, String> {
+    https_base(url, "leaderboard_url")
+}
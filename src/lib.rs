@@ -0,0 +1,43 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! The szsol rules engine: boards, moves, auto-moves and the solver.
+//!
+//! Nothing here reads stdin, writes stdout or touches files, so the same
+//! rules can be embedded in other frontends.  The terminal game in
+//! `main.rs` is built on top of this library.
+
+pub mod agent;
+pub mod board;
+pub mod card;
+pub mod compact;
+pub mod event;
+pub mod ffi;
+pub mod i18n;
+pub mod json;
+pub mod json_serde;
+pub mod packed;
+pub mod rules_file;
+pub mod solver;
+pub mod toml;
+#[cfg(feature = "wasm")]
+pub mod wasm;
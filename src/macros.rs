@@ -0,0 +1,94 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Command macros: `macro record <name>` captures the commands typed until
+//! `macro stop`, and `macro play <name>` runs them again.  Macros are kept
+//! in `macros.txt` in the config directory, one per line as
+//! `name = cmd; cmd; ...`.
+use std::fs;
+use std::path::PathBuf;
+
+use crate::profile;
+
+/// Separates the commands of a macro in `macros.txt`.
+const SEPARATOR: &str = "; ";
+
+#[derive(Debug, Clone, Default)]
+pub struct Macros {
+    list: Vec<(String, Vec<String>)>,
+    /// Where the macros are saved; `None` keeps them for this session only.
+    path: Option<PathBuf>,
+}
+
+impl Macros {
+    /// The macros saved in the config directory.
+    pub fn load() -> Self {
+        let path = profile::config_dir().map(|dir| dir.join("macros.txt"));
+        let mut macros = Self { list: Vec::new(), path: path.clone() };
+        let Some(content) = path.and_then(|p| fs::read_to_string(p).ok()) else {
+            return macros;
+        };
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, commands)) = line.split_once('=') else {
+                continue;
+            };
+            let commands: Vec<String> = commands
+                .split(SEPARATOR.trim())
+                .map(|c| c.trim().to_string())
+                // Recording leaves macro commands out; a hand-edited file
+                // must not make a macro play itself.
+                .filter(|c| !c.is_empty() && c.split_whitespace().next() != Some("macro"))
+                .collect();
+            if is_valid_name(name.trim()) && !commands.is_empty() {
+                macros.list.push((name.trim().to_string(), commands));
+            }
+        }
+        macros
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.list.iter().find(|(n, _)| n == name).map(|(_, c)| c.as_slice())
+    }
+
+    /// Store `commands` under `name`, replacing a macro of that name.
+    pub fn set(&mut self, name: &str, commands: Vec<String>) {
+        match self.list.iter_mut().find(|(n, _)| n == name) {
+            Some((_, c)) => *c = commands,
+            None => self.list.push((name.to_string(), commands)),
+        }
+        self.save();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.list.iter().map(|(n, c)| (n.as_str(), c.as_slice()))
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let mut content = String::from("# szsol-rs macros: name = command; command; ...\n");
+        for (name, commands) in &self.list {
+            content.push_str(&format!("{} = {}\n", name, commands.join(SEPARATOR)));
+        }
+        let _ = fs::write(path, content);
+    }
+}
+
+/// Macro names are single words of letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
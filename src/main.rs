@@ -20,36 +20,479 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
-mod solver;
-mod board;
-mod card;
+
+use szsol_rs::{agent, board, card, compact, event, i18n, json, json_serde, packed, rules_file, solver, toml};
+
+mod analyze;
+mod api;
+mod campaign;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod config;
+mod daily_share;
+mod dashboard;
+mod diag;
 mod command;
-mod event;
+mod compress;
+mod export;
+#[cfg(feature = "external-agents")]
+mod external_agent;
 mod game;
+mod ghost;
 mod history;
+mod hook;
+mod http;
+mod input;
+mod keymap;
+#[cfg(feature = "leaderboard")]
+mod leaderboard;
+mod macros;
+mod observer;
+mod profile;
+mod puzzle;
+mod rating;
 mod renderer;
+mod selfplay;
+mod server;
+mod share;
+mod signals;
+mod telemetry;
+mod theme;
 mod tui_renderer;
+mod weekly;
+
+use std::io::IsTerminal;
 
+use board::{DealStyle, Rules, Variant};
+use config::{AssistPreset, RendererKind, SaveFormat, Source, UserConfig};
 use game::Game;
-use renderer::CliRenderer;
+use history::History;
+use i18n::tr;
+use renderer::{CliRenderer, PlainRenderer};
 use tui_renderer::TuiRenderer;
 
 fn main() {
     let args: Vec<String> = std::env::args().skip(1).collect();
-    let cli_mode  = args.contains(&"--cli".to_string());
-    let seed: Option<u64> = args.iter()
-        .find(|a| !a.starts_with('-'))
-        .and_then(|s| s.parse().ok());
-
-    if cli_mode {
-        let mut game = Game::init(seed, CliRenderer::new());
-        game.run();
+    if let Some(lang) = i18n::Lang::from_env() {
+        i18n::set_lang(lang);
+    }
+    // The profile decides which config and history files are read, so it
+    // is settled first.
+    let profile = match args.iter().position(|a| a == "--profile") {
+        Some(i) => match args.get(i + 1) {
+            Some(name) if profile::is_valid_name(name) => Some(name.clone()),
+            Some(name) if name == "default" => None,
+            _ => {
+                eprintln!("--profile expects a name of letters, digits, '-' and '_'");
+                std::process::exit(2);
+            }
+        },
+        // Scripts run unattended.
+        None if args.iter().any(|a| a == "--script") => None,
+        None => profile::choose_interactively(),
+    };
+    profile::set(profile);
+    let mut user_config = UserConfig::load();
+    i18n::set_lang(user_config.language.value);
+    if let Some(i) = args.iter().position(|a| a == "--save-format") {
+        match args.get(i + 1).and_then(|name| SaveFormat::parse(name)) {
+            Some(format) => user_config.save_format.set(format, Source::CommandLine),
+            None => user_config.warnings.push("--save-format expects one of: binary, json".to_string()),
+        }
+    }
+    history::set_format(user_config.save_format.value);
+    if let Some(i) = args.iter().position(|a| a == "--deal-style") {
+        match args.get(i + 1).and_then(|name| DealStyle::parse(name)) {
+            Some(style) => user_config.deal_style.set(style, Source::CommandLine),
+            None => user_config.warnings.push("--deal-style expects one of: classic, shenzhen".to_string()),
+        }
+    }
+    for (flag, setting, range) in [
+        ("--columns", &mut user_config.columns, Rules::COLUMNS),
+        ("--free-cells", &mut user_config.free_cells, Rules::FREE_CELLS),
+        ("--suits", &mut user_config.suits, Rules::SUITS),
+        ("--ranks", &mut user_config.ranks, Rules::RANKS),
+    ] {
+        if let Some(i) = args.iter().position(|a| a == flag) {
+            match args.get(i + 1).and_then(|n| n.parse().ok()).filter(|n| range.contains(n)) {
+                Some(n) => setting.set(n, Source::CommandLine),
+                None => user_config
+                    .warnings
+                    .push(format!("{} expects a number from {} to {}", flag, range.start(), range.end())),
+            }
+        }
+    }
+    if let Some(i) = args.iter().position(|a| a == "--game") {
+        match args.get(i + 1).and_then(|name| Variant::parse(name)) {
+            Some(game) => user_config.game.set(game, Source::CommandLine),
+            None => user_config.warnings.push("--game expects one of: shenzhen, freecell".to_string()),
+        }
+    }
+    // `--rules <file>`: a TOML or JSON rules file replaces the game and the
+    // table's counts.  A broken one stops the game, so nobody plays by
+    // rules they didn't ask for.
+    if let Some(i) = args.iter().position(|a| a == "--rules") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("--rules expects a TOML or JSON rules file");
+            std::process::exit(2);
+        };
+        match std::fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|text| rules_file::parse(&text)) {
+            Ok(rules) => user_config.custom_rules = Some(rules),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                std::process::exit(2);
+            }
+        }
+    }
+    if positional(&args).next().is_some_and(|a| a == "selfplay") {
+        std::process::exit(selfplay(&args, user_config.rules()));
+    }
+    if positional(&args).next().is_some_and(|a| a == "analyze") {
+        std::process::exit(analyze(&args, user_config.rules()));
+    }
+    // `serve [--port N]`: clients play on the network with in-memory
+    // histories, so the host's save is neither locked nor touched.
+    let serving = positional(&args).next().is_some_and(|a| a == "serve");
+    // `--bot <name>`: a computer player takes the deal.  Its games are not
+    // recorded.
+    let mut bot = args.iter().position(|a| a == "--bot").map(|i| {
+        args.get(i + 1).and_then(|name| load_agent(name)).unwrap_or_else(|| {
+            eprintln!("{}", bot_usage());
+            std::process::exit(2);
+        })
+    });
+    // `--script <file>`: run the commands in a file (`-` reads stdin), then
+    // report the final board.  Scripted games are not recorded either.
+    let script = args.iter().position(|a| a == "--script").map(|i| {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("{}", tr!("--script expects a file of commands, or - for stdin"));
+            std::process::exit(2);
+        };
+        let text = match path.as_str() {
+            "-" => std::io::read_to_string(std::io::stdin()),
+            path => std::fs::read_to_string(path),
+        };
+        text.unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            std::process::exit(2);
+        })
+    });
+    if serving || bot.is_some() || script.is_some() {
+        History::in_memory();
+    } else if !History::lock() {
+        user_config.warnings.push(tr!("Another szsol is already running with this profile; this session will not be saved."));
+    }
+    if positional(&args).next().is_some_and(|a| a == "restore-backup") {
+        std::process::exit(restore_backup(positional(&args).nth(1)));
+    }
+    // `challenge <token>`: start on a friend's challenge.
+    let challenge = positional(&args).next().filter(|a| *a == "challenge").map(|_| {
+        positional(&args).nth(1).cloned().unwrap_or_else(|| {
+            eprintln!("{}", tr!("Usage: szsol-rs challenge <token>"));
+            std::process::exit(2);
+        })
+    });
+    if args.contains(&"--cli".to_string()) {
+        user_config.renderer.set(RendererKind::Cli, Source::CommandLine);
+    } else if args.contains(&"--tui".to_string()) {
+        user_config.renderer.set(RendererKind::Tui, Source::CommandLine);
+    } else if args.contains(&"--plain".to_string()) {
+        user_config.renderer.set(RendererKind::Plain, Source::CommandLine);
+    }
+    // Bots and scripts play in the text renderers.
+    if (bot.is_some() || script.is_some()) && user_config.renderer.value == RendererKind::Tui {
+        user_config.renderer.set(RendererKind::Cli, Source::CommandLine);
+    }
+    // `-v` / `-vv`: diagnostics, kept out of the TUI's way in the log file.
+    let to_file = args.contains(&"--log-file".to_string()) || user_config.renderer.value == RendererKind::Tui;
+    diag::init(diag::verbosity(&args), to_file);
+    if args.contains(&"--speedrun".to_string()) {
+        user_config.speedrun.set(true, Source::CommandLine);
+    }
+    if args.contains(&"--golf".to_string()) {
+        user_config.golf.set(true, Source::CommandLine);
+    }
+    if args.contains(&"--keys".to_string()) {
+        user_config.input.set(input::InputMode::Keys, Source::CommandLine);
+    }
+    if args.contains(&"--in-place".to_string()) {
+        user_config.in_place.set(true, Source::CommandLine);
+    }
+    if args.contains(&"--hardcore".to_string()) {
+        user_config.hardcore.set(true, Source::CommandLine);
+    }
+    if let Some(i) = args.iter().position(|a| a == "--assist") {
+        match args.get(i + 1).and_then(|name| AssistPreset::parse(name)) {
+            Some(preset) => user_config.assist.set(preset, Source::CommandLine),
+            None => user_config.warnings.push("--assist expects one of: purist, standard, relaxed".to_string()),
+        }
+    }
+    if let Some(i) = args.iter().position(|a| a == "--theme") {
+        match args.get(i + 1).and_then(|name| theme::Theme::by_name(name)) {
+            Some(theme) => user_config.theme.set(theme.name.to_string(), Source::CommandLine),
+            None => user_config.warnings.push(format!(
+                "--theme expects one of: {}",
+                theme::Theme::names()
+            )),
+        }
+    }
+    // https://no-color.org: any non-empty NO_COLOR disables styling.
+    if args.contains(&"--no-color".to_string()) {
+        user_config.color.set(false, Source::CommandLine);
+    } else if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+        || (!serving && !std::io::stdout().is_terminal())
+        || (!serving && !ansi_console())
+    {
+        user_config.color.set(false, Source::Environment);
+    }
+    if serving {
+        // `--http` serves the JSON API instead of terminal sessions.
+        let http = args.contains(&"--http".to_string());
+        let default_port = if http { api::DEFAULT_PORT } else { server::DEFAULT_PORT };
+        let Some(port) = server::parse_port(&args, default_port) else {
+            eprintln!("{}", tr!("--port expects a port number"));
+            std::process::exit(2);
+        };
+        if http {
+            std::process::exit(api::serve(port, user_config));
+        }
+        if user_config.renderer.value == RendererKind::Tui {
+            user_config.renderer.set(RendererKind::Cli, Source::CommandLine);
+        }
+        std::process::exit(server::serve(port, user_config));
+    }
+    // `--seed <value>` accepts a number, 0x-hex, `daily`, or any phrase;
+    // a bare positional argument is still accepted for numeric seeds.
+    let (seed, seed_phrase) = match args.iter().position(|a| a == "--seed") {
+        Some(i) => match args.get(i + 1) {
+            Some(value) => {
+                let (seed, phrase) = command::parse_seed_or_phrase(value);
+                (Some(seed), phrase)
+            }
+            None => (None, None),
+        },
+        None => {
+            let seed = positional(&args).next().and_then(|s| command::parse_seed(s).ok());
+            (seed, None)
+        }
+    };
+
+    // `--events <file>`: every game event as a JSON line, for other
+    // programs to follow.
+    let mut event_log = args.iter().position(|a| a == "--events").map(|i| {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("{}", tr!("--events expects a file to write"));
+            std::process::exit(2);
+        };
+        observer::EventLog::create(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            std::process::exit(2);
+        })
+    });
+
+    signals::install(user_config.renderer.value == RendererKind::Tui);
+    let (input_mode, index_base) = (user_config.input.value, user_config.index_base());
+    // Bots and scripts exit with their game's outcome.
+    let mut outcome = None;
+    if user_config.renderer.value == RendererKind::Cli {
+        let renderer = CliRenderer::new()
+            .with_index_base(user_config.index_base())
+            .with_theme(user_config.theme())
+            .with_color(user_config.color.value)
+            .with_in_place(user_config.in_place.value)
+            .with_card_style(user_config.card_style.value)
+            .with_labels(user_config.labels());
+        let mut game = Game::init(seed, seed_phrase, renderer, user_config);
+        if let Some(log) = event_log.take() {
+            game.subscribe(Box::new(log));
+        }
+        if let Some(token) = &challenge {
+            game.take_challenge(token);
+        }
+        outcome = match (&mut bot, &script) {
+            (Some(agent), _) => Some(game.run_bot(agent.as_mut())),
+            (None, Some(script)) => Some(game.run_script(script)),
+            (None, None) => {
+                game.run(input::default_input(input_mode, index_base).as_mut());
+                None
+            }
+        };
+    } else if user_config.renderer.value == RendererKind::Plain {
+        let renderer = PlainRenderer::new().with_index_base(user_config.index_base());
+        let mut game = Game::init(seed, seed_phrase, renderer, user_config);
+        if let Some(log) = event_log.take() {
+            game.subscribe(Box::new(log));
+        }
+        if let Some(token) = &challenge {
+            game.take_challenge(token);
+        }
+        outcome = match (&mut bot, &script) {
+            (Some(agent), _) => Some(game.run_bot(agent.as_mut())),
+            (None, Some(script)) => Some(game.run_script(script)),
+            (None, None) => {
+                game.run(input::default_input(input_mode, index_base).as_mut());
+                None
+            }
+        };
     } else {
         // Detect glyph display width BEFORE entering alternate screen / raw mode.
 
         let renderer = TuiRenderer::new().expect("Failed to initialise terminal");
-        let mut game = Game::init(seed, renderer);
+        let mut game = Game::init(seed, seed_phrase, renderer, user_config);
+        if let Some(log) = event_log.take() {
+            game.subscribe(Box::new(log));
+        }
+        if let Some(token) = &challenge {
+            game.take_challenge(token);
+        }
         game.run_tui();
     }
+    History::flush();
+    if let Some(outcome) = outcome {
+        std::process::exit(outcome.exit_code());
+    }
+}
+
+/// Whether the console shows ANSI escapes as colors.  Legacy Windows
+/// consoles print them literally until virtual terminal processing is
+/// switched on, which this does; where that fails, play without color.
+fn ansi_console() -> bool {
+    #[cfg(windows)]
+    {
+        crossterm::ansi_support::supports_ansi()
+    }
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
+
+/// Arguments that are neither options nor the values of options that take one.
+fn positional(args: &[String]) -> impl Iterator<Item = &String> {
+    args.iter().enumerate().filter_map(|(i, a)| {
+        let is_value = i > 0
+            && matches!(
+                args[i - 1].as_str(),
+                "--theme" | "--assist" | "--profile" | "--save-format" | "--deal-style" | "--columns" | "--free-cells" | "--suits" | "--ranks" | "--game" | "--port" | "--bot" | "--games" | "--out" | "--agent" | "--seeds" | "--threads" | "--script" | "--events"
+            );
+        (!a.starts_with('-') && !is_value).then_some(a)
+    })
+}
+
+/// `selfplay [--games N] [--out file.jsonl] [--agent name|solver]`, on
+/// deals under `rules`.
+fn selfplay(args: &[String], rules: Rules) -> i32 {
+    let value = |name: &str| args.iter().position(|a| a == name).map(|i| args.get(i + 1));
+    let games = match value("--games") {
+        None => 100,
+        Some(n) => match n.and_then(|n| n.parse().ok()) {
+            Some(n) => n,
+            None => {
+                eprintln!("{}", tr!("--games expects a number"));
+                return 2;
+            }
+        },
+    };
+    let out = value("--out").flatten().map_or("selfplay.jsonl", |out| out.as_str());
+    let player = match value("--agent").map(|name| name.map(String::as_str)) {
+        Some(Some("solver")) => selfplay::Player::Solver,
+        Some(name) => match name.and_then(load_agent) {
+            Some(agent) => selfplay::Player::Agent(agent),
+            None => {
+                eprintln!("{}", tr!("--agent expects 'solver' or a --bot agent"));
+                return 2;
+            }
+        },
+        None => selfplay::Player::Agent(Box::new(agent::GreedyAgent::default())),
+    };
+    selfplay::run(games, rules, out, player)
+}
+
+/// `analyze [--seeds a..b] [--threads N] [--out file.csv]`, on deals
+/// under `rules`.
+fn analyze(args: &[String], rules: Rules) -> i32 {
+    let value = |name: &str| args.iter().position(|a| a == name).map(|i| args.get(i + 1));
+    let seeds = match value("--seeds") {
+        None => 1..=1000,
+        Some(range) => match range.and_then(|range| analyze::parse_range(range)) {
+            Some(range) => range,
+            None => {
+                eprintln!("{}", tr!("--seeds expects a range such as 1..1000"));
+                return 2;
+            }
+        },
+    };
+    let threads = match value("--threads") {
+        // A hard deal can take the solver close to a gigabyte, so more
+        // threads are opt-in.
+        None => 1,
+        Some(n) => match n.and_then(|n| n.parse().ok()).filter(|&n| n > 0) {
+            Some(n) => n,
+            None => {
+                eprintln!("{}", tr!("--threads expects a number"));
+                return 2;
+            }
+        },
+    };
+    let out = value("--out").flatten().map_or("analysis.csv", |out| out.as_str());
+    analyze::run(seeds, rules, threads, out)
+}
+
+/// A built-in agent by name or, with the `external-agents` feature, a
+/// program to run as one.
+fn load_agent(name: &str) -> Option<Box<dyn agent::Agent>> {
+    if let Some(agent) = agent::builtin(name) {
+        return Some(agent);
+    }
+    #[cfg(feature = "external-agents")]
+    match external_agent::ProcessAgent::spawn(name) {
+        Ok(agent) => return Some(Box::new(agent)),
+        Err(e) => eprintln!("{}", tr!("Could not start {}: {}", name, e)),
+    }
+    None
+}
+
+fn bot_usage() -> String {
+    let names = agent::BUILTIN_NAMES.join(", ");
+    if cfg!(feature = "external-agents") {
+        tr!("--bot expects one of: {}, or an agent program", names)
+    } else {
+        tr!("--bot expects one of: {}", names)
+    }
+}
+
+/// `restore-backup [n]`: list the save file's backups, or restore backup `n`.
+fn restore_backup(choice: Option<&String>) -> i32 {
+    let backups = History::backups();
+    let Some(choice) = choice else {
+        if backups.is_empty() {
+            println!("{}", tr!("There are no backups of the save file yet."));
+            return 0;
+        }
+        println!("{}", tr!("Backups of the save file, newest first:"));
+        for backup in &backups {
+            let when = backup.modified.map_or_else(|| "?".to_string(), history::format_date);
+            let state = match &backup.contents {
+                Ok(history) => tr!("{} game(s)", history.records.len()),
+                Err(problem) => i18n::lookup(problem.message()).to_string(),
+            };
+            println!("  {}  {}  {}", backup.index, when, state);
+        }
+        println!("{}", tr!("Restore one with: szsol-rs restore-backup <n>"));
+        return 0;
+    };
+    let Ok(index) = choice.parse::<usize>() else {
+        eprintln!("{}", tr!("restore-backup expects a backup number"));
+        return 2;
+    };
+    match History::restore_backup(index) {
+        Ok(history) => {
+            println!("{}", tr!("Restored backup {} ({} game(s)).", index, history.records.len()));
+            0
+        }
+        Err(problem) => {
+            eprintln!("{}", i18n::lookup(problem));
+            1
+        }
+    }
 }
@@ -0,0 +1,72 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! What happens in a game, for anything that wants to follow along:
+//! [`Game::subscribe`](crate::game::Game::subscribe) takes an [`EventSink`]
+//! and hands it every [`PlayEvent`].  `--events <file>` subscribes an
+//! [`EventLog`], which writes them out as JSON lines.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::card::Suit;
+use crate::json::Json;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayEvent {
+    /// The player's move went through; `moves` counts it.
+    MoveApplied { moves: u32 },
+    /// Cards went home on their own after a move or the deal.
+    AutoMoved { cards: usize },
+    /// A suit's dragons were merged, by hand or automatically.
+    DragonsMerged { suit: Suit },
+    /// The game was won and recorded.
+    GameWon { seed: u64, moves: u32 },
+    /// `steps` moves were taken back.
+    UndoPerformed { steps: usize },
+}
+
+impl PlayEvent {
+    pub fn to_json(&self) -> Json {
+        let (name, mut fields): (&str, Vec<(String, Json)>) = match self {
+            PlayEvent::MoveApplied { moves } => ("move_applied", vec![("moves".into(), (*moves).into())]),
+            PlayEvent::AutoMoved { cards } => ("auto_moved", vec![("cards".into(), (*cards as u64).into())]),
+            PlayEvent::DragonsMerged { suit } => ("dragons_merged", vec![("suit".into(), suit.name().into())]),
+            PlayEvent::GameWon { seed, moves } => {
+                ("game_won", vec![("seed".into(), (*seed).into()), ("moves".into(), (*moves).into())])
+            }
+            PlayEvent::UndoPerformed { steps } => ("undo_performed", vec![("steps".into(), (*steps as u64).into())]),
+        };
+        fields.insert(0, ("event".into(), name.into()));
+        Json::Object(fields)
+    }
+}
+
+/// Hears about every [`PlayEvent`] of the game it subscribed to.
+pub trait EventSink {
+    fn on_event(&mut self, event: &PlayEvent);
+}
+
+/// Writes each event as one JSON line, flushed as it happens so another
+/// program can follow the file.
+pub struct EventLog {
+    out: BufWriter<File>,
+}
+
+impl EventLog {
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        Ok(EventLog { out: BufWriter::new(File::create(path)?) })
+    }
+}
+
+impl EventSink for EventLog {
+    fn on_event(&mut self, event: &PlayEvent) {
+        let _ = writeln!(self.out, "{}", event.to_json().to_line());
+        let _ = self.out.flush();
+    }
+}
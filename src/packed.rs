@@ -0,0 +1,167 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! A fixed-size, `Copy` form of [`Board`] for the solver's search.
+//!
+//! A `Board` keeps each column in its own `Vec`, so every copy allocates
+//! nine times.  The solver keeps hundreds of thousands of positions, so it
+//! stores them as `PackedBoard`s instead: each card is a 6-bit code in one
+//! byte and the columns share a single array, which makes copying,
+//! hashing and comparing a matter of a few dozen bytes.
+use crate::board::{Board, DealStyle, FreeCellState, Rules, Stacking, MAX_COLUMNS, MAX_DECK, MAX_FREE_CELLS, MAX_RANK, MAX_SUITS};
+use crate::card::{Card, Suit};
+
+/// Every card of the largest deal.  A board never holds more than this in
+/// its columns.
+const MAX_CARDS: usize = MAX_DECK;
+
+/// First dragon code; the numbered cards come before it.
+const DRAGONS: u8 = 1 + MAX_SUITS as u8 * MAX_RANK;
+const FLOWER: u8 = DRAGONS + MAX_SUITS as u8;
+/// Free-cell codes above the card codes mark a cell locked by dragons.
+const LOCKED: u8 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedBoard {
+    /// The columns one after another, bottom card first; `lens` says where
+    /// each one ends.  Unused bytes stay 0 so equal boards compare equal.
+    cards: [u8; MAX_CARDS],
+    lens: [u8; MAX_COLUMNS],
+    /// 0 empty, a card code, or `LOCKED` + suit.
+    free_cells: [u8; MAX_FREE_CELLS],
+    /// The board's `Rules`: how many of `lens` and `free_cells` are in use.
+    column_count: u8,
+    free_cell_count: u8,
+    suit_count: u8,
+    rank_count: u8,
+    dragon_count: u8,
+    flower: bool,
+    stacking: Stacking,
+    supermove: bool,
+    deal_style: DealStyle,
+    /// Unused slots past `suit_count` stay 0.
+    foundations: [u8; MAX_SUITS],
+    flower_placed: bool,
+    seed: u64,
+}
+
+/// Numbered cards from 1 (room for `MAX_RANK` per suit), then `DRAGONS`
+/// and `FLOWER`.
+fn card_code(card: Card) -> u8 {
+    match card {
+        Card::Numbered(suit, value) => 1 + suit_index(suit) * MAX_RANK + (value - 1),
+        Card::Dragon(suit) => DRAGONS + suit_index(suit),
+        Card::Flower => FLOWER,
+    }
+}
+
+fn code_card(code: u8) -> Card {
+    match code {
+        1..DRAGONS => Card::Numbered(Suit::ALL[((code - 1) / MAX_RANK) as usize], (code - 1) % MAX_RANK + 1),
+        DRAGONS..FLOWER => Card::Dragon(Suit::ALL[(code - DRAGONS) as usize]),
+        _ => Card::Flower,
+    }
+}
+
+fn suit_index(suit: Suit) -> u8 {
+    Suit::ALL.iter().position(|&s| s == suit).unwrap_or(0) as u8
+}
+
+impl From<&Board> for PackedBoard {
+    fn from(board: &Board) -> Self {
+        let mut packed = PackedBoard {
+            cards: [0; MAX_CARDS],
+            lens: [0; MAX_COLUMNS],
+            free_cells: [0; MAX_FREE_CELLS],
+            column_count: board.rules.columns as u8,
+            free_cell_count: board.rules.free_cells as u8,
+            suit_count: board.rules.suits as u8,
+            rank_count: board.rules.ranks as u8,
+            dragon_count: board.rules.dragons as u8,
+            flower: board.rules.flower,
+            stacking: board.rules.stacking,
+            supermove: board.rules.supermove,
+            deal_style: board.rules.deal_style,
+            foundations: [0; MAX_SUITS],
+            flower_placed: board.flower_placed,
+            seed: board.seed,
+        };
+        packed.foundations[..board.foundations.len()].copy_from_slice(&board.foundations);
+        let mut next = 0;
+        for (col, column) in board.columns.iter().enumerate() {
+            for &card in column {
+                packed.cards[next] = card_code(card);
+                next += 1;
+            }
+            packed.lens[col] = column.len() as u8;
+        }
+        for (slot, cell) in board.free_cells.iter().enumerate() {
+            packed.free_cells[slot] = match cell {
+                FreeCellState::Empty => 0,
+                FreeCellState::Card(card) => card_code(*card),
+                FreeCellState::DragonLocked(suit) => LOCKED + suit_index(*suit),
+            };
+        }
+        packed
+    }
+}
+
+impl PackedBoard {
+    pub fn unpack(&self) -> Board {
+        let rules = Rules {
+            columns: self.column_count as usize,
+            free_cells: self.free_cell_count as usize,
+            suits: self.suit_count as usize,
+            ranks: self.rank_count as usize,
+            dragons: self.dragon_count as usize,
+            flower: self.flower,
+            stacking: self.stacking,
+            supermove: self.supermove,
+            deal_style: self.deal_style,
+        };
+        let mut next = 0;
+        let columns = self.lens[..rules.columns]
+            .iter()
+            .map(|&len| {
+                let len = len as usize;
+                next += len;
+                self.cards[next - len..next].iter().map(|&code| code_card(code)).collect()
+            })
+            .collect();
+        let free_cells = self.free_cells[..rules.free_cells]
+            .iter()
+            .map(|&code| match code {
+                0 => FreeCellState::Empty,
+                LOCKED.. => FreeCellState::DragonLocked(Suit::ALL[(code - LOCKED) as usize]),
+                _ => FreeCellState::Card(code_card(code)),
+            })
+            .collect();
+        Board {
+            rules,
+            columns,
+            free_cells,
+            foundations: self.foundations[..rules.suits].to_vec(),
+            flower_placed: self.flower_placed,
+            seed: self.seed,
+        }
+    }
+}
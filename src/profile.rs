@@ -0,0 +1,130 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Named player profiles.  Each profile keeps its own history and config
+//! files under `profiles/<name>/` in the data and config directories; the
+//! default profile uses the directories themselves, as before profiles
+//! existed.
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use directories::ProjectDirs;
+
+use crate::i18n::tr;
+
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Select the profile for the rest of the process; call once at startup,
+/// before any file is read.
+pub fn set(name: Option<String>) {
+    let _ = PROFILE.set(name);
+}
+
+/// The active profile, or `None` for the default one.
+pub fn name() -> Option<&'static str> {
+    PROFILE.get().and_then(|p| p.as_deref())
+}
+
+/// Profile names become directory names, so keep them to letters, digits,
+/// `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        && name != "default"
+}
+
+/// Where the active profile's history lives.
+pub fn data_dir() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("com", "szsol", "szsol")?;
+    Some(scoped(dirs.data_dir().to_path_buf()))
+}
+
+/// Where the active profile's config files live.
+pub fn config_dir() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("com", "szsol", "szsol")?;
+    Some(scoped(dirs.config_dir().to_path_buf()))
+}
+
+fn scoped(root: PathBuf) -> PathBuf {
+    match name() {
+        Some(name) => root.join("profiles").join(name),
+        None => root,
+    }
+}
+
+/// Named profiles that have saved anything, sorted.
+pub fn list() -> Vec<String> {
+    let Some(dirs) = ProjectDirs::from("com", "szsol", "szsol") else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = [dirs.data_dir(), dirs.config_dir()]
+        .iter()
+        .filter_map(|root| std::fs::read_dir(root.join("profiles")).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_name(name))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Ask which profile to play when named profiles exist and the game runs
+/// in a terminal.  Returns `None` for the default profile.
+pub fn choose_interactively() -> Option<String> {
+    let profiles = list();
+    if profiles.is_empty() || !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let mut menu = format!("{}  0) {}", tr!("Profiles:"), tr!("default"));
+    for (i, name) in profiles.iter().enumerate() {
+        menu.push_str(&format!("  {}) {}", i + 1, name));
+    }
+    println!("{}", menu);
+    let stdin = std::io::stdin();
+    loop {
+        print!("{} ", tr!("Choose a profile (number or a new name, Enter for default):"));
+        let _ = std::io::stdout().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return None;
+        }
+        let choice = line.trim();
+        if choice.is_empty() || choice == "0" || choice == "default" {
+            return None;
+        }
+        if let Ok(n) = choice.parse::<usize>() {
+            match profiles.get(n.wrapping_sub(1)) {
+                Some(name) => return Some(name.clone()),
+                None => continue,
+            }
+        }
+        if is_valid_name(choice) {
+            return Some(choice.to_string());
+        }
+        println!("{}", tr!("Profile names may use letters, digits, '-' and '_'."));
+    }
+}
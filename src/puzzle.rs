@@ -0,0 +1,199 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Puzzles: mid-game positions with a goal, such as merging the red
+//! dragons within five moves.  A pack is a TOML file of puzzles, each a
+//! position code with its goal; the starter pack ships with the game and
+//! others load with `puzzles <file>`.
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, FreeCellState};
+use crate::card::Suit;
+use crate::i18n::{self, tr};
+use crate::toml::{parse_toml_value, strip_comment, TomlValue};
+
+const STARTER: &str = include_str!("../puzzles/starter.toml");
+
+/// What a puzzle asks the player to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Goal {
+    /// Merge a suit's dragons.
+    Merge(Suit),
+    /// Build a suit's foundation up to a rank.
+    Home(Suit, u8),
+    /// Place the flower.
+    Flower,
+    /// Win the game.
+    Win,
+}
+
+impl Goal {
+    /// `merge r`, `home g 5`, `flower` or `win`.
+    pub fn parse(text: &str) -> Option<Goal> {
+        let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let suit = |s: &str| Suit::ALL.into_iter().find(|suit| suit.symbol().eq_ignore_ascii_case(s));
+        match words.as_slice() {
+            ["merge", s] => Some(Goal::Merge(suit(s)?)),
+            ["home", s, rank] => Some(Goal::Home(suit(s)?, rank.parse().ok().filter(|&r| r > 0)?)),
+            ["flower"] => Some(Goal::Flower),
+            ["win"] => Some(Goal::Win),
+            _ => None,
+        }
+    }
+
+    /// Whether the board has reached the goal.
+    pub fn is_met(self, board: &Board) -> bool {
+        match self {
+            Goal::Merge(suit) => board.free_cells.contains(&FreeCellState::DragonLocked(suit)),
+            Goal::Home(suit, rank) => board.foundations.get(suit as usize).is_some_and(|&f| f >= rank),
+            Goal::Flower => board.flower_placed,
+            Goal::Win => board.is_won(),
+        }
+    }
+
+    /// Whether the goal can be reached under the board's rules at all.
+    fn fits(self, board: &Board) -> bool {
+        match self {
+            Goal::Merge(suit) => board.rules.dragons > 0 && board.rules.suits().contains(&suit),
+            Goal::Home(suit, rank) => board.rules.suits().contains(&suit) && rank <= board.rules.top_rank(),
+            Goal::Flower => board.rules.flower,
+            Goal::Win => true,
+        }
+    }
+
+    /// The goal as an instruction, e.g. "Merge the red dragons".
+    pub fn describe(self) -> String {
+        match self {
+            Goal::Merge(suit) => tr!("Merge the {} dragons", i18n::suit_name(suit).to_lowercase()),
+            Goal::Home(suit, rank) => tr!("Build the {} foundation to {}", i18n::suit_name(suit).to_lowercase(), rank),
+            Goal::Flower => tr!("Place the flower"),
+            Goal::Win => tr!("Win the game"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// The puzzle's table name, unique within its pack.
+    pub name: String,
+    pub title: String,
+    pub goal: Goal,
+    /// Moves allowed to reach the goal; `None` for no limit.
+    pub moves: Option<u32>,
+    pub board: Board,
+}
+
+impl Puzzle {
+    /// "Merge the red dragons within 5 moves."
+    pub fn task(&self) -> String {
+        task(self.goal, self.moves)
+    }
+}
+
+/// A puzzle's goal and move limit as a sentence.
+pub fn task(goal: Goal, moves: Option<u32>) -> String {
+    match moves {
+        Some(moves) => tr!("{} within {} moves.", goal.describe(), moves),
+        None => format!("{}.", goal.describe()),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pack {
+    /// `starter`, or the file name a pack was loaded from.
+    pub name: String,
+    pub puzzles: Vec<Puzzle>,
+}
+
+impl Pack {
+    /// The pack that ships with the game.
+    pub fn starter() -> Pack {
+        Pack::parse("starter", STARTER).expect("the starter pack is valid")
+    }
+
+    /// Read a pack: one `[name]` table per puzzle with its `title`, `goal`,
+    /// `position` code and optional `moves` limit.  Errors give the line.
+    pub fn parse(name: &str, text: &str) -> Result<Pack, String> {
+        #[derive(Default)]
+        struct Draft {
+            line: usize,
+            name: String,
+            title: Option<String>,
+            goal: Option<Goal>,
+            moves: Option<u32>,
+            board: Option<Board>,
+        }
+        let finish = |draft: Draft| -> Result<Puzzle, String> {
+            let missing = |key: &str| format!("line {}: [{}] has no {}", draft.line, draft.name, key);
+            let board = draft.board.ok_or_else(|| missing("position"))?;
+            let goal = draft.goal.ok_or_else(|| missing("goal"))?;
+            if !goal.fits(&board) {
+                return Err(format!("line {}: [{}]'s goal can't be reached with its deck", draft.line, draft.name));
+            }
+            Ok(Puzzle {
+                title: draft.title.unwrap_or_else(|| draft.name.clone()),
+                name: draft.name,
+                goal,
+                moves: draft.moves,
+                board,
+            })
+        };
+
+        let mut puzzles = Vec::new();
+        let mut draft: Option<Draft> = None;
+        for (n, raw_line) in text.lines().enumerate() {
+            let line = strip_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                if let Some(done) = draft.take() {
+                    puzzles.push(finish(done)?);
+                }
+                let table = table.trim().to_string();
+                if puzzles.iter().any(|p: &Puzzle| p.name == table) {
+                    return Err(format!("line {}: [{}] appears twice", n + 1, table));
+                }
+                draft = Some(Draft { line: n + 1, name: table, ..Draft::default() });
+                continue;
+            }
+            let at = |e: String| format!("line {}: {}", n + 1, e);
+            let Some(current) = draft.as_mut() else {
+                return Err(at("expected a [puzzle] table first".to_string()));
+            };
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(at("expected `key = value`".to_string()));
+            };
+            match (key.trim(), parse_toml_value(value.trim())) {
+                ("title", TomlValue::Str(v)) => current.title = Some(v),
+                ("goal", TomlValue::Str(v)) => {
+                    current.goal = Some(Goal::parse(&v).ok_or_else(|| {
+                        at(format!("unknown goal '{}' (merge <suit>, home <suit> <rank>, flower, win)", v))
+                    })?);
+                }
+                ("moves", TomlValue::Int(v)) if v > 0 && v <= u32::MAX as i64 => current.moves = Some(v as u32),
+                ("position", TomlValue::Str(v)) => {
+                    current.board = Some(crate::share::decode(&v).map_err(|e| at(format!("position: {}", e)))?);
+                }
+                (key @ ("title" | "goal" | "moves" | "position"), _) => {
+                    return Err(at(format!("invalid value for '{}'", key)));
+                }
+                (key, _) => return Err(at(format!("unknown key '{}'", key))),
+            }
+        }
+        if let Some(done) = draft.take() {
+            puzzles.push(finish(done)?);
+        }
+        if puzzles.is_empty() {
+            return Err("the pack has no puzzles".to_string());
+        }
+        Ok(Pack { name: name.to_string(), puzzles })
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! An Elo-style player rating.  Every finished deal is a match against the
+//! deal itself, rated by the solver: the longer its par, the stronger the
+//! opponent, and a deal the solver could not win is the strongest of all.
+//! A win against a strong deal gains much, a loss to a weak one costs much.
+use serde::{Deserialize, Serialize};
+
+/// Every player's first rating.
+pub const START: u32 = 1500;
+/// How far one game can move the rating.
+const K: f64 = 32.0;
+/// The lowest rating; a run of losses stops here.
+const FLOOR: u32 = 100;
+/// A deal with a par of `PAR_BASE` moves is rated `START`; each move more
+/// or less is worth `PER_MOVE` points.
+const PAR_BASE: u32 = 40;
+const PER_MOVE: u32 = 15;
+/// The rating of a deal the solver gave up on.
+const UNSOLVED: u32 = 2400;
+
+/// One rated game, kept in the history in the order played.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RatingChange {
+    pub seed: u64,
+    /// When the game ended.
+    pub time: i64,
+    /// The deal's rating.
+    pub opponent: u32,
+    pub won: bool,
+    /// The player's rating after the game.
+    pub rating: u32,
+}
+
+/// The rating of the deal whose par is `par` (`None`: no solution found).
+pub fn deal_rating(par: Option<u32>) -> u32 {
+    match par {
+        Some(par) => (START + par * PER_MOVE).saturating_sub(PAR_BASE * PER_MOVE).max(FLOOR),
+        None => UNSOLVED,
+    }
+}
+
+/// `rating` after a game against a deal rated `opponent`.
+pub fn update(rating: u32, opponent: u32, won: bool) -> u32 {
+    let expected = 1.0 / (1.0 + 10f64.powf((f64::from(opponent) - f64::from(rating)) / 400.0));
+    let score = if won { 1.0 } else { 0.0 };
+    let updated = f64::from(rating) + K * (score - expected);
+    (updated.round() as u32).max(FLOOR)
+}
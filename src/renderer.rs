@@ -20,6 +20,100 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use std::io::Write;
+use std::time::Duration;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::board::{FreeCellState, Rules};
+use crate::card::{full_deck, AsciiLabels, Card, LabelProvider, Suit};
+use crate::config::AssistPreset;
+use crate::campaign::CampaignMap;
+use crate::ghost::{self, GhostStatus};
+use crate::history::{PersonalBests, SeedRecords};
+use crate::i18n::{self, tr, Lang};
+use crate::solver::Winnability;
+use crate::theme::Theme;
+
+/// Whether the position can still be won, for the header (`winnability`
+/// setting).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outlook {
+    /// The background solver hasn't answered for this position yet.
+    Checking,
+    Known(Winnability),
+}
+
+impl Outlook {
+    /// ✓ winnable, ✗ unwinnable, ? unknown within the solver's budget.
+    pub fn mark(self) -> &'static str {
+        match self {
+            Outlook::Checking => "…",
+            Outlook::Known(Winnability::Winnable) => "✓",
+            Outlook::Known(Winnability::Unwinnable) => "✗",
+            Outlook::Known(Winnability::Unknown) => "?",
+        }
+    }
+
+    /// The indicator as a sentence, for the plain renderer.
+    pub fn sentence(self) -> String {
+        match self {
+            Outlook::Checking => tr!("Checking whether the game can still be won."),
+            Outlook::Known(Winnability::Winnable) => tr!("The game can still be won."),
+            Outlook::Known(Winnability::Unwinnable) => tr!("The game can no longer be won."),
+            Outlook::Known(Winnability::Unknown) => tr!("The solver could not tell whether the game can still be won."),
+        }
+    }
+}
+
+/// Below this many legal moves the mobility count is drawn in red.
+pub const LOW_MOBILITY: usize = 3;
+
+/// Optional indicators for the header; `None` hides one.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderStatus {
+    /// Whether the position can still be won (`winnability` setting).
+    pub outlook: Option<Outlook>,
+    /// Legal moves in the position (`mobility` setting).
+    pub mobility: Option<usize>,
+    /// Time until the next weekly challenge, while this week's is played.
+    pub weekly: Option<Duration>,
+    /// The best run on the seed, while racing it (`ghost` setting).
+    pub ghost: Option<GhostStatus>,
+}
+
+/// How much the CLI and plain renderers print after each command
+/// (`set render`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// Only the rows that changed, and no auto-move messages.
+    Quiet,
+    #[default]
+    Normal,
+    /// Also name each auto-moved card and show the foundations after every
+    /// command.
+    Verbose,
+}
+
+impl RenderMode {
+    pub fn parse(value: &str) -> Option<RenderMode> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "quiet" => Some(RenderMode::Quiet),
+            "normal" => Some(RenderMode::Normal),
+            "verbose" => Some(RenderMode::Verbose),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RenderMode::Quiet => "quiet",
+            RenderMode::Normal => "normal",
+            RenderMode::Verbose => "verbose",
+        }
+    }
+}
+
 /// Trait that abstracts the rendering layer.
 ///
 /// Implement this trait for:
@@ -36,8 +130,32 @@ pub trait Renderer {
     fn help(&mut self);
     /// Display the win screen.
     fn win(&mut self);
+    /// Display the summary of a game just won.  The default prints it as
+    /// info lines, then the share text.
+    fn win_summary(&mut self, summary: &WinSummary) {
+        for line in summary.lines() {
+            self.info(&line);
+        }
+        for line in summary.share_lines() {
+            self.info(&line);
+        }
+    }
     /// Display the active dynamic dashboard with titles.
-    fn render_header(&mut self, total_wins: usize, seed: u64);
+    /// `seed_phrase` is the text the seed was hashed from, if any; `moves` and
+    /// `elapsed` are the current game's move count and play time.
+    fn render_header(&mut self, total_wins: usize, seed: u64, seed_phrase: Option<&str>, moves: u32, par: Option<usize>, elapsed: Duration);
+
+    /// Set the indicators shown by the next `render_header`.  The default
+    /// shows none.
+    fn set_status(&mut self, _status: HeaderStatus) {}
+
+    /// Choose how much later renders print.  The default ignores it (the
+    /// TUI always redraws the whole screen).
+    fn set_render_mode(&mut self, _mode: RenderMode) {}
+
+    /// A typed command is about to run.  The in-place CLI keeps the
+    /// messages printed from here on below the board it redraws.
+    fn begin_command(&mut self) {}
 
     /// Deliver events produced by board mutations to the renderer.
     /// The default no-op is used by `CliRenderer` (events are never animated).
@@ -45,186 +163,627 @@ pub trait Renderer {
 
     fn tick(&mut self) {}
 
+    /// Display a rejected move.  The default spells it out with the ASCII
+    /// labels and 0-based slots; renderers with other labels override it.
+    fn move_error(&mut self, e: &crate::board::MoveError) {
+        self.error(&i18n::move_error(e, &crate::card::AsciiLabels, 0));
+    }
+
+    /// Ring the terminal bell for an audio cue (`bell` setting).  The
+    /// default stays silent.
+    fn bell(&mut self) {}
+
+    /// Show the campaign map.  The default prints it; the TUI opens it as
+    /// a screen.
+    fn show_campaign(&mut self, map: &CampaignMap) {
+        for line in map.lines() {
+            self.info(&line);
+        }
+    }
 }
 
+/// What the post-win summary shows.
+#[derive(Debug, Clone)]
+pub struct WinSummary {
+    pub seed: u64,
+    pub elapsed: Duration,
+    pub moves: u32,
+    /// The solver's move count for the deal, if it was worked out.
+    pub par: Option<usize>,
+    pub undos: u32,
+    pub hints: u32,
+    pub dragon_order: Vec<Suit>,
+    pub score: u32,
+    pub assist: AssistPreset,
+    pub hardcore: bool,
+    pub bests: PersonalBests,
+    /// The seed's records this win beat, marked "PB!".
+    pub seed_records: SeedRecords,
+    /// The share text, when the game was a daily deal.
+    pub share: Option<String>,
+}
+
+impl WinSummary {
+    /// The summary as unframed text lines, in the active language.
+    pub fn lines(&self) -> Vec<String> {
+        let moves = match self.par {
+            Some(par) => {
+                let diff = self.moves as i64 - par as i64;
+                let sign = if diff < 0 { '-' } else { '+' };
+                tr!("{} (par {}, {}{})", self.moves, par, sign, diff.abs())
+            }
+            None => self.moves.to_string(),
+        };
+        let dragons = if self.dragon_order.is_empty() {
+            tr!("none")
+        } else {
+            let names: Vec<String> = self.dragon_order.iter().map(|&s| i18n::suit_name(s)).collect();
+            names.join(" → ")
+        };
+        let mut mode = self.assist.name().to_string();
+        if self.hardcore {
+            mode = tr!("{}, hardcore", mode);
+        }
+        let mut bests = Vec::new();
+        if self.bests.score {
+            bests.push(tr!("score"));
+        }
+        if self.bests.time {
+            bests.push(tr!("time"));
+        }
+        if self.bests.moves {
+            bests.push(tr!("moves"));
+        }
+        let best = if bests.is_empty() { tr!("no") } else { tr!("yes ({})", bests.join(", ")) };
+        let pb = |beat: bool| if beat { "  PB!" } else { "" };
+        vec![
+            format!("{}: {}", tr!("Seed"), self.seed),
+            format!("{}: {}{}", tr!("Time"), format_elapsed(self.elapsed), pb(self.seed_records.time)),
+            format!("{}: {}{}", tr!("Moves"), moves, pb(self.seed_records.moves)),
+            format!("{}: {}", tr!("Undos"), self.undos),
+            format!("{}: {}", tr!("Hints"), self.hints),
+            format!("{}: {}", tr!("Dragons merged"), dragons),
+            format!("{}: {}", tr!("Assists"), mode),
+            format!("{}: {}", tr!("Score"), self.score),
+            format!("{}: {}", tr!("Personal best"), best),
+        ]
+    }
+
+    /// The share text under a heading, or nothing for a game that wasn't
+    /// a daily deal.
+    pub fn share_lines(&self) -> Vec<String> {
+        let Some(share) = &self.share else { return Vec::new() };
+        let mut lines = vec![tr!("Share your result:")];
+        lines.extend(share.lines().map(str::to_string));
+        lines
+    }
+}
 
 // ---------------------------------------------------------------------------
 // CLI Renderer
 // ---------------------------------------------------------------------------
 
+/// How `CliRenderer` draws card faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardStyle {
+    /// Bracketed labels such as `[R5]`.
+    Text,
+    /// Box-drawn faces with suit glyphs: `│5♦│`, `│中│`, `│🌸│`.
+    Unicode,
+}
+
 /// A simple ANSI-color CLI renderer.
-pub struct CliRenderer;
+pub struct CliRenderer {
+    /// Number shown for the first column and free cell.
+    index_base: usize,
+    theme: Theme,
+    /// When false, nothing but plain text is written (no ANSI escapes at all).
+    color: bool,
+    card_style: CardStyle,
+    /// Face text for the `Text` card style.
+    labels: Box<dyn LabelProvider>,
+    /// What the events since the last render changed; shown highlighted.
+    last_move: LastMove,
+    /// Highest rank of the board being drawn; slots fit its widest card.
+    top_rank: u8,
+    status: HeaderStatus,
+    board_frame: Redraw,
+    header_frame: Redraw,
+    /// Redraw the header and board at the top of the screen instead of
+    /// scrolling, with the prompt on the bottom row (`in_place` setting).
+    in_place: bool,
+    /// Everything but the board printed since the command began, shown
+    /// again under each in-place redraw.
+    said: Vec<String>,
+    out: Output,
+}
 
-impl CliRenderer {
-    pub fn new() -> Self {
-        CliRenderer
-    }
+/// The slots touched since the previous render, built from `GameEvent`s.
+#[derive(Debug, Default)]
+struct LastMove {
+    /// Number of cards that just landed on top of each column.
+    columns: [usize; crate::board::MAX_COLUMNS],
+    free_cells: [bool; crate::board::MAX_FREE_CELLS],
+    foundations: [bool; crate::board::MAX_SUITS],
+    flower: bool,
+}
 
-    fn card_str(&self, card: crate::card::Card) -> String {
-        use crate::card::{Card, Suit};
-        let label = card.label();
-        match card {
-            Card::Numbered(Suit::Red, _) | Card::Dragon(Suit::Red) => {
-                format!("\x1b[31m{}\x1b[0m", label) // red
-            }
-            Card::Numbered(Suit::Green, _) | Card::Dragon(Suit::Green) => {
-                format!("\x1b[32m{}\x1b[0m", label) // green
+impl LastMove {
+    fn record(&mut self, event: &crate::event::GameEvent) {
+        use crate::board::Location;
+        use crate::event::GameEvent;
+        match event {
+            GameEvent::CardMoved { src, dst, .. } => {
+                // A card that moves on again (e.g. auto-moved home) no
+                // longer marks the slot it left.
+                match *src {
+                    Location::Column(c) => self.columns[c] = self.columns[c].saturating_sub(1),
+                    Location::FreeCell(i) => self.free_cells[i] = false,
+                    Location::Foundation(_) | Location::Flower => {}
+                }
+                match *dst {
+                    Location::Column(c) => self.columns[c] += 1,
+                    Location::FreeCell(i) => self.free_cells[i] = true,
+                    Location::Foundation(suit) => self.foundations[suit as usize] = true,
+                    Location::Flower => self.flower = true,
+                }
             }
-            Card::Numbered(Suit::Black, _) | Card::Dragon(Suit::Black) => {
-                format!("\x1b[90m{}\x1b[0m", label) // dark gray
+            GameEvent::StackMoved { stack, src_col, dst_col } => {
+                self.columns[*src_col] = self.columns[*src_col].saturating_sub(stack.len());
+                self.columns[*dst_col] += stack.len();
             }
-            Card::Flower => format!("\x1b[35m{}\x1b[0m", label), // magenta
+            GameEvent::DragonsMerged { locked_cell, .. } => self.free_cells[*locked_cell] = true,
+            GameEvent::Won => {}
+            GameEvent::Dealt { .. } | GameEvent::RestoreDealt { .. } => *self = LastMove::default(),
         }
     }
+}
 
-    fn freecell_str(&self, fc: &crate::board::FreeCellState) -> String {
-        use crate::board::FreeCellState;
-        match fc {
-            FreeCellState::Empty => "   ".to_string(),
-            FreeCellState::Card(c) => format!("[{}]", self.card_str(*c)),
-            FreeCellState::DragonLocked(s) => {
-                use crate::card::Suit;
-                let label = match s {
-                    Suit::Red => "\x1b[31mXXX\x1b[0m",
-                    Suit::Green => "\x1b[32mXXX\x1b[0m",
-                    Suit::Black => "\x1b[90mXXX\x1b[0m",
-                };
-                format!("[{}]", label)
-            }
+impl CliRenderer {
+    pub fn new() -> Self {
+        CliRenderer {
+            index_base: 0,
+            theme: Theme::default(),
+            color: true,
+            card_style: CardStyle::Text,
+            labels: Box::new(AsciiLabels),
+            last_move: LastMove::default(),
+            top_rank: Rules::STANDARD.top_rank(),
+            status: HeaderStatus::default(),
+            board_frame: Redraw::default(),
+            header_frame: Redraw::default(),
+            in_place: false,
+            said: Vec::new(),
+            out: Output::default(),
         }
     }
-}
 
-impl Renderer for CliRenderer {
-    fn render(&mut self, board: &crate::board::Board) {
-        use crate::card::Suit;
+    /// Write to `out` (a network client) instead of stdout.
+    pub fn with_output(mut self, out: Box<dyn Write + Send>) -> Self {
+        self.out = Output::to(out);
+        self
+    }
 
-        println!();
-        println!("\n  Seed: {}", board.seed);
-        // ---- Top row: free cells | flower | foundations ----
-        // Free cells (0–2)
-        print!("  FREE CELLS:  ");
-        for (i, fc) in board.free_cells.iter().enumerate() {
-            print!("{}: {}  ", i, self.freecell_str(fc));
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_in_place(mut self, in_place: bool) -> Self {
+        self.in_place = in_place;
+        self
+    }
+
+    /// Whether frames are redrawn in place: asked for, on a terminal, and
+    /// escapes allowed.
+    fn anchored(&self) -> bool {
+        self.in_place && self.color && self.out.size().is_some()
+    }
+
+    /// Print a message, keeping it for the next in-place redraw.
+    fn say(&mut self, text: String) {
+        if self.in_place {
+            self.said.push(text.clone());
         }
+        self.out.line(text);
+    }
+
+    pub fn with_card_style(mut self, style: CardStyle) -> Self {
+        self.card_style = style;
+        self
+    }
+
+    pub fn with_labels(mut self, labels: Box<dyn LabelProvider>) -> Self {
+        self.labels = labels;
+        self
+    }
 
-        // Flower slot
-        if board.flower_placed {
-            print!("  FLOWER: \x1b[35m[FL]\x1b[0m  ");
+    /// Wrap `text` in the SGR sequence `sgr` (no-op when `sgr` is empty or
+    /// color is disabled).
+    /// The legal-move count, in red when the position is getting tight.
+    fn mobility(&self, count: usize) -> String {
+        if count < LOW_MOBILITY {
+            self.paint(self.theme.error, &count.to_string())
         } else {
-            print!("  FLOWER: [  ]  ");
+            count.to_string()
         }
+    }
 
-        // Foundations
-        print!("  FOUND: ");
-        for suit in &[Suit::Red, Suit::Green, Suit::Black] {
-            let idx = match suit {
-                Suit::Red => 0,
-                Suit::Green => 1,
-                Suit::Black => 2,
-            };
-            let v = board.foundations[idx];
-            if v == 0 {
-                print!("{}[--] ", suit.symbol());
-            } else {
-                let card = crate::card::Card::Numbered(*suit, v);
-                print!("{}[{}] ", suit.symbol(), self.card_str(card));
+    /// Print a drawn board, or just its changed rows in quiet mode.  In
+    /// place, the command's messages follow it and the cursor drops to the
+    /// bottom row for the prompt.
+    fn draw_board(&mut self, seed: u64, lines: Vec<String>) {
+        for line in self.board_frame.diff(seed, lines) {
+            self.out.line(line);
+        }
+        if let Some((_, rows)) = self.out.size().filter(|_| self.anchored()) {
+            for line in self.said.clone() {
+                self.out.line(line);
             }
+            self.out.text(format!("\x1b[{};1H", rows));
         }
-        println!();
+    }
 
-        // ---- Column indices header ----
-        println!();
-        print!("  COL:   ");
-        for i in 0..crate::board::NUM_COLUMNS {
-            print!("  {:^4}", i);
+    /// Print the header after a blank line, or just its changed lines in
+    /// quiet mode.  In place, the screen is cleared first and the whole
+    /// frame drawn from the top.
+    fn draw_header(&mut self, seed: u64, header: &str) {
+        if self.anchored() {
+            self.out.text("\x1b[H\x1b[2J");
+            self.header_frame.drawn.clear();
+            self.board_frame.drawn.clear();
+        }
+        let lines = std::iter::once(String::new()).chain(header.lines().map(str::to_string)).collect();
+        for line in self.header_frame.diff(seed, lines) {
+            self.out.line(line);
         }
-        println!();
+    }
 
-        // ---- Tableau ----
-        // Find the longest column
-        let max_len = board.columns.iter().map(|c| c.len()).max().unwrap_or(0);
+    fn paint(&self, sgr: &str, text: &str) -> String {
+        if sgr.is_empty() || !self.color {
+            text.to_string()
+        } else {
+            format!("\x1b[{}m{}\x1b[0m", sgr, text)
+        }
+    }
 
-        for row in 0..max_len {
-            print!("  {:>3}:   ", row);
-            for col in &board.columns {
-                if row < col.len() {
-                    print!(" [{}] ", self.card_str(col[row]));
+    /// Label columns and free cells starting from `base` (see `one_indexed`).
+    pub fn with_index_base(mut self, base: usize) -> Self {
+        self.index_base = base;
+        self
+    }
+
+    /// The unstyled face of a card, without its frame.
+    fn face(&self, card: Card) -> String {
+        match self.card_style {
+            CardStyle::Text => self.labels.card(card),
+            CardStyle::Unicode => match card {
+                Card::Numbered(suit, v) => format!("{}{}", v, suit_glyph(suit)),
+                // The mahjong dragons, so the suits differ even without color.
+                Card::Dragon(Suit::Red) => "中".to_string(),
+                Card::Dragon(Suit::Green) => "發".to_string(),
+                Card::Dragon(Suit::Black) => "白".to_string(),
+                Card::Dragon(Suit::Yellow) => "鳳".to_string(),
+                Card::Flower => "🌸".to_string(),
+            },
+        }
+    }
+
+    /// Frame characters around a card of `suit` (`None`: flower or empty slot).
+    fn frame(&self, suit: Option<Suit>) -> (char, char) {
+        match self.card_style {
+            CardStyle::Text => self.theme.brackets(suit),
+            CardStyle::Unicode => ('│', '│'),
+        }
+    }
+
+    /// A framed, painted card and its display width (escapes excluded),
+    /// e.g. `[R5]` or `<R5>` when the theme brackets suits.
+    fn framed(&self, card: Card) -> (String, usize) {
+        let face = self.face(card);
+        let (open, close) = self.frame(card.suit());
+        let width = face.width() + 2;
+        (format!("{}{}{}", open, self.paint(self.theme.card(card), &face), close), width)
+    }
+
+    /// `framed`, marked as part of the last move: inverse video, or `*R5*`
+    /// when color is off.
+    fn highlighted(&self, card: Card) -> (String, usize) {
+        let face = self.face(card);
+        let width = face.width() + 2;
+        if !self.color {
+            return (format!("*{}*", face), width);
+        }
+        let (open, close) = self.frame(card.suit());
+        let sgr = match self.theme.card(card) {
+            "" => "7".to_string(),
+            sgr => format!("{};7", sgr),
+        };
+        let text = format!(
+            "{}{}{}",
+            self.paint("7", &open.to_string()),
+            self.paint(&sgr, &face),
+            self.paint("7", &close.to_string())
+        );
+        (text, width)
+    }
+
+    /// `framed` or `highlighted`, depending on `marked`.
+    fn card_slot(&self, card: Card, marked: bool) -> (String, usize) {
+        if marked { self.highlighted(card) } else { self.framed(card) }
+    }
+
+    /// Display width of the widest framed card; every slot is padded to it.
+    fn slot_width(&self) -> usize {
+        let widest = Rules { suits: crate::board::MAX_SUITS, ranks: self.top_rank as usize, ..Rules::STANDARD };
+        full_deck(widest).into_iter().map(|c| self.framed(c).1).max().unwrap_or(4)
+    }
+
+    /// A framed placeholder such as `[--]`, as wide as a card.
+    fn placeholder(&self, fill: char, suit: Option<Suit>) -> String {
+        let (open, close) = self.frame(suit);
+        let inner: String = std::iter::repeat_n(fill, self.slot_width() - 2).collect();
+        format!("{}{}{}", open, inner, close)
+    }
+
+    /// The free cells, flower slot and foundations, as separate pieces so a
+    /// narrow layout can put them on different lines.  The flower piece is
+    /// empty in FreeCell, which has none.
+    fn top_row(&self, board: &crate::board::Board) -> (String, String, String) {
+        let slot = self.slot_width();
+        let cells: Vec<String> = board
+            .free_cells
+            .iter()
+            .enumerate()
+            .map(|(i, fc)| format!("{}: {}", i + self.index_base, self.freecell_str(fc, self.last_move.free_cells[i])))
+            .collect();
+        let cells = format!("{}:  {}", tr!("FREE CELLS"), cells.join("  "));
+
+        let flower = if !board.rules.flower {
+            String::new()
+        } else if board.flower_placed {
+            format!("{}: {}", tr!("FLOWER"), self.card_slot(Card::Flower, self.last_move.flower).0)
+        } else {
+            format!("{}: {}", tr!("FLOWER"), self.placeholder(' ', None))
+        };
+
+        let mut found = format!("{}: ", tr!("FOUND"));
+        for (suit, &v) in board.rules.suits().iter().zip(board.foundations.iter()) {
+            if v == 0 {
+                found += &format!("{}{} ", self.labels.suit(*suit), self.placeholder('-', Some(*suit)));
+            } else {
+                let (text, width) = self.card_slot(Card::Numbered(*suit, v), self.last_move.foundations[*suit as usize]);
+                found += &format!("{}{} ", self.labels.suit(*suit), center(&text, width, slot));
+            }
+        }
+        (cells, flower, found)
+    }
+
+    fn freecell_str(&self, fc: &FreeCellState, marked: bool) -> String {
+        match fc {
+            FreeCellState::Empty => " ".repeat(self.slot_width()),
+            FreeCellState::Card(c) => {
+                let (text, width) = self.card_slot(*c, marked);
+                center(&text, width, self.slot_width())
+            }
+            FreeCellState::DragonLocked(s) => {
+                let (open, close) = self.frame(Some(*s));
+                let fill = match self.card_style {
+                    CardStyle::Text => 'X',
+                    CardStyle::Unicode => '▓',
+                };
+                let inner: String = std::iter::repeat_n(fill, self.slot_width() - 2).collect();
+                if marked && !self.color {
+                    format!("*{}*", inner)
+                } else if marked {
+                    format!("{}{}{}", open, self.paint(&format!("{};7", self.theme.suit(*s)), &inner), close)
                 } else {
-                    print!("  ..  ");
+                    format!("{}{}{}", open, self.paint(self.theme.suit(*s), &inner), close)
                 }
             }
-            println!();
         }
+    }
+}
 
-        if max_len == 0 {
-            println!("  (all columns empty)");
+/// How the CLI board is laid out, picked from the terminal width on every
+/// render so resizing takes effect on the next command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// Free cells, flower and foundations on one line; roomy tableau.
+    Wide,
+    /// Top row split over two lines; tighter tableau.
+    Compact,
+    /// One line per column, for terminals under 60 columns.
+    Vertical,
+}
+
+impl Layout {
+    /// `top_width` is the display width of the one-line top row.
+    fn for_width(columns: Option<usize>, top_width: usize) -> Layout {
+        match columns {
+            None => Layout::Wide,
+            Some(w) if w < 60 => Layout::Vertical,
+            Some(w) if w < top_width => Layout::Compact,
+            Some(_) => Layout::Wide,
         }
+    }
+}
 
-        println!();
+/// Where the text renderers write: stdout, or a client's connection in
+/// server mode.
+struct Output {
+    writer: Box<dyn Write + Send>,
+    stdout: bool,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Output { writer: Box::new(std::io::stdout()), stdout: true }
     }
+}
 
-    fn info(&mut self, msg: &str) {
-        println!("\x1b[36m[INFO]\x1b[0m {}", msg);
+impl Output {
+    fn to(writer: Box<dyn Write + Send>) -> Self {
+        Output { writer, stdout: false }
     }
 
-    fn error(&mut self, msg: &str) {
-        println!("\x1b[31m[ERR ]\x1b[0m {}", msg);
+    fn line(&mut self, text: impl AsRef<str>) {
+        let _ = writeln!(self.writer, "{}", text.as_ref());
     }
 
-    fn render_header(&mut self, total_wins: usize, seed: u64) {
-        let title = if total_wins == 0 {
-             "【来面试的】"
-        } else if total_wins < 10 {
-             "【带薪如厕生】"
-        } else if total_wins < 25 {
-             "【划水工程师】"
-        } else if total_wins < 50 {
-             "【工位地缚灵】"
-        } else if total_wins < 100 {
-             "【需求粉碎机】"
+    fn text(&mut self, text: impl AsRef<str>) {
+        let _ = write!(self.writer, "{}", text.as_ref());
+    }
+
+    /// Send BEL now rather than with the next line.
+    fn bell(&mut self) {
+        let _ = self.writer.write_all(b"\x07");
+        let _ = self.writer.flush();
+    }
+
+    /// Width of the terminal on stdout, or `None` when it is not a
+    /// terminal (pipes and clients keep the wide layout).
+    fn width(&self) -> Option<usize> {
+        self.size().map(|(w, _)| w)
+    }
+
+    /// Columns and rows of the terminal on stdout, if it is one.
+    fn size(&self) -> Option<(usize, usize)> {
+        if !self.stdout || !std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+            return None;
+        }
+        crossterm::terminal::size().ok().map(|(w, h)| (w as usize, h as usize))
+    }
+}
+
+/// The lines of the last board or header drawn, so quiet mode can print
+/// only the ones that changed.
+#[derive(Debug, Default)]
+struct Redraw {
+    mode: RenderMode,
+    /// Identifies the deal the lines belong to; a new deal is drawn whole.
+    key: u64,
+    drawn: Vec<String>,
+}
+
+impl Redraw {
+    /// The lines of `frame` to print.  Outside quiet mode that is all of
+    /// them; in quiet mode only those that differ from the previous frame
+    /// of the same deal and shape.
+    fn diff(&mut self, key: u64, frame: Vec<String>) -> Vec<String> {
+        let whole = self.mode != RenderMode::Quiet || key != self.key || frame.len() != self.drawn.len();
+        let shown = if whole {
+            frame.clone()
         } else {
-             "【摸鱼仙人】"
+            frame.iter().zip(&self.drawn).filter(|(new, old)| new != old).map(|(new, _)| new.clone()).collect()
         };
-        // Line 1: `    Wins: 0000 |  Seed: 12345678901234567890`
-        let wins_str = format!("{:<4}", total_wins);
-        let seed_str = format!("{:<20}", seed);
-        
-        let rank_str = format!("Rank: {}", title);
-        // Calculate display width: English/spaces = 1, Chinese = 2
-        // "Rank: " is 6 chars. Title is full-width (each char is 2 wide)
-        let rank_display_width = 6 + title.chars().count() * 2;
-        let rank_padding = 52usize.saturating_sub(4 + rank_display_width); 
+        self.key = key;
+        self.drawn = frame;
+        shown
+    }
+}
 
-        if total_wins < 100 {
-            println!(
-                "\n┌────────────────────────────────────────────────────┐\n\
-                 │           SHENZHEN I/O: SOLITAIRE                  │\n\
-                 │    Wins: {} |  Seed: {}        │\n\
-                 │    {}{}│\n\
-                 └────────────────────────────────────────────────────┘",
-                wins_str, seed_str, rank_str, " ".repeat(rank_padding)
-            );
+/// Display width of `text`, ignoring SGR escape sequences.
+fn visible_width(text: &str) -> usize {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip up to and including the final `m`.
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
         } else {
-            let padding = " ".repeat(40_usize.saturating_sub(rank_display_width) / 2);
-            println!(
-                "\n\x1b[32m\
-                 /// KERNEL PANIC: TOO MUCH FREE TIME ///\n\
-                 ========================================\n\
-                     [!] EMPLOYEE OF THE MONTH [!]\n\
-                 Wins: {:<4} | Seed: {:<15}\n\
-                 {}{}\n\
-                 ========================================\
-                 \x1b[0m",
-                 total_wins, seed, padding, rank_str
-            );
+            plain.push(c);
         }
     }
+    plain.width()
+}
 
-    fn help(&mut self) {
-        println!(
-            r#"
-╔══════════════════════════════════════════════════════════════╗
+/// Suit glyph for the Unicode card style; the shapes differ so suits stay
+/// distinguishable without color.
+fn suit_glyph(suit: Suit) -> char {
+    match suit {
+        Suit::Red => '♦',
+        Suit::Green => '♣',
+        Suit::Black => '♠',
+        Suit::Yellow => '♥',
+    }
+}
+
+/// Play time as `m:ss`, or `h:mm:ss` from an hour on.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Time to a coming event, to the minute: `2d 4h`, `4h 13m` or `13m`.
+pub fn format_countdown(left: Duration) -> String {
+    let mins = left.as_secs().div_ceil(60);
+    let (days, hours, mins) = (mins / 1440, mins / 60 % 24, mins % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// Speedrun time with tenths: `m:ss.t`, or `h:mm:ss.t` from an hour on.
+pub fn format_split(elapsed: Duration) -> String {
+    format!("{}.{}", format_elapsed(elapsed), elapsed.subsec_millis() / 100)
+}
+
+/// Center `text` (of display width `width`, escapes excluded) in `target` columns.
+fn center(text: &str, width: usize, target: usize) -> String {
+    let pad = target.saturating_sub(width);
+    format!("{}{}{}", " ".repeat(pad / 2), text, " ".repeat(pad - pad / 2))
+}
+
+/// Pad `text` with spaces to `width` display columns.
+fn pad_line(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(visible_width(text))))
+}
+
+/// Center `text` in `width` display columns (no trailing padding).
+fn center_line(text: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(text.width()) / 2), text)
+}
+
+/// Draw `lines` in a box with `inner` display columns.  A line of just `-`
+/// becomes a divider.
+fn boxed(lines: &[String], inner: usize, top: (char, char, char), side: char, bottom: (char, char, char)) -> String {
+    let rule = |(l, h, r): (char, char, char)| format!("{}{}{}", l, h.to_string().repeat(inner), r);
+    let mut out = vec![rule(top)];
+    for line in lines {
+        if line == "-" {
+            out.push(rule(('╠', '═', '╣')));
+        } else {
+            out.push(format!("{}{}{}", side, pad_line(line, inner), side));
+        }
+    }
+    out.push(rule(bottom));
+    out.join("\n")
+}
+
+/// The CLI help box in the active language.
+fn help_box() -> String {
+    if i18n::lang() == Lang::Zh {
+        return help_zh();
+    }
+    r#"╔══════════════════════════════════════════════════════════════╗
 ║          SHENZHEN I/O Solitaire – CLI Help                   ║
 ╠══════════════════════════════════════════════════════════════╣
 ║  GOAL: Move all numbered cards (1-9) to the foundation and   ║
@@ -250,34 +809,570 @@ impl Renderer for CliRenderer {
 ║                          (0=top card only, 1=top 2, etc.)    ║
 ║  cf  <col> <cell>        Move top card: column → free cell   ║
 ║  fc  <cell> <col>        Move card: free cell → column       ║
+║  <src><dst>              e.g. 47: longest run col 4 → col 7  ║
+║  m   <col>               Move top card to best destination   ║
 ║  ctf <col>               Move top card: column → foundation  ║
 ║  ftf <cell>              Move card: free cell → foundation   ║
-║  dragon r|g|b            Merge all 4 exposed dragons         ║
-║  undo                    Undo last move                      ║
+║  mv  <src> <dst>         Unified move; locations are:        ║
+║                          c<N>[:<depth>]  f<N>  fo            ║
+║  dragon r|g|b|y          Merge all 4 exposed dragons         ║
+║  finish                  Send all cards home once it is safe ║
+║  undo [<n>|all]          Undo last move, n moves, or all     ║
+║  lines                   List lines kept after undo          ║
+║  switch <n>              Switch to kept line n               ║
+║  mark [<name>]           Bookmark position / list bookmarks  ║
+║  goto <name>             Restore a bookmarked position       ║
+║  config                  Show settings and their sources     ║
+║  keys                    List the key bindings (key_* config)║
+║  macro record <name>     Capture commands until 'macro stop' ║
+║  macro play <name>       Replay a macro ('macro' lists them) ║
+║  set render <mode>       quiet | normal | verbose output     ║
+║  describe | desc         Read the board out as sentences     ║
+║  splits                  Speedrun splits vs personal bests   ║
+║  history [won|lost]      List past games; filters also:      ║
+║    [seed <s>] [page <n>] seed, page; history play <#> deals  ║
+║  history weekly          Weekly challenge results by week    ║
+║  export stats <file>     Write per-game stats as CSV         ║
+║  export history <file>   Write every game record as JSON     ║
+║  import history <file>   Merge games from a JSON export      ║
+║  import grid <file>      Play a board typed as 5 rows of 8   ║
+║  games                   List unfinished games               ║
+║  resume <n>              Switch to unfinished game n         ║
+║  share                   Print a code for this position      ║
+║  share daily             Your daily result, to post in chats ║
+║  copy seed|share|daily   Put one of those on the clipboard   ║
+║  load <code>             Play from a shared position code    ║
+║  challenge [<token>]     Send this deal, or take one up      ║
+║  challenges              List challenges taken               ║
+║  report difficulty       Played seeds: difficulty vs. wins   ║
+║  report rating           Your rating and its latest changes  ║
+║  puzzles [<file>]        List puzzles (or a pack's in file)  ║
+║  puzzle <n>              Play puzzle n of the list           ║
+║  campaign [<n>]          Campaign map, or play stage n       ║
+║  best [<seed>]           A seed's best time and fewest moves ║
+║  submit                  Post today's daily win online       ║
+║  leaderboard             Today's top times online            ║
+║  telemetry [on|off|send] Opt-in usage stats; alone: preview  ║
 ║  solve                   Run A* solver (suggest moves)       ║
-║  new                     Start a new random game             ║
+║  par                     Show par (solver's move count)      ║
+║  new [<seed>|daily|weekly] New game (random if no seed)      ║
+║  restart                 Re-deal the current seed            ║
+║  resign | giveup         Record this game as a loss          ║
+║  show solution           Replay a winning line after resign  ║
 ║  quit                    Exit                                ║
 ║  help | h | ?            Show this help                      ║
 ╠══════════════════════════════════════════════════════════════╣
 ║  Example: cc 4:2 7  →  move top 3 cards of col 4 to col 7    ║
+║           mv c4:2 c7, mv c0 f1, mv f1 fo                     ║
 ║                                                              ║
 ║  * Safe cards are moved to foundation automatically.         ║
-╚══════════════════════════════════════════════════════════════╝
-"#
-        );
+╚══════════════════════════════════════════════════════════════╝"#
+    .to_string()
+}
+
+/// The CLI help in Simplified Chinese, laid out like the English box.
+fn help_zh() -> String {
+    let lines: Vec<String> = [
+        "          深圳 I/O 接龙 – 命令行帮助",
+        "-",
+        "  目标：把所有数字牌（1-9）送入收牌区，并清空牌桌。",
+        "",
+        "  卡牌：3 种花色（红 / 绿 / 黑），每种包括：",
+        "    · 数字牌 1-9    · 4 张龙牌（RD/GD/BD）",
+        "    · 1 张花牌（FL），不属于任何花色",
+        "",
+        "  规则：",
+        "    · 列上叠牌：花色不同、点数小 1",
+        "      例如 R5 可以叠在 G6 或 B6 上，但不能叠在 R6 上",
+        "    · 收牌区按花色从小到大：R1 → R2 → ... → R9",
+        "    · 3 个空位：每个可暂存 1 张牌",
+        "    · 花牌放入花位（露出时自动放入）",
+        "    · 同色 4 张龙牌全部露出时可以合并",
+        "      （会永久占用一个空位）",
+        "-",
+        "  命令（不区分大小写）：",
+        "",
+        "  cc  <起点> <终点>       移动顶牌：列 → 列",
+        "  cc  <起点>:<N> <终点>   从顶部移动 N+1 张牌",
+        "                          （0=仅顶牌，1=顶部 2 张，依此类推）",
+        "  cf  <列> <空位>         移动顶牌：列 → 空位",
+        "  fc  <空位> <列>         移动卡牌：空位 → 列",
+        "  <起点><终点>            例如 47：第 4 列最长序列 → 第 7 列",
+        "  m   <列>                把顶牌移到最佳位置",
+        "  ctf <列>                移动顶牌：列 → 收牌区",
+        "  ftf <空位>              移动卡牌：空位 → 收牌区",
+        "  mv  <起点> <终点>       统一移动命令，位置写法：",
+        "                          c<N>[:<深度>]  f<N>  fo",
+        "  dragon r|g|b|y          合并 4 张露出的龙牌",
+        "  finish                  可以安全收完时一键收牌",
+        "  undo [<n>|all]          悔一步、悔 n 步或回到开局",
+        "  lines                   列出悔棋后保留的分支",
+        "  switch <n>              切换到保留的分支 n",
+        "  mark [<名称>]           保存书签 / 列出书签",
+        "  goto <名称>             恢复书签局面",
+        "  config                  显示设置及其来源",
+        "  keys                    列出按键绑定（配置项 key_*）",
+        "  macro record <名称>     录制之后的命令，直到 macro stop",
+        "  macro play <名称>       重放宏（macro 列出所有宏）",
+        "  set render <模式>       输出详略：quiet | normal | verbose",
+        "  describe | desc         用句子朗读当前局面",
+        "  splits                  速通分段及个人最佳对比",
+        "  history [won|lost]      列出历史对局，可按胜负、种子筛选",
+        "    [seed <s>] [page <n>] 翻页；history play <#> 重开该局",
+        "  history weekly          每周挑战的逐周成绩",
+        "  export stats <文件>     导出每局统计（CSV）",
+        "  export history <文件>   导出全部对局记录（JSON）",
+        "  import history <文件>   合并另一台机器导出的对局（JSON）",
+        "  import grid <文件>      游玩按 5 行 8 列抄下的牌面",
+        "  games                   列出未完成的对局",
+        "  resume <n>              切换到第 n 个未完成的对局",
+        "  share                   输出当前局面的分享码",
+        "  share daily             输出今日挑战成绩，可发到聊天中",
+        "  copy seed|share|daily   把种子、分享码或每日成绩复制到剪贴板",
+        "  load <分享码>           从分享的局面开始游戏",
+        "  challenge [<挑战码>]    发起本局挑战，或接受挑战",
+        "  challenges              列出已接受的挑战",
+        "  report difficulty       已玩种子：求解难度与胜率对照",
+        "  report rating           你的等级分及其最近的变化",
+        "  puzzles [<文件>]        列出谜题（或文件中谜题包的谜题）",
+        "  puzzle <n>              游玩列表中的第 n 个谜题",
+        "  campaign [<n>]          战役地图，或游玩第 n 关",
+        "  best [<种子>]           某个种子的最快用时和最少步数",
+        "  submit                  把今日每日挑战的胜局提交到线上",
+        "  leaderboard             查看今日线上最快成绩",
+        "  telemetry [on|off|send] 自愿的使用统计；单独输入可预览",
+        "  solve                   运行 A* 求解器（给出走法）",
+        "  par                     显示本局标准杆（求解器步数）",
+        "  new [<种子>|daily|weekly] 新局（省略种子则随机）",
+        "  restart                 重开当前种子",
+        "  resign | giveup         认输并记为失败",
+        "  show solution           认输或走投无路后回放一条获胜路线",
+        "  quit                    退出",
+        "  help | h | ?            显示本帮助",
+        "-",
+        "  示例：cc 4:2 7  →  把第 4 列顶部 3 张牌移到第 7 列",
+        "        mv c4:2 c7, mv c0 f1, mv f1 fo",
+        "",
+        "  * 安全的牌会自动送入收牌区。",
+    ]
+    .iter()
+    .map(|l| l.to_string())
+    .collect();
+    boxed(&lines, 62, ('╔', '═', '╗'), '║', ('╚', '═', '╝'))
+}
+
+impl Renderer for CliRenderer {
+    fn render(&mut self, board: &crate::board::Board) {
+        self.top_rank = board.rules.top_rank();
+        let slot = self.slot_width();
+        // Each tableau cell is a slot with a space either side.
+        let cell = slot + 2;
+
+        let mut lines = vec![String::new(), String::new(), format!("  {}: {}", tr!("Seed"), board.seed)];
+        // ---- Top row: free cells | flower | foundations ----
+        let (cells, flower, found) = self.top_row(board);
+        let join = |pieces: &[&str]| pieces.iter().filter(|p| !p.is_empty()).copied().collect::<Vec<_>>().join("    ");
+        let top = format!("  {}", join(&[&cells, &flower, &found]));
+        let layout = Layout::for_width(self.out.width(), visible_width(&top));
+        if layout == Layout::Wide {
+            lines.push(top);
+        } else {
+            lines.push(format!("  {}", cells));
+            lines.push(format!("  {}", join(&[&flower, &found])));
+        }
+
+        if layout == Layout::Vertical {
+            // One line per column, bottom card first, so nothing wraps.
+            lines.push(String::new());
+            for (i, col) in board.columns.iter().enumerate() {
+                let fresh = col.len().saturating_sub(self.last_move.columns[i]);
+                let cards: Vec<String> =
+                    col.iter().enumerate().map(|(row, c)| self.card_slot(*c, row >= fresh).0).collect();
+                let label = format!("{}{}:", tr!("C"), column_label(board, i, self.index_base));
+                if cards.is_empty() {
+                    lines.push(format!("  {} ..", pad_line(&label, 8)));
+                } else {
+                    lines.push(format!("  {} {}", pad_line(&label, 8), cards.join(" ")));
+                }
+            }
+            lines.push(String::new());
+            self.draw_board(board.seed, lines);
+            self.last_move = LastMove::default();
+            return;
+        }
+
+        // Compact: shorter row labels and a single space between cards.
+        let (label_width, cell) = match layout {
+            Layout::Wide => (7, cell),
+            _ => (3, slot + 1),
+        };
+
+        // ---- Column indices header, with each column's depth ----
+        lines.push(String::new());
+        // Same width as the row labels below.
+        let mut header = format!("  {}", pad_line(&format!("{}:", tr!("COL")), label_width));
+        for i in 0..board.rules.columns {
+            let label = column_label(board, i, self.index_base);
+            header.push_str(&center(&label, visible_width(&label), cell));
+        }
+        lines.push(header);
+
+        // ---- Tableau ----
+        // Find the longest column
+        let max_len = board.columns.iter().map(|c| c.len()).max().unwrap_or(0);
+
+        for row in 0..max_len {
+            let mut line = match layout {
+                Layout::Wide => format!("  {:>3}:   ", row),
+                _ => format!("  {:>2}: ", row),
+            };
+            for (i, col) in board.columns.iter().enumerate() {
+                if row < col.len() {
+                    let fresh = col.len().saturating_sub(self.last_move.columns[i]);
+                    let (text, width) = self.card_slot(col[row], row >= fresh);
+                    line.push_str(&center(&text, width, cell));
+                } else {
+                    line.push_str(&format!("{}{:^width$}", " ".repeat(cell - slot), "..", width = slot));
+                }
+            }
+            lines.push(line);
+        }
+
+        if max_len == 0 {
+            lines.push(format!("  {}", tr!("(all columns empty)")));
+        }
+
+        lines.push(String::new());
+        self.draw_board(board.seed, lines);
+        self.last_move = LastMove::default();
+    }
+
+    fn push_events(&mut self, events: Vec<crate::event::GameEvent>) {
+        for event in &events {
+            self.last_move.record(event);
+        }
+    }
+
+    fn move_error(&mut self, e: &crate::board::MoveError) {
+        let msg = i18n::move_error(e, self.labels.as_ref(), self.index_base);
+        self.error(&msg);
+    }
+
+    fn info(&mut self, msg: &str) {
+        self.say(format!("{} {}", self.paint(self.theme.info, "[INFO]"), msg));
+    }
+
+    fn error(&mut self, msg: &str) {
+        self.say(format!("{} {}", self.paint(self.theme.error, "[ERR ]"), msg));
+    }
+
+    fn begin_command(&mut self) {
+        self.said.clear();
+    }
+
+    fn bell(&mut self) {
+        self.out.bell();
+    }
+
+    fn render_header(&mut self, total_wins: usize, seed: u64, seed_phrase: Option<&str>, moves: u32, par: Option<usize>, elapsed: Duration) {
+        let title = if total_wins == 0 {
+             "【来面试的】"
+        } else if total_wins < 10 {
+             "【带薪如厕生】"
+        } else if total_wins < 25 {
+             "【划水工程师】"
+        } else if total_wins < 50 {
+             "【工位地缚灵】"
+        } else if total_wins < 100 {
+             "【需求粉碎机】"
+        } else {
+             "【摸鱼仙人】"
+        };
+        // Lines are padded by display width, so translated labels and the
+        // full-width title keep the right border aligned.
+        let mut lines = vec![
+            "           SHENZHEN I/O: SOLITAIRE".to_string(),
+            format!("    {}: {:<4} |  {}: {}", tr!("Wins"), total_wins, tr!("Seed"), seed),
+            format!("    {}: {:<4} |  {}: {}", tr!("Moves"), moves, tr!("Time"), format_elapsed(elapsed)),
+        ];
+        // Optional phrase line; long phrases are cut to fit the box.
+        if let Some(p) = seed_phrase {
+            let shown: String = p.chars().take(36).collect();
+            lines.push(format!("    {}: \"{}\"", tr!("Phrase"), shown));
+        }
+        if let Some(par) = par {
+            lines.push(format!("    {}: {}", tr!("Par"), par));
+        }
+        if let Some(outlook) = self.status.outlook {
+            lines.push(format!("    {}: {}", tr!("Winnable"), outlook.mark()));
+        }
+        if let Some(count) = self.status.mobility {
+            lines.push(format!("    {}: {}", tr!("Legal moves"), self.mobility(count)));
+        }
+        if let Some(left) = self.status.weekly {
+            lines.push(format!("    {}: {}", tr!("Weekly"), tr!("next deal in {}", format_countdown(left))));
+        }
+        if let Some(ghost) = &self.status.ghost {
+            lines.push(format!("    {}: {}  {}", tr!("Ghost now"), ghost::describe(ghost.suits, &ghost.at_time), tr!("you {}", ghost::lead(ghost.lead))));
+            lines.push(format!("    {}: {}", tr!("Ghost at this move"), ghost::describe(ghost.suits, &ghost.at_moves)));
+        }
+        lines.push(format!("    {}: {}", tr!("Rank"), title));
+
+        if total_wins < 100 {
+            let header = boxed(&lines, 52, ('┌', '─', '┐'), '│', ('└', '─', '┘'));
+            self.draw_header(seed, &self.theme.frame.apply(&header));
+        } else {
+            let rule = "=".repeat(40);
+            let mut banner = vec![
+                tr!("/// KERNEL PANIC: TOO MUCH FREE TIME ///"),
+                rule.clone(),
+                center_line(&tr!("[!] EMPLOYEE OF THE MONTH [!]"), 40),
+                format!("{}: {:<4} | {}: {:<15}", tr!("Wins"), total_wins, tr!("Seed"), seed),
+                format!("{}: {:<4} | {}: {}", tr!("Moves"), moves, tr!("Time"), format_elapsed(elapsed)),
+            ];
+            if let Some(p) = seed_phrase {
+                banner.push(format!("{}: \"{}\"", tr!("Phrase"), p));
+            }
+            if let Some(outlook) = self.status.outlook {
+                banner.push(format!("{}: {}", tr!("Winnable"), outlook.mark()));
+            }
+            if let Some(count) = self.status.mobility {
+                banner.push(format!("{}: {}", tr!("Legal moves"), self.mobility(count)));
+            }
+            if let Some(left) = self.status.weekly {
+                banner.push(format!("{}: {}", tr!("Weekly"), tr!("next deal in {}", format_countdown(left))));
+            }
+            if let Some(ghost) = &self.status.ghost {
+                banner.push(format!("{}: {}  {}", tr!("Ghost now"), ghost::describe(ghost.suits, &ghost.at_time), tr!("you {}", ghost::lead(ghost.lead))));
+                banner.push(format!("{}: {}", tr!("Ghost at this move"), ghost::describe(ghost.suits, &ghost.at_moves)));
+            }
+            banner.push(center_line(&format!("{}: {}", tr!("Rank"), title), 40));
+            banner.push(rule);
+            // Painted line by line so quiet mode can print any one of them.
+            let banner: Vec<String> = banner.iter().map(|l| self.paint(self.theme.green, l)).collect();
+            self.draw_header(seed, &banner.join("\n"));
+        }
+    }
+
+    fn set_status(&mut self, status: HeaderStatus) {
+        self.status = status;
+    }
+
+    fn set_render_mode(&mut self, mode: RenderMode) {
+        self.board_frame.mode = mode;
+        self.header_frame.mode = mode;
+    }
+
+    fn help(&mut self) {
+        self.say(format!("\n{}\n", self.theme.frame.apply(&help_box())));
     }
 
     fn win(&mut self) {
-        println!(
-            "\n\x1b[33m\
+        let banner = "\
             \n  ██╗    ██╗ ██████╗ ███╗   ██╗██╗\
             \n  ██║    ██║██╔═══██╗████╗  ██║██║\
             \n  ██║ █╗ ██║██║   ██║██╔██╗ ██║██║\
             \n  ██║███╗██║██║   ██║██║╚██╗██║╚═╝\
             \n  ╚███╔███╔╝╚██████╔╝██║ ╚████║██╗\
             \n   ╚══╝╚══╝  ╚═════╝ ╚═╝  ╚═══╝╚═╝\
-            \n\x1b[0m\
-            \n  Congratulations! You solved it!  Type 'new' for another game.\n"
-        );
+            \n";
+        self.say(format!(
+            "\n{}\n  {}\n",
+            self.paint(self.theme.highlight, banner),
+            tr!("Congratulations! You solved it!  Type 'new' for another game.")
+        ));
+    }
+
+    fn win_summary(&mut self, summary: &WinSummary) {
+        let lines: Vec<String> = summary.lines().iter().map(|l| format!("  {}", l)).collect();
+        let inner = lines.iter().map(|l| l.width()).max().unwrap_or(0).max(36) + 2;
+        let mut framed = vec![center_line(&tr!("GAME SUMMARY"), inner), "-".to_string()];
+        framed.extend(lines);
+        let boxed = boxed(&framed, inner, ('╔', '═', '╗'), '║', ('╚', '═', '╝'));
+        self.say(format!("{}\n", self.theme.frame.apply(&boxed)));
+        // Unframed, so it can be copied as it is.
+        let share = summary.share_lines();
+        if !share.is_empty() {
+            self.say(format!("{}\n", share.join("\n")));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Plain Renderer
+// ---------------------------------------------------------------------------
+
+/// Spoken name of a card, e.g. "Red 5", "Green Dragon", "Flower".
+fn card_name(card: Card) -> String {
+    match card {
+        Card::Numbered(suit, v) => tr!("{} {}", i18n::suit_name(suit), v),
+        Card::Dragon(suit) => tr!("{} Dragon", i18n::suit_name(suit)),
+        Card::Flower => tr!("Flower"),
+    }
+}
+
+/// A column's number and depth, `3(5)`, with a `✓` when the whole column
+/// is one ordered run.
+fn column_label(board: &crate::board::Board, col: usize, base: usize) -> String {
+    let mark = if board.is_ordered(col) { "✓" } else { "" };
+    format!("{}({}){}", col + base, board.columns[col].len(), mark)
+}
+
+/// The board as plain sentences, one per slot, for screen readers:
+/// "Column 3: B7, G6, R5 on top."  Columns and free cells are numbered
+/// from `base`.
+pub fn describe_board(board: &crate::board::Board, base: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, col) in board.columns.iter().enumerate() {
+        lines.push(match col.split_last() {
+            None => tr!("Column {}: empty.", i + base),
+            Some((top, [])) => tr!("Column {}: {} alone.", i + base, top.label()),
+            Some((top, rest)) => {
+                let below: Vec<String> = rest.iter().map(|c| c.label()).collect();
+                tr!("Column {}: {}, {} on top.", i + base, below.join(", "), top.label())
+            }
+        });
+    }
+    for (i, fc) in board.free_cells.iter().enumerate() {
+        lines.push(match fc {
+            FreeCellState::Empty => tr!("Free cell {}: empty.", i + base),
+            FreeCellState::Card(card) => tr!("Free cell {}: {}.", i + base, card_name(*card)),
+            FreeCellState::DragonLocked(suit) => {
+                tr!("Free cell {}: locked by {} dragons.", i + base, i18n::suit_name(*suit))
+            }
+        });
+    }
+    lines.push(describe_foundations(board));
+    if board.rules.flower {
+        lines.push(if board.flower_placed { tr!("Flower: placed.") } else { tr!("Flower: not placed.") });
+    }
+    lines
+}
+
+/// "Foundations: Red up to 3, Green none, Black up to 1."
+pub fn describe_foundations(board: &crate::board::Board) -> String {
+    let piles: Vec<String> = board
+        .rules
+        .suits()
+        .iter()
+        .zip(board.foundations.iter().copied())
+        .map(|(suit, v)| match v {
+            0 => tr!("{} none", i18n::suit_name(*suit)),
+            v => tr!("{} up to {}", i18n::suit_name(*suit), v),
+        })
+        .collect();
+    tr!("Foundations: {}.", piles.join(", "))
+}
+
+/// A renderer with no alignment art, box drawing or escapes: every board is
+/// printed as `describe_board` sentences, for screen readers and logs.
+pub struct PlainRenderer {
+    index_base: usize,
+    status: HeaderStatus,
+    board_frame: Redraw,
+    header_frame: Redraw,
+    out: Output,
+}
+
+impl PlainRenderer {
+    pub fn new() -> Self {
+        PlainRenderer {
+            index_base: 0,
+            status: HeaderStatus::default(),
+            board_frame: Redraw::default(),
+            header_frame: Redraw::default(),
+            out: Output::default(),
+        }
+    }
+
+    /// Write to `out` (a network client) instead of stdout.
+    pub fn with_output(mut self, out: Box<dyn Write + Send>) -> Self {
+        self.out = Output::to(out);
+        self
+    }
+
+    pub fn with_index_base(mut self, base: usize) -> Self {
+        self.index_base = base;
+        self
+    }
+}
+
+impl Renderer for PlainRenderer {
+    fn render(&mut self, board: &crate::board::Board) {
+        for line in self.board_frame.diff(board.seed, describe_board(board, self.index_base)) {
+            self.out.line(line);
+        }
+    }
+
+    fn info(&mut self, msg: &str) {
+        self.out.line(msg);
+    }
+
+    fn error(&mut self, msg: &str) {
+        self.out.line(format!("{} {}", tr!("Error:"), msg));
+    }
+
+    fn move_error(&mut self, e: &crate::board::MoveError) {
+        let msg = i18n::move_error(e, &AsciiLabels, self.index_base);
+        self.error(&msg);
+    }
+
+    fn bell(&mut self) {
+        self.out.bell();
+    }
+
+    /// The CLI help with its frame removed.
+    fn help(&mut self) {
+        for line in help_box().lines() {
+            let text = line.trim_matches(|c| matches!(c, '║' | '╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '═'));
+            let text = text.trim_end();
+            if !text.is_empty() {
+                self.out.line(text);
+            }
+        }
+    }
+
+    fn win(&mut self) {
+        self.out.line(tr!("Congratulations! You solved it!  Type 'new' for another game."));
+    }
+
+    fn render_header(&mut self, total_wins: usize, seed: u64, seed_phrase: Option<&str>, moves: u32, par: Option<usize>, elapsed: Duration) {
+        let mut lines = vec![match seed_phrase {
+            Some(p) => tr!("Wins: {}. Seed: {} (phrase \"{}\").", total_wins, seed, p),
+            None => tr!("Wins: {}. Seed: {}.", total_wins, seed),
+        }];
+        lines.push(tr!("Moves: {}. Time: {}.", moves, format_elapsed(elapsed)));
+        if let Some(par) = par {
+            lines.push(tr!("Par: {} moves.", par));
+        }
+        if let Some(outlook) = self.status.outlook {
+            lines.push(outlook.sentence());
+        }
+        if let Some(count) = self.status.mobility {
+            lines.push(tr!("Legal moves: {}.", count));
+        }
+        if let Some(left) = self.status.weekly {
+            lines.push(tr!("Weekly challenge: next deal in {}.", format_countdown(left)));
+        }
+        if let Some(ghost) = &self.status.ghost {
+            lines.push(tr!(
+                "Ghost: {} at this time, {} after as many moves; your lead: {}.",
+                ghost::describe(ghost.suits, &ghost.at_time),
+                ghost::describe(ghost.suits, &ghost.at_moves),
+                ghost::lead(ghost.lead)
+            ));
+        }
+        for line in self.header_frame.diff(seed, lines) {
+            self.out.line(line);
+        }
+    }
+
+    fn set_status(&mut self, status: HeaderStatus) {
+        self.status = status;
+    }
+
+    fn set_render_mode(&mut self, mode: RenderMode) {
+        self.board_frame.mode = mode;
+        self.header_frame.mode = mode;
     }
 }
@@ -0,0 +1,172 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Rules files: a game's table, deck and stacking rules as TOML or JSON,
+//! loaded with `--rules <file>`.  The built-in games are rules files too,
+//! shipped in `rules/`; fields a file leaves out keep SHENZHEN I/O's value.
+//!
+//! ```toml
+//! [deck]
+//! suits = 4
+//! ranks = 13
+//! ```
+//!
+//! or, as JSON, `{"deck": {"suits": 4, "ranks": 13}}`.  Errors name the
+//! offending field as `table.field`, with its line in a TOML file.
+use crate::board::{Rules, Stacking};
+use crate::json::Json;
+use crate::toml::{parse_toml_value, strip_comment, TomlValue};
+
+/// SHENZHEN I/O's rules, the default game.
+pub const SHENZHEN: &str = include_str!("../rules/shenzhen.toml");
+/// Classic FreeCell's.
+pub const FREECELL: &str = include_str!("../rules/freecell.toml");
+
+/// Every field a rules file can set, by the table it sits in.
+const FIELDS: &[&str] = &[
+    "table.columns",
+    "table.free_cells",
+    "deck.suits",
+    "deck.ranks",
+    "deck.dragons",
+    "deck.flower",
+    "stacking.rule",
+    "stacking.supermove",
+];
+
+/// A field's value, from either format.
+enum Value {
+    Int(i128),
+    Bool(bool),
+    Str(String),
+    /// Anything else: an array, `null`, or TOML this parser can't read.
+    Other,
+}
+
+/// A field as it appears in the file: its line (TOML only), its path and
+/// its value.
+struct Field {
+    line: Option<usize>,
+    path: String,
+    value: Value,
+}
+
+impl Field {
+    fn error(&self, problem: impl std::fmt::Display) -> String {
+        match self.line {
+            Some(n) => format!("line {}: {}: {}", n, self.path, problem),
+            None => format!("{}: {}", self.path, problem),
+        }
+    }
+}
+
+/// Read a rules file: JSON if it starts with `{`, TOML otherwise.
+pub fn parse(text: &str) -> Result<Rules, String> {
+    if text.trim_start().starts_with('{') { parse_json(text) } else { parse_toml(text) }
+}
+
+pub fn parse_toml(text: &str) -> Result<Rules, String> {
+    let mut fields = Vec::new();
+    let mut table = String::new();
+    for (n, raw_line) in text.lines().enumerate() {
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = name.trim().to_string();
+            if !FIELDS.iter().any(|f| f.split('.').next() == Some(table.as_str())) {
+                return Err(format!("line {}: unknown table [{}] (table, deck, stacking)", n + 1, table));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected `key = value`", n + 1));
+        };
+        // Top-level keys may name their table themselves: `deck.ranks = 13`.
+        let key = key.trim();
+        let path = if table.is_empty() { key.to_string() } else { format!("{}.{}", table, key) };
+        let value = match parse_toml_value(value.trim()) {
+            TomlValue::Int(v) => Value::Int(v.into()),
+            TomlValue::Bool(v) => Value::Bool(v),
+            TomlValue::Str(v) => Value::Str(v),
+            TomlValue::Invalid => Value::Other,
+        };
+        fields.push(Field { line: Some(n + 1), path, value });
+    }
+    build(&fields)
+}
+
+pub fn parse_json(text: &str) -> Result<Rules, String> {
+    let Json::Object(tables) = Json::parse(text)? else {
+        return Err("expected an object of tables".to_string());
+    };
+    let mut fields = Vec::new();
+    for (table, members) in tables {
+        let Json::Object(members) = members else {
+            return Err(format!("{}: expected an object of fields", table));
+        };
+        for (key, value) in members {
+            let value = match value {
+                Json::Int(v) => Value::Int(v),
+                Json::Bool(v) => Value::Bool(v),
+                Json::Str(v) => Value::Str(v),
+                _ => Value::Other,
+            };
+            fields.push(Field { line: None, path: format!("{}.{}", table, key), value });
+        }
+    }
+    build(&fields)
+}
+
+/// Apply `fields` over the default rules and check the result.
+fn build(fields: &[Field]) -> Result<Rules, String> {
+    let mut rules = Rules::STANDARD;
+    for field in fields {
+        let count = |v: i128| usize::try_from(v).map_err(|_| field.error("expected a count of 0 or more"));
+        match (field.path.as_str(), &field.value) {
+            ("table.columns", Value::Int(v)) => rules.columns = count(*v)?,
+            ("table.free_cells", Value::Int(v)) => rules.free_cells = count(*v)?,
+            ("deck.suits", Value::Int(v)) => rules.suits = count(*v)?,
+            ("deck.ranks", Value::Int(v)) => rules.ranks = count(*v)?,
+            ("deck.dragons", Value::Int(v)) => rules.dragons = count(*v)?,
+            ("deck.flower", Value::Bool(v)) => rules.flower = *v,
+            ("stacking.supermove", Value::Bool(v)) => rules.supermove = *v,
+            ("stacking.rule", Value::Str(v)) => {
+                rules.stacking = Stacking::parse(v)
+                    .ok_or_else(|| field.error(format!("unknown rule '{}' (alternate-suit, alternate-color)", v)))?;
+            }
+            ("deck.flower" | "stacking.supermove", _) => return Err(field.error("expected true or false")),
+            ("stacking.rule", _) => return Err(field.error("expected a string")),
+            (path, _) if FIELDS.contains(&path) => return Err(field.error("expected a whole number")),
+            _ => return Err(field.error("unknown field")),
+        }
+    }
+    rules.check().map_err(|(name, problem)| {
+        let path = FIELDS.iter().find(|f| f.ends_with(&format!(".{}", name))).copied().unwrap_or(name);
+        match fields.iter().rfind(|f| f.path == path) {
+            Some(field) => field.error(problem),
+            None => format!("{}: {}", path, problem),
+        }
+    })?;
+    Ok(rules)
+}
@@ -0,0 +1,115 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! `selfplay`: play many random deals and write training data.
+//!
+//! Every move becomes one JSON line, `{"seed", "ply", "state", "move",
+//! "outcome"}`: the board before the move, the move chosen, and how that
+//! game ended (`"won"` or `"lost"`).  Deals the player cannot finish are
+//! kept too, so the data also shows what losing looks like.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::agent::{Agent, Move};
+use crate::board::{Board, Rules};
+use crate::game::BOT_MOVE_LIMIT;
+use crate::i18n::tr;
+use crate::json::Json;
+use crate::json_serde::to_json;
+
+/// Who makes the moves.
+pub enum Player {
+    /// The solver's solution, for deals it can solve.
+    Solver,
+    Agent(Box<dyn Agent>),
+}
+
+/// Play `games` random deals under `rules` with `player`, writing moves
+/// to `out`.  Returns the process exit code.
+pub fn run(games: u32, rules: Rules, out: &str, mut player: Player) -> i32 {
+    let file = match File::create(out) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("{}", tr!("Could not write {}: {}", out, e));
+            return 1;
+        }
+    };
+    let mut writer = BufWriter::new(file);
+    let (mut won, mut lines) = (0, 0);
+    for game in 1..=games {
+        let seed = rand::random::<u64>();
+        let mut board = Board::deal(seed, rules);
+        board.auto_move();
+        let moves = match &mut player {
+            Player::Solver => crate::solver::solve_uncached(&board, |_| true)
+                .map(|path| path.iter().map(|step| step.next_move).collect())
+                .unwrap_or_default(),
+            Player::Agent(agent) => play(agent.as_mut(), &board),
+        };
+        let mut states = Vec::with_capacity(moves.len());
+        for &m in &moves {
+            states.push((board.clone(), m));
+            board.apply_move(m);
+        }
+        let outcome = if board.is_won() { "won" } else { "lost" };
+        won += board.is_won() as u32;
+        for (ply, (state, m)) in states.iter().enumerate() {
+            let line = Json::Object(vec![
+                ("seed".to_string(), Json::from(seed)),
+                ("ply".to_string(), Json::from(ply as u32)),
+                ("state".to_string(), to_json(state).unwrap_or(Json::Null)),
+                ("move".to_string(), to_json(m).unwrap_or(Json::Null)),
+                ("outcome".to_string(), Json::from(outcome)),
+            ]);
+            if let Err(e) = writeln!(writer, "{}", line.to_line()) {
+                eprintln!("{}", tr!("Could not write {}: {}", out, e));
+                return 1;
+            }
+        }
+        lines += states.len();
+        eprint!("\r{}", tr!("Played {} of {} games ({} won).", game, games, won));
+    }
+    eprintln!();
+    if let Err(e) = writer.flush() {
+        eprintln!("{}", tr!("Could not write {}: {}", out, e));
+        return 1;
+    }
+    eprintln!("{}", tr!("Wrote {} moves to {}.", lines, out));
+    0
+}
+
+/// The agent's moves from `start` until it wins, gives up, stalls on a
+/// move that is not among the board's candidates, or hits the move limit.
+fn play(agent: &mut dyn Agent, start: &Board) -> Vec<Move> {
+    agent.new_game();
+    let mut board = start.clone();
+    let mut moves = Vec::new();
+    while !board.is_won() && moves.len() < BOT_MOVE_LIMIT {
+        let Some(m) = agent.choose_move(&board) else { break };
+        if !board.valid_moves().contains(&m) {
+            break;
+        }
+        board.apply_move(m);
+        moves.push(m);
+    }
+    moves
+}
@@ -0,0 +1,226 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! `serve`: play over the network.
+//!
+//! Each telnet or raw TCP connection gets its own [`Game`] on its own
+//! thread, driven by the ordinary command loop: the text renderers write to
+//! the socket and [`SocketInput`] reads commands from it.  Sessions keep
+//! their history in memory only, so clients never touch the host's saves.
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::config::{RendererKind, UserConfig};
+use crate::game::Game;
+use crate::i18n::tr;
+use crate::input::{Completer, LineInput, ReadOutcome};
+use crate::renderer::{CliRenderer, PlainRenderer};
+
+/// Port used when `--port` is not given.
+pub const DEFAULT_PORT: u16 = 2323;
+
+/// Sessions played at once; each holds a thread, so later clients are
+/// turned away until one leaves.
+const MAX_SESSIONS: usize = 32;
+
+/// Longest command line a client may send; past this the connection is
+/// dropped rather than buffered.
+const MAX_LINE: usize = 4096;
+
+static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Holds a session slot and gives it back when the session's thread ends.
+struct Slot;
+
+impl Slot {
+    fn take() -> Option<Slot> {
+        SESSIONS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| (n < MAX_SESSIONS).then_some(n + 1))
+            .ok()
+            .map(|_| Slot)
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        SESSIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Telnet command bytes (RFC 854).
+const IAC: u8 = 255;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const IP: u8 = 244;
+
+/// Accept connections on `port` until the process is stopped.  Returns the
+/// exit code for a listener that could not be opened.
+pub fn serve(port: u16, user_config: UserConfig) -> i32 {
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("{}", tr!("Could not listen on port {}: {}", port, e));
+            return 1;
+        }
+    };
+    eprintln!("{}", tr!("Serving szsol on port {}. Connect with: telnet <host> {}", port, port));
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else { continue };
+        let peer = stream.peer_addr().map_or_else(|_| "?".to_string(), |a| a.to_string());
+        let Some(slot) = Slot::take() else {
+            eprintln!("{}", tr!("{} turned away: the server is full.", peer));
+            let _ = stream.write_all(format!("{}\r\n", tr!("The server is full; try again later.")).as_bytes());
+            continue;
+        };
+        let config = user_config.clone();
+        thread::spawn(move || {
+            let _slot = slot;
+            eprintln!("{}", tr!("{} connected.", peer));
+            if let Err(e) = session(stream, config) {
+                eprintln!("{}: {}", peer, e);
+            }
+            eprintln!("{}", tr!("{} disconnected.", peer));
+        });
+    }
+    0
+}
+
+/// One client's game, from the deal until it quits or hangs up.
+fn session(stream: TcpStream, user_config: UserConfig) -> io::Result<()> {
+    let output = Box::new(Crlf(stream.try_clone()?));
+    let mut input = SocketInput::new(stream)?;
+    if user_config.renderer.value == RendererKind::Plain {
+        let renderer = PlainRenderer::new()
+            .with_index_base(user_config.index_base())
+            .with_output(output);
+        Game::init(None, None, renderer, user_config).run(&mut input);
+    } else {
+        let renderer = CliRenderer::new()
+            .with_index_base(user_config.index_base())
+            .with_theme(user_config.theme())
+            .with_color(user_config.color.value)
+            .with_card_style(user_config.card_style.value)
+            .with_labels(user_config.labels())
+            .with_output(output);
+        Game::init(None, None, renderer, user_config).run(&mut input);
+    }
+    Ok(())
+}
+
+/// Writes through to the socket, turning `\n` into the `\r\n` that telnet
+/// expects.
+struct Crlf(TcpStream);
+
+impl Write for Crlf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut start = 0;
+        for (i, &b) in buf.iter().enumerate() {
+            if b == b'\n' {
+                self.0.write_all(&buf[start..i])?;
+                self.0.write_all(b"\r\n")?;
+                start = i + 1;
+            }
+        }
+        self.0.write_all(&buf[start..])?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Command lines from a client.  Telnet negotiation is read and ignored, so
+/// both `telnet` and plain `nc` work.
+struct SocketInput {
+    reader: BufReader<TcpStream>,
+    writer: Crlf,
+}
+
+impl SocketInput {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        let writer = Crlf(stream.try_clone()?);
+        Ok(SocketInput { reader: BufReader::new(stream), writer })
+    }
+
+    /// Next byte from the client, or `None` once it has gone.
+    fn byte(&mut self) -> Option<u8> {
+        let mut b = [0];
+        match self.reader.read_exact(&mut b) {
+            Ok(()) => Some(b[0]),
+            Err(_) => None,
+        }
+    }
+}
+
+impl LineInput for SocketInput {
+    fn read_line(&mut self, prompt: &str, _complete: Completer) -> ReadOutcome {
+        if self.writer.write_all(prompt.as_bytes()).and_then(|()| self.writer.flush()).is_err() {
+            return ReadOutcome::Eof;
+        }
+        let mut line = Vec::new();
+        loop {
+            let Some(b) = self.byte() else { return ReadOutcome::Eof };
+            match b {
+                b'\n' => break,
+                b'\r' | 0 => {}
+                IAC => match self.byte() {
+                    None => return ReadOutcome::Eof,
+                    Some(IAC) => line.push(IAC),
+                    Some(IP) => return ReadOutcome::Interrupted,
+                    // WILL/WONT/DO/DONT carry one option byte.
+                    Some(251..=254) => {
+                        self.byte();
+                    }
+                    // Subnegotiation runs up to IAC SE.
+                    Some(SB) => {
+                        let mut last = 0;
+                        loop {
+                            match self.byte() {
+                                None => return ReadOutcome::Eof,
+                                Some(SE) if last == IAC => break,
+                                Some(b) => last = b,
+                            }
+                        }
+                    }
+                    Some(_) => {}
+                },
+                _ => line.push(b),
+            }
+            if line.len() > MAX_LINE {
+                return ReadOutcome::Eof;
+            }
+        }
+        ReadOutcome::Line(String::from_utf8_lossy(&line).into_owned())
+    }
+}
+
+/// `serve [--port N]` reads its port here, falling back to `default`;
+/// `None` means the value was bad.
+pub fn parse_port(args: &[String], default: u16) -> Option<u16> {
+    match args.iter().position(|a| a == "--port") {
+        Some(i) => args.get(i + 1)?.parse().ok().filter(|&p| p != 0),
+        None => Some(default),
+    }
+}
@@ -0,0 +1,497 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Position codes: a whole board as a short string (`share`) that a friend
+//! can play from (`load <code>`).  Challenge tokens (`challenge`) work the
+//! same way for a deal, its par and the assists to play it with.
+//!
+//! The board is bit-packed: a format version, the seed, the table's shape
+//! unless it is the standard one, the flower and foundations, the free
+//! cells, then each column's cards as 5-bit codes (wider for bigger decks)
+//! ending in a zero.  A checksum byte catches typos, and the bytes are
+//! written in z-base-32, which has no look-alike characters.
+use sha2::{Digest, Sha256};
+
+use crate::board::{Board, DealStyle, FreeCellState, Rules, Stacking, MAX_DECK};
+use crate::card::{full_deck, Card, Suit};
+use crate::config::AssistPreset;
+
+const CODE_VERSION: u64 = 1;
+/// A position code for a non-standard table: the column and free-cell
+/// counts follow the seed.  Standard tables keep version 1 codes.
+const SHAPED_CODE_VERSION: u64 = 2;
+/// A shaped code for a four-suit deck, whose card codes need six bits.
+const FOUR_SUIT_CODE_VERSION: u64 = 3;
+/// A shaped code for a deck of more than nine ranks: the suit and rank
+/// counts follow the free cells.
+const RANKED_CODE_VERSION: u64 = 4;
+/// A FreeCell deal, laid out like a version 4 code.
+const FREECELL_CODE_VERSION: u64 = 5;
+/// A deal under rules from a rules file: a version 4 code followed by the
+/// dragon count and the flower, stacking and supermove rules.
+const CUSTOM_CODE_VERSION: u64 = 6;
+/// Leads a challenge token where a position code has its version.
+const CHALLENGE_VERSION: u64 = 8;
+/// A challenge under other rules: they follow the seed, laid out as in a
+/// version 6 code and then the deal style.  Standard deals keep version 8
+/// tokens.
+const SHAPED_CHALLENGE_VERSION: u64 = 9;
+const PAR_BITS: usize = 10;
+const ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+/// Longer than any column can get, so a corrupt code can't grow one forever.
+const MAX_COLUMN: usize = MAX_DECK;
+
+pub fn encode(board: &Board) -> String {
+    let mut bits = BitWriter::default();
+    if board.rules.is_standard() {
+        bits.push(CODE_VERSION, 4);
+        bits.push(board.seed, 64);
+    } else {
+        let rules = board.rules;
+        let version = match (rules.suits, rules.ranks) {
+            _ if play(rules) == play(Rules::FREECELL) => FREECELL_CODE_VERSION,
+            _ if play(rules) != play(Rules::STANDARD) => CUSTOM_CODE_VERSION,
+            (3, 9) => SHAPED_CODE_VERSION,
+            (_, 9) => FOUR_SUIT_CODE_VERSION,
+            _ => RANKED_CODE_VERSION,
+        };
+        bits.push(version, 4);
+        bits.push(board.seed, 64);
+        bits.push(rules.columns as u64, 4);
+        bits.push(rules.free_cells as u64, 3);
+        if matches!(version, RANKED_CODE_VERSION | FREECELL_CODE_VERSION | CUSTOM_CODE_VERSION) {
+            bits.push(rules.suits as u64, 3);
+            bits.push(rules.ranks as u64, 4);
+        }
+        if version == CUSTOM_CODE_VERSION {
+            bits.push(rules.dragons as u64, 3);
+            bits.push(u64::from(rules.flower), 1);
+            bits.push(u64::from(rules.stacking == Stacking::AlternateColor), 1);
+            bits.push(u64::from(rules.supermove), 1);
+        }
+    }
+    let rules = board.rules;
+    let width = card_bits(rules);
+    bits.push(u64::from(board.flower_placed), 1);
+    for &value in &board.foundations {
+        bits.push(u64::from(value), 4);
+    }
+    for cell in &board.free_cells {
+        match cell {
+            FreeCellState::Empty => bits.push(0, 2),
+            FreeCellState::Card(card) => {
+                bits.push(1, 2);
+                bits.push(card_code(*card, rules), width);
+            }
+            FreeCellState::DragonLocked(suit) => {
+                bits.push(2, 2);
+                bits.push(suit_index(*suit) as u64, 2);
+            }
+        }
+    }
+    for column in &board.columns {
+        for &card in column {
+            bits.push(card_code(card, rules), width);
+        }
+        bits.push(0, width);
+    }
+    let mut bytes = bits.finish();
+    bytes.push(checksum(&bytes));
+    to_base32(&bytes)
+}
+
+/// Read a code back into a board, checking that it holds a real position.
+pub fn decode(code: &str) -> Result<Board, String> {
+    let mut bytes = from_base32(code).ok_or("not a position code")?;
+    let check = bytes.pop().ok_or("not a position code")?;
+    if checksum(&bytes) != check {
+        return Err("the code has a typo (checksum mismatch)".to_string());
+    }
+    let mut bits = BitReader { bytes: &bytes, pos: 0 };
+    let truncated = || "the code is cut short".to_string();
+    let version = bits.take(4).ok_or_else(truncated)?;
+    let shaped = match version {
+        CODE_VERSION => false,
+        SHAPED_CODE_VERSION | FOUR_SUIT_CODE_VERSION | RANKED_CODE_VERSION | FREECELL_CODE_VERSION
+        | CUSTOM_CODE_VERSION => true,
+        CHALLENGE_VERSION | SHAPED_CHALLENGE_VERSION => {
+            return Err("that is a challenge token; start it with 'challenge <token>'".to_string());
+        }
+        _ => return Err("the code is from a newer version of szsol".to_string()),
+    };
+    let seed = bits.take(64).ok_or_else(truncated)?;
+    let mut rules = Rules::STANDARD;
+    if shaped {
+        rules.columns = bits.take(4).ok_or_else(truncated)? as usize;
+        rules.free_cells = bits.take(3).ok_or_else(truncated)? as usize;
+        if version == FOUR_SUIT_CODE_VERSION {
+            rules.suits = 4;
+        }
+        if matches!(version, RANKED_CODE_VERSION | FREECELL_CODE_VERSION | CUSTOM_CODE_VERSION) {
+            rules.suits = bits.take(3).ok_or_else(truncated)? as usize;
+            rules.ranks = bits.take(4).ok_or_else(truncated)? as usize;
+        }
+        if version == FREECELL_CODE_VERSION {
+            (rules.dragons, rules.flower, rules.stacking, rules.supermove) = play(Rules::FREECELL);
+        }
+        if version == CUSTOM_CODE_VERSION {
+            rules.dragons = bits.take(3).ok_or_else(truncated)? as usize;
+            rules.flower = bits.take(1).ok_or_else(truncated)? == 1;
+            rules.stacking =
+                if bits.take(1).ok_or_else(truncated)? == 1 { Stacking::AlternateColor } else { Stacking::AlternateSuit };
+            rules.supermove = bits.take(1).ok_or_else(truncated)? == 1;
+        }
+        if !rules.is_valid() {
+            return Err("the table's shape is out of range".to_string());
+        }
+    }
+    let width = card_bits(rules);
+    let flower_placed = bits.take(1).ok_or_else(truncated)? == 1;
+    let mut foundations = vec![0; rules.suits];
+    for value in &mut foundations {
+        *value = bits.take(4).ok_or_else(truncated)? as u8;
+    }
+    let mut free_cells = vec![FreeCellState::Empty; rules.free_cells];
+    for cell in &mut free_cells {
+        *cell = match bits.take(2).ok_or_else(truncated)? {
+            0 => FreeCellState::Empty,
+            1 => FreeCellState::Card(
+                code_card(bits.take(width).ok_or_else(truncated)?, rules).ok_or("bad card in a free cell")?,
+            ),
+            2 => {
+                let suit = rules.suits().get(bits.take(2).ok_or_else(truncated)? as usize);
+                FreeCellState::DragonLocked(*suit.ok_or("bad dragon suit in a free cell")?)
+            }
+            _ => return Err("bad free cell".to_string()),
+        };
+    }
+    let mut columns = vec![Vec::new(); rules.columns];
+    for column in &mut columns {
+        loop {
+            let code = bits.take(width).ok_or_else(truncated)?;
+            if code == 0 {
+                break;
+            }
+            column.push(code_card(code, rules).ok_or("bad card in a column")?);
+            if column.len() > MAX_COLUMN {
+                return Err("a column is too long".to_string());
+            }
+        }
+    }
+    let board = Board { rules, columns, free_cells, foundations, flower_placed, seed };
+    check_cards(&board)?;
+    Ok(board)
+}
+
+/// Build a fresh deal from a text grid transcribed from the original game:
+/// five rows of eight card labels (`R5 GD B9 ... FL`), the top row being the
+/// bottom card of each column.  Blank lines and `#` comments are skipped.
+/// The seed is derived from the cards, so the same grid always gets the same
+/// one.
+pub fn parse_grid(text: &str) -> Result<Board, String> {
+    let rows: Vec<Vec<&str>> = text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().split_whitespace().collect::<Vec<_>>())
+        .filter(|row| !row.is_empty())
+        .collect();
+    if rows.len() != 5 {
+        return Err(format!("expected 5 rows of cards, found {}", rows.len()));
+    }
+    let rules = Rules::STANDARD;
+    let mut columns = vec![Vec::new(); rules.columns];
+    for (r, row) in rows.iter().enumerate() {
+        if row.len() != rules.columns {
+            return Err(format!("row {} has {} cards, expected {}", r + 1, row.len(), rules.columns));
+        }
+        for (column, label) in columns.iter_mut().zip(row) {
+            column.push(Card::from_label(label).ok_or(format!("'{}' in row {} is not a card", label, r + 1))?);
+        }
+    }
+    let labels: Vec<String> = columns.iter().flatten().map(|card| card.label()).collect();
+    let board = Board {
+        rules,
+        columns,
+        free_cells: vec![FreeCellState::Empty; rules.free_cells],
+        foundations: vec![0; rules.suits],
+        flower_placed: false,
+        seed: Board::seed_from_phrase(&labels.join(" ")),
+    };
+    check_cards(&board)?;
+    Ok(board)
+}
+
+/// A deal sent to a friend: they play the same seed under the same rules
+/// with the same assists and compare their result against the par.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub seed: u64,
+    pub rules: Rules,
+    pub par: Option<u32>,
+    pub assist: AssistPreset,
+    pub hardcore: bool,
+}
+
+pub fn encode_challenge(challenge: &Challenge) -> String {
+    let mut bits = BitWriter::default();
+    if challenge.rules == Rules::STANDARD {
+        bits.push(CHALLENGE_VERSION, 4);
+        bits.push(challenge.seed, 64);
+    } else {
+        let rules = challenge.rules;
+        bits.push(SHAPED_CHALLENGE_VERSION, 4);
+        bits.push(challenge.seed, 64);
+        bits.push(rules.columns as u64, 4);
+        bits.push(rules.free_cells as u64, 3);
+        bits.push(rules.suits as u64, 3);
+        bits.push(rules.ranks as u64, 4);
+        bits.push(rules.dragons as u64, 3);
+        bits.push(u64::from(rules.flower), 1);
+        bits.push(u64::from(rules.stacking == Stacking::AlternateColor), 1);
+        bits.push(u64::from(rules.supermove), 1);
+        bits.push(u64::from(rules.deal_style == DealStyle::Shenzhen), 1);
+    }
+    let assist = match challenge.assist {
+        AssistPreset::Purist => 0,
+        AssistPreset::Standard => 1,
+        AssistPreset::Relaxed => 2,
+    };
+    bits.push(assist, 2);
+    bits.push(u64::from(challenge.hardcore), 1);
+    // Pars too long for the field are left out rather than cut short.
+    match challenge.par.filter(|&par| par < 1 << PAR_BITS) {
+        Some(par) => {
+            bits.push(1, 1);
+            bits.push(u64::from(par), PAR_BITS);
+        }
+        None => bits.push(0, 1 + PAR_BITS),
+    }
+    let mut bytes = bits.finish();
+    bytes.push(checksum(&bytes));
+    to_base32(&bytes)
+}
+
+pub fn decode_challenge(token: &str) -> Result<Challenge, String> {
+    let mut bytes = from_base32(token).ok_or("not a challenge token")?;
+    let check = bytes.pop().ok_or("not a challenge token")?;
+    if checksum(&bytes) != check {
+        return Err("the token has a typo (checksum mismatch)".to_string());
+    }
+    let mut bits = BitReader { bytes: &bytes, pos: 0 };
+    let truncated = || "the token is cut short".to_string();
+    let version = bits.take(4).ok_or_else(truncated)?;
+    match version {
+        CHALLENGE_VERSION | SHAPED_CHALLENGE_VERSION => {}
+        CODE_VERSION | SHAPED_CODE_VERSION | FOUR_SUIT_CODE_VERSION | RANKED_CODE_VERSION | FREECELL_CODE_VERSION
+        | CUSTOM_CODE_VERSION => {
+            return Err("that is a position code; play it with 'load <code>'".to_string());
+        }
+        _ => return Err("the token is from a newer version of szsol".to_string()),
+    }
+    let seed = bits.take(64).ok_or_else(truncated)?;
+    let mut rules = Rules::STANDARD;
+    if version == SHAPED_CHALLENGE_VERSION {
+        rules.columns = bits.take(4).ok_or_else(truncated)? as usize;
+        rules.free_cells = bits.take(3).ok_or_else(truncated)? as usize;
+        rules.suits = bits.take(3).ok_or_else(truncated)? as usize;
+        rules.ranks = bits.take(4).ok_or_else(truncated)? as usize;
+        rules.dragons = bits.take(3).ok_or_else(truncated)? as usize;
+        rules.flower = bits.take(1).ok_or_else(truncated)? == 1;
+        rules.stacking =
+            if bits.take(1).ok_or_else(truncated)? == 1 { Stacking::AlternateColor } else { Stacking::AlternateSuit };
+        rules.supermove = bits.take(1).ok_or_else(truncated)? == 1;
+        rules.deal_style =
+            if bits.take(1).ok_or_else(truncated)? == 1 { DealStyle::Shenzhen } else { DealStyle::Classic };
+        if !rules.is_valid() {
+            return Err("the table's shape is out of range".to_string());
+        }
+    }
+    let assist = match bits.take(2).ok_or_else(truncated)? {
+        0 => AssistPreset::Purist,
+        1 => AssistPreset::Standard,
+        2 => AssistPreset::Relaxed,
+        _ => return Err("bad assist preset".to_string()),
+    };
+    let hardcore = bits.take(1).ok_or_else(truncated)? == 1;
+    let has_par = bits.take(1).ok_or_else(truncated)? == 1;
+    let par = bits.take(PAR_BITS).ok_or_else(truncated)? as u32;
+    Ok(Challenge { seed, rules, par: has_par.then_some(par), assist, hardcore })
+}
+
+/// Every card of the deck must be somewhere exactly once: on the table, in
+/// a foundation, merged away as a dragon, or in the flower slot.
+fn check_cards(board: &Board) -> Result<(), String> {
+    let mut expected = full_deck(board.rules);
+    let mut take = |card: Card| match expected.iter().position(|&c| c == card) {
+        Some(i) => {
+            expected.swap_remove(i);
+            Ok(())
+        }
+        None => Err(format!("{:?} appears too often", card)),
+    };
+    for (i, &value) in board.foundations.iter().enumerate() {
+        if value > board.rules.top_rank() {
+            return Err(format!("a foundation is past {}", board.rules.ranks));
+        }
+        for v in 1..=value {
+            take(Card::Numbered(Suit::ALL[i], v))?;
+        }
+    }
+    if board.flower_placed {
+        take(Card::Flower)?;
+    }
+    for cell in &board.free_cells {
+        match cell {
+            FreeCellState::Empty => {}
+            FreeCellState::Card(card) => take(*card)?,
+            FreeCellState::DragonLocked(_) if board.rules.dragons == 0 => {
+                return Err("a free cell is locked but the deck has no dragons".to_string());
+            }
+            FreeCellState::DragonLocked(suit) => {
+                for _ in 0..board.rules.dragons {
+                    take(Card::Dragon(*suit))?;
+                }
+            }
+        }
+    }
+    for &card in board.columns.iter().flatten() {
+        take(card)?;
+    }
+    if !expected.is_empty() {
+        return Err(format!("{} card(s) are missing", expected.len()));
+    }
+    Ok(())
+}
+
+/// The rules beyond the table's shape and the deck's size.
+fn play(rules: Rules) -> (usize, bool, Stacking, bool) {
+    (rules.dragons, rules.flower, rules.stacking, rules.supermove)
+}
+
+fn suit_index(suit: Suit) -> usize {
+    Suit::ALL.iter().position(|&s| s == suit).unwrap_or(0)
+}
+
+/// Bits per card code for a deck under `rules`: enough for the flower's.
+fn card_bits(rules: Rules) -> usize {
+    (u64::BITS - card_code(Card::Flower, rules).leading_zeros()) as usize
+}
+
+/// For the standard deck: 1..=27 numbered cards, 28..=30 dragons, 31 the
+/// flower (in general, the numbered cards suit by suit, then a dragon per
+/// suit, then the flower); 0 ends a column.
+fn card_code(card: Card, rules: Rules) -> u64 {
+    let (suits, ranks) = (rules.suits as u64, rules.ranks as u64);
+    match card {
+        Card::Numbered(suit, value) => 1 + suit_index(suit) as u64 * ranks + u64::from(value) - 1,
+        Card::Dragon(suit) => 1 + suits * ranks + suit_index(suit) as u64,
+        Card::Flower => 1 + suits * (ranks + 1),
+    }
+}
+
+fn code_card(code: u64, rules: Rules) -> Option<Card> {
+    let ranks = rules.ranks as u64;
+    let (numbered, dragons) = (rules.suits as u64 * ranks, rules.suits as u64 * (ranks + 1));
+    match code {
+        0 => None,
+        _ if code <= numbered => Some(Card::Numbered(
+            Suit::ALL[((code - 1) / ranks) as usize],
+            ((code - 1) % ranks) as u8 + 1,
+        )),
+        _ if code <= dragons => Some(Card::Dragon(Suit::ALL[(code - numbered - 1) as usize])),
+        _ if code == dragons + 1 => Some(Card::Flower),
+        _ => None,
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    Sha256::digest(bytes)[0]
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    /// Append the low `count` bits of `value`, most significant first.
+    fn push(&mut self, value: u64, count: usize) {
+        for i in (0..count).rev() {
+            if self.bits.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn take(&mut self, count: usize) -> Option<u64> {
+        let mut value = 0;
+        for _ in 0..count {
+            let byte = *self.bytes.get(self.pos / 8)?;
+            value = (value << 1) | u64::from((byte >> (7 - self.pos % 8)) & 1);
+            self.pos += 1;
+        }
+        Some(value)
+    }
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut bits = BitReader { bytes, pos: 0 };
+    let mut out = String::new();
+    let total = bytes.len() * 8;
+    while bits.pos < total {
+        let count = (total - bits.pos).min(5);
+        let value = bits.take(count).unwrap_or(0) << (5 - count);
+        out.push(ALPHABET[value as usize] as char);
+    }
+    out
+}
+
+fn from_base32(text: &str) -> Option<Vec<u8>> {
+    // Spaces and dashes may be added for readability.
+    let text: String = text.chars().filter(|c| !c.is_whitespace() && *c != '-').collect::<String>().to_ascii_lowercase();
+    let mut bits = BitWriter::default();
+    for c in text.chars() {
+        let value = ALPHABET.iter().position(|&a| a as char == c)?;
+        bits.push(value as u64, 5);
+    }
+    // The last character's padding bits don't make a byte.  A stray extra
+    // character or non-zero padding means the code was mistyped.
+    let mut bytes = bits.finish();
+    bytes.truncate(text.len() * 5 / 8);
+    (to_base32(&bytes) == text).then_some(bytes)
+}
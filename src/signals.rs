@@ -0,0 +1,80 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Ctrl-C and SIGTERM save the game like `quit` instead of killing it.
+//!
+//! `signal-hook` catches the signals; a watcher thread waits for one,
+//! writes the save the last command queued (board and history included),
+//! puts the terminal back and exits.
+use std::io;
+
+use crossterm::cursor::Show;
+use crossterm::event::DisableMouseCapture;
+use crossterm::execute;
+use crossterm::terminal::{self, LeaveAlternateScreen};
+use signal_hook::consts::{SIGINT, SIGTERM};
+
+use crate::history::History;
+
+/// Install the handlers.  `alternate_screen` says whether the TUI owns the
+/// terminal and has to hand it back before exiting.
+#[cfg(unix)]
+pub fn install(alternate_screen: bool) {
+    let Ok(mut signals) = signal_hook::iterator::Signals::new([SIGINT, SIGTERM]) else { return };
+    std::thread::spawn(move || {
+        if let Some(signum) = signals.forever().next() {
+            exit(signum, alternate_screen);
+        }
+    });
+}
+
+/// Install the handlers.  Windows has no signal iterator, so the watcher
+/// checks a flag the handlers set.
+#[cfg(not(unix))]
+pub fn install(alternate_screen: bool) {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let caught = Arc::new(AtomicUsize::new(0));
+    for signum in [SIGINT, SIGTERM] {
+        if signal_hook::flag::register_usize(signum, Arc::clone(&caught), signum as usize).is_err() {
+            return;
+        }
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        match caught.load(Ordering::SeqCst) {
+            0 => continue,
+            signum => exit(signum as i32, alternate_screen),
+        }
+    });
+}
+
+/// Save, hand the terminal back and exit as killed by `signum`.
+fn exit(signum: i32, alternate_screen: bool) -> ! {
+    History::flush();
+    let _ = terminal::disable_raw_mode();
+    if alternate_screen {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+    }
+    std::process::exit(128 + signum);
+}
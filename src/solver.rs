@@ -21,13 +21,19 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 use std::collections::{HashMap, HashSet};
-use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::board::{Board, Location, NUM_COLUMNS, NUM_FREE_CELLS};
-use crate::card::Suit;
+use crate::board::{Board, Location};
+use crate::card::{Card, Suit};
+use crate::i18n::tr;
+use crate::packed::PackedBoard;
 
 pub const NODE_LIMIT: usize = 500_000;
 pub const PROGRESS_INTERVAL: usize = 2_000;
@@ -35,12 +41,62 @@ pub const PROGRESS_INTERVAL: usize = 2_000;
 #[derive(Debug, Clone, Copy)]
 pub enum SolverFailure {
     NodeLimit,
+    /// The budget's time ran out.
+    TimeLimit,
     Exhausted,
 }
 
+/// How `solve` searches (the `solver` setting).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Best-first A*: usually quickest, but keeps every position it reaches.
+    #[default]
+    AStar,
+    /// Iterative deepening with move ordering: memory is capped however
+    /// long it runs.  Its estimate of the moves left can be too high when
+    /// dragon merges are involved, so a solution is not always shortest.
+    Deepening,
+}
+
+impl Strategy {
+    pub fn parse(value: &str) -> Option<Strategy> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "astar" | "a*" => Some(Strategy::AStar),
+            "deepening" | "ida" => Some(Strategy::Deepening),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::AStar => "astar",
+            Strategy::Deepening => "deepening",
+        }
+    }
+}
+
+/// When a search gives up and reports the deal as unknown.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub nodes: usize,
+    pub time: Option<Duration>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget { nodes: NODE_LIMIT, time: None }
+    }
+}
+
+impl Budget {
+    fn deadline(&self) -> Option<Instant> {
+        self.time.map(|time| Instant::now() + time)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum SolverProgress {
-    Started { node_limit: usize },
+    Started { node_limit: usize, strategy: Strategy },
     CacheHit { seed: u64, remaining_moves: usize },
     CacheMiss { seed: u64 },
     Progress { nodes_explored: usize, node_limit: usize },
@@ -62,7 +118,7 @@ impl SolverProgress {
 
     pub fn node_limit(self) -> usize {
         match self {
-            SolverProgress::Started { node_limit } => node_limit,
+            SolverProgress::Started { node_limit, .. } => node_limit,
             SolverProgress::CacheHit { .. } => NODE_LIMIT,
             SolverProgress::CacheMiss { .. } => NODE_LIMIT,
             SolverProgress::Progress { node_limit, .. } => node_limit,
@@ -82,28 +138,35 @@ impl SolverProgress {
 
     pub fn message(self) -> String {
         match self {
-            SolverProgress::Started { .. } => "Solver: started A* search.".to_string(),
-            SolverProgress::CacheHit { seed, remaining_moves } => format!(
+            SolverProgress::Started { strategy: Strategy::AStar, .. } => tr!("Solver: started A* search."),
+            SolverProgress::Started { strategy: Strategy::Deepening, .. } => {
+                tr!("Solver: started iterative-deepening search.")
+            }
+            SolverProgress::CacheHit { seed, remaining_moves } => tr!(
                 "Solver: cache hit for seed {}. Reusing remaining solution ({} moves).",
                 seed, remaining_moves
             ),
-            SolverProgress::CacheMiss { seed } => format!(
+            SolverProgress::CacheMiss { seed } => tr!(
                 "Solver: cached solution for seed {} does not match current board. Keeping cache and recomputing.",
                 seed
             ),
-            SolverProgress::Progress { nodes_explored, .. } => {
-                format!("Solver: {} / {} nodes explored.", nodes_explored, NODE_LIMIT)
+            SolverProgress::Progress { nodes_explored, node_limit } => {
+                tr!("Solver: {} / {} nodes explored.", nodes_explored, node_limit)
             }
-            SolverProgress::Finished { solution_len, nodes_explored } => format!(
+            SolverProgress::Finished { solution_len, nodes_explored } => tr!(
                 "Solver: found solution in {} moves after exploring {} nodes.",
                 solution_len, nodes_explored
             ),
-            SolverProgress::Failed { nodes_explored, reason, .. } => match reason {
-                SolverFailure::NodeLimit => format!(
+            SolverProgress::Failed { nodes_explored, node_limit, reason } => match reason {
+                SolverFailure::NodeLimit => tr!(
                     "Solver: node limit ({}) reached after exploring {} nodes.",
-                    NODE_LIMIT, nodes_explored
+                    node_limit, nodes_explored
+                ),
+                SolverFailure::TimeLimit => tr!(
+                    "Solver: time limit reached after exploring {} nodes; whether the deal can be won is unknown.",
+                    nodes_explored
                 ),
-                SolverFailure::Exhausted => format!(
+                SolverFailure::Exhausted => tr!(
                     "Solver: search exhausted after exploring {} nodes.",
                     nodes_explored
                 ),
@@ -127,24 +190,32 @@ pub enum SolverMove {
 impl SolverMove {
     /// Format this move as the game CLI command string the player would type.
     pub fn to_command_str(self) -> String {
+        self.command_in_base(0)
+    }
+
+    /// Like [`SolverMove::to_command_str`], with columns and free cells
+    /// numbered from `base` (the `one_indexed` setting).
+    pub fn command_in_base(self, base: usize) -> String {
+        let b = |index: usize| index + base;
         match self {
             SolverMove::ColToCol { src, dst, depth_from_top: 0 } =>
-                format!("cc {} {}", src, dst),
+                format!("cc {} {}", b(src), b(dst)),
             SolverMove::ColToCol { src, dst, depth_from_top: d } =>
-                format!("cc {}:{} {}", src, d, dst),
+                format!("cc {}:{} {}", b(src), d, b(dst)),
             SolverMove::ColToFree { src, dst } =>
-                format!("cf {} {}", src, dst),
+                format!("cf {} {}", b(src), b(dst)),
             SolverMove::FreeToCol { src, dst } =>
-                format!("fc {} {}", src, dst),
+                format!("fc {} {}", b(src), b(dst)),
             SolverMove::ColToFound { src } =>
-                format!("ctf {}", src),
+                format!("ctf {}", b(src)),
             SolverMove::FreeToFound { src } =>
-                format!("ftf {}", src),
+                format!("ftf {}", b(src)),
             SolverMove::Merge { suit } => {
                 let s = match suit {
-                    Suit::Red   => "r",
-                    Suit::Green => "g",
-                    Suit::Black => "b",
+                    Suit::Red    => "r",
+                    Suit::Green  => "g",
+                    Suit::Black  => "b",
+                    Suit::Yellow => "y",
                 };
                 format!("dragon {}", s)
             }
@@ -158,7 +229,7 @@ impl Board {
         let mut moves = Vec::new();
 
         // 1. Merge dragons (if we can, we typically should!)
-        for &suit in &Suit::ALL {
+        for &suit in self.rules.suits() {
             if self.can_merge_dragons(suit) {
                 // In many cases, if a merge is available, it's strictly optimal.
                 // We'll add it as a move. Future optimization: if merge is possible, ONLY return merge.
@@ -167,14 +238,14 @@ impl Board {
         }
 
         // 2. Column to Foundation
-        for src_col in 0..NUM_COLUMNS {
+        for src_col in 0..self.rules.columns {
             if !self.columns[src_col].is_empty() && self.can_move_to_foundation(Location::Column(src_col)) {
                 moves.push(SolverMove::ColToFound { src: src_col });
             }
         }
 
         // 3. Free to Foundation
-        for src_cell in 0..NUM_FREE_CELLS {
+        for src_cell in 0..self.rules.free_cells {
             if self.free_cell_card(src_cell).is_some() && self.can_move_to_foundation(Location::FreeCell(src_cell)) {
                 moves.push(SolverMove::FreeToFound { src: src_cell });
             }
@@ -182,9 +253,9 @@ impl Board {
 
         // 4. Column to Free Cell
         // Optimization: pick only the FIRST empty free cell. Identical otherwise.
-        let first_empty = (0..NUM_FREE_CELLS).find(|&i| self.free_cells[i].is_empty());
+        let first_empty = (0..self.rules.free_cells).find(|&i| self.free_cells[i].is_empty());
         if let Some(dst_cell) = first_empty {
-            for src_col in 0..NUM_COLUMNS {
+            for src_col in 0..self.rules.columns {
                 if !self.columns[src_col].is_empty() {
                     // Always valid to put single top card into an empty free cell
                     moves.push(SolverMove::ColToFree { src: src_col, dst: dst_cell });
@@ -193,7 +264,7 @@ impl Board {
         }
 
         // 5. Column to Column
-        for src_col in 0..NUM_COLUMNS {
+        for src_col in 0..self.rules.columns {
             let col_len = self.columns[src_col].len();
             if col_len == 0 { continue; }
             
@@ -204,13 +275,14 @@ impl Board {
                     // Convert absolute index → depth from top (0 = only top card)
                     let depth_from_top = col_len - 1 - start_idx;
 
-                    for dst_col in 0..NUM_COLUMNS {
+                    for dst_col in 0..self.rules.columns {
                         if src_col == dst_col { continue; }
 
-                        let can_place = match self.column_top(dst_col) {
-                            None => true,
-                            Some(top) => bottom_card.can_stack_on(top),
-                        };
+                        let can_place = col_len - start_idx <= self.max_stack_move(dst_col)
+                            && match self.column_top(dst_col) {
+                                None => true,
+                                Some(top) => self.rules.can_stack(bottom_card, top),
+                            };
 
                         if can_place {
                             // Skip moving an entire column to an empty column (symmetrical no-op)
@@ -225,12 +297,12 @@ impl Board {
         }
 
         // 6. Free to Column
-        for src_cell in 0..NUM_FREE_CELLS {
+        for src_cell in 0..self.rules.free_cells {
             if let Some(card) = self.free_cell_card(src_cell) {
-                for dst_col in 0..NUM_COLUMNS {
+                for dst_col in 0..self.rules.columns {
                     let can_place = match self.column_top(dst_col) {
                         None => true,
-                        Some(top) => card.can_stack_on(top),
+                        Some(top) => self.rules.can_stack(card, top),
                     };
                     if can_place {
                         moves.push(SolverMove::FreeToCol { src: src_cell, dst: dst_col });
@@ -242,6 +314,12 @@ impl Board {
         moves
     }
 
+    /// True when the game is not won and no legal move (including dragon
+    /// merges and foundation plays) remains.
+    pub fn is_stuck(&self) -> bool {
+        !self.is_won() && self.valid_moves().is_empty()
+    }
+
     /// Execute a solver move on this board.
     pub fn apply_move(&mut self, m: SolverMove) {
         match m {
@@ -345,13 +423,11 @@ fn heuristic(board: &Board) -> i32 {
     // Penalty: for each needed-but-buried card, count how many cards are above it.
     // "Needed" = the next card to go to the foundation for each suit.
     // The deeper it's buried, the harder the position.
-    use crate::board::{FreeCellState, NUM_FOUNDATIONS};
+    use crate::board::FreeCellState;
     use crate::card::Card;
-    let suits = crate::card::Suit::ALL;
-    for (idx, &suit) in suits.iter().enumerate() {
-        if idx >= NUM_FOUNDATIONS { break; }
+    for (idx, &suit) in board.rules.suits().iter().enumerate() {
         let needed_val = board.foundations[idx] + 1;
-        if needed_val > 9 { continue; }
+        if needed_val > board.rules.top_rank() { continue; }
         let target = Card::Numbered(suit, needed_val);
 
         // Search every column for the target card and count how many cards are above it.
@@ -394,10 +470,9 @@ struct SearchNode {
 }
 
 struct SearchRecord {
-    board: Board,
+    board: PackedBoard,
     parent: Option<usize>,
     incoming_move: Option<SolverMove>,
-    board_hash: String,
 }
 
 impl PartialEq for SearchNode {
@@ -426,7 +501,7 @@ fn reconstruct_solution(records: &[SearchRecord], mut node_id: usize) -> SolverS
             .incoming_move
             .expect("child record must have incoming move");
         steps_rev.push(SolverStep {
-            board_hash: records[parent_id].board_hash.clone(),
+            board_hash: board_hash(&records[parent_id].board.unpack()),
             next_move,
         });
         node_id = parent_id;
@@ -444,10 +519,34 @@ fn reconstruct_solution(records: &[SearchRecord], mut node_id: usize) -> SolverS
 ///
 /// A* pathfinding solver. `progress` receives structured solver updates.
 /// Return `false` from `progress` to abort the search early.
-pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progress: F) -> Option<SolverSolution> {
-    if !progress(SolverProgress::Started { node_limit: NODE_LIMIT }) {
+pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, progress: F) -> Option<SolverSolution> {
+    solve_with(initial_board, Strategy::AStar, Budget::default(), progress)
+}
+
+/// [`solve`] with the given strategy and budget.  A* solutions are cached
+/// per seed; deepening searches always start afresh.
+pub fn solve_with<F: FnMut(SolverProgress) -> bool>(
+    initial_board: &Board,
+    strategy: Strategy,
+    budget: Budget,
+    mut progress: F,
+) -> Option<SolverSolution> {
+    let seed = initial_board.seed;
+    // How each run ends goes to the diagnostics log (`-v`).
+    let mut progress = move |update: SolverProgress| {
+        match update {
+            SolverProgress::Progress { .. } => {}
+            SolverProgress::Finished { .. } | SolverProgress::Failed { .. } => log::info!("seed {}: {:?}", seed, update),
+            _ => log::debug!("seed {}: {:?}", seed, update),
+        }
+        progress(update)
+    };
+    if !progress(SolverProgress::Started { node_limit: budget.nodes, strategy }) {
         return None;
     }
+    if strategy == Strategy::Deepening {
+        return deepen(initial_board, budget, progress);
+    }
 
     if let Some(cached) = SolverCache::global()
         .lock()
@@ -467,19 +566,39 @@ pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progre
         }
     }
 
+    let solution = search(initial_board, budget, progress)?;
+    if let Ok(mut cache) = SolverCache::global().lock() {
+        cache.entries.insert(initial_board.seed, solution.clone());
+    }
+    Some(solution)
+}
+
+/// Like [`solve`], but the solution is neither looked up in nor added to the
+/// in-memory cache; for runs over many deals.
+pub fn solve_uncached<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progress: F) -> Option<SolverSolution> {
+    let budget = Budget::default();
+    if !progress(SolverProgress::Started { node_limit: budget.nodes, strategy: Strategy::AStar }) {
+        return None;
+    }
+    search(initial_board, budget, progress)
+}
+
+/// The A* search itself.
+fn search<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, budget: Budget, mut progress: F) -> Option<SolverSolution> {
+    let deadline = budget.deadline();
     let mut heap: BinaryHeap<SearchNode> = BinaryHeap::new();
     let mut records: Vec<SearchRecord> = Vec::new();
-    let mut visited: HashSet<Board> = HashSet::new();
+    let mut visited: HashSet<PackedBoard> = HashSet::new();
 
     let mut start = initial_board.clone();
     let _ = start.auto_move();
 
     let h0 = heuristic(&start);
+    let start = PackedBoard::from(&start);
     records.push(SearchRecord {
-        board: start.clone(),
+        board: start,
         parent: None,
         incoming_move: None,
-        board_hash: board_hash(&start),
     });
     heap.push(SearchNode {
         neg_f: h0,
@@ -490,12 +609,9 @@ pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progre
 
     let mut nodes_explored = 0usize;
     while let Some(SearchNode { node_id, g, .. }) = heap.pop() {
-        let state = records[node_id].board.clone();
+        let state = records[node_id].board.unpack();
         if state.is_won() {
             let solution = reconstruct_solution(&records, node_id);
-            if let Ok(mut cache) = SolverCache::global().lock() {
-                cache.entries.insert(initial_board.seed, solution.clone());
-            }
             let _ = progress(SolverProgress::Finished {
                 solution_len: solution.len(),
                 nodes_explored,
@@ -504,39 +620,38 @@ pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progre
         }
 
         nodes_explored += 1;
-        if nodes_explored > NODE_LIMIT {
+        if let Some(reason) = over_budget(nodes_explored, &budget, deadline) {
             let _ = progress(SolverProgress::Failed {
                 nodes_explored,
-                node_limit: NODE_LIMIT,
-                reason: SolverFailure::NodeLimit,
+                node_limit: budget.nodes,
+                reason,
             });
             return None;
         }
 
-        if nodes_explored % PROGRESS_INTERVAL == 0 {
-            if !progress(SolverProgress::Progress {
+        if nodes_explored.is_multiple_of(PROGRESS_INTERVAL)
+            && !progress(SolverProgress::Progress {
                 nodes_explored,
-                node_limit: NODE_LIMIT,
-            }) {
-                return None;
-            }
+                node_limit: budget.nodes,
+            })
+        {
+            return None;
         }
 
         for m in state.valid_moves() {
             let mut next = state.clone();
             next.apply_move(m);
 
-            if visited.insert(next.clone()) {
+            let packed = PackedBoard::from(&next);
+            if visited.insert(packed) {
                 let g_next = g + 1;
                 let h = heuristic(&next);
                 let neg_f = h - g_next as i32;
-                let next_hash = board_hash(&next);
                 let next_id = records.len();
                 records.push(SearchRecord {
-                    board: next,
+                    board: packed,
                     parent: Some(node_id),
                     incoming_move: Some(m),
-                    board_hash: next_hash,
                 });
                 heap.push(SearchNode { neg_f, g: g_next, node_id: next_id });
             }
@@ -550,3 +665,353 @@ pub fn solve<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, mut progre
     });
     None
 }
+
+/// Why a search must stop now, if it must.  The clock is read only every
+/// few hundred nodes.
+fn over_budget(nodes_explored: usize, budget: &Budget, deadline: Option<Instant>) -> Option<SolverFailure> {
+    if nodes_explored > budget.nodes {
+        return Some(SolverFailure::NodeLimit);
+    }
+    match deadline {
+        Some(deadline) if nodes_explored.is_multiple_of(256) && Instant::now() >= deadline => {
+            Some(SolverFailure::TimeLimit)
+        }
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Iterative-deepening solver
+// ---------------------------------------------------------------------------
+
+/// Rough count of the moves still needed: every card in a free cell must
+/// move, every card sitting on one it cannot stack on must move, and every
+/// suit of dragons still needs its merge.  Used as the `h` of IDA*.  It is
+/// not a true lower bound: one merge can clear several dragons counted as
+/// breaks, so it may overestimate and the solution found may not be the
+/// shortest.
+fn moves_left(board: &Board) -> u32 {
+    let parked = board.free_cells.iter().filter(|cell| cell.card().is_some()).count();
+    let breaks: usize = board
+        .columns
+        .iter()
+        .map(|column| column.windows(2).filter(|pair| !board.rules.can_stack(pair[1], pair[0])).count())
+        .sum();
+    let merges = board.rules.suits()
+        .iter()
+        .filter(|_| board.rules.dragons > 0)
+        .filter(|&&suit| !board.free_cells.contains(&crate::board::FreeCellState::DragonLocked(suit)))
+        .count();
+    (parked + breaks + merges) as u32
+}
+
+/// `valid_moves` with the most promising first: merges and foundation
+/// plays, then moves that uncover a dragon, then moves that empty a
+/// column, then the rest.
+fn ordered_moves(board: &Board) -> Vec<SolverMove> {
+    let uncovers = |src: usize, count: usize| {
+        let column = &board.columns[src];
+        column.len().checked_sub(count + 1).map(|i| column[i])
+    };
+    let rank = |m: &SolverMove| match *m {
+        SolverMove::Merge { .. } | SolverMove::ColToFound { .. } | SolverMove::FreeToFound { .. } => 0,
+        SolverMove::ColToCol { src, depth_from_top, .. } => match uncovers(src, depth_from_top + 1) {
+            Some(Card::Dragon(_)) => 1,
+            None => 2,
+            Some(_) => 3,
+        },
+        SolverMove::ColToFree { src, .. } => match uncovers(src, 1) {
+            Some(Card::Dragon(_)) => 1,
+            None => 2,
+            Some(_) => 3,
+        },
+        SolverMove::FreeToCol { .. } => 3,
+    };
+    let mut moves = board.valid_moves();
+    moves.sort_by_key(rank);
+    moves
+}
+
+/// What one pass of the depth-first search found.
+enum Pass {
+    Found,
+    /// Nothing within the bound; the smallest `f` seen beyond it.
+    Deeper(u32),
+    /// Nothing within the bound and nothing beyond it.
+    Exhausted,
+    /// Out of budget, or aborted by `progress` (`None`).
+    Stop(Option<SolverFailure>),
+}
+
+struct Deepening<'a, F> {
+    budget: Budget,
+    deadline: Option<Instant>,
+    nodes_explored: usize,
+    path: Vec<SolverMove>,
+    /// Fewest moves each position was reached in during this pass, for up
+    /// to `SEEN_LIMIT` positions.
+    seen: HashMap<PackedBoard, u32>,
+    progress: &'a mut F,
+}
+
+impl<F: FnMut(SolverProgress) -> bool> Deepening<'_, F> {
+    fn visit(&mut self, board: &Board, g: u32, bound: u32) -> Pass {
+        let f = g + moves_left(board);
+        if f > bound {
+            return Pass::Deeper(f);
+        }
+        if board.is_won() {
+            return Pass::Found;
+        }
+        self.nodes_explored += 1;
+        if let Some(reason) = over_budget(self.nodes_explored, &self.budget, self.deadline) {
+            return Pass::Stop(Some(reason));
+        }
+        if self.nodes_explored.is_multiple_of(PROGRESS_INTERVAL)
+            && !(self.progress)(SolverProgress::Progress {
+                nodes_explored: self.nodes_explored,
+                node_limit: self.budget.nodes,
+            })
+        {
+            return Pass::Stop(None);
+        }
+        let packed = PackedBoard::from(board);
+        let full = self.seen.len() >= SEEN_LIMIT;
+        match self.seen.get_mut(&packed) {
+            Some(best) if *best <= g => return Pass::Exhausted,
+            Some(best) => *best = g,
+            // A full table only prunes less; the search stays correct.
+            None if full => {}
+            None => {
+                self.seen.insert(packed, g);
+            }
+        }
+
+        let mut next_bound: Option<u32> = None;
+        for m in ordered_moves(board) {
+            let mut next = board.clone();
+            next.apply_move(m);
+            self.path.push(m);
+            match self.visit(&next, g + 1, bound) {
+                Pass::Found => return Pass::Found,
+                Pass::Stop(reason) => return Pass::Stop(reason),
+                Pass::Deeper(f) => next_bound = Some(next_bound.map_or(f, |b| b.min(f))),
+                Pass::Exhausted => {}
+            }
+            self.path.pop();
+        }
+        next_bound.map_or(Pass::Exhausted, Pass::Deeper)
+    }
+}
+
+/// Positions the deepening search remembers per pass.
+const SEEN_LIMIT: usize = 1 << 18;
+
+/// IDA*: depth-first passes under a rising bound on moves made plus
+/// [`moves_left`].  Memory holds at most `SEEN_LIMIT` of a pass's
+/// positions, and the budget turns a pathological deal into "unknown"
+/// rather than a hang.
+fn deepen<F: FnMut(SolverProgress) -> bool>(initial_board: &Board, budget: Budget, mut progress: F) -> Option<SolverSolution> {
+    let mut start = initial_board.clone();
+    let _ = start.auto_move();
+    let mut search = Deepening {
+        budget,
+        deadline: budget.deadline(),
+        nodes_explored: 0,
+        path: Vec::new(),
+        seen: HashMap::new(),
+        progress: &mut progress,
+    };
+    let mut bound = moves_left(&start);
+    let failure = loop {
+        search.seen.clear();
+        match search.visit(&start, 0, bound) {
+            Pass::Found => break None,
+            Pass::Deeper(next) => bound = next,
+            Pass::Exhausted => break Some(SolverFailure::Exhausted),
+            Pass::Stop(None) => return None,
+            Pass::Stop(Some(reason)) => break Some(reason),
+        }
+    };
+    let (nodes_explored, path) = (search.nodes_explored, std::mem::take(&mut search.path));
+    if let Some(reason) = failure {
+        let _ = progress(SolverProgress::Failed { nodes_explored, node_limit: budget.nodes, reason });
+        return None;
+    }
+    let mut board = start;
+    let solution: SolverSolution = path
+        .into_iter()
+        .map(|next_move| {
+            let step = SolverStep { board_hash: board_hash(&board), next_move };
+            board.apply_move(next_move);
+            step
+        })
+        .collect();
+    let _ = progress(SolverProgress::Finished { solution_len: solution.len(), nodes_explored });
+    Some(solution)
+}
+
+// ---------------------------------------------------------------------------
+// Background winnability checks
+// ---------------------------------------------------------------------------
+
+/// Verdict of a background solver run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winnability {
+    Winnable,
+    /// The search space was exhausted without finding a win.
+    Unwinnable,
+    /// The node limit was hit; nothing can be concluded.
+    Unknown,
+}
+
+/// Runs the solver on a worker thread so the input loop never blocks.
+///
+/// Only the most recently submitted board matters: a newer submission aborts
+/// the search in progress, and stale results are dropped by `poll`.
+pub struct WinnabilityChecker {
+    requests: Sender<(u64, Board)>,
+    results: Receiver<(u64, Winnability)>,
+    latest: Arc<AtomicU64>,
+}
+
+impl WinnabilityChecker {
+    pub fn spawn() -> Self {
+        let (req_tx, req_rx) = mpsc::channel::<(u64, Board)>();
+        let (res_tx, res_rx) = mpsc::channel();
+        let latest = Arc::new(AtomicU64::new(0));
+        let worker_latest = Arc::clone(&latest);
+
+        thread::spawn(move || {
+            while let Ok(mut request) = req_rx.recv() {
+                // Skip boards that were superseded while we were busy.
+                while let Ok(newer) = req_rx.try_recv() {
+                    request = newer;
+                }
+                let (id, board) = request;
+
+                let mut failure = None;
+                let solved = solve(&board, |progress| {
+                    if let SolverProgress::Failed { reason, .. } = progress {
+                        failure = Some(reason);
+                    }
+                    worker_latest.load(AtomicOrdering::Relaxed) == id
+                });
+                let verdict = match (solved, failure) {
+                    (Some(_), _) => Winnability::Winnable,
+                    (None, Some(SolverFailure::Exhausted)) => Winnability::Unwinnable,
+                    (None, Some(SolverFailure::NodeLimit | SolverFailure::TimeLimit)) => Winnability::Unknown,
+                    // Aborted by a newer request.
+                    (None, None) => continue,
+                };
+                if res_tx.send((id, verdict)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { requests: req_tx, results: res_rx, latest }
+    }
+
+    /// Queue `board` for checking, superseding any earlier submission.
+    pub fn submit(&mut self, board: &Board) {
+        let id = self.latest.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let _ = self.requests.send((id, board.clone()));
+    }
+
+    /// Return the verdict for the latest submission, if it has arrived.
+    pub fn poll(&mut self) -> Option<Winnability> {
+        let latest = self.latest.load(AtomicOrdering::Relaxed);
+        let mut verdict = None;
+        while let Ok((id, v)) = self.results.try_recv() {
+            if id == latest {
+                verdict = Some(v);
+            }
+        }
+        verdict
+    }
+
+    /// Like `poll`, but wait up to `timeout` for the verdict to arrive.
+    pub fn wait(&mut self, timeout: Duration) -> Option<Winnability> {
+        let deadline = Instant::now() + timeout;
+        let latest = self.latest.load(AtomicOrdering::Relaxed);
+        loop {
+            let left = deadline.checked_duration_since(Instant::now())?;
+            match self.results.recv_timeout(left) {
+                Ok((id, verdict)) if id == latest => return Some(verdict),
+                Ok(_) => {}
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Background par
+// ---------------------------------------------------------------------------
+
+/// Works out a deal's par (the solver's move count) on a worker thread.
+///
+/// Like `WinnabilityChecker`, a newer submission (or `cancel`) aborts the
+/// search in progress, and `poll` only returns the latest deal's result.
+pub struct ParFinder {
+    requests: Sender<(u64, Board)>,
+    results: Receiver<(u64, u64, Option<usize>)>,
+    latest: Arc<AtomicU64>,
+}
+
+impl ParFinder {
+    pub fn spawn() -> Self {
+        let (req_tx, req_rx) = mpsc::channel::<(u64, Board)>();
+        let (res_tx, res_rx) = mpsc::channel();
+        let latest = Arc::new(AtomicU64::new(0));
+        let worker_latest = Arc::clone(&latest);
+
+        thread::spawn(move || {
+            while let Ok(mut request) = req_rx.recv() {
+                while let Ok(newer) = req_rx.try_recv() {
+                    request = newer;
+                }
+                let (id, board) = request;
+
+                let mut aborted = false;
+                let solved = solve(&board, |_| {
+                    aborted = worker_latest.load(AtomicOrdering::Relaxed) != id;
+                    !aborted
+                });
+                if aborted {
+                    continue;
+                }
+                if res_tx.send((id, board.seed, solved.map(|path| path.len()))).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { requests: req_tx, results: res_rx, latest }
+    }
+
+    /// Work out the par of the deal starting at `board`.
+    pub fn submit(&mut self, board: &Board) {
+        let id = self.latest.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let _ = self.requests.send((id, board.clone()));
+    }
+
+    /// Abandon the search in progress.
+    pub fn cancel(&mut self) {
+        self.latest.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    /// The seed and par of the latest submission once it is done; `None`
+    /// as the par means the solver gave up.
+    pub fn poll(&mut self) -> Option<(u64, Option<usize>)> {
+        let latest = self.latest.load(AtomicOrdering::Relaxed);
+        let mut found = None;
+        while let Ok((id, seed, par)) = self.results.try_recv() {
+            if id == latest {
+                found = Some((seed, par));
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! Anonymous usage statistics, off unless the player types `telemetry on`.
+//!
+//! Only counters are kept: games finished, games won, and how often each
+//! kind of command was used.  No seeds, boards, names or times.  They live
+//! in `telemetry.json` in the data directory, `telemetry status` shows
+//! exactly what would be sent, and nothing leaves the machine until
+//! `telemetry send` posts it to `telemetry_url`.  `telemetry off` deletes
+//! the file.
+//!
+//! Counting a command only touches memory; the file is written when a game
+//! finishes and when szsol exits.
+use std::fs;
+use std::path::PathBuf;
+
+use crate::command::Command;
+use crate::http::{https_base, request};
+use crate::json::Json;
+use crate::profile;
+
+#[derive(Debug, Default)]
+pub struct Telemetry {
+    enabled: bool,
+    games_played: u64,
+    games_won: u64,
+    /// Uses of each command kind, by name, in name order.
+    features: Vec<(String, u64)>,
+    /// Where the counters are kept; `None` keeps nothing.
+    path: Option<PathBuf>,
+    /// Whether there are counts the file doesn't have yet.
+    unsaved: bool,
+}
+
+impl Telemetry {
+    /// The counters kept in the data directory.
+    pub fn load() -> Self {
+        let path = profile::data_dir().map(|dir| dir.join("telemetry.json"));
+        let mut telemetry = Self::default();
+        telemetry.path = path.clone();
+        let Some(doc) = path.and_then(|p| fs::read_to_string(p).ok()).and_then(|t| Json::parse(&t).ok()) else {
+            return telemetry;
+        };
+        let count = |key: &str| doc.get(key).and_then(Json::as_int).and_then(|n| u64::try_from(n).ok());
+        telemetry.enabled = doc.get("enabled").and_then(Json::as_bool).unwrap_or(false);
+        telemetry.games_played = count("games_played").unwrap_or(0);
+        telemetry.games_won = count("games_won").unwrap_or(0);
+        if let Some(Json::Object(features)) = doc.get("features") {
+            for (name, n) in features {
+                if let Some(n) = n.as_int().and_then(|n| u64::try_from(n).ok()) {
+                    telemetry.features.push((name.clone(), n));
+                }
+            }
+            telemetry.features.sort();
+        }
+        telemetry
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Start counting, or stop and delete everything counted so far.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.games_played = 0;
+        self.games_won = 0;
+        self.features.clear();
+        self.unsaved = false;
+        if enabled {
+            self.save();
+        } else if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Count a finished game.
+    pub fn record_game(&mut self, won: bool) {
+        if !self.enabled {
+            return;
+        }
+        self.games_played += 1;
+        self.games_won += u64::from(won);
+        self.save();
+    }
+
+    /// Count a use of `cmd`'s kind.  Only the kind is kept, never its
+    /// arguments.
+    pub fn record_command(&mut self, cmd: &Command) {
+        if !self.enabled {
+            return;
+        }
+        let name = feature_name(cmd);
+        match self.features.binary_search_by(|(n, _)| n.as_str().cmp(&name)) {
+            Ok(i) => self.features[i].1 += 1,
+            Err(i) => self.features.insert(i, (name, 1)),
+        }
+        self.unsaved = true;
+    }
+
+    /// Exactly what `send` uploads.
+    pub fn report(&self) -> Json {
+        let win_rate = (self.games_won * 100).checked_div(self.games_played).unwrap_or(0);
+        Json::Object(vec![
+            ("version".into(), env!("CARGO_PKG_VERSION").into()),
+            ("games_played".into(), self.games_played.into()),
+            ("games_won".into(), self.games_won.into()),
+            ("win_rate_percent".into(), win_rate.into()),
+            ("features".into(), Json::Object(self.features.iter().map(|(n, c)| (n.clone(), (*c).into())).collect())),
+        ])
+    }
+
+    /// Post the report to `url` and start counting afresh; returns the
+    /// server's reply.
+    pub fn send(&mut self, url: &str) -> Result<String, String> {
+        let reply = request(https_base(url, "telemetry_url")?, Some(&self.report().pretty()))?;
+        self.set_enabled(true);
+        Ok(reply.trim().to_string())
+    }
+
+    fn save(&mut self) {
+        self.unsaved = false;
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let mut doc = self.report();
+        if let Json::Object(members) = &mut doc {
+            members.insert(0, ("enabled".into(), self.enabled.into()));
+        }
+        let _ = fs::write(path, doc.pretty());
+    }
+}
+
+impl Drop for Telemetry {
+    /// Write the commands counted since the last finished game.
+    fn drop(&mut self) {
+        if self.unsaved {
+            self.save();
+        }
+    }
+}
+
+/// The command's kind, e.g. `Undo` for `undo 3`: the variant name, without
+/// the fields that carry what was typed.
+fn feature_name(cmd: &Command) -> String {
+    let debug = format!("{:?}", cmd);
+    debug.split([' ', '{', '(']).next().unwrap_or_default().to_string()
+}
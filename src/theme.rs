@@ -0,0 +1,214 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! Color themes for the CLI renderer.
+use crate::card::{Card, Suit};
+
+/// How box-drawing frames (header, help) are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameStyle {
+    /// Frames as written: single lines for the header, double for help.
+    Native,
+    /// Single lines everywhere.
+    Light,
+    /// Plain `+`, `-` and `|` for terminals without box-drawing glyphs.
+    Ascii,
+}
+
+impl FrameStyle {
+    /// Redraw the frame characters of `text` in this style.  Each glyph maps
+    /// to exactly one character, so column alignment is preserved.
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            FrameStyle::Native => text.to_string(),
+            FrameStyle::Light => text
+                .chars()
+                .map(|c| match c {
+                    '║' => '│',
+                    '═' => '─',
+                    '╔' => '┌',
+                    '╗' => '┐',
+                    '╚' => '└',
+                    '╝' => '┘',
+                    '╠' => '├',
+                    '╣' => '┤',
+                    c => c,
+                })
+                .collect(),
+            FrameStyle::Ascii => text
+                .chars()
+                .map(|c| match c {
+                    '║' | '│' => '|',
+                    '═' | '─' => '-',
+                    '╔' | '╗' | '╚' | '╝' | '╠' | '╣' | '┌' | '┐' | '└' | '┘' | '├' | '┤' => '+',
+                    c => c,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// ANSI SGR parameters (the part between `ESC[` and `m`) for each role.
+/// An empty string leaves the text unstyled.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub name: &'static str,
+    pub red: &'static str,
+    pub green: &'static str,
+    pub black: &'static str,
+    pub yellow: &'static str,
+    pub flower: &'static str,
+    pub info: &'static str,
+    pub error: &'static str,
+    /// Win banner and other celebratory text.
+    pub highlight: &'static str,
+    pub frame: FrameStyle,
+    /// Bracket each suit differently (`<R5>`, `(G5)`, `[B5]`, `{Y5}`) so suits can be
+    /// told apart without color.
+    pub suit_brackets: bool,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        name: "default",
+        red: "31",
+        green: "32",
+        black: "90",
+        yellow: "33",
+        flower: "35",
+        info: "36",
+        error: "31",
+        highlight: "33",
+        frame: FrameStyle::Native,
+        suit_brackets: false,
+    };
+
+    /// Solarized accent colors (256-color approximations).
+    pub const SOLARIZED: Theme = Theme {
+        name: "solarized",
+        red: "38;5;160",
+        green: "38;5;64",
+        black: "38;5;240",
+        yellow: "38;5;136",
+        flower: "38;5;125",
+        info: "38;5;37",
+        error: "38;5;166",
+        highlight: "38;5;136",
+        frame: FrameStyle::Light,
+        suit_brackets: false,
+    };
+
+    /// No hues at all: suits differ by weight only.
+    pub const MONOCHROME: Theme = Theme {
+        name: "monochrome",
+        red: "1",
+        green: "",
+        black: "2",
+        yellow: "4",
+        flower: "1",
+        info: "1",
+        error: "1",
+        highlight: "1",
+        frame: FrameStyle::Ascii,
+        suit_brackets: true,
+    };
+
+    /// Red/green deficiencies (deuteranopia, protanopia): orange and blue
+    /// from the Okabe–Ito palette instead of red and green.
+    pub const DEUTERANOPIA: Theme = Theme {
+        name: "deuteranopia",
+        red: "38;5;208",
+        green: "38;5;33",
+        black: "38;5;250",
+        yellow: "38;5;175",
+        flower: "38;5;220",
+        info: "38;5;33",
+        error: "38;5;208",
+        highlight: "38;5;220",
+        frame: FrameStyle::Native,
+        suit_brackets: true,
+    };
+
+    /// Blue/yellow deficiency (tritanopia): red and teal, no blue/yellow pairs.
+    pub const TRITANOPIA: Theme = Theme {
+        name: "tritanopia",
+        red: "38;5;160",
+        green: "38;5;30",
+        black: "38;5;250",
+        yellow: "38;5;136",
+        flower: "38;5;211",
+        info: "38;5;30",
+        error: "38;5;160",
+        highlight: "38;5;211",
+        frame: FrameStyle::Native,
+        suit_brackets: true,
+    };
+
+    pub const ALL: [Theme; 5] = [
+        Theme::DEFAULT,
+        Theme::SOLARIZED,
+        Theme::MONOCHROME,
+        Theme::DEUTERANOPIA,
+        Theme::TRITANOPIA,
+    ];
+
+    pub fn by_name(name: &str) -> Option<Theme> {
+        Self::ALL.into_iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn names() -> String {
+        Self::ALL.map(|t| t.name).join(", ")
+    }
+
+    pub fn suit(&self, suit: Suit) -> &'static str {
+        match suit {
+            Suit::Red => self.red,
+            Suit::Green => self.green,
+            Suit::Black => self.black,
+            Suit::Yellow => self.yellow,
+        }
+    }
+
+    /// Opening and closing brackets drawn around a card of `suit`
+    /// (`None` for the flower and empty slots).
+    pub fn brackets(&self, suit: Option<Suit>) -> (char, char) {
+        match suit {
+            Some(Suit::Red) if self.suit_brackets => ('<', '>'),
+            Some(Suit::Green) if self.suit_brackets => ('(', ')'),
+            Some(Suit::Yellow) if self.suit_brackets => ('{', '}'),
+            _ => ('[', ']'),
+        }
+    }
+
+    pub fn card(&self, card: Card) -> &'static str {
+        match card.suit() {
+            Some(suit) => self.suit(suit),
+            None => self.flower,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DEFAULT
+    }
+}
@@ -21,36 +21,41 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 use std::collections::{HashMap, VecDeque};
-use std::io::Stdout;
+use std::io::{Stdout, Write};
 use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableMouseCapture, EnableMouseCapture, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     symbols,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    widgets::{BarChart, Block, Borders, Clear, Gauge, Paragraph, Sparkline, Tabs},
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::board::{Board, FreeCellState, Location, NUM_COLUMNS, NUM_FREE_CELLS};
+use crate::board::{Board, FreeCellState, Location, Rules, MAX_COLUMNS, MAX_FREE_CELLS};
+use crate::campaign::{row_bands, CampaignMap, StageState, MAP_COLUMNS};
 use crate::card::{Card, Suit};
+use crate::dashboard::{Dashboard, StatsPage, WIN_RATE_WINDOW};
 use crate::event::GameEvent;
-use crate::renderer::Renderer;
+use crate::i18n::tr;
+use crate::keymap::{key_name, KeyMap};
+use crate::renderer::{format_countdown, format_elapsed, HeaderStatus, Renderer, WinSummary, LOW_MOBILITY};
 use crate::solver::{SolverMove, SolverProgress};
 
 // ---------------------------------------------------------------------------
 // Key bindings
 // ---------------------------------------------------------------------------
 
-pub const COL_KEYS: [char; 8] = ['q', 'w', 'e', 'r', 't', 'y', 'u', 'i'];
-pub const FC_KEYS: [char; 3] = ['1', '2', '3'];
+pub const COL_KEYS: [char; MAX_COLUMNS] = ['q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p'];
+pub const FC_KEYS: [char; MAX_FREE_CELLS] = ['1', '2', '3', '4', '5', '6'];
 
 #[allow(dead_code)]
 pub fn key_to_location(c: char) -> Option<Location> {
@@ -106,9 +111,10 @@ impl CardSpec {
     pub fn suit_str(self, suit: Suit) -> &'static str {
         let _ = self;
         match suit {
-            Suit::Red   => "♦",
-            Suit::Green => "♣",
-            Suit::Black => "♠",
+            Suit::Red    => "♦",
+            Suit::Green  => "♣",
+            Suit::Black  => "♠",
+            Suit::Yellow => "♥",
         }
     }
 
@@ -253,9 +259,10 @@ impl BoardLayout {
 
 fn suit_color(suit: Suit) -> Color {
     match suit {
-        Suit::Red   => Color::Red,
-        Suit::Green => Color::Green,
-        Suit::Black => Color::Gray,
+        Suit::Red    => Color::Red,
+        Suit::Green  => Color::Green,
+        Suit::Black  => Color::Gray,
+        Suit::Yellow => Color::Yellow,
     }
 }
 
@@ -532,12 +539,23 @@ pub struct TuiRenderer {
     status_log:  VecDeque<(LogLevel, String)>,
     header_wins: usize,
     header_seed: u64,
+    header_phrase: Option<String>,
+    header_moves: u32,
+    header_par: Option<usize>,
+    header_elapsed: Duration,
+    header_status: HeaderStatus,
     show_help:   bool,
     solving:     bool,
     solving_message: String,
     solving_progress: u16,
     solving_frame: usize,
     solver_failed_until: Option<Instant>,
+    /// Post-win summary lines, shown until the next key or click.
+    summary:     Option<Vec<String>>,
+    /// The statistics screen and the page it is on, while open.
+    stats:       Option<(Dashboard, StatsPage)>,
+    /// The campaign map and the stage picked on it, while open.
+    campaign:    Option<(CampaignMap, usize)>,
     spec:        CardSpec,
     pub hint:    HintState,
     // Animation state
@@ -546,6 +564,14 @@ pub struct TuiRenderer {
     anim_board:  Option<Board>,
     pub anim_speed: AnimSpeed,
     pub anim_style: AnimStyle,
+    /// h/j/k/l, Space, `u` and `:` bindings are on (`vim_keys`).
+    vim_keys:    bool,
+    /// The pile the vim cursor is on, once it has been moved.
+    cursor:      Option<Location>,
+    /// Text typed after `:`, while the command line is open.
+    command_line: Option<String>,
+    /// Key bindings, for the labels and hints.
+    keys:        KeyMap,
 }
 
 impl TuiRenderer {
@@ -564,12 +590,20 @@ impl TuiRenderer {
             status_log: VecDeque::with_capacity(Self::LOG_CAP),
             header_wins: 0,
             header_seed: 0,
+            header_phrase: None,
+            header_moves: 0,
+            header_par: None,
+            header_elapsed: Duration::ZERO,
+            header_status: HeaderStatus::default(),
             show_help: false,
             solving: false,
             solving_message: "少女祈祷中".to_string(),
             solving_progress: 0,
             solving_frame: 0,
             solver_failed_until: None,
+            summary: None,
+            stats: None,
+            campaign: None,
             spec,
             hint: HintState::Inactive,
             anim_queue: VecDeque::new(),
@@ -577,6 +611,10 @@ impl TuiRenderer {
             anim_board: None,
             anim_speed: AnimSpeed::Normal,
             anim_style: AnimStyle::EaseOutQuad,
+            vim_keys: false,
+            cursor: None,
+            command_line: None,
+            keys: KeyMap::default(),
         })
     }
 
@@ -644,7 +682,7 @@ impl TuiRenderer {
         if hide_all {
             board.columns.iter_mut().for_each(|c| c.clear());
             board.free_cells.iter_mut().for_each(|f| *f = FreeCellState::Empty);
-            board.foundations = [0; 3];
+            board.foundations.iter_mut().for_each(|f| *f = 0);
             board.flower_placed = false;
         }
 
@@ -673,9 +711,17 @@ impl TuiRenderer {
 
         let wins      = self.header_wins;
         let seed      = self.header_seed;
+        let phrase    = self.header_phrase.clone();
+        let moves     = self.header_moves;
+        let par       = self.header_par;
+        let elapsed   = self.header_elapsed;
+        let status    = self.header_status.clone();
         let log: Vec<_> = self.status_log.iter().cloned().collect();
         let sel       = self.selection.clone();
         let show_help = self.show_help;
+        let summary   = self.summary.clone();
+        let stats     = self.stats.clone();
+        let campaign  = self.campaign.clone();
         let spec      = self.spec;
         let hint_active = self.hint.is_active();
         let hint_src  = self.hint_locs().map(|(s, _)| s);
@@ -701,6 +747,10 @@ impl TuiRenderer {
             .map(|until| Instant::now() < until)
             .unwrap_or(false);
         let speed = self.anim_speed;
+        let vim_keys = self.vim_keys;
+        let cursor = self.cursor;
+        let command_line = self.command_line.clone();
+        let keys = self.keys.clone();
 
         let mut new_layout = BoardLayout::default();
 
@@ -715,23 +765,27 @@ impl TuiRenderer {
                 return;
             }
             let top_row_h = spec.card_h() + 1; // cards + key-label row
+            let header_h = if status.ghost.is_some() { 2 } else { 1 };
 
             let root = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(1),            // header bar
+                    Constraint::Length(header_h),     // header bar (and ghost)
                     Constraint::Length(top_row_h),    // free cells + flower + foundations
                     Constraint::Min(spec.card_h() + 2), // tableau (at least one full card)
                     Constraint::Length(3),            // status bar
                 ])
                 .split(area);
 
-            render_header_bar(frame, root[0], wins, seed);
-            render_top_row(frame, root[1], &board, &sel, hint_src, hint_merge_suit, &mut new_layout, spec);
-            render_tableau(frame, root[2], &board, &sel, hint_src, hint_col_depth, hint_merge_suit, &mut new_layout, spec);
-            render_statusbar(frame, root[3], &log, &sel, hint_active, speed);
+            render_header_bar(frame, root[0], wins, seed, phrase.as_deref(), moves, par, elapsed, &status);
+            render_top_row(frame, root[1], &board, &sel, hint_src, hint_merge_suit, &mut new_layout, spec, &keys);
+            render_tableau(frame, root[2], &board, &sel, hint_src, hint_col_depth, hint_merge_suit, &mut new_layout, spec, &keys);
+            render_statusbar(frame, root[3], &log, &sel, board.rules, hint_active, speed, vim_keys, command_line.as_deref(), &keys);
+            if let Some(loc) = cursor {
+                render_cursor(frame, area, &new_layout, loc);
+            }
 
-            if show_help { render_help_overlay(frame, area); }
+            if show_help { render_help_overlay(frame, area, &keys); }
             if solving   { render_solving_overlay(frame, area, &solving_message, solving_progress, solving_frame); }
             if solver_failed { render_solver_failed_overlay(frame, area); }
 
@@ -741,7 +795,16 @@ impl TuiRenderer {
 
             // Draw Animation Overlay Custom
             if let Some(anim) = &current_anim_clone {
-                render_animation_overlay(frame, root[1], root[2], anim, &board_to_draw, spec, anim_style_clone);
+                render_animation_overlay(frame, root[1], root[2], anim, board_to_draw, spec, anim_style_clone);
+            } else if let Some(lines) = &summary {
+                // Shown once the final cards have flown home.
+                render_summary_overlay(frame, area, lines);
+            }
+            if let Some((dashboard, page)) = &stats {
+                render_stats_overlay(frame, area, dashboard, *page, &keys);
+            }
+            if let Some((map, selected)) = &campaign {
+                render_campaign_overlay(frame, area, map, *selected);
             }
         });
 
@@ -756,17 +819,17 @@ impl TuiRenderer {
 fn render_too_small(frame: &mut Frame, area: Rect, wins: usize, seed: u64) {
     let lines = vec![
         Line::from(Span::styled(
-            " Screen too small ",
+            format!(" {} ", tr!("Screen too small")),
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(format!(" Current size: {}x{}", area.width, area.height)),
-        Line::from(format!(" Required size: at least {}x{}", MIN_TUI_WIDTH, MIN_TUI_HEIGHT)),
+        Line::from(format!(" {}", tr!("Current size: {}x{}", area.width, area.height))),
+        Line::from(format!(" {}", tr!("Required size: at least {}x{}", MIN_TUI_WIDTH, MIN_TUI_HEIGHT))),
         Line::from(""),
-        Line::from(" Resize the terminal to continue."),
-        Line::from(" The game is still running and will redraw automatically."),
+        Line::from(format!(" {}", tr!("Resize the terminal to continue."))),
+        Line::from(format!(" {}", tr!("The game is still running and will redraw automatically."))),
         Line::from(""),
-        Line::from(format!(" Seed: {}   Wins: {}", seed, wins)),
+        Line::from(format!(" {}", tr!("Seed: {}   Wins: {}", seed, wins))),
     ];
 
     frame.render_widget(Clear, area);
@@ -781,7 +844,18 @@ fn render_too_small(frame: &mut Frame, area: Rect, wins: usize, seed: u64) {
     );
 }
 
-fn render_header_bar(frame: &mut Frame, area: Rect, wins: usize, seed: u64) {
+#[allow(clippy::too_many_arguments)]
+fn render_header_bar(
+    frame: &mut Frame,
+    area: Rect,
+    wins: usize,
+    seed: u64,
+    phrase: Option<&str>,
+    moves: u32,
+    par: Option<usize>,
+    elapsed: Duration,
+    status: &HeaderStatus,
+) {
     let rank = match wins {
         0       => "来面试的",
         1..=9   => "带薪如厕生",
@@ -790,17 +864,59 @@ fn render_header_bar(frame: &mut Frame, area: Rect, wins: usize, seed: u64) {
         50..=99 => "需求粉碎机",
         _       => "摸鱼仙人",
     };
-    let text = format!(
-        " SHENZHEN I/O  │  Seed: {:<20}  │  Wins: {:>4}  │  {}",
-        seed, wins, rank
+    let seed_text = match phrase {
+        Some(p) => format!("\"{}\" ({})", p, seed),
+        None => seed.to_string(),
+    };
+    let moves_text = match par {
+        Some(par) => format!("{:>3}/{}", moves, par),
+        None => format!("{:>3}", moves),
+    };
+    let mut text = format!(
+        " SHENZHEN I/O  │  Seed: {:<20}  │  Wins: {:>4}  │  Moves: {}  {:>7}  │  {}",
+        seed_text, wins, moves_text, format_elapsed(elapsed), rank
     );
+    if let Some(outlook) = status.outlook {
+        text.push_str(&format!("  │  {}", outlook.mark()));
+    }
+    let mut spans = vec![Span::raw(text)];
+    if let Some(count) = status.mobility {
+        let style = if count < LOW_MOBILITY { Style::default().fg(Color::Red) } else { Style::default() };
+        spans.push(Span::raw("  │  Legal: "));
+        spans.push(Span::styled(count.to_string(), style));
+    }
+    if let Some(left) = status.weekly {
+        spans.push(Span::raw(format!("  │  Weekly: {}", format_countdown(left))));
+    }
+    let mut lines = vec![Line::from(spans)];
+    // The ghost gets a line of its own under the header.
+    if let Some(ghost) = &status.ghost {
+        let lead_style = match ghost.lead {
+            0 => Style::default(),
+            n if n > 0 => Style::default().fg(Color::LightGreen),
+            _ => Style::default().fg(Color::LightRed),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    " GHOST         │  now: {}  │  at move {}: {}  │  you ",
+                    crate::ghost::describe(ghost.suits, &ghost.at_time),
+                    moves,
+                    crate::ghost::describe(ghost.suits, &ghost.at_moves)
+                ),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::styled(crate::ghost::lead(ghost.lead), lead_style),
+        ]));
+    }
     frame.render_widget(
-        Paragraph::new(text)
+        Paragraph::new(lines)
             .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         area,
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_top_row(
     frame: &mut Frame,
     area: Rect,
@@ -810,13 +926,14 @@ fn render_top_row(
     hint_merge_suit: Option<Suit>,
     layout: &mut BoardLayout,
     spec: CardSpec,
+    keys: &KeyMap,
 ) {
     let cw = spec.card_w();
     let ch = spec.card_h();
 
     // Horizontal split: free cells | gap | flower | gap | foundations
-    let fc_block_w  = NUM_FREE_CELLS as u16 * (cw + 1) + 1;
-    let found_w     = Suit::ALL.len() as u16 * (cw + 1) + 1;
+    let fc_block_w  = board.free_cells.len() as u16 * (cw + 1) + 1;
+    let found_w     = board.rules.suits as u16 * (cw + 1) + 1;
     let cols = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -838,7 +955,7 @@ fn render_top_row(
             || matches!((hint_merge_suit, fc), (Some(s), FreeCellState::Card(c)) if *c == Card::Dragon(s));
         let lines: Vec<Line> = match fc {
             FreeCellState::Empty => {
-                let key = FC_KEYS[i].to_string();
+                let key = keys.cells[i].to_string();
                 empty_slot(spec, Some(key.as_str()))
             }
             FreeCellState::Card(c) => {
@@ -875,7 +992,7 @@ fn render_top_row(
         if ky < area.y + area.height {
             let kr = Rect { x: sx + cw / 2, y: ky, width: 1, height: 1 };
             frame.render_widget(
-                Paragraph::new(FC_KEYS[i].to_string())
+                Paragraph::new(keys.cells[i].to_string())
                     .style(Style::default().fg(Color::DarkGray)),
                 kr,
             );
@@ -885,7 +1002,9 @@ fn render_top_row(
     // ── Flower ───────────────────────────────────────────────────────────────
     let fx = cols[2].x + 1;
     let fr = Rect { x: fx, y: area.y, width: cw, height: ch };
-    let flower_lines: Vec<Line> = if board.flower_placed {
+    let flower_lines: Vec<Line> = if !board.rules.flower {
+        Vec::new()
+    } else if board.flower_placed {
         card_lines(Card::Flower, false, false, spec)
     } else {
         empty_slot(spec, Some(spec.flower_str()))
@@ -893,7 +1012,7 @@ fn render_top_row(
     frame.render_widget(Paragraph::new(flower_lines), fr);
 
     // ── Foundations ──────────────────────────────────────────────────────────
-    for (i, &suit) in Suit::ALL.iter().enumerate() {
+    for (i, &suit) in board.rules.suits().iter().enumerate() {
         let sx = cols[4].x + 1 + i as u16 * (cw + 1);
         let sr = Rect { x: sx, y: area.y, width: cw, height: ch };
         let v  = board.foundations[i];
@@ -922,6 +1041,7 @@ fn render_top_row(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_tableau(
     frame: &mut Frame,
     area: Rect,
@@ -932,20 +1052,24 @@ fn render_tableau(
     hint_merge_suit: Option<Suit>,
     layout: &mut BoardLayout,
     spec: CardSpec,
+    keys: &KeyMap,
 ) {
     let cw  = spec.card_w();
     let ch  = spec.card_h();
     let col_step = cw + 2; // 1 gap each side
 
-    // Key labels row
-    for (i, &k) in COL_KEYS.iter().enumerate() {
-        let kx = area.x + i as u16 * col_step + cw / 2;
-        let kr = Rect { x: kx, y: area.y, width: 1, height: 1 };
+    // Key labels row, with each column's depth and a ✓ over ordered columns
+    for (i, &k) in keys.columns[..board.columns.len()].iter().enumerate() {
+        let mark = if board.is_ordered(i) { "✓" } else { "" };
+        let label = format!("{}({}){}", k, board.columns[i].len(), mark);
+        let kx = area.x + i as u16 * col_step;
+        let kr = Rect { x: kx, y: area.y, width: cw, height: 1 };
         if kr.x < area.x + area.width {
             frame.render_widget(
-                Paragraph::new(k.to_string())
+                Paragraph::new(label)
+                    .alignment(Alignment::Center)
                     .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM)),
-                kr,
+                kr.intersection(area),
             );
         }
     }
@@ -953,7 +1077,7 @@ fn render_tableau(
     let cards_y = area.y + 1;
     let bottom  = area.y + area.height;
 
-    for col_idx in 0..NUM_COLUMNS {
+    for col_idx in 0..board.columns.len() {
         let col_x = area.x + col_idx as u16 * col_step;
         let col   = &board.columns[col_idx];
 
@@ -1207,13 +1331,18 @@ fn render_hint_arrow(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_statusbar(
     frame: &mut Frame,
     area: Rect,
     log: &[(LogLevel, String)],
     sel: &SelectionState,
+    rules: Rules,
     hint_active: bool,
     anim_speed: AnimSpeed,
+    vim_keys: bool,
+    command_line: Option<&str>,
+    keys: &KeyMap,
 ) {
     let speed_label = match anim_speed {
         AnimSpeed::Off => "Off",
@@ -1221,32 +1350,51 @@ fn render_statusbar(
         AnimSpeed::Normal => "Norm",
         AnimSpeed::Slow => "Slow",
     };
+    let big = |key| key_name(key).to_uppercase();
+    let cancel = key_name(keys.cancel);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(1), Constraint::Min(1)])
         .split(area);
 
     let hint = match sel {
+        _ if command_line.is_some() =>
+            Span::styled(
+                format!(":{}█", command_line.unwrap_or_default()),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
         SelectionState::Idle if hint_active =>
             Span::styled(
-                " HINT  |  Green = next card to move  |  H = exit  |  wrong move auto-exits",
+                tr!(" HINT  |  Green = next card to move  |  H = exit  |  wrong move auto-exits"),
                 Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD)),
+        SelectionState::Idle if vim_keys =>
+            Span::styled(
+                tr!(" {} {} {} {}=move  {}=pick up/drop  |  {}=command  |  {}=drgn {}=hint {}=spd({}) {}=undo {}=new Ctrl-C=exit",
+                    key_name(keys.left), key_name(keys.down), key_name(keys.up), key_name(keys.right), key_name(keys.pick),
+                    key_name(keys.command), big(keys.dragon), big(keys.hint), big(keys.speed), speed_label,
+                    key_name(keys.vim_undo), big(keys.new_game)),
+                Style::default().fg(Color::DarkGray)),
         SelectionState::Idle =>
             Span::styled(
-                format!(" cols: q w e r t y u i  |  cells: 1 2 3  |  D=drgn H=hint S=spd({}) Z=undo N=new Ctrl-C=exit", speed_label),
+                tr!(" cols: {}  |  cells: {}  |  {}=drgn {}=hint {}=spd({}) {}=undo {}=new Ctrl-C=exit",
+                    spaced(&keys.columns[..rules.columns]), spaced(&keys.cells[..rules.free_cells]),
+                    big(keys.dragon), big(keys.hint), big(keys.speed), speed_label, big(keys.undo), big(keys.new_game)),
                 Style::default().fg(Color::DarkGray)),
         SelectionState::Column { col, depth } =>
             Span::styled(
-                format!(" Selected col {} ×{}  |  same key → grow stack  |  dest key → move  |  Esc=cancel",
-                    COL_KEYS[*col], depth),
+                tr!(" Selected col {} ×{}  |  same key → grow stack  |  dest key → move  |  {}=cancel",
+                    keys.columns[*col], depth, cancel),
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
         SelectionState::FreeCell { idx } =>
             Span::styled(
-                format!(" Selected cell {}  |  col key → move  |  Esc=cancel", idx + 1),
+                tr!(" Selected cell {}  |  col key → move  |  {}=cancel", keys.cells[*idx], cancel),
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
         SelectionState::WaitDragonSuit =>
             Span::styled(
-                " Dragon merge: press r / g / b for suit  |  Esc=cancel",
+                if rules.suits > 3 {
+                    tr!(" Dragon merge: press r / g / b / y for suit  |  {}=cancel", cancel)
+                } else {
+                    tr!(" Dragon merge: press r / g / b for suit  |  {}=cancel", cancel)
+                },
                 Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
     };
     frame.render_widget(Paragraph::new(Line::from(hint)), chunks[0]);
@@ -1271,6 +1419,34 @@ fn render_statusbar(
     );
 }
 
+/// Point at the pile under the vim cursor with a marker just right of it,
+/// level with its top card.
+fn render_cursor(frame: &mut Frame, area: Rect, layout: &BoardLayout, loc: Location) {
+    let Some(rect) = layout.slots.get(&loc) else {
+        return;
+    };
+    let x = rect.x + rect.width;
+    let y = rect.y + rect.height.saturating_sub(3);
+    if x < area.x + area.width && y < area.y + area.height {
+        frame.render_widget(
+            Paragraph::new("◀").style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Rect { x, y, width: 1, height: 1 },
+        );
+    }
+}
+
+/// A key as on the keycap: `Enter`, `Esc`, `Z`.
+fn label(key: KeyCode) -> String {
+    let name = key_name(key);
+    let mut chars = name.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or(name)
+}
+
+/// Keys separated by spaces, as in `q w e r`.
+fn spaced(keys: &[char]) -> String {
+    keys.iter().map(char::to_string).collect::<Vec<_>>().join(" ")
+}
+
 fn render_solving_overlay(frame: &mut Frame, area: Rect, _message: &str, progress: u16, frame_idx: usize) {
     const FRAMES: [&str; 8] = [
         "(>_<)  .",
@@ -1353,7 +1529,7 @@ fn render_solver_failed_overlay(frame: &mut Frame, area: Rect) {
         Paragraph::new(vec![
             Line::from(""),
             Line::from(Span::styled(
-                "  No solution found  ",
+                format!("  {}  ", tr!("No solution found")),
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             )),
         ]),
@@ -1361,48 +1537,256 @@ fn render_solver_failed_overlay(frame: &mut Frame, area: Rect) {
     );
 }
 
-fn render_help_overlay(frame: &mut Frame, area: Rect) {
+fn render_summary_overlay(frame: &mut Frame, area: Rect, lines: &[String]) {
+    let widest = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16;
+    let w = (widest + 6).max(30).min(area.width);
+    let h = (lines.len() as u16 + 5).min(area.height);
+    let popup = Rect {
+        x: area.width.saturating_sub(w) / 2,
+        y: area.height.saturating_sub(h) / 2,
+        width: w,
+        height: h,
+    };
+
+    frame.render_widget(Clear, popup);
+    let title = format!(" {} ", tr!("GAME SUMMARY"));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(symbols::border::ROUNDED)
+        .border_style(Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD))
+        .title(Span::styled(title, Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD)));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let mut text = vec![Line::from("")];
+    text.extend(lines.iter().map(|l| Line::from(format!("  {}", l))));
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        format!("  {}", tr!("Press any key to continue.")),
+        Style::default().fg(Color::DarkGray),
+    )));
+    frame.render_widget(Paragraph::new(text), inner);
+}
+
+fn render_stats_overlay(frame: &mut Frame, area: Rect, dashboard: &Dashboard, page: StatsPage, keys: &KeyMap) {
+    let w = 66u16.min(area.width);
+    let h = 17u16.min(area.height);
+    let popup = Rect {
+        x: area.width.saturating_sub(w) / 2,
+        y: area.height.saturating_sub(h) / 2,
+        width: w, height: h,
+    };
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(symbols::border::ROUNDED)
+        .title(format!(" {} ", tr!("Statistics")))
+        .style(Style::default().fg(Color::White));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // tabs
+            Constraint::Length(2), // caption
+            Constraint::Min(3),    // chart
+            Constraint::Length(1), // key hint
+        ])
+        .split(inner);
+
+    let titles = [tr!("Win rate"), tr!("Solve times"), tr!("Weekdays"), tr!("Rating")];
+    let selected = StatsPage::ALL.iter().position(|&p| p == page).unwrap_or(0);
+    frame.render_widget(
+        Tabs::new(titles).select(selected).highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        rows[0],
+    );
+
+    let caption = match page {
+        StatsPage::Rating => match (dashboard.ratings.iter().min(), dashboard.ratings.iter().max()) {
+            (Some(low), Some(high)) => tr!(
+                "Your rating after each of the latest {} rated games, between {} and {}:",
+                dashboard.ratings.len(), low, high
+            ),
+            _ => tr!("No rated games yet. Finish a dealt game to get a rating."),
+        },
+        _ if dashboard.games == 0 => tr!("No finished games yet."),
+        StatsPage::WinRate => tr!(
+            "{} of {} games won. Win rate over the last {} games, after each of the latest {}:",
+            dashboard.wins, dashboard.games, WIN_RATE_WINDOW, dashboard.win_rate.len()
+        ),
+        StatsPage::SolveTimes => tr!("Wins by play time ({} wins):", dashboard.wins),
+        StatsPage::Weekdays => tr!("Games by the weekday they were started (UTC):"),
+    };
+    let indent = |r: Rect| Rect { x: r.x + 1, width: r.width.saturating_sub(2), ..r };
+    frame.render_widget(Paragraph::new(caption).wrap(ratatui::widgets::Wrap { trim: true }), indent(rows[1]));
+
+    let chart = indent(rows[2]);
+    if page == StatsPage::Rating {
+        if let Some(&low) = dashboard.ratings.iter().min() {
+            // Draw the line from just under its lowest point, so small
+            // changes show.
+            let base = low.saturating_sub(10);
+            let data: Vec<u64> = dashboard.ratings.iter().map(|r| r - base).collect();
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(2), Constraint::Length(1)])
+                .split(chart);
+            frame.render_widget(Sparkline::default().data(&data).style(Style::default().fg(Color::LightYellow)), parts[0]);
+            frame.render_widget(Paragraph::new(tr!("Now {}", dashboard.rating)), parts[1]);
+        }
+    } else if dashboard.games > 0 {
+        match page {
+            StatsPage::WinRate => {
+                let latest = dashboard.win_rate.last().copied().unwrap_or(0);
+                let parts = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(2), Constraint::Length(1)])
+                    .split(chart);
+                frame.render_widget(
+                    Sparkline::default().data(&dashboard.win_rate).max(100).style(Style::default().fg(Color::LightGreen)),
+                    parts[0],
+                );
+                frame.render_widget(Paragraph::new(tr!("Now {}%", latest)), parts[1]);
+            }
+            StatsPage::SolveTimes => {
+                let bars: Vec<(&str, u64)> = dashboard.solve_times.iter().map(|(label, n)| (label.as_str(), *n)).collect();
+                frame.render_widget(
+                    BarChart::default().data(bars.as_slice()).bar_width(6).bar_gap(2)
+                        .bar_style(Style::default().fg(Color::LightCyan)),
+                    chart,
+                );
+            }
+            StatsPage::Weekdays => {
+                let names = [tr!("Mon"), tr!("Tue"), tr!("Wed"), tr!("Thu"), tr!("Fri"), tr!("Sat"), tr!("Sun")];
+                let bars: Vec<(&str, u64)> = names.iter().map(String::as_str).zip(dashboard.weekdays).collect();
+                frame.render_widget(
+                    BarChart::default().data(bars.as_slice()).bar_width(6).bar_gap(2)
+                        .bar_style(Style::default().fg(Color::LightMagenta)),
+                    chart,
+                );
+            }
+            StatsPage::Rating => {}
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!(" {}", tr!("{}: next page, Shift-Tab: previous, any other key closes", label(keys.stats))),
+            Style::default().fg(Color::DarkGray),
+        )),
+        rows[3],
+    );
+}
+
+fn render_campaign_overlay(frame: &mut Frame, area: Rect, map: &CampaignMap, selected: usize) {
+    let w = 66u16.min(area.width);
+    let h = 15u16.min(area.height);
+    let popup = Rect {
+        x: area.width.saturating_sub(w) / 2,
+        y: area.height.saturating_sub(h) / 2,
+        width: w, height: h,
+    };
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(symbols::border::ROUNDED)
+        .title(format!(" {} ", tr!("Campaign")))
+        .style(Style::default().fg(Color::White));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let mut lines = vec![
+        Line::from(format!(" {}", tr!("Campaign: {} of {} stages cleared.", map.cleared, map.stages.len()))),
+        Line::from(""),
+    ];
+    // One row of stages per line, joined by a path, easiest first.
+    for row in map.stages.chunks(MAP_COLUMNS) {
+        let mut spans = vec![Span::raw(" ")];
+        for (i, stage) in row.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled("──", Style::default().fg(Color::DarkGray)));
+            }
+            let mut style = match stage.state {
+                StageState::Cleared => Style::default().fg(Color::LightGreen),
+                StageState::Open => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                StageState::Locked => Style::default().fg(Color::DarkGray),
+            };
+            if stage.number == selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(format!("[{:>2} {}]", stage.number, stage.mark()), style));
+        }
+        spans.push(Span::styled(format!("  {}", row_bands(row)), Style::default().fg(Color::DarkGray)));
+        lines.push(Line::from(spans));
+        lines.push(Line::from(""));
+    }
+    if let Some(stage) = map.stages.get(selected - 1) {
+        lines.push(Line::from(format!(" {}", stage.summary())));
+        if stage.state == StageState::Locked {
+            lines.push(Line::from(format!(" {}", tr!("Locked: win stage {} to open it.", map.cleared + 1))));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+
+    let hint = Rect { y: inner.y + inner.height.saturating_sub(1), height: 1.min(inner.height), ..inner };
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!(" {}", tr!("Arrows: pick a stage, Enter: play it, any other key closes")),
+            Style::default().fg(Color::DarkGray),
+        )),
+        hint,
+    );
+}
+
+fn render_help_overlay(frame: &mut Frame, area: Rect, keys: &KeyMap) {
     let w = 68u16.min(area.width);
-    let h = 22u16.min(area.height);
+    let h = 25u16.min(area.height);
     let popup = Rect {
         x: area.width.saturating_sub(w) / 2,
         y: area.height.saturating_sub(h) / 2,
         width: w, height: h,
     };
     frame.render_widget(Clear, popup);
+    let row = |key: &str, desc: String| Line::from(format!("  {:<17} {}", key, desc));
     let lines = vec![
-        Line::from(Span::styled(" TUI Help",
+        Line::from(Span::styled(format!(" {}", tr!("TUI Help")),
             Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED))),
         Line::from(""),
-        Line::from("  Keyboard"),
-        Line::from("  q w e r t y u i   select a tableau column"),
-        Line::from("  same key again    grow selection up a valid ordered stack"),
-        Line::from("  1 2 3             select / target a free cell"),
-        Line::from("  destination key   move selected card(s)"),
-        Line::from("  Enter             send selected single card to foundation"),
-        Line::from("  Esc               cancel selection"),
-        Line::from("  D then r / g / b  merge dragons by suit"),
-        Line::from("  Z                 undo"),
-        Line::from("  S                 toggle animation speed"),
-        Line::from("  N                 new game"),
-        Line::from("  H                 run solver hint / exit hint mode"),
-        Line::from("  ?                 toggle this help"),
+        Line::from(format!("  {}", tr!("Keyboard"))),
+        row(&spaced(&keys.columns[..8]), tr!("select a tableau column")),
+        row("same key again", tr!("grow selection up a valid ordered stack")),
+        row(&spaced(&keys.cells[..3]), tr!("select / target a free cell")),
+        row("destination key", tr!("move selected card(s)")),
+        row(&label(keys.foundation), tr!("send selected single card to foundation")),
+        row(&label(keys.cancel), tr!("cancel selection")),
+        row(&format!("{} then r / g / b / y", label(keys.dragon)), tr!("merge dragons by suit")),
+        row(&label(keys.undo), tr!("undo")),
+        row(&label(keys.speed), tr!("toggle animation speed")),
+        row(&label(keys.new_game), tr!("new game")),
+        row(&label(keys.hint), tr!("run solver hint / exit hint mode")),
+        row(&label(keys.help), tr!("toggle this help")),
+        row(&label(keys.stats), tr!("statistics screen / next page")),
+        row(&label(keys.campaign), tr!("campaign map")),
+        row(&format!("{} {} {} {}, {}, {}", key_name(keys.left), key_name(keys.down), key_name(keys.up),
+            key_name(keys.right), label(keys.pick), key_name(keys.command)),
+            tr!("cursor, pick up / drop, command line (vim_keys)")),
         Line::from(""),
-        Line::from("  Mouse"),
-        Line::from("  click column      select from clicked card up to the top"),
-        Line::from("  click free cell   select that card"),
-        Line::from("  click destination move selection there"),
-        Line::from("  click foundation  send selected single card to foundation"),
-        Line::from("  double-click dragon  try merge that dragon suit"),
+        Line::from(format!("  {}", tr!("Mouse"))),
+        row("click column", tr!("select from clicked card up to the top")),
+        row("click free cell", tr!("select that card")),
+        row("click destination", tr!("move selection there")),
+        row("click foundation", tr!("send selected single card to foundation")),
+        row("double-click dragon", tr!("try merge that dragon suit")),
         Line::from(""),
-        Line::from("  Ctrl-C            quit"),
+        row("Ctrl-C", tr!("quit")),
         Line::from(""),
-        Line::from(Span::styled("  Press ? to close",
+        Line::from(Span::styled(format!("  {}", tr!("Press {} to close", key_name(keys.help))),
             Style::default().fg(Color::DarkGray))),
     ];
     frame.render_widget(
         Paragraph::new(lines).block(
-            Block::default().borders(Borders::ALL).title(" Help ")
+            Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("Help")))
                 .style(Style::default().fg(Color::White))
         ),
         popup,
@@ -1426,12 +1810,12 @@ fn get_slot_pos(loc: &Location, board: &Board, spec: CardSpec, top_area: Rect, t
             (top_area.x + 1 + *fc as u16 * (cw + 1), top_area.y)
         }
         Location::Flower => {
-            let fc_block_w = NUM_FREE_CELLS as u16 * (cw + 1) + 1;
+            let fc_block_w = board.free_cells.len() as u16 * (cw + 1) + 1;
             (top_area.x + fc_block_w + 3, top_area.y)
         }
         Location::Foundation(suit) => {
-            let idx = match suit { Suit::Red => 0, Suit::Green => 1, Suit::Black => 2 };
-            let fc_block_w = NUM_FREE_CELLS as u16 * (cw + 1) + 1;
+            let idx = match suit { Suit::Red => 0, Suit::Green => 1, Suit::Black => 2, Suit::Yellow => 3 };
+            let fc_block_w = board.free_cells.len() as u16 * (cw + 1) + 1;
             let start = top_area.x + fc_block_w + cw + 6;
             (start + 1 + idx as u16 * (cw + 1), top_area.y)
         }
@@ -1548,15 +1932,15 @@ fn render_animation_overlay(
                 }
             }
         }
-        GameEvent::Dealt { seed } => {
-            let new_board = Board::deal_seeded(*seed);
+        GameEvent::Dealt { seed, rules } => {
+            let new_board = Board::deal(*seed, *rules);
             
             // source position (bottom right corner of tab_rect)
             let sx = tab_rect.x + tab_rect.width.saturating_sub(spec.card_w());
             let sy = tab_rect.y + tab_rect.height.saturating_sub(spec.card_h());
             
             let raw_t = anim.start_time.elapsed().as_secs_f32() / anim.duration.as_secs_f32();
-            let total_cards = 40;
+            let total_cards = new_board.rules.deck_size();
             
             let mut i = 0;
             for (col_idx, col) in new_board.columns.iter().enumerate() {
@@ -1601,7 +1985,7 @@ fn render_animation_overlay(
             let sy = tab_rect.y + tab_rect.height.saturating_sub(spec.card_h());
             
             let raw_t = anim.start_time.elapsed().as_secs_f32() / anim.duration.as_secs_f32();
-            let total_cards = 40;
+            let total_cards = new_board.rules.deck_size();
             
             let mut i = 0;
             for (col_idx, col) in new_board.columns.iter().enumerate() {
@@ -1686,6 +2070,28 @@ pub trait TuiRendererExt {
     fn hide_solving(&mut self);
     fn update_solving_progress(&mut self, progress: SolverProgress);
     fn show_solver_failed(&mut self);
+    /// Close the post-win summary.  Returns `true` if one was showing.
+    fn dismiss_summary(&mut self) -> bool;
+    // Vim-style keys
+    fn set_vim_keys(&mut self, on: bool);
+    fn cursor(&self) -> Option<Location>;
+    fn set_cursor(&mut self, loc: Option<Location>);
+    /// The `:` command line's text, if it is open.
+    fn command_line(&self) -> Option<&str>;
+    fn set_command_line(&mut self, text: Option<String>);
+    fn set_keymap(&mut self, keys: KeyMap);
+    // Statistics screen
+    fn show_stats(&mut self, dashboard: Dashboard);
+    fn stats_open(&self) -> bool;
+    fn turn_stats_page(&mut self, forward: bool);
+    fn close_stats(&mut self);
+    // Campaign map (opened through `Renderer::show_campaign`)
+    fn campaign_open(&self) -> bool;
+    /// Move the pick on the map by `step` stages, staying on the map.
+    fn move_campaign_pick(&mut self, step: isize);
+    /// The stage picked on the map.
+    fn campaign_pick(&self) -> Option<usize>;
+    fn close_campaign(&mut self);
 }
 
 impl TuiRendererExt for TuiRenderer {
@@ -1756,6 +2162,31 @@ impl TuiRendererExt for TuiRenderer {
     fn show_solver_failed(&mut self) {
         self.solver_failed_until = Some(Instant::now() + Duration::from_millis(1600));
     }
+    fn dismiss_summary(&mut self) -> bool {
+        self.summary.take().is_some()
+    }
+    fn set_vim_keys(&mut self, on: bool) { self.vim_keys = on; }
+    fn cursor(&self) -> Option<Location> { self.cursor }
+    fn set_cursor(&mut self, loc: Option<Location>) { self.cursor = loc; }
+    fn command_line(&self) -> Option<&str> { self.command_line.as_deref() }
+    fn set_command_line(&mut self, text: Option<String>) { self.command_line = text; }
+    fn set_keymap(&mut self, keys: KeyMap) { self.keys = keys; }
+    fn show_stats(&mut self, dashboard: Dashboard) { self.stats = Some((dashboard, StatsPage::WinRate)); }
+    fn stats_open(&self) -> bool { self.stats.is_some() }
+    fn turn_stats_page(&mut self, forward: bool) {
+        if let Some((_, page)) = &mut self.stats {
+            *page = if forward { page.next() } else { page.prev() };
+        }
+    }
+    fn close_stats(&mut self) { self.stats = None; }
+    fn campaign_open(&self) -> bool { self.campaign.is_some() }
+    fn move_campaign_pick(&mut self, step: isize) {
+        if let Some((map, selected)) = &mut self.campaign {
+            *selected = selected.saturating_add_signed(step).clamp(1, map.stages.len());
+        }
+    }
+    fn campaign_pick(&self) -> Option<usize> { self.campaign.as_ref().map(|(_, selected)| *selected) }
+    fn close_campaign(&mut self) { self.campaign = None; }
 }
 
 // ---------------------------------------------------------------------------
@@ -1767,14 +2198,38 @@ impl Renderer for TuiRenderer {
     fn info(&mut self, msg: &str)  { self.push_log(LogLevel::Info,  msg.to_string()); }
     fn error(&mut self, msg: &str) { self.push_log(LogLevel::Error, msg.to_string()); }
     fn help(&mut self)  { self.show_help = !self.show_help; }
-    fn win(&mut self)   { self.push_log(LogLevel::Info, "YOU WIN!  Press N for another game.".to_string()); }
-    fn render_header(&mut self, total_wins: usize, seed: u64) {
+    fn win(&mut self)   { self.push_log(LogLevel::Info, tr!("YOU WIN!  Press N for another game.")); }
+    fn win_summary(&mut self, summary: &WinSummary) {
+        let mut lines = summary.lines();
+        let share = summary.share_lines();
+        if !share.is_empty() {
+            lines.push(String::new());
+            lines.extend(share);
+        }
+        self.summary = Some(lines);
+    }
+    fn render_header(&mut self, total_wins: usize, seed: u64, seed_phrase: Option<&str>, moves: u32, par: Option<usize>, elapsed: Duration) {
         self.header_wins = total_wins;
         self.header_seed = seed;
+        self.header_moves = moves;
+        self.header_par = par;
+        self.header_elapsed = elapsed;
+        if self.header_phrase.as_deref() != seed_phrase {
+            self.header_phrase = seed_phrase.map(str::to_string);
+        }
     }
+    fn set_status(&mut self, status: HeaderStatus) { self.header_status = status; }
     fn push_events(&mut self, events: Vec<GameEvent>) {
         self.anim_queue.extend(events);
     }
+    fn bell(&mut self) {
+        let out = self.terminal.backend_mut();
+        let _ = out.write_all(b"\x07");
+        let _ = out.flush();
+    }
+    fn show_campaign(&mut self, map: &CampaignMap) {
+        self.campaign = Some((map.clone(), map.next_stage()));
+    }
 
     fn tick(&mut self) {
         if self
@@ -1786,14 +2241,12 @@ impl Renderer for TuiRenderer {
         }
 
         // Step 1: Advance current animation timeout
-        if let Some(anim) = &self.current_anim {
-            if anim.start_time.elapsed() >= anim.duration {
-                // Animation finished: apply event permanently to our visual board tracker
-                if let Some(board) = &mut self.anim_board {
-                    board.apply_event(&anim.event);
-                }
-                self.current_anim = None;
+        if let Some(anim) = &self.current_anim && anim.start_time.elapsed() >= anim.duration {
+            // Animation finished: apply event permanently to our visual board tracker
+            if let Some(board) = &mut self.anim_board {
+                board.apply_event(&anim.event);
             }
+            self.current_anim = None;
         }
 
         // Step 2: Pick next animation from queue
@@ -0,0 +1,74 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * RELICENSING NOTICE:
+ * This project was originally released under the MIT License. As of March 2026, 
+ * the sole copyright holder (ghoker143) has officially transitioned the 
+ * entire project and its history to the GNU General Public License v3.0.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+//! wasm-bindgen exports of the engine for a browser UI, built only with
+//! `--features wasm`.
+//!
+//! The same rules and auto-moves as the game and the C API: a `Board` is
+//! dealt by seed on the standard table, lists its moves as the C API's
+//! `SzsolMove`s, applies them with the safe auto-moves after each, and
+//! asks the solver for a winning line.  Build it for
+//! `wasm32-unknown-unknown` as a `cdylib` and run `wasm-bindgen` over the
+//! result (see the README).
+use wasm_bindgen::prelude::*;
+
+use crate::board::{Board, Rules};
+use crate::ffi::{self, SzsolMove};
+
+/// A board, exported to JavaScript as `Board`.
+#[wasm_bindgen(js_name = Board)]
+pub struct WasmBoard(Board);
+
+#[wasm_bindgen(js_class = Board)]
+impl WasmBoard {
+    /// Deal the board for `seed` (a `BigInt`) on a standard table, with the
+    /// opening auto-moves made.
+    #[wasm_bindgen(constructor)]
+    pub fn deal(seed: u64) -> WasmBoard {
+        let mut board = Board::deal(seed, Rules::STANDARD);
+        board.auto_move();
+        WasmBoard(board)
+    }
+
+    /// The moves that could help, as `szsol_board_moves` lists them.
+    pub fn moves(&self) -> Vec<SzsolMove> {
+        self.0.valid_moves().into_iter().map(SzsolMove::from).collect()
+    }
+
+    /// Make `m` followed by the safe auto-moves.  Returns `false`, leaving
+    /// the board unchanged, when the move is not legal.
+    pub fn apply(&mut self, m: &SzsolMove) -> bool {
+        ffi::apply(&mut self.0, *m)
+    }
+
+    /// A winning line from here, or `undefined` when the solver finds none
+    /// within its budget.
+    pub fn solve(&self) -> Option<Vec<SzsolMove>> {
+        let solution = crate::solver::solve(&self.0, |_| true)?;
+        Some(solution.into_iter().map(|step| step.next_move.into()).collect())
+    }
+
+    /// The board as `szsol_board_json` writes it.
+    pub fn json(&self) -> String {
+        ffi::json(&self.0).pretty()
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * szsol-rs
+ * Copyright (C) 2026 ghoker143
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ */
+//! The weekly challenge: one hard deal a week, the same for everyone,
+//! rotating on Monday at 00:00 UTC.  The deals come from a fixed list of
+//! seeds the solver rated hard with `analyze` (over 100 000 positions
+//! explored, solution known), so no one's machine has to find them and a
+//! newer solver can't change the week's deal.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The rotation, one seed a week, in order.
+const SEEDS: [u64; 48] = [
+    3, 4, 15, 21, 33, 37, 58, 64, 74, 86, 87, 89, 96, 98, 123, 136, 141, 143, 149, 156, 188, 189, 216, 236, 237, 242,
+    243, 246, 256, 271, 301, 308, 309, 312, 319, 326, 334, 358, 365, 411, 422, 441, 443, 454, 465, 469, 476, 490,
+];
+
+const WEEK_SECS: i64 = 7 * 86_400;
+/// The first Monday after the epoch (1970-01-05) began week 0.
+const FIRST_MONDAY: i64 = 4 * 86_400;
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+/// The week a Unix time falls in.
+pub fn week(time: i64) -> i64 {
+    (time - FIRST_MONDAY).div_euclid(WEEK_SECS)
+}
+
+/// The current week.
+pub fn current_week() -> i64 {
+    week(now())
+}
+
+/// The Unix time `week` began.
+pub fn week_start(week: i64) -> i64 {
+    FIRST_MONDAY + week * WEEK_SECS
+}
+
+/// The deal of `week`.
+pub fn seed(week: i64) -> u64 {
+    SEEDS[week.rem_euclid(SEEDS.len() as i64) as usize]
+}
+
+/// This week's deal.
+pub fn current_seed() -> u64 {
+    seed(current_week())
+}
+
+/// Time left until the next deal.
+pub fn time_left() -> Duration {
+    let now = now();
+    Duration::from_secs((week_start(week(now) + 1) - now) as u64)
+}